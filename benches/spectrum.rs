@@ -0,0 +1,47 @@
+//! Performance baselines for the core calculations
+//!
+//! Run with `cargo bench --bench spectrum`. Inputs are fixed so runs are
+//! comparable across commits.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use nanocalc::compute::{efficiency_at_x, serial_spectrum, ComputeEngine};
+use nanocalc::core::{OpticalModel, RefractiveIndex};
+use nanocalc::physics::optical::mie::MieModel;
+use num_complex::Complex64;
+
+/// Gold-like index at 520 nm
+const GOLD: RefractiveIndex = RefractiveIndex { real: 0.47, imaginary: 2.40 };
+
+fn gold_sphere() -> MieModel {
+    MieModel::new(20.0, 520.0, GOLD, 1.33)
+}
+
+fn rayleigh_point(c: &mut Criterion) {
+    let model = gold_sphere();
+    c.bench_function("rayleigh_point", |b| b.iter(|| black_box(&model).calculate()));
+}
+
+fn mie_point(c: &mut Criterion) {
+    let m = Complex64::new(1.5, 0.01);
+    let mut group = c.benchmark_group("mie_point");
+    for x in [0.5, 5.0, 50.0, 500.0] {
+        group.bench_with_input(BenchmarkId::from_parameter(x), &x, |b, &x| {
+            b.iter(|| efficiency_at_x(black_box(m), black_box(x)))
+        });
+    }
+    group.finish();
+}
+
+fn spectrum_1000(c: &mut Criterion) {
+    let model = gold_sphere();
+    let wavelengths: Vec<f64> = (0..1000).map(|i| 300.0 + i as f64 * 0.5).collect();
+    let engine = ComputeEngine::default();
+
+    let mut group = c.benchmark_group("spectrum_1000");
+    group.bench_function("serial", |b| b.iter(|| serial_spectrum(&model, black_box(&wavelengths))));
+    group.bench_function("parallel", |b| b.iter(|| engine.spectrum(&model, black_box(&wavelengths))));
+    group.finish();
+}
+
+criterion_group!(benches, rayleigh_point, mie_point, spectrum_1000);
+criterion_main!(benches);
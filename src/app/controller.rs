@@ -1,3 +1,209 @@
 //! Application controller
+//!
+//! The calculate and export steps behind the GUI buttons, kept free of egui
+//! so the whole pipeline can also be driven headless.
 
-// Placeholder for MVP
+use super::history::RunHistory;
+use super::profile::REFINEMENT_THRESHOLD;
+use super::registry::build_optical_model;
+use super::state::AppState;
+use crate::compute::{refine_until, ComputeEngine};
+use crate::core::{
+    CacheableOpticalModel, CalcResult, CalculationError, OpticalResult, RefractiveIndex, RunInputs,
+};
+use crate::export::batch::{batch_template, read_batch_csv};
+use crate::export::bundle::Provenance;
+use crate::export::result_set::read_optical_csv;
+use crate::export::{export_error, CsvExporter, ExportError, Exporter, ImportError, JsonExporter, OpticalColumn, SpectrumFile};
+use crate::physics::materials::Dispersion;
+use crate::physics::optical::mie::{MieConfig, MieModel};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Default wavelength span of the full-spectrum calculation in nm
+pub const SPECTRUM_RANGE: (f64, f64) = (300.0, 800.0);
+
+/// Errors from running or exporting a calculation
+#[derive(Debug, thiserror::Error)]
+pub enum PipelineError {
+    #[error(transparent)]
+    Calculation(#[from] CalculationError),
+
+    #[error(transparent)]
+    Export(#[from] ExportError),
+
+    #[error(transparent)]
+    Import(#[from] ImportError),
+
+    #[error("Cannot serialize spectrum: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Model for the particle and medium in `state`
+///
+/// With the magnetic host on, the host comes from its ε and µ and
+/// may be rejected as not applicable. The series follows the solver
+/// method, tolerance and term cap.
+pub fn spectrum_model(state: &AppState) -> CalcResult<MieModel> {
+    let n_particle = RefractiveIndex::new(state.n_particle_real, state.n_particle_imag);
+    let model = if state.magnetic_medium {
+        MieModel::in_medium(state.particle_radius, state.wavelength, n_particle, &state.medium_spec)?
+    } else {
+        MieModel::new(state.particle_radius, state.wavelength, n_particle, state.n_medium)
+    };
+    let config = MieConfig {
+        tolerance: state.solver.tolerance,
+        max_terms: state.solver.max_terms,
+        dispatch: state.solver.method.into(),
+    };
+    Ok(MieModel { config, ..model })
+}
+
+/// [`spectrum_model`] with the particle index taken from `material` at each
+/// wavelength, if `state.dispersive_spectrum` is on
+pub fn dispersive_model(state: &AppState, material: Option<&Arc<dyn Dispersion>>) -> CalcResult<MieModel> {
+    let model = spectrum_model(state)?;
+    Ok(match material {
+        Some(material) if state.dispersive_spectrum => model.with_material(material.clone()),
+        _ => model,
+    })
+}
+
+/// The model selected by `state.model_kind`, see [`build_optical_model`]
+///
+/// The particle follows `material` as in [`dispersive_model`], whatever
+/// the model.
+pub fn optical_model(state: &AppState, material: Option<&Arc<dyn Dispersion>>) -> CalcResult<Box<dyn CacheableOpticalModel>> {
+    build_optical_model(state.model_kind, state, material)
+}
+
+/// Full spectrum over the range and with the solver settings in `state`
+///
+/// The particle follows `material` as in [`dispersive_model`]. Every model
+/// runs on the engine's pool and cache, with adaptive refinement or
+/// refinement to the target accuracy as the solver asks. The run is
+/// appended to `history` if it succeeds.
+pub fn calculate_spectrum(
+    state: &AppState,
+    material: Option<&Arc<dyn Dispersion>>,
+    engine: &ComputeEngine,
+    history: &mut RunHistory,
+) -> CalcResult<Vec<OpticalResult>> {
+    state.validate()?;
+    let model = optical_model(state, material)?;
+    history.record(model.name(), RunInputs::from(state), || {
+        spectrum_with_progress(state, material, engine, &mut |_, _| {}, &|_| {}, &AtomicBool::new(false))
+    })
+}
+
+/// [`calculate_spectrum`] reporting progress and stopping when asked, for a
+/// background run that records its own history
+///
+/// A plain grid goes through [`ComputeEngine::cached_spectrum_with_progress`],
+/// so a cancelled run returns the points finished so far and each chunk is
+/// passed to `partial` as it finishes. Refined runs pick their points as they
+/// go; they report only on completion, pass nothing to `partial` and run to
+/// the end.
+pub fn spectrum_with_progress(
+    state: &AppState,
+    material: Option<&Arc<dyn Dispersion>>,
+    engine: &ComputeEngine,
+    progress: &mut dyn FnMut(usize, usize),
+    partial: &(dyn Fn(&[OpticalResult]) + Sync),
+    cancel: &AtomicBool,
+) -> CalcResult<Vec<OpticalResult>> {
+    state.validate()?;
+    let solver = state.solver;
+    solver.validate()?;
+    let range = state.spectrum_range();
+    let wavelengths = solver.wavelength_grid(range.0, range.1)?;
+    let model = optical_model(state, material)?;
+    let results = if let Some(target) = solver.target_accuracy {
+        refine_until(model.as_ref(), range, target)?
+    } else if solver.adaptive_refinement {
+        engine.refined_spectrum(model.as_ref(), &wavelengths, REFINEMENT_THRESHOLD)?
+    } else {
+        return engine.cached_spectrum_with_progress(model.as_ref(), &wavelengths, progress, partial, cancel);
+    };
+    progress(results.len(), results.len());
+    Ok(results)
+}
+
+/// One single-point result per row of a batch, each recorded in `history`
+///
+/// Rows replace the particle and medium inputs of `state`; the remaining
+/// settings are shared. The first failing row stops the batch.
+pub fn calculate_batch(
+    state: &AppState,
+    rows: &[RunInputs],
+    history: &mut RunHistory,
+) -> CalcResult<Vec<OpticalResult>> {
+    rows.iter()
+        .map(|inputs| {
+            let row = AppState {
+                particle_radius: inputs.particle_radius,
+                wavelength: inputs.wavelength,
+                n_particle_real: inputs.n_particle_real,
+                n_particle_imag: inputs.n_particle_imag,
+                n_medium: inputs.n_medium,
+                magnetic_medium: false,
+                ..state.clone()
+            };
+            row.validate()?;
+            let model = build_optical_model(row.model_kind, &row, None)?;
+            let mut results = history.record(model.name(), *inputs, || model.calculate().map(|r| vec![r]))?;
+            Ok(results.remove(0))
+        })
+        .collect()
+}
+
+/// Write an empty batch CSV template
+pub fn write_batch_template(path: impl AsRef<Path>) -> Result<(), PipelineError> {
+    std::fs::write(path, batch_template())?;
+    Ok(())
+}
+
+/// Read the rows of a batch CSV
+pub fn read_batch(path: impl AsRef<Path>) -> Result<Vec<RunInputs>, PipelineError> {
+    Ok(read_batch_csv(&std::fs::read_to_string(path)?)?)
+}
+
+/// Write `results` to `path` with `exporter`
+///
+/// The file is only created once the whole export has rendered, so a
+/// refused export leaves nothing behind.
+pub fn write_export(path: impl AsRef<Path>, exporter: &dyn Exporter, results: &[OpticalResult]) -> Result<(), PipelineError> {
+    let buffer = exporter.export_to_vec(results).map_err(|e| match export_error(&e) {
+        Some(export) => PipelineError::Export(export.clone()),
+        None => PipelineError::Io(e),
+    })?;
+    std::fs::write(path, buffer)?;
+    Ok(())
+}
+
+/// Write `results` as CSV with the wavelength followed by `columns`
+pub fn write_csv(path: impl AsRef<Path>, results: &[OpticalResult], columns: &[OpticalColumn]) -> Result<(), PipelineError> {
+    write_export(path, &CsvExporter::new(columns.iter().copied()), results)
+}
+
+/// Read a CSV written by [`write_csv`] back as its columns and rows
+pub fn read_csv(path: impl AsRef<Path>) -> Result<(Vec<OpticalColumn>, Vec<Vec<f64>>), PipelineError> {
+    Ok(read_optical_csv(&std::fs::read_to_string(path)?)?)
+}
+
+/// Write `results` and the inputs in `state` as a JSON spectrum file,
+/// stamped with the model that `state` selects
+pub fn write_json(path: impl AsRef<Path>, state: &AppState, results: &[OpticalResult]) -> Result<(), PipelineError> {
+    let provenance = Provenance::now(build_optical_model(state.model_kind, state, None)?.name());
+    write_export(path, &JsonExporter::new(state, provenance), results)
+}
+
+/// Read a JSON spectrum file
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_json(path: impl AsRef<Path>) -> Result<SpectrumFile, PipelineError> {
+    Ok(SpectrumFile::load(path)?)
+}
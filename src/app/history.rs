@@ -0,0 +1,253 @@
+//! Structured run history
+//!
+//! Every calculation appends a [`RunRecord`] with its inputs and timing, so a
+//! session leaves an audit trail that can be reviewed or exported as CSV.
+
+use super::state::AppState;
+use crate::core::{CalcResult, OpticalResult, RunInputs};
+use crate::export::csv::push_row;
+use crate::utils::clock::{self, Clock, SystemClock};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default number of records kept before the oldest are dropped
+pub const DEFAULT_HISTORY_LIMIT: usize = 200;
+
+/// CSV header written by [`RunHistory::to_csv`]
+pub const HISTORY_CSV_HEADER: &str = "timestamp (s),model,radius (nm),wavelength (nm),n,k,n_medium,points,duration (ms),peak wavelength (nm)";
+
+impl From<&AppState> for RunInputs {
+    fn from(state: &AppState) -> Self {
+        Self {
+            particle_radius: state.particle_radius,
+            wavelength: state.wavelength,
+            n_particle_real: state.n_particle_real,
+            n_particle_imag: state.n_particle_imag,
+            n_medium: state.n_medium,
+        }
+    }
+}
+
+/// One completed calculation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Unix time (s) the run finished
+    pub timestamp: u64,
+    /// Name of the model that produced the results
+    pub model: String,
+    pub inputs: RunInputs,
+    /// Number of wavelengths calculated
+    pub n_points: usize,
+    /// Wall-clock duration in milliseconds
+    pub duration_ms: f64,
+    /// Wavelength of maximum Q_ext, if any points were calculated
+    pub peak_wavelength: Option<f64>,
+}
+
+impl RunRecord {
+    /// Record of a run that finished at `timestamp` (Unix seconds)
+    pub fn new(
+        timestamp: u64,
+        model: impl Into<String>,
+        inputs: RunInputs,
+        results: &[OpticalResult],
+        duration: Duration,
+    ) -> Self {
+        Self {
+            timestamp,
+            model: model.into(),
+            inputs,
+            n_points: results.len(),
+            duration_ms: duration.as_secs_f64() * 1e3,
+            peak_wavelength: results
+                .iter()
+                .max_by(|a, b| a.q_ext.total_cmp(&b.q_ext))
+                .map(|r| r.wavelength),
+        }
+    }
+
+    /// Time of day the run finished, as HH:MM:SS (UTC)
+    pub fn time_of_day(&self) -> String {
+        clock::time_of_day(self.timestamp)
+    }
+}
+
+/// Bounded, oldest-first list of run records
+#[derive(Clone)]
+pub struct RunHistory {
+    records: VecDeque<RunRecord>,
+    limit: usize,
+    clock: Arc<dyn Clock>,
+}
+
+impl RunHistory {
+    /// Empty history keeping at most `limit` records
+    pub fn new(limit: usize) -> Self {
+        Self::with_clock(limit, Arc::new(SystemClock))
+    }
+
+    /// Empty history stamping records from `clock`
+    pub fn with_clock(limit: usize, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            records: VecDeque::new(),
+            limit,
+            clock,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Change the cap, dropping the oldest records if over it
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    pub fn push(&mut self, record: RunRecord) {
+        self.records.push_back(record);
+        self.truncate();
+    }
+
+    /// Time `run` and record it under `model` if it succeeds
+    pub fn record<F>(&mut self, model: &str, inputs: RunInputs, run: F) -> CalcResult<Vec<OpticalResult>>
+    where
+        F: FnOnce() -> CalcResult<Vec<OpticalResult>>,
+    {
+        let start = Instant::now();
+        let results = run()?;
+        self.record_finished(model, inputs, &results, start.elapsed());
+        Ok(results)
+    }
+
+    /// Record a run timed elsewhere, e.g. one streamed from a background worker
+    pub fn record_finished(&mut self, model: &str, inputs: RunInputs, results: &[OpticalResult], duration: Duration) {
+        let record = RunRecord::new(self.clock.now_unix_secs(), model, inputs, results, duration);
+        self.push(record);
+    }
+
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &RunRecord> {
+        self.records.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Render the history as CSV, oldest run first
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(HISTORY_CSV_HEADER);
+        csv.push('\n');
+        for r in &self.records {
+            let i = &r.inputs;
+            push_row(
+                &mut csv,
+                [
+                    r.timestamp.to_string(),
+                    r.model.clone(),
+                    i.particle_radius.to_string(),
+                    i.wavelength.to_string(),
+                    i.n_particle_real.to_string(),
+                    i.n_particle_imag.to_string(),
+                    i.n_medium.to_string(),
+                    r.n_points.to_string(),
+                    r.duration_ms.to_string(),
+                    r.peak_wavelength.map(|v| v.to_string()).unwrap_or_default(),
+                ],
+            );
+        }
+        csv
+    }
+
+    fn truncate(&mut self) {
+        while self.records.len() > self.limit {
+            self.records.pop_front();
+        }
+    }
+}
+
+impl Default for RunHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{OpticalModel, PhysicsModel, RefractiveIndex};
+    use crate::physics::optical::mie::MieModel;
+    use crate::physics::optical::rgd::RgdModel;
+    use crate::utils::clock::FixedClock;
+
+    fn inputs() -> RunInputs {
+        RunInputs::from(&AppState::default())
+    }
+
+    #[test]
+    fn test_two_runs_give_two_records() {
+        let wavelengths: Vec<f64> = (300..=800).step_by(5).map(|w| w as f64).collect();
+        let n = RefractiveIndex::new(1.5, 0.01);
+        let mie = MieModel::new(50.0, 500.0, n, 1.33);
+        let rgd = RgdModel::new(50.0, 500.0, n, 1.33);
+
+        let mut history = RunHistory::default();
+        history.record(mie.name(), inputs(), || mie.calculate_spectrum(&wavelengths)).unwrap();
+        history.record(rgd.name(), inputs(), || rgd.calculate_spectrum(&wavelengths)).unwrap();
+
+        let records: Vec<_> = history.records().collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].model, mie.name());
+        assert_eq!(records[1].model, rgd.name());
+        assert_ne!(records[0], records[1]);
+        for r in records {
+            assert_eq!(r.n_points, wavelengths.len());
+            assert!(r.duration_ms > 0.0);
+            assert!(r.peak_wavelength.is_some());
+        }
+        assert_eq!(history.to_csv().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_records_are_stamped_from_the_clock() {
+        let mut history = RunHistory::with_clock(10, Arc::new(FixedClock(3_600 + 61)));
+        let model = MieModel::new(50.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        history.record(model.name(), inputs(), || model.calculate_spectrum(&[500.0])).unwrap();
+        let record = history.records().next().unwrap();
+        assert_eq!(record.timestamp, 3_661);
+        assert_eq!(record.time_of_day(), "01:01:01");
+    }
+
+    #[test]
+    fn test_failed_run_is_not_recorded() {
+        let mut history = RunHistory::default();
+        let bad = MieModel::new(-1.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        assert!(history.record(bad.name(), inputs(), || bad.calculate_spectrum(&[500.0])).is_err());
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_limit_drops_oldest() {
+        let mut history = RunHistory::new(2);
+        for model in ["a", "b", "c"] {
+            history.push(RunRecord::new(0, model, inputs(), &[], Duration::from_millis(1)));
+        }
+        let models: Vec<_> = history.records().map(|r| r.model.as_str()).collect();
+        assert_eq!(models, ["b", "c"]);
+
+        history.set_limit(1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.records().next().unwrap().model, "c");
+    }
+}
@@ -0,0 +1,38 @@
+//! Common surrounding media
+
+use serde::{Deserialize, Serialize};
+
+/// One-click medium refractive indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediumPreset {
+    Air,
+    Water,
+    Glass,
+}
+
+impl MediumPreset {
+    pub const ALL: [MediumPreset; 3] = [MediumPreset::Air, MediumPreset::Water, MediumPreset::Glass];
+
+    /// Refractive index in the visible
+    pub fn index(self) -> f64 {
+        match self {
+            MediumPreset::Air => 1.0,
+            MediumPreset::Water => 1.33,
+            MediumPreset::Glass => 1.5,
+        }
+    }
+
+    /// Display name as an (English, Spanish) pair
+    pub fn label(self) -> (&'static str, &'static str) {
+        match self {
+            MediumPreset::Air => ("Air", "Aire"),
+            MediumPreset::Water => ("Water", "Agua"),
+            MediumPreset::Glass => ("Glass", "Vidrio"),
+        }
+    }
+
+    /// The preset whose index is `n_medium`, if any
+    pub fn matching(n_medium: f64) -> Option<MediumPreset> {
+        Self::ALL.into_iter().find(|p| (p.index() - n_medium).abs() < 1e-9)
+    }
+}
@@ -2,5 +2,16 @@
 
 pub mod state;
 pub mod controller;
+pub mod profile;
+pub mod history;
+pub mod medium;
+pub mod working_point;
+pub mod registry;
 
 pub use state::*;
+pub use controller::{PipelineError, SPECTRUM_RANGE};
+pub use history::{RunHistory, RunRecord, DEFAULT_HISTORY_LIMIT};
+pub use medium::MediumPreset;
+pub use registry::build_optical_model;
+pub use working_point::{GapComparison, WorkingPoint, ROOM_TEMPERATURE};
+pub use profile::{ComputeProfile, REFINEMENT_THRESHOLD, SolverMethod, SolverSettings};
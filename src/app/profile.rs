@@ -0,0 +1,273 @@
+//! Accuracy profiles: coherent presets over the advanced solver settings
+
+use crate::core::ValidationError;
+use crate::physics::optical::mie::MieDispatch;
+use serde::{Deserialize, Serialize};
+
+/// Relative Q_ext jump between grid points that triggers adaptive refinement
+pub const REFINEMENT_THRESHOLD: f64 = 0.02;
+
+/// Which optical solver path to use, see [`MieDispatch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolverMethod {
+    /// Rayleigh approximation at every size, fast but wrong for large x
+    Rayleigh,
+    /// Pick Rayleigh or the Mie series from the size parameter
+    Auto,
+    /// Always sum the full Mie series
+    FullMie,
+}
+
+impl From<SolverMethod> for MieDispatch {
+    fn from(method: SolverMethod) -> Self {
+        match method {
+            SolverMethod::Rayleigh => MieDispatch::Rayleigh,
+            SolverMethod::Auto => MieDispatch::Auto,
+            SolverMethod::FullMie => MieDispatch::FullSeries,
+        }
+    }
+}
+
+/// Advanced solver settings that a profile bundles together
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SolverSettings {
+    pub method: SolverMethod,
+    /// Hard cap on Mie series terms (n_max)
+    pub max_terms: usize,
+    /// Series convergence tolerance
+    pub tolerance: f64,
+    /// Spectrum grid step in nm
+    pub grid_step_nm: f64,
+    /// Insert extra wavelengths where Q_ext changes steeply between grid points
+    pub adaptive_refinement: bool,
+    /// Ignore the grid step and refine until this relative curve accuracy
+    #[serde(default)]
+    pub target_accuracy: Option<f64>,
+}
+
+/// Named presets over [`SolverSettings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComputeProfile {
+    Fast,
+    Balanced,
+    Accurate,
+}
+
+impl ComputeProfile {
+    pub const ALL: [ComputeProfile; 3] = [
+        ComputeProfile::Fast,
+        ComputeProfile::Balanced,
+        ComputeProfile::Accurate,
+    ];
+
+    /// The settings bundle this profile stands for
+    pub fn settings(self) -> SolverSettings {
+        match self {
+            ComputeProfile::Fast => SolverSettings {
+                method: SolverMethod::Rayleigh,
+                max_terms: 50,
+                tolerance: 1e-4,
+                grid_step_nm: 10.0,
+                adaptive_refinement: false,
+                target_accuracy: None,
+            },
+            ComputeProfile::Balanced => SolverSettings {
+                method: SolverMethod::Auto,
+                max_terms: 200,
+                tolerance: 1e-6,
+                grid_step_nm: 5.0,
+                adaptive_refinement: false,
+                target_accuracy: None,
+            },
+            ComputeProfile::Accurate => SolverSettings {
+                method: SolverMethod::FullMie,
+                max_terms: 1000,
+                tolerance: 1e-10,
+                grid_step_nm: 1.0,
+                adaptive_refinement: true,
+                target_accuracy: None,
+            },
+        }
+    }
+
+    /// Display name (English, Spanish)
+    pub fn name(self) -> (&'static str, &'static str) {
+        match self {
+            ComputeProfile::Fast => ("Fast", "Rápido"),
+            ComputeProfile::Balanced => ("Balanced", "Equilibrado"),
+            ComputeProfile::Accurate => ("Accurate", "Preciso"),
+        }
+    }
+
+    /// What choosing this profile implies (English, Spanish)
+    pub fn description(self) -> (&'static str, &'static str) {
+        match self {
+            ComputeProfile::Fast => (
+                "Rayleigh dipoles only, 10 nm grid",
+                "Solo dipolos de Rayleigh, malla de 10 nm",
+            ),
+            ComputeProfile::Balanced => (
+                "Automatic Mie, 5 nm grid",
+                "Mie automático, malla de 5 nm",
+            ),
+            ComputeProfile::Accurate => (
+                "Full Mie, tight tolerance, 1 nm grid with adaptive refinement",
+                "Mie completo, tolerancia estricta, malla de 1 nm con refinamiento adaptativo",
+            ),
+        }
+    }
+}
+
+impl SolverSettings {
+    /// The profile these settings match exactly, or None if customized
+    pub fn active_profile(&self) -> Option<ComputeProfile> {
+        ComputeProfile::ALL.into_iter().find(|p| p.settings() == *self)
+    }
+
+    /// Check the settings are usable together
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !(self.tolerance > 0.0 && self.tolerance < 1.0) {
+            return Err(ValidationError::OutOfRange {
+                value: self.tolerance,
+                min: 0.0,
+                max: 1.0,
+            });
+        }
+        if !(0.1..=50.0).contains(&self.grid_step_nm) {
+            return Err(ValidationError::OutOfRange {
+                value: self.grid_step_nm,
+                min: 0.1,
+                max: 50.0,
+            });
+        }
+        if let Some(target) = self.target_accuracy {
+            if !(target > 0.0 && target < 1.0) {
+                return Err(ValidationError::OutOfRange {
+                    value: target,
+                    min: 0.0,
+                    max: 1.0,
+                });
+            }
+        }
+        if self.max_terms == 0 {
+            return Err(ValidationError::InvalidParameter(
+                "max_terms must be at least 1".to_string(),
+            ));
+        }
+        if self.method == SolverMethod::FullMie && self.max_terms < 10 {
+            return Err(ValidationError::InvalidParameter(
+                "Full Mie needs at least 10 series terms".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Evenly spaced wavelengths from `min` to `max` (inclusive) at the grid step
+    ///
+    /// `min == max` gives that single wavelength. A reversed or non-finite
+    /// range, or a step wider than the range, is rejected.
+    pub fn wavelength_grid(&self, min: f64, max: f64) -> Result<Vec<f64>, ValidationError> {
+        if !(min.is_finite() && max.is_finite() && min <= max) {
+            return Err(ValidationError::InvalidParameter(format!(
+                "Empty wavelength range {}–{} nm: the end must not be below the start",
+                min, max
+            )));
+        }
+        if min == max {
+            return Ok(vec![min]);
+        }
+        if !(self.grid_step_nm > 0.0 && self.grid_step_nm <= max - min) {
+            return Err(ValidationError::InvalidParameter(format!(
+                "Grid step {} nm does not fit in the range {}–{} nm",
+                self.grid_step_nm, min, max
+            )));
+        }
+        let steps = ((max - min) / self.grid_step_nm).round() as usize;
+        Ok((0..=steps).map(|i| min + i as f64 * self.grid_step_nm).collect())
+    }
+}
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        ComputeProfile::Balanced.settings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_are_valid_and_self_consistent() {
+        for profile in ComputeProfile::ALL {
+            let settings = profile.settings();
+            assert!(settings.validate().is_ok(), "{:?}", profile);
+            assert_eq!(settings.active_profile(), Some(profile));
+
+            let grid = settings.wavelength_grid(300.0, 800.0).unwrap();
+            assert_eq!(grid.first(), Some(&300.0));
+            assert!((grid.last().unwrap() - 800.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_profiles_increase_in_accuracy() {
+        let [fast, balanced, accurate] = ComputeProfile::ALL.map(|p| p.settings());
+        assert!(fast.tolerance > balanced.tolerance && balanced.tolerance > accurate.tolerance);
+        assert!(fast.grid_step_nm > balanced.grid_step_nm && balanced.grid_step_nm > accurate.grid_step_nm);
+        assert!(fast.max_terms < balanced.max_terms && balanced.max_terms < accurate.max_terms);
+    }
+
+    #[test]
+    fn test_customized_settings_have_no_profile() {
+        let mut settings = ComputeProfile::Balanced.settings();
+        settings.grid_step_nm = 2.0;
+        assert_eq!(settings.active_profile(), None);
+        assert!(settings.validate().is_ok());
+
+        settings.tolerance = 0.0;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_wavelength_grid_edge_ranges() {
+        let settings = SolverSettings::default();
+
+        assert_eq!(settings.wavelength_grid(500.0, 500.0).unwrap(), vec![500.0]);
+        assert!(matches!(
+            settings.wavelength_grid(800.0, 300.0),
+            Err(ValidationError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            settings.wavelength_grid(f64::NAN, 300.0),
+            Err(ValidationError::InvalidParameter(_))
+        ));
+        // Step wider than the range would give a lone start point
+        assert!(matches!(
+            settings.wavelength_grid(500.0, 500.0 + settings.grid_step_nm / 2.0),
+            Err(ValidationError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_solver_method_picks_the_mie_branch() {
+        use crate::app::{controller, AppState};
+        use crate::core::OpticalModel;
+
+        // Gold in water at x ≈ 1, where Rayleigh dipoles are well off
+        let q_ext = |profile: ComputeProfile| {
+            let state = AppState {
+                particle_radius: 60.0,
+                wavelength: 500.0,
+                solver: profile.settings(),
+                ..AppState::default()
+            };
+            controller::spectrum_model(&state).unwrap().calculate().unwrap().q_ext
+        };
+        let rayleigh = q_ext(ComputeProfile::Fast);
+        let full = q_ext(ComputeProfile::Accurate);
+        assert!((rayleigh - full).abs() / full > 0.1, "{} vs {}", rayleigh, full);
+        // Auto sums the series here too
+        assert!((q_ext(ComputeProfile::Balanced) - full).abs() / full < 1e-6);
+    }
+}
@@ -0,0 +1,143 @@
+//! Optical models by kind
+//!
+//! Offering another model in the GUI takes a variant of
+//! [`OpticalModelKind`] and its arm in [`build_optical_model`].
+
+use super::controller;
+use super::state::AppState;
+use crate::core::{CacheableOpticalModel, CalcResult, CalculationError, OpticalModelKind, RefractiveIndex};
+use crate::physics::materials::Dispersion;
+use crate::physics::optical::anomalous_diffraction::AnomalousDiffractionModel;
+use crate::physics::optical::rgd::RgdModel;
+use std::sync::Arc;
+
+/// The `kind` model for the particle and medium in `state`
+///
+/// Mie follows [`controller::spectrum_model`]; the approximations take the
+/// plain medium index and refuse the magnetic host. With
+/// `state.dispersive_spectrum` on, every kind takes the particle index from
+/// `material` at each wavelength.
+pub fn build_optical_model(
+    kind: OpticalModelKind,
+    state: &AppState,
+    material: Option<&Arc<dyn Dispersion>>,
+) -> CalcResult<Box<dyn CacheableOpticalModel>> {
+    if kind != OpticalModelKind::Mie && state.magnetic_medium {
+        return Err(CalculationError::InvalidInput(format!(
+            "{} does not support the magnetic host",
+            kind.name().0
+        )));
+    }
+    let n_particle = RefractiveIndex::new(state.n_particle_real, state.n_particle_imag);
+    let material = material.filter(|_| state.dispersive_spectrum).cloned();
+    Ok(match kind {
+        OpticalModelKind::Mie => {
+            let model = controller::spectrum_model(state)?;
+            Box::new(match material {
+                Some(material) => model.with_material(material),
+                None => model,
+            })
+        }
+        OpticalModelKind::RayleighGansDebye => {
+            let model = RgdModel::new(state.particle_radius, state.wavelength, n_particle, state.n_medium);
+            Box::new(match material {
+                Some(material) => model.with_material(material),
+                None => model,
+            })
+        }
+        OpticalModelKind::AnomalousDiffraction => {
+            let model = AnomalousDiffractionModel::new(state.particle_radius, state.wavelength, n_particle, state.n_medium);
+            Box::new(match material {
+                Some(material) => model.with_material(material),
+                None => model,
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_kind_builds_and_calculates() {
+        let state = AppState {
+            particle_radius: 100.0,
+            n_particle_real: 1.05,
+            n_particle_imag: 0.01,
+            ..AppState::default()
+        };
+        let names: Vec<String> = OpticalModelKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let model = build_optical_model(kind, &state, None).unwrap();
+                let result = model.calculate().unwrap();
+                assert_eq!(result.wavelength, state.wavelength);
+                assert!(result.q_ext > 0.0, "{:?}", kind);
+                model.name().to_string()
+            })
+            .collect();
+        assert_eq!(names, ["Mie Scattering", "Rayleigh-Gans-Debye", "Anomalous Diffraction"]);
+    }
+
+    #[test]
+    fn test_every_kind_sees_the_same_size_parameter() {
+        // A soft sphere in water, where RGD should track the full series
+        let state = AppState {
+            particle_radius: 100.0,
+            n_particle_real: 1.40,
+            n_particle_imag: 0.0,
+            n_medium: 1.33,
+            ..AppState::default()
+        };
+        let results: Vec<_> = OpticalModelKind::ALL
+            .into_iter()
+            .map(|kind| build_optical_model(kind, &state, None).unwrap().calculate().unwrap())
+            .collect();
+        let x = 2.0 * std::f64::consts::PI * 1.33 * 100.0 / state.wavelength;
+        for result in &results {
+            assert!((result.metadata.size_parameter - x).abs() < 1e-12, "{:?}", result.metadata.notes);
+        }
+        let (mie, rgd) = (&results[0], &results[1]);
+        assert!((rgd.q_ext - mie.q_ext).abs() / mie.q_ext < 0.15, "{} vs {}", rgd.q_ext, mie.q_ext);
+    }
+
+    #[test]
+    fn test_approximations_refuse_magnetic_medium() {
+        let state = AppState {
+            magnetic_medium: true,
+            ..AppState::default()
+        };
+        assert!(build_optical_model(OpticalModelKind::RayleighGansDebye, &state, None).is_err());
+        assert!(build_optical_model(OpticalModelKind::AnomalousDiffraction, &state, None).is_err());
+    }
+
+    #[test]
+    fn test_every_kind_follows_the_dispersive_material() {
+        use crate::physics::materials::TabulatedMaterial;
+
+        // A flat table at a different index from the state's
+        let rows = [(300.0, 1.45, 0.02), (900.0, 1.45, 0.02)];
+        let material: Arc<dyn Dispersion> = Arc::new(TabulatedMaterial::new("flat", &rows).unwrap());
+        let state = AppState {
+            particle_radius: 100.0,
+            n_particle_real: 1.05,
+            n_particle_imag: 0.0,
+            dispersive_spectrum: true,
+            ..AppState::default()
+        };
+        let as_table = AppState {
+            n_particle_real: 1.45,
+            n_particle_imag: 0.02,
+            dispersive_spectrum: false,
+            ..state.clone()
+        };
+        for kind in OpticalModelKind::ALL {
+            let dispersive = build_optical_model(kind, &state, Some(&material)).unwrap();
+            let fixed = build_optical_model(kind, &as_table, None).unwrap();
+            let (a, b) = (dispersive.calculate().unwrap(), fixed.calculate().unwrap());
+            assert_eq!(a.q_ext.to_bits(), b.q_ext.to_bits(), "{:?}", kind);
+            assert_ne!(dispersive.cache_key(), fixed.cache_key());
+        }
+    }
+}
@@ -1,7 +1,49 @@
 //! Application state management
 
+use super::controller::SPECTRUM_RANGE;
+use super::history::DEFAULT_HISTORY_LIMIT;
+use super::medium::MediumPreset;
+use super::profile::SolverSettings;
+use super::working_point::{WorkingPoint, ROOM_TEMPERATURE};
+use crate::core::{OpticalModelKind, PhysicsContext, RefractiveIndex, ValidationError, ValidationResult, Warning, WarningCode, WarningSeverity};
+use crate::physics::materials::Dispersion;
+use crate::physics::optical::MediumSpec;
 use serde::{Deserialize, Serialize};
 
+/// Wavelengths in nm accepted by [`AppState::validate`]
+pub const WAVELENGTH_LIMITS: (f64, f64) = (100.0, 5000.0);
+
+/// An input checked by [`AppState::check_inputs`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputField {
+    ParticleRadius,
+    Wavelength,
+    ParticleIndexN,
+    ParticleIndexK,
+    MediumIndex,
+    ReferenceWavelength,
+    Temperature,
+    SpectrumStart,
+    SpectrumEnd,
+}
+
+impl InputField {
+    /// (English, Spanish) label
+    pub fn name(self) -> (&'static str, &'static str) {
+        match self {
+            InputField::ParticleRadius => ("Particle radius", "Radio de la partícula"),
+            InputField::Wavelength => ("Wavelength", "Longitud de onda"),
+            InputField::ParticleIndexN => ("Particle index n", "Índice n de la partícula"),
+            InputField::ParticleIndexK => ("Particle index k", "Índice k de la partícula"),
+            InputField::MediumIndex => ("Medium index", "Índice del medio"),
+            InputField::ReferenceWavelength => ("Reference wavelength", "Longitud de onda de referencia"),
+            InputField::Temperature => ("Temperature", "Temperatura"),
+            InputField::SpectrumStart => ("Spectrum start", "Inicio del espectro"),
+            InputField::SpectrumEnd => ("Spectrum end", "Fin del espectro"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppState {
     pub particle_radius: f64,
@@ -9,6 +51,68 @@ pub struct AppState {
     pub n_particle_real: f64,
     pub n_particle_imag: f64,
     pub n_medium: f64,
+    /// Worker threads for parallel compute (None = all cores)
+    #[serde(default)]
+    pub compute_threads: Option<usize>,
+    /// Solver settings, normally set through an accuracy profile
+    #[serde(default)]
+    pub solver: SolverSettings,
+    /// Optical model for single points and spectra
+    #[serde(default)]
+    pub model_kind: OpticalModelKind,
+    /// Maximum number of runs kept in the session history
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// Wavelength (nm) for single n,k snapshots from a dispersion model;
+    /// None follows the working wavelength
+    #[serde(default)]
+    pub reference_wavelength: Option<f64>,
+    /// Last medium index not matching a preset, restored by "Custom"
+    #[serde(default)]
+    pub custom_n_medium: Option<f64>,
+    /// Stream spectrum chunks to the plot as a background worker computes them
+    #[serde(default)]
+    pub stream_spectrum: bool,
+    /// Ambient temperature in K
+    #[serde(default = "default_temperature")]
+    pub temperature: f64,
+    /// Evaluate the active dispersive material at every spectrum wavelength
+    /// instead of the single n, k snapshot
+    #[serde(default)]
+    pub dispersive_spectrum: bool,
+    /// Describe the medium by ε and µ instead of `n_medium`, for a magnetic host (µ ≠ 1)
+    #[serde(default, alias = "magnetic_medium")]
+    pub magnetic_medium: bool,
+    /// Medium used while `magnetic_medium` is on
+    #[serde(default)]
+    pub medium_spec: MediumSpec,
+    /// First wavelength of the full spectrum in nm; the grid step is
+    /// `solver.grid_step_nm`
+    #[serde(default = "default_wl_min")]
+    pub wl_min: f64,
+    /// Last wavelength of the full spectrum in nm
+    #[serde(default = "default_wl_max")]
+    pub wl_max: f64,
+    /// What-if scaling of physical constants; never saved, so every
+    /// session starts from CODATA
+    #[serde(skip)]
+    pub constants: PhysicsContext,
+}
+
+fn default_history_limit() -> usize {
+    DEFAULT_HISTORY_LIMIT
+}
+
+fn default_temperature() -> f64 {
+    ROOM_TEMPERATURE
+}
+
+fn default_wl_min() -> f64 {
+    SPECTRUM_RANGE.0
+}
+
+fn default_wl_max() -> f64 {
+    SPECTRUM_RANGE.1
 }
 
 impl Default for AppState {
@@ -19,6 +123,393 @@ impl Default for AppState {
             n_particle_real: 0.5,    // Au at 500nm (approx)
             n_particle_imag: 2.5,
             n_medium: 1.33,          // water
+            compute_threads: None,
+            solver: SolverSettings::default(),
+            model_kind: OpticalModelKind::Mie,
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            reference_wavelength: None,
+            custom_n_medium: None,
+            stream_spectrum: false,
+            temperature: ROOM_TEMPERATURE,
+            dispersive_spectrum: false,
+            magnetic_medium: false,
+            medium_spec: MediumSpec::default(),
+            wl_min: SPECTRUM_RANGE.0,
+            wl_max: SPECTRUM_RANGE.1,
+            constants: PhysicsContext::CODATA,
+        }
+    }
+}
+
+impl AppState {
+    /// Reject unphysical inputs before they reach a calculation
+    ///
+    /// See [`Self::check_inputs`] for which field failed.
+    pub fn validate(&self) -> ValidationResult<()> {
+        self.check_inputs().map_err(|(_, e)| e)
+    }
+
+    /// Check every input, reporting the first bad field and why
+    ///
+    /// Every number must be finite. Beyond that the radius must be positive,
+    /// wavelengths within [`WAVELENGTH_LIMITS`], k non-negative and the
+    /// medium index at least 1 (unless the medium is given by ε and µ), all
+    /// as [`ValidationError::OutOfRange`]. The spectrum range must also be a
+    /// non-empty interval. Finer limits are left to the models.
+    pub fn check_inputs(&self) -> Result<(), (InputField, ValidationError)> {
+        let fields = [
+            (InputField::ParticleRadius, Some(self.particle_radius)),
+            (InputField::Wavelength, Some(self.wavelength)),
+            (InputField::ParticleIndexN, Some(self.n_particle_real)),
+            (InputField::ParticleIndexK, Some(self.n_particle_imag)),
+            (InputField::MediumIndex, Some(self.n_medium)),
+            (InputField::ReferenceWavelength, self.reference_wavelength),
+            (InputField::Temperature, Some(self.temperature)),
+            (InputField::SpectrumStart, Some(self.wl_min)),
+            (InputField::SpectrumEnd, Some(self.wl_max)),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value.filter(|v| !v.is_finite()) {
+                return Err((
+                    field,
+                    ValidationError::InvalidParameter(format!("{} must be a finite number, got {}", field.name().0, value)),
+                ));
+            }
+        }
+
+        let out_of_range = |field, value: f64, (min, max): (f64, f64)| (field, ValidationError::OutOfRange { value, min, max });
+        if self.particle_radius <= 0.0 {
+            return Err(out_of_range(InputField::ParticleRadius, self.particle_radius, (0.0, f64::INFINITY)));
+        }
+        let wavelengths = [
+            (InputField::Wavelength, Some(self.wavelength)),
+            (InputField::ReferenceWavelength, self.reference_wavelength),
+            (InputField::SpectrumStart, Some(self.wl_min)),
+            (InputField::SpectrumEnd, Some(self.wl_max)),
+        ];
+        for (field, value) in wavelengths {
+            if let Some(value) = value.filter(|v| !(WAVELENGTH_LIMITS.0..=WAVELENGTH_LIMITS.1).contains(v)) {
+                return Err(out_of_range(field, value, WAVELENGTH_LIMITS));
+            }
+        }
+        if self.n_particle_imag < 0.0 {
+            return Err(out_of_range(InputField::ParticleIndexK, self.n_particle_imag, (0.0, f64::INFINITY)));
+        }
+        if !self.magnetic_medium && self.n_medium < 1.0 {
+            return Err(out_of_range(InputField::MediumIndex, self.n_medium, (1.0, f64::INFINITY)));
+        }
+        if self.temperature <= 0.0 {
+            return Err(out_of_range(InputField::Temperature, self.temperature, (0.0, f64::INFINITY)));
+        }
+        if self.wl_min >= self.wl_max {
+            return Err((
+                InputField::SpectrumStart,
+                ValidationError::InvalidParameter(format!(
+                    "Spectrum range {}–{} nm is empty: the start must be below the end",
+                    self.wl_min, self.wl_max
+                )),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Wavelength span of the full spectrum in nm
+    pub fn spectrum_range(&self) -> (f64, f64) {
+        (self.wl_min, self.wl_max)
+    }
+
+    /// Condition shared by every view, read from the working inputs
+    pub fn working_point(&self) -> WorkingPoint {
+        WorkingPoint {
+            wavelength: self.wavelength,
+            radius: self.particle_radius,
+            temperature: self.temperature,
+            constants: self.constants,
+        }
+    }
+
+    /// Wavelength at which dispersive materials are snapshotted to one n,k
+    pub fn snapshot_wavelength(&self) -> f64 {
+        self.reference_wavelength.unwrap_or(self.wavelength)
+    }
+
+    /// Index of `material` at the snapshot wavelength
+    pub fn snapshot_index(&self, material: &dyn Dispersion) -> RefractiveIndex {
+        material.refractive_index_at(self.snapshot_wavelength())
+    }
+
+    /// The preset matching the current medium index, if any
+    pub fn medium_preset(&self) -> Option<MediumPreset> {
+        MediumPreset::matching(self.n_medium)
+    }
+
+    /// Switch to `preset`, first remembering a custom medium index
+    pub fn select_medium_preset(&mut self, preset: MediumPreset) {
+        if self.medium_preset().is_none() {
+            self.custom_n_medium = Some(self.n_medium);
+        }
+        self.n_medium = preset.index();
+    }
+
+    /// Use a material's index as the surrounding medium
+    ///
+    /// The models take a real medium index, so only n is applied; a warning
+    /// is returned when a non-zero k had to be discarded. An n below 1, as
+    /// for metals in the visible, would fail [`AppState::validate`] and is
+    /// refused with the state left unchanged.
+    pub fn apply_medium_index(&mut self, index: RefractiveIndex) -> ValidationResult<Option<Warning>> {
+        if !self.magnetic_medium && index.real < 1.0 {
+            return Err(ValidationError::OutOfRange {
+                value: index.real,
+                min: 1.0,
+                max: f64::INFINITY,
+            });
+        }
+        self.n_medium = index.real;
+        if self.medium_preset().is_none() {
+            self.custom_n_medium = Some(index.real);
+        }
+        Ok((index.imaginary > 0.0).then(|| {
+            Warning::new(
+                WarningCode::MediumAbsorptionDiscarded,
+                WarningSeverity::Caution,
+                format!("k = {:.3}", index.imaginary),
+            )
+        }))
+    }
+
+    /// Return to the last custom medium index, if one was remembered
+    pub fn restore_custom_medium(&mut self) {
+        if let Some(n) = self.custom_n_medium {
+            self.n_medium = n;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConfinementRegime, ElectronicMetadata, ElectronicResult};
+    use crate::physics::materials::database;
+
+    #[test]
+    fn test_working_wavelength_reaches_gap_comparison() {
+        // CdSe-like dot with a 2.1 eV gap
+        let dot = ElectronicResult {
+            diameter: 4.0,
+            bandgap: 2.1,
+            bulk_bandgap: 1.74,
+            confinement_energy: 0.5,
+            coulomb_correction: 0.14,
+            bohr_radius: Some(5.6),
+            regime: ConfinementRegime::Strong,
+            metadata: ElectronicMetadata::default(),
+        };
+        let mut state = AppState {
+            wavelength: 700.0,
+            ..Default::default()
+        };
+        let below = state.working_point().compare_gap(&dot).unwrap();
+        assert!((below.photon_energy - 1239.84193 / 700.0).abs() < 1e-12);
+        assert!(!below.above_gap());
+
+        // Picking a bluer wavelength moves the shared photon energy above the gap
+        state.wavelength = 500.0;
+        let point = state.working_point();
+        let above = point.compare_gap(&dot).unwrap();
+        assert_eq!(above.photon_energy, point.photon_energy().unwrap());
+        assert!((above.excess() - (1239.84193 / 500.0 - 2.1)).abs() < 1e-12);
+        assert!(above.above_gap());
+        assert_eq!(point.diameter(), 2.0 * state.particle_radius);
+    }
+
+    #[test]
+    fn test_constant_overrides_reach_working_point() {
+        let mut state = AppState::default();
+        let codata = state.working_point().photon_energy().unwrap();
+
+        state.constants.h_scale = 3.0;
+        let scaled = state.working_point().photon_energy().unwrap();
+        assert!((scaled / codata - 3.0).abs() < 1e-15);
+
+        state.constants.reset();
+        assert_eq!(state.working_point().photon_energy().unwrap(), codata);
+        // Overrides are session-only
+        state.constants.c_scale = 2.0;
+        let saved: AppState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert!(!saved.constants.is_overridden());
+    }
+
+    #[test]
+    fn test_reference_wavelength_drives_snapshot() {
+        let gold = database::element("Au").unwrap();
+        let mut state = AppState {
+            wavelength: 500.0,
+            ..AppState::default()
+        };
+        assert_eq!(state.snapshot_wavelength(), 500.0);
+        let at_working = state.snapshot_index(&gold);
+
+        state.reference_wavelength = Some(700.0);
+        assert_eq!(state.snapshot_wavelength(), 700.0);
+        let at_reference = state.snapshot_index(&gold);
+        assert_ne!(at_working, at_reference);
+        assert_eq!(at_reference, gold.refractive_index_at(700.0));
+
+        // The working wavelength no longer matters once a reference is set
+        state.wavelength = 400.0;
+        assert_eq!(state.snapshot_index(&gold), at_reference);
+    }
+
+    #[test]
+    fn test_non_finite_inputs_are_rejected() {
+        assert!(AppState::default().validate().is_ok());
+
+        let setters: [fn(&mut AppState, f64); 8] = [
+            |s, v| s.particle_radius = v,
+            |s, v| s.wavelength = v,
+            |s, v| s.n_particle_real = v,
+            |s, v| s.n_particle_imag = v,
+            |s, v| s.n_medium = v,
+            |s, v| s.reference_wavelength = Some(v),
+            |s, v| s.wl_min = v,
+            |s, v| s.wl_max = v,
+        ];
+        for set in setters {
+            for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                let mut state = AppState::default();
+                set(&mut state, bad);
+                match state.validate() {
+                    Err(ValidationError::InvalidParameter(msg)) => {
+                        assert!(msg.contains("must be a finite number"), "{}", msg)
+                    }
+                    other => panic!("expected a validation error for {}, got {:?}", bad, other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unphysical_inputs_report_their_field() {
+        let base = AppState::default;
+        let cases = [
+            (AppState { particle_radius: 0.0, ..base() }, InputField::ParticleRadius, 0.0),
+            (AppState { particle_radius: -5.0, ..base() }, InputField::ParticleRadius, -5.0),
+            (AppState { wavelength: 50.0, ..base() }, InputField::Wavelength, 50.0),
+            (AppState { wavelength: 20_000.0, ..base() }, InputField::Wavelength, 20_000.0),
+            (AppState { reference_wavelength: Some(10.0), ..base() }, InputField::ReferenceWavelength, 10.0),
+            (AppState { wl_max: 9000.0, ..base() }, InputField::SpectrumEnd, 9000.0),
+            (AppState { n_medium: 0.8, ..base() }, InputField::MediumIndex, 0.8),
+            (AppState { n_particle_imag: -0.1, ..base() }, InputField::ParticleIndexK, -0.1),
+            (AppState { temperature: 0.0, ..base() }, InputField::Temperature, 0.0),
+            (AppState { temperature: -10.0, ..base() }, InputField::Temperature, -10.0),
+        ];
+        for (state, field, bad) in cases {
+            match state.check_inputs() {
+                Err((f, ValidationError::OutOfRange { value, .. })) => {
+                    assert_eq!(f, field);
+                    assert_eq!(value, bad);
+                }
+                other => panic!("expected {:?} out of range, got {:?}", field, other),
+            }
+            assert!(state.validate().is_err());
+        }
+
+        // The edges themselves are fine, and ε, µ media have no n_medium limit
+        let edges = AppState {
+            n_medium: 1.0,
+            n_particle_imag: 0.0,
+            wavelength: WAVELENGTH_LIMITS.0,
+            wl_max: WAVELENGTH_LIMITS.1,
+            ..Default::default()
+        };
+        assert!(edges.validate().is_ok());
+        let magnetic = AppState {
+            n_medium: 0.5,
+            magnetic_medium: true,
+            ..Default::default()
+        };
+        assert!(magnetic.validate().is_ok());
+    }
+
+    #[test]
+    fn test_spectrum_range_must_be_a_positive_interval() {
+        let state = AppState {
+            wl_min: 900.0,
+            wl_max: 1700.0,
+            ..Default::default()
+        };
+        assert!(state.validate().is_ok());
+        assert_eq!(state.spectrum_range(), (900.0, 1700.0));
+        assert_eq!(AppState::default().spectrum_range(), SPECTRUM_RANGE);
+
+        for (min, max) in [(800.0, 300.0), (500.0, 500.0), (0.0, 400.0)] {
+            let state = AppState { wl_min: min, wl_max: max, ..Default::default() };
+            assert!(state.validate().is_err(), "{}–{}", min, max);
+        }
+
+        // Projects saved before the range existed load with the default
+        let mut json = serde_json::to_value(AppState::default()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("wl_min");
+        fields.remove("wl_max");
+        let old: AppState = serde_json::from_value(json).unwrap();
+        assert_eq!(old.spectrum_range(), SPECTRUM_RANGE);
+    }
+
+    #[test]
+    fn test_apply_as_medium_drops_absorption_with_warning() {
+        let mut state = AppState::default();
+        let particle = (state.n_particle_real, state.n_particle_imag);
+
+        assert!(matches!(state.apply_medium_index(RefractiveIndex::new(1.46, 0.0)), Ok(None)));
+        assert_eq!(state.n_medium, 1.46);
+
+        let warning = state.apply_medium_index(RefractiveIndex::new(2.1, 0.35)).unwrap().unwrap();
+        assert_eq!(warning.code, WarningCode::MediumAbsorptionDiscarded);
+        assert!(warning.detail.contains("0.350"));
+        assert_eq!(state.n_medium, 2.1);
+        assert_eq!(state.custom_n_medium, Some(2.1));
+        // The particle is untouched
+        assert_eq!((state.n_particle_real, state.n_particle_imag), particle);
+        assert!(state.validate().is_ok());
+    }
+
+    #[test]
+    fn test_metal_as_medium_is_refused() {
+        let mut state = AppState::default();
+        let before = state.n_medium;
+
+        // Gold in the visible has n < 1, which validate would reject
+        let gold = RefractiveIndex::new(0.47, 2.4);
+        assert!(matches!(
+            state.apply_medium_index(gold),
+            Err(ValidationError::OutOfRange { min, .. }) if min == 1.0
+        ));
+        assert_eq!(state.n_medium, before);
+        assert!(state.validate().is_ok());
+
+        // A magnetic host takes it as given
+        state.magnetic_medium = true;
+        assert!(state.apply_medium_index(gold).is_ok());
+        assert_eq!(state.n_medium, 0.47);
+    }
+
+    #[test]
+    fn test_medium_toggles_restore_custom_value() {
+        let mut state = AppState {
+            n_medium: 1.41,
+            ..AppState::default()
+        };
+        state.select_medium_preset(MediumPreset::Air);
+        assert_eq!(state.n_medium, 1.0);
+        assert_eq!(state.medium_preset(), Some(MediumPreset::Air));
+        state.select_medium_preset(MediumPreset::Water);
+        assert_eq!(state.n_medium, 1.33);
+
+        // Moving between presets must not overwrite the custom value
+        state.restore_custom_medium();
+        assert_eq!(state.n_medium, 1.41);
+        assert_eq!(state.medium_preset(), None);
+    }
+}
@@ -0,0 +1,69 @@
+//! Shared working point
+//!
+//! The wavelength, particle size and temperature every view is currently
+//! looking at. Optical, thermal and electronic panels all read it from
+//! [`AppState::working_point`](super::AppState::working_point), so picking
+//! a wavelength on one plot moves the matching photon energy everywhere.
+
+use crate::core::units::Wavelength;
+use crate::core::{ElectronicResult, PhysicsContext, ValidationResult};
+use serde::{Deserialize, Serialize};
+
+/// Default ambient temperature in K
+pub const ROOM_TEMPERATURE: f64 = 293.15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkingPoint {
+    /// Vacuum wavelength in nm
+    pub wavelength: f64,
+    /// Particle radius in nm
+    pub radius: f64,
+    /// Ambient temperature in K
+    pub temperature: f64,
+    /// Constants the derived energies are computed with
+    #[serde(default)]
+    pub constants: PhysicsContext,
+}
+
+impl WorkingPoint {
+    /// Photon energy in eV at the working wavelength
+    pub fn photon_energy(&self) -> ValidationResult<f64> {
+        self.constants.photon_energy_ev(Wavelength(self.wavelength))
+    }
+
+    /// Thermal energy k_B·T in eV at the working temperature
+    pub fn thermal_energy(&self) -> f64 {
+        self.constants.thermal_energy_ev(self.temperature)
+    }
+
+    pub fn diameter(&self) -> f64 {
+        2.0 * self.radius
+    }
+
+    /// The working photon energy set against an electronic result's bandgap
+    pub fn compare_gap(&self, result: &ElectronicResult) -> ValidationResult<GapComparison> {
+        Ok(GapComparison {
+            photon_energy: self.photon_energy()?,
+            bandgap: result.bandgap,
+        })
+    }
+}
+
+/// Photon energy and bandgap side by side, both in eV
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GapComparison {
+    pub photon_energy: f64,
+    pub bandgap: f64,
+}
+
+impl GapComparison {
+    /// Photon energy above the gap (negative below it)
+    pub fn excess(&self) -> f64 {
+        self.photon_energy - self.bandgap
+    }
+
+    /// Whether the photon can excite across the gap
+    pub fn above_gap(&self) -> bool {
+        self.excess() >= 0.0
+    }
+}
@@ -0,0 +1,239 @@
+//! Cancellable background runs
+//!
+//! A long calculation runs on its own thread and reports how far it has got
+//! through a [`RunProgress`]. A job started with
+//! [`BackgroundRun::spawn_with_partials`] can also hand over pieces of its
+//! result as they finish, so a plot fills in while a slow sweep is still
+//! running. The GUI polls the [`BackgroundRun`] each frame to draw a
+//! progress bar, and can stop it. On the web, where threads are
+//! unavailable, the job runs to completion inside [`BackgroundRun::spawn`].
+
+use crate::core::{CalcResult, CalculationError};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Progress and cancel flag shared between a run and its handle
+pub struct RunProgress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    cancel: AtomicBool,
+    notify: Box<dyn Fn() + Send + Sync>,
+}
+
+impl RunProgress {
+    fn new(notify: Box<dyn Fn() + Send + Sync>) -> Self {
+        Self {
+            done: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            cancel: AtomicBool::new(false),
+            notify,
+        }
+    }
+
+    /// Record that `done` of `total` units of work are finished
+    pub fn report(&self, done: usize, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(done, Ordering::Relaxed);
+        (self.notify)();
+    }
+
+    /// Flag set by [`BackgroundRun::cancel`], for the job to check between chunks
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancel
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Fraction of the work reported done, 0 before the first report
+    pub fn fraction(&self) -> f64 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            0.0
+        } else {
+            self.done.load(Ordering::Relaxed) as f64 / total as f64
+        }
+    }
+}
+
+/// Handle on a calculation running in the background
+///
+/// Dropping the handle cancels the job at its next check.
+pub struct BackgroundRun<T> {
+    receiver: Receiver<CalcResult<T>>,
+    partials: Receiver<T>,
+    progress: Arc<RunProgress>,
+    started: Instant,
+}
+
+impl<T: Send + 'static> BackgroundRun<T> {
+    /// Start `job`; `notify` is called after each progress report and once
+    /// the result is ready, e.g. to request a repaint
+    pub fn spawn<F, N>(job: F, notify: N) -> Self
+    where
+        F: FnOnce(&RunProgress) -> CalcResult<T> + Send + 'static,
+        N: Fn() + Send + Sync + 'static,
+    {
+        Self::spawn_with_partials(move |progress, _| job(progress), notify)
+    }
+
+    /// [`Self::spawn`] for a job that passes pieces of its result to
+    /// `partial` as they finish, collected by [`Self::take_partials`]
+    pub fn spawn_with_partials<F, N>(job: F, notify: N) -> Self
+    where
+        F: FnOnce(&RunProgress, &(dyn Fn(T) + Sync)) -> CalcResult<T> + Send + 'static,
+        N: Fn() + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let (partial_sender, partials) = mpsc::channel();
+        let progress = Arc::new(RunProgress::new(Box::new(notify)));
+        let worker = Arc::clone(&progress);
+        let run = move || {
+            let partial = |piece: T| {
+                let _ = partial_sender.send(piece);
+            };
+            // The handle may be gone already; then nobody wants the result
+            let _ = sender.send(job(&worker, &partial));
+            (worker.notify)();
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(run);
+        #[cfg(target_arch = "wasm32")]
+        run();
+
+        Self {
+            receiver,
+            partials,
+            progress,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl<T> BackgroundRun<T> {
+    /// The job's result once it has finished, None while it is still running
+    pub fn poll(&self) -> Option<CalcResult<T>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(CalculationError::NumericalInstability(
+                "Background worker stopped without a result".to_string(),
+            ))),
+        }
+    }
+
+    /// Pieces passed on by the job since the last call, in the order it sent them
+    pub fn take_partials(&self) -> Vec<T> {
+        self.partials.try_iter().collect()
+    }
+
+    /// Ask the job to stop; it still delivers whatever it returns
+    pub fn cancel(&self) {
+        self.progress.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.progress.is_cancelled()
+    }
+
+    /// See [`RunProgress::fraction`]
+    pub fn progress(&self) -> f64 {
+        self.progress.fraction()
+    }
+
+    /// Time since the run started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+impl<T> Drop for BackgroundRun<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{OpticalModel, RefractiveIndex};
+    use crate::physics::optical::mie::MieModel;
+
+    fn wait<T>(run: &BackgroundRun<T>) -> CalcResult<T> {
+        loop {
+            if let Some(result) = run.poll() {
+                return result;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_run_reports_progress_and_result() {
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let wavelengths: Vec<f64> = (300..=800).map(|w| w as f64).collect();
+        let expected = model.calculate_spectrum(&wavelengths).unwrap();
+
+        let job_model = model.clone();
+        let job_wavelengths = wavelengths.clone();
+        let run = BackgroundRun::spawn(
+            move |progress| {
+                job_model.calculate_spectrum_with_progress(
+                    &job_wavelengths,
+                    &mut |done, total| progress.report(done, total),
+                    progress.cancel_flag(),
+                )
+            },
+            || {},
+        );
+        let results = wait(&run).unwrap();
+        assert_eq!(results.len(), expected.len());
+        assert_eq!(results[250].q_ext.to_bits(), expected[250].q_ext.to_bits());
+        assert_eq!(run.progress(), 1.0);
+        assert!(!run.is_cancelled());
+    }
+
+    #[test]
+    fn test_partials_arrive_before_the_result() {
+        let run = BackgroundRun::spawn_with_partials(
+            |progress, partial| {
+                partial(vec![1, 2]);
+                partial(vec![3]);
+                while !progress.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Ok(vec![1, 2, 3])
+            },
+            || {},
+        );
+        let mut pieces = Vec::new();
+        while pieces.len() < 2 {
+            pieces.extend(run.take_partials());
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(pieces, [vec![1, 2], vec![3]]);
+        assert!(run.poll().is_none());
+        run.cancel();
+        assert_eq!(wait(&run).unwrap(), [1, 2, 3]);
+        assert!(run.take_partials().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_reaches_the_job() {
+        let run = BackgroundRun::spawn(
+            |progress| {
+                while !progress.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err::<(), _>(CalculationError::Cancelled)
+            },
+            || {},
+        );
+        assert!(run.poll().is_none());
+        run.cancel();
+        assert!(matches!(wait(&run), Err(CalculationError::Cancelled)));
+    }
+}
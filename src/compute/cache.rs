@@ -0,0 +1,282 @@
+//! Memoized spectra
+//!
+//! Recomputing a spectrum for inputs that have not changed is wasted work.
+//! [`ComputeCache`] keeps the most recently used results keyed by
+//! [`Cacheable::cache_key`] and evicts the least recently used once full.
+
+use crate::core::{CalcResult, Cacheable, OpticalResult};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Spectra kept by [`ComputeCache::default`]
+pub const DEFAULT_CACHE_CAPACITY: usize = 32;
+
+#[derive(Debug, Default)]
+struct Entries {
+    results: HashMap<String, Vec<OpticalResult>>,
+    /// Keys from least to most recently used
+    order: VecDeque<String>,
+    hits: usize,
+    misses: usize,
+}
+
+impl Entries {
+    fn touch(&mut self, key: &str) {
+        if let Some(i) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(i).expect("position is in range");
+            self.order.push_back(key);
+        }
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.results.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Least-recently-used cache of computed spectra, shareable between threads
+#[derive(Debug)]
+pub struct ComputeCache {
+    capacity: Mutex<usize>,
+    entries: Mutex<Entries>,
+}
+
+impl Default for ComputeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl ComputeCache {
+    /// Cache holding up to `capacity` results; 0 disables caching
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: Mutex::new(capacity),
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        *self.capacity.lock().expect("cache lock")
+    }
+
+    /// Change the capacity, evicting the least recently used if it shrank
+    pub fn set_capacity(&self, capacity: usize) {
+        *self.capacity.lock().expect("cache lock") = capacity;
+        self.entries.lock().expect("cache lock").evict_to(capacity);
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("cache lock").results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// (hits, misses) since the cache was created or cleared
+    pub fn stats(&self) -> (usize, usize) {
+        let entries = self.entries.lock().expect("cache lock");
+        (entries.hits, entries.misses)
+    }
+
+    pub fn clear(&self) {
+        *self.entries.lock().expect("cache lock") = Entries::default();
+    }
+
+    /// Cached results for `model`, or the output of `compute` stored under its key
+    ///
+    /// Errors are returned without being cached. `compute` runs without the
+    /// cache locked, so other threads can read it meanwhile.
+    pub fn get_or_compute<M, F>(&self, model: &M, compute: F) -> CalcResult<Vec<OpticalResult>>
+    where
+        M: Cacheable + ?Sized,
+        F: FnOnce() -> CalcResult<Vec<OpticalResult>>,
+    {
+        self.get_or_compute_key(model.cache_key(), compute)
+    }
+
+    /// [`Self::get_or_compute`] for `model` evaluated over `wavelengths`
+    pub fn get_or_compute_spectrum<M, F>(&self, model: &M, wavelengths: &[f64], compute: F) -> CalcResult<Vec<OpticalResult>>
+    where
+        M: Cacheable + ?Sized,
+        F: FnOnce() -> CalcResult<Vec<OpticalResult>>,
+    {
+        self.get_or_compute_key(spectrum_key(model, wavelengths), compute)
+    }
+
+    /// Cached results for `model` over `wavelengths`, counted as a hit or miss
+    ///
+    /// With [`Self::store_spectrum`], for callers that decide after computing
+    /// whether the results are worth keeping.
+    pub fn lookup_spectrum<M: Cacheable + ?Sized>(&self, model: &M, wavelengths: &[f64]) -> Option<Vec<OpticalResult>> {
+        self.lookup(&spectrum_key(model, wavelengths))
+    }
+
+    /// Store results for `model` over `wavelengths`
+    pub fn store_spectrum<M: Cacheable + ?Sized>(&self, model: &M, wavelengths: &[f64], results: Vec<OpticalResult>) {
+        self.store(spectrum_key(model, wavelengths), results);
+    }
+
+    fn get_or_compute_key<F>(&self, key: String, compute: F) -> CalcResult<Vec<OpticalResult>>
+    where
+        F: FnOnce() -> CalcResult<Vec<OpticalResult>>,
+    {
+        if let Some(results) = self.lookup(&key) {
+            return Ok(results);
+        }
+        let results = compute()?;
+        self.store(key, results.clone());
+        Ok(results)
+    }
+
+    fn lookup(&self, key: &str) -> Option<Vec<OpticalResult>> {
+        let mut entries = self.entries.lock().expect("cache lock");
+        match entries.results.get(key).cloned() {
+            Some(results) => {
+                entries.hits += 1;
+                entries.touch(key);
+                Some(results)
+            }
+            None => {
+                entries.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn store(&self, key: String, results: Vec<OpticalResult>) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().expect("cache lock");
+        if entries.results.insert(key.clone(), results).is_some() {
+            entries.touch(&key);
+        } else {
+            entries.order.push_back(key);
+        }
+        entries.evict_to(capacity);
+    }
+}
+
+/// Key of `model` over a wavelength grid: the model key plus the grid's
+/// length, span and a hash of every point
+pub fn spectrum_key<M: Cacheable + ?Sized>(model: &M, wavelengths: &[f64]) -> String {
+    let mut hasher = DefaultHasher::new();
+    for wl in wavelengths {
+        wl.to_bits().hash(&mut hasher);
+    }
+    format!(
+        "{}|grid={}:{}..{}#{:016x}",
+        model.cache_key(),
+        wavelengths.len(),
+        wavelengths.first().copied().unwrap_or(f64::NAN),
+        wavelengths.last().copied().unwrap_or(f64::NAN),
+        hasher.finish()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CalculationError, OpticalModel, RefractiveIndex};
+    use crate::physics::optical::mie::MieModel;
+    use std::cell::Cell;
+
+    fn model(radius: f64) -> MieModel {
+        MieModel::new(radius, 500.0, RefractiveIndex::new(0.47, 2.4), 1.33)
+    }
+
+    #[test]
+    fn test_second_request_is_served_from_cache() {
+        let cache = ComputeCache::default();
+        let wavelengths = [400.0, 500.0, 600.0];
+        let calls = Cell::new(0);
+        let compute = || {
+            calls.set(calls.get() + 1);
+            model(40.0).calculate_spectrum(&wavelengths)
+        };
+
+        let first = cache.get_or_compute_spectrum(&model(40.0), &wavelengths, compute).unwrap();
+        let second = cache
+            .get_or_compute_spectrum(&model(40.0), &wavelengths, || panic!("compute must not run on a hit"))
+            .unwrap();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cache.stats(), (1, 1));
+        assert!(first.iter().zip(&second).all(|(a, b)| a.q_ext == b.q_ext));
+
+        // A different grid or radius is a different entry
+        assert_ne!(spectrum_key(&model(40.0), &wavelengths), spectrum_key(&model(40.0), &wavelengths[..2]));
+        assert_ne!(model(40.0).cache_key(), model(40.5).cache_key());
+        let mut tighter = model(40.0);
+        tighter.config.tolerance = 1e-9;
+        assert_ne!(model(40.0).cache_key(), tighter.cache_key());
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let cache = ComputeCache::new(2);
+        let compute = |r: f64| move || model(r).calculate().map(|result| vec![result]);
+        cache.get_or_compute(&model(10.0), compute(10.0)).unwrap();
+        cache.get_or_compute(&model(20.0), compute(20.0)).unwrap();
+        // Using 10 nm again leaves 20 nm as the oldest
+        cache.get_or_compute(&model(10.0), compute(10.0)).unwrap();
+        cache.get_or_compute(&model(30.0), compute(30.0)).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let calls = Cell::new(0);
+        let counted = |r: f64| {
+            let calls = &calls;
+            move || {
+                calls.set(calls.get() + 1);
+                model(r).calculate().map(|result| vec![result])
+            }
+        };
+        cache.get_or_compute(&model(10.0), counted(10.0)).unwrap();
+        cache.get_or_compute(&model(30.0), counted(30.0)).unwrap();
+        assert_eq!(calls.get(), 0);
+        cache.get_or_compute(&model(20.0), counted(20.0)).unwrap();
+        assert_eq!(calls.get(), 1);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats(), (0, 0));
+    }
+
+    #[test]
+    fn test_errors_and_zero_capacity_are_not_cached() {
+        let cache = ComputeCache::default();
+        let failed = cache.get_or_compute(&model(10.0), || Err(CalculationError::InvalidInput("boom".to_string())));
+        assert!(failed.is_err());
+        assert!(cache.is_empty());
+
+        let off = ComputeCache::new(0);
+        off.get_or_compute(&model(10.0), || model(10.0).calculate().map(|r| vec![r])).unwrap();
+        assert!(off.is_empty());
+    }
+
+    #[test]
+    fn test_materials_are_keyed_on_their_data() {
+        use crate::physics::materials::{Dispersion, TabulatedMaterial};
+        use std::sync::Arc;
+
+        let table = |k: f64| {
+            let rows = [(400.0, 1.5, k), (800.0, 1.4, k)];
+            Arc::new(TabulatedMaterial::new("film", &rows).unwrap()) as Arc<dyn Dispersion>
+        };
+        let key = |material: Arc<dyn Dispersion>| model(40.0).with_material(material).cache_key();
+
+        // Same name, new allocation: only the data decides
+        assert_eq!(key(table(0.1)), key(table(0.1)));
+        assert_ne!(key(table(0.1)), key(table(0.2)));
+    }
+}
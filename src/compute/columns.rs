@@ -0,0 +1,172 @@
+//! Columnar (struct-of-arrays) spectra
+//!
+//! [`SpectrumColumns`] holds one contiguous `Vec` per quantity, the layout
+//! plotting, FFT and FFI code usually wants, and converts losslessly to and
+//! from `Vec<OpticalResult>`.
+
+use crate::core::{OpticalMetadata, OpticalResult};
+
+/// A spectrum with one column per quantity, all of the same length
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpectrumColumns {
+    /// Wavelength in nm
+    pub wavelength: Vec<f64>,
+    pub q_sca: Vec<f64>,
+    pub q_abs: Vec<f64>,
+    pub q_ext: Vec<f64>,
+    /// Cross sections in nm²
+    pub c_sca: Vec<f64>,
+    pub c_abs: Vec<f64>,
+    pub c_ext: Vec<f64>,
+    pub g: Vec<f64>,
+    pub q_pr: Vec<f64>,
+    pub size_parameter: Vec<f64>,
+    pub num_terms: Vec<Option<usize>>,
+    pub converged: Vec<bool>,
+    pub notes: Vec<Vec<String>>,
+}
+
+impl SpectrumColumns {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            wavelength: Vec::with_capacity(capacity),
+            q_sca: Vec::with_capacity(capacity),
+            q_abs: Vec::with_capacity(capacity),
+            q_ext: Vec::with_capacity(capacity),
+            c_sca: Vec::with_capacity(capacity),
+            c_abs: Vec::with_capacity(capacity),
+            c_ext: Vec::with_capacity(capacity),
+            g: Vec::with_capacity(capacity),
+            q_pr: Vec::with_capacity(capacity),
+            size_parameter: Vec::with_capacity(capacity),
+            num_terms: Vec::with_capacity(capacity),
+            converged: Vec::with_capacity(capacity),
+            notes: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.wavelength.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wavelength.is_empty()
+    }
+
+    /// Append one result to every column
+    pub fn push(&mut self, result: &OpticalResult) {
+        self.wavelength.push(result.wavelength);
+        self.q_sca.push(result.q_sca);
+        self.q_abs.push(result.q_abs);
+        self.q_ext.push(result.q_ext);
+        self.c_sca.push(result.c_sca);
+        self.c_abs.push(result.c_abs);
+        self.c_ext.push(result.c_ext);
+        self.g.push(result.g);
+        self.q_pr.push(result.q_pr);
+        self.size_parameter.push(result.metadata.size_parameter);
+        self.num_terms.push(result.metadata.num_terms);
+        self.converged.push(result.metadata.converged);
+        self.notes.push(result.metadata.notes.clone());
+    }
+
+    /// The `i`-th row as an [`OpticalResult`]
+    pub fn row(&self, i: usize) -> OpticalResult {
+        OpticalResult {
+            wavelength: self.wavelength[i],
+            q_sca: self.q_sca[i],
+            q_abs: self.q_abs[i],
+            q_ext: self.q_ext[i],
+            c_sca: self.c_sca[i],
+            c_abs: self.c_abs[i],
+            c_ext: self.c_ext[i],
+            g: self.g[i],
+            q_pr: self.q_pr[i],
+            metadata: OpticalMetadata {
+                num_terms: self.num_terms[i],
+                converged: self.converged[i],
+                size_parameter: self.size_parameter[i],
+                notes: self.notes[i].clone(),
+            },
+        }
+    }
+
+    /// (wavelength, value) pairs of one column, e.g. for a plot line
+    pub fn series(&self, values: &[f64]) -> Vec<[f64; 2]> {
+        self.wavelength.iter().zip(values).map(|(&x, &y)| [x, y]).collect()
+    }
+}
+
+impl From<&[OpticalResult]> for SpectrumColumns {
+    fn from(results: &[OpticalResult]) -> Self {
+        let mut columns = Self::with_capacity(results.len());
+        for result in results {
+            columns.push(result);
+        }
+        columns
+    }
+}
+
+impl From<&SpectrumColumns> for Vec<OpticalResult> {
+    fn from(columns: &SpectrumColumns) -> Self {
+        (0..columns.len()).map(|i| columns.row(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::ComputeEngine;
+    use crate::core::RefractiveIndex;
+    use crate::physics::optical::mie::MieModel;
+
+    fn assert_same(a: &OpticalResult, b: &OpticalResult) {
+        assert_eq!(
+            (a.wavelength, a.q_sca, a.q_abs, a.q_ext, a.c_sca, a.c_abs, a.c_ext),
+            (b.wavelength, b.q_sca, b.q_abs, b.q_ext, b.c_sca, b.c_abs, b.c_ext)
+        );
+        assert_eq!((a.g, a.q_pr), (b.g, b.q_pr));
+        assert_eq!(a.metadata.size_parameter, b.metadata.size_parameter);
+        assert_eq!(a.metadata.num_terms, b.metadata.num_terms);
+        assert_eq!(a.metadata.converged, b.metadata.converged);
+        assert_eq!(a.metadata.notes, b.metadata.notes);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_values_and_lengths() {
+        let model = MieModel::new(30.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        let wavelengths: Vec<f64> = (400..=700).step_by(25).map(f64::from).collect();
+        let results = ComputeEngine::new(Some(2)).unwrap().spectrum(&model, &wavelengths).unwrap();
+
+        let columns = SpectrumColumns::from(results.as_slice());
+        assert_eq!(columns.len(), results.len());
+        for column in [
+            &columns.q_sca,
+            &columns.q_abs,
+            &columns.q_ext,
+            &columns.c_sca,
+            &columns.c_abs,
+            &columns.c_ext,
+            &columns.g,
+            &columns.q_pr,
+            &columns.size_parameter,
+        ] {
+            assert_eq!(column.len(), results.len());
+        }
+        assert_eq!(columns.wavelength, wavelengths);
+        assert_eq!(columns.series(&columns.q_ext)[3], [results[3].wavelength, results[3].q_ext]);
+
+        let back = Vec::<OpticalResult>::from(&columns);
+        assert_eq!(back.len(), results.len());
+        for (a, b) in results.iter().zip(&back) {
+            assert_same(a, b);
+        }
+    }
+
+    #[test]
+    fn test_empty_spectrum() {
+        let columns = SpectrumColumns::from(&[][..]);
+        assert!(columns.is_empty());
+        assert!(Vec::<OpticalResult>::from(&columns).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+//! Baseline vs current spectrum alignment
+//!
+//! Spectra computed on different wavelength grids are compared on the
+//! current grid, interpolating the baseline linearly where they overlap.
+
+use super::columns::SpectrumColumns;
+use crate::core::OpticalResult;
+use crate::utils::interp::{self, Extrapolation};
+
+/// Efficiency triple at one wavelength
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Efficiencies {
+    pub q_sca: f64,
+    pub q_abs: f64,
+    pub q_ext: f64,
+}
+
+impl From<&OpticalResult> for Efficiencies {
+    fn from(r: &OpticalResult) -> Self {
+        Self {
+            q_sca: r.q_sca,
+            q_abs: r.q_abs,
+            q_ext: r.q_ext,
+        }
+    }
+}
+
+/// Baseline and current efficiencies on a shared wavelength
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlignedPoint {
+    pub wavelength: f64,
+    pub baseline: Efficiencies,
+    pub current: Efficiencies,
+}
+
+/// Percent change from `baseline` to `current`, `None` when the baseline is zero
+pub fn percent_change(baseline: f64, current: f64) -> Option<f64> {
+    if baseline == 0.0 || !baseline.is_finite() {
+        None
+    } else {
+        Some((current - baseline) / baseline * 100.0)
+    }
+}
+
+/// Interpolate `spectrum` (sorted by wavelength) at `wavelength`
+///
+/// Returns `None` outside the spectrum's wavelength range.
+pub(crate) fn interpolate(spectrum: &SpectrumColumns, wavelength: f64) -> Option<Efficiencies> {
+    let at = |ys: &[f64]| interp::linear(&spectrum.wavelength, ys, wavelength, Extrapolation::Error).ok();
+    Some(Efficiencies {
+        q_sca: at(&spectrum.q_sca)?,
+        q_abs: at(&spectrum.q_abs)?,
+        q_ext: at(&spectrum.q_ext)?,
+    })
+}
+
+/// Align a baseline spectrum onto the current spectrum's grid
+///
+/// Only wavelengths of `current` inside the baseline's range are kept.
+pub fn align_spectra(baseline: &[OpticalResult], current: &[OpticalResult]) -> Vec<AlignedPoint> {
+    let baseline = SpectrumColumns::from(baseline);
+    current
+        .iter()
+        .filter_map(|r| {
+            interpolate(&baseline, r.wavelength).map(|b| AlignedPoint {
+                wavelength: r.wavelength,
+                baseline: b,
+                current: r.into(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(wavelength: f64, q: f64) -> OpticalResult {
+        OpticalResult::for_test(wavelength, q, 2.0 * q)
+    }
+
+    #[test]
+    fn test_align_on_offset_grids() {
+        // Baseline every 10 nm, current every 5 nm and extending past the baseline
+        let baseline: Vec<_> = (0..=10).map(|i| point(400.0 + 10.0 * i as f64, i as f64)).collect();
+        let current: Vec<_> = (0..=24).map(|i| point(395.0 + 5.0 * i as f64, 1.0)).collect();
+
+        let aligned = align_spectra(&baseline, &current);
+        assert_eq!(aligned.first().unwrap().wavelength, 400.0);
+        assert_eq!(aligned.last().unwrap().wavelength, 500.0);
+        assert_eq!(aligned.len(), 21);
+
+        // 405 nm lies halfway between baseline samples 0 and 1
+        let p = aligned.iter().find(|p| p.wavelength == 405.0).unwrap();
+        assert!((p.baseline.q_sca - 0.5).abs() < 1e-12);
+        assert!((p.baseline.q_ext - 1.5).abs() < 1e-12);
+        assert_eq!(p.current.q_sca, 1.0);
+    }
+
+    #[test]
+    fn test_percent_change_zero_baseline() {
+        assert_eq!(percent_change(0.0, 1.0), None);
+        assert_eq!(percent_change(2.0, 3.0), Some(50.0));
+    }
+}
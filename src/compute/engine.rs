@@ -1,3 +1,290 @@
-//! Compute engine stub
+//! Compute engine
+//!
+//! Runs spectrum and sweep calculations on a dedicated rayon thread pool so
+//! NanoCalc never oversubscribes the global pool on shared machines.
 
-// Placeholder for MVP
+use super::cache::ComputeCache;
+use super::columns::SpectrumColumns;
+use crate::core::{
+    CalcResult, CacheableOpticalModel, CalculationError, OpticalModel, OpticalResult, ParticleShape, PhysicsModel, ValidationResult,
+    ValidityFlag, Warning,
+};
+use crate::physics::optical::mie::MieModel;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Parallel executor backed by its own thread pool
+///
+/// Clones share the pool and the cache, so a background run can own one.
+#[derive(Clone)]
+pub struct ComputeEngine {
+    pool: Arc<ThreadPool>,
+    requested_threads: Option<usize>,
+    cache: Arc<ComputeCache>,
+}
+
+impl ComputeEngine {
+    /// Create an engine with `compute_threads` workers (`None` = all cores)
+    pub fn new(compute_threads: Option<usize>) -> CalcResult<Self> {
+        let mut builder = ThreadPoolBuilder::new().thread_name(|i| format!("nanocalc-{}", i));
+        if let Some(threads) = compute_threads {
+            if threads == 0 {
+                return Err(CalculationError::InvalidInput(
+                    "Thread count must be at least 1".to_string(),
+                ));
+            }
+            builder = builder.num_threads(threads);
+        }
+        let pool = builder
+            .build()
+            .map_err(|e| CalculationError::InvalidInput(format!("Cannot build thread pool: {}", e)))?;
+
+        Ok(Self {
+            pool: Arc::new(pool),
+            requested_threads: compute_threads,
+            cache: Arc::new(ComputeCache::default()),
+        })
+    }
+
+    /// Thread count this engine was configured with
+    pub fn requested_threads(&self) -> Option<usize> {
+        self.requested_threads
+    }
+
+    /// Actual number of worker threads in the pool
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Map `f` over `items` in parallel on this engine's pool, preserving order
+    pub fn par_map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync + Send,
+    {
+        self.pool.install(|| items.par_iter().map(f).collect())
+    }
+
+    /// Calculate a spectrum in parallel, one task per wavelength
+    ///
+    /// Each point is computed independently, so the result is identical to
+    /// [`serial_spectrum`] whatever the thread count.
+    pub fn spectrum<M: OpticalModel + ?Sized>(&self, model: &M, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        self.par_map(wavelengths, |&wl| spectrum_point(model, wl))
+            .into_iter()
+            .collect()
+    }
+
+    /// [`Self::spectrum`], reusing the result of an identical earlier request
+    pub fn cached_spectrum<M: CacheableOpticalModel + ?Sized>(&self, model: &M, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        self.cache
+            .get_or_compute_spectrum(model, wavelengths, || self.spectrum(model, wavelengths))
+    }
+
+    /// [`Self::cached_spectrum`] through
+    /// [`OpticalModel::calculate_spectrum_with_progress`]
+    ///
+    /// Each chunk is computed in parallel and passed to `partial` once it is
+    /// done; a cached spectrum is passed whole. Only a complete spectrum is
+    /// cached; a cancelled run returns the points finished so far.
+    pub fn cached_spectrum_with_progress<M: CacheableOpticalModel + ?Sized>(
+        &self,
+        model: &M,
+        wavelengths: &[f64],
+        progress: &mut dyn FnMut(usize, usize),
+        partial: &(dyn Fn(&[OpticalResult]) + Sync),
+        cancel: &AtomicBool,
+    ) -> CalcResult<Vec<OpticalResult>> {
+        if let Some(results) = self.cache.lookup_spectrum(model, wavelengths) {
+            partial(&results);
+            progress(results.len(), results.len());
+            return Ok(results);
+        }
+        let pooled = Pooled { engine: self, model, partial };
+        let results = pooled.calculate_spectrum_with_progress(wavelengths, progress, cancel)?;
+        if results.len() == wavelengths.len() {
+            self.cache.store_spectrum(model, wavelengths, results.clone());
+        }
+        Ok(results)
+    }
+
+    /// Results cache used by [`Self::cached_spectrum`]
+    pub fn cache(&self) -> &ComputeCache {
+        &self.cache
+    }
+
+    /// [`Self::spectrum`] as one contiguous column per quantity
+    pub fn spectrum_columns(&self, model: &MieModel, wavelengths: &[f64]) -> CalcResult<SpectrumColumns> {
+        Ok(SpectrumColumns::from(self.spectrum(model, wavelengths)?.as_slice()))
+    }
+
+    /// Spectrum with one pass of adaptive refinement
+    ///
+    /// Wherever Q_ext changes by more than `threshold` (relative to the peak)
+    /// between neighbouring points, the midpoint is computed and merged in.
+    pub fn refined_spectrum<M: OpticalModel + ?Sized>(
+        &self,
+        model: &M,
+        wavelengths: &[f64],
+        threshold: f64,
+    ) -> CalcResult<Vec<OpticalResult>> {
+        let mut results = self.spectrum(model, wavelengths)?;
+        let peak = results.iter().map(|r| r.q_ext.abs()).fold(0.0, f64::max);
+        if peak == 0.0 {
+            return Ok(results);
+        }
+
+        let midpoints: Vec<f64> = results
+            .windows(2)
+            .filter(|w| (w[1].q_ext - w[0].q_ext).abs() > threshold * peak)
+            .map(|w| 0.5 * (w[0].wavelength + w[1].wavelength))
+            .collect();
+        results.extend(self.spectrum(model, &midpoints)?);
+        results.sort_by(|a, b| a.wavelength.total_cmp(&b.wavelength));
+        Ok(results)
+    }
+}
+
+/// Calculate a spectrum on the calling thread
+pub fn serial_spectrum<M: OpticalModel + ?Sized>(model: &M, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+    wavelengths.iter().map(|&wl| spectrum_point(model, wl)).collect()
+}
+
+/// `model` evaluated at `wavelength`
+pub(super) fn spectrum_point<M: OpticalModel + ?Sized>(model: &M, wavelength: f64) -> CalcResult<OpticalResult> {
+    let mut results = model.calculate_spectrum(&[wavelength])?;
+    results.pop().ok_or_else(|| CalculationError::NumericalInstability(format!("No result at {} nm", wavelength)))
+}
+
+/// A model whose spectra run on an engine's pool, each passed on as it finishes
+struct Pooled<'a, M: ?Sized> {
+    engine: &'a ComputeEngine,
+    model: &'a M,
+    partial: &'a (dyn Fn(&[OpticalResult]) + Sync),
+}
+
+impl<M: OpticalModel + ?Sized> PhysicsModel for Pooled<'_, M> {
+    fn name(&self) -> &str {
+        self.model.name()
+    }
+
+    fn description(&self) -> &str {
+        self.model.description()
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        self.model.validate()
+    }
+
+    fn warnings(&self) -> Vec<Warning> {
+        self.model.warnings()
+    }
+}
+
+impl<M: OpticalModel + ?Sized> OpticalModel for Pooled<'_, M> {
+    fn calculate(&self) -> CalcResult<OpticalResult> {
+        self.model.calculate()
+    }
+
+    fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        let results = self.engine.spectrum(self.model, wavelengths)?;
+        (self.partial)(&results);
+        Ok(results)
+    }
+
+    fn shape(&self) -> ParticleShape {
+        self.model.shape()
+    }
+
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+        self.model.validity_region(wavelengths)
+    }
+}
+
+impl Default for ComputeEngine {
+    fn default() -> Self {
+        Self::new(None).expect("default thread pool")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+
+    fn model() -> MieModel {
+        MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33)
+    }
+
+    #[test]
+    fn test_pool_sized_as_requested() {
+        let engine = ComputeEngine::new(Some(1)).unwrap();
+        assert_eq!(engine.num_threads(), 1);
+        let engine = ComputeEngine::new(Some(3)).unwrap();
+        assert_eq!(engine.num_threads(), 3);
+        assert!(ComputeEngine::new(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_single_thread_matches_multi_thread() {
+        let wavelengths: Vec<f64> = (300..=800).step_by(5).map(|w| w as f64).collect();
+        let single = ComputeEngine::new(Some(1)).unwrap().spectrum(&model(), &wavelengths).unwrap();
+        let multi = ComputeEngine::new(Some(4)).unwrap().spectrum(&model(), &wavelengths).unwrap();
+        let serial = serial_spectrum(&model(), &wavelengths).unwrap();
+
+        assert_eq!(single.len(), multi.len());
+        assert_eq!(serial.len(), multi.len());
+        for ((a, b), c) in single.iter().zip(&multi).zip(&serial) {
+            assert_eq!(a.wavelength, b.wavelength);
+            assert_eq!(a.q_ext.to_bits(), b.q_ext.to_bits());
+            assert_eq!(a.q_sca.to_bits(), b.q_sca.to_bits());
+            assert_eq!(c.q_ext.to_bits(), b.q_ext.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_refinement_adds_points_only_where_steep() {
+        let engine = ComputeEngine::new(Some(2)).unwrap();
+        let wavelengths: Vec<f64> = (300..=800).step_by(50).map(|w| w as f64).collect();
+        let coarse = engine.spectrum(&model(), &wavelengths).unwrap();
+        let refined = engine.refined_spectrum(&model(), &wavelengths, 0.05).unwrap();
+
+        assert!(refined.len() > coarse.len());
+        assert!(refined.windows(2).all(|w| w[0].wavelength < w[1].wavelength));
+
+        let flat = engine.refined_spectrum(&model(), &wavelengths, f64::INFINITY).unwrap();
+        assert_eq!(flat.len(), coarse.len());
+    }
+
+    #[test]
+    fn test_partials_add_up_to_the_spectrum() {
+        use crate::core::PROGRESS_INTERVAL;
+        use std::sync::Mutex;
+
+        let engine = ComputeEngine::new(Some(2)).unwrap();
+        let wavelengths: Vec<f64> = (300..=800).step_by(5).map(|w| w as f64).collect();
+        let never = AtomicBool::new(false);
+        let chunks = Mutex::new(Vec::new());
+        let partial = |chunk: &[OpticalResult]| chunks.lock().unwrap().push(chunk.to_vec());
+        let full = engine
+            .cached_spectrum_with_progress(&model(), &wavelengths, &mut |_, _| {}, &partial, &never)
+            .unwrap();
+
+        let streamed = std::mem::take(&mut *chunks.lock().unwrap());
+        assert_eq!(streamed.len(), wavelengths.len().div_ceil(PROGRESS_INTERVAL));
+        let joined: Vec<OpticalResult> = streamed.into_iter().flatten().collect();
+        assert_eq!(joined.len(), full.len());
+        for (a, b) in joined.iter().zip(&full) {
+            assert_eq!((a.wavelength, a.q_ext.to_bits()), (b.wavelength, b.q_ext.to_bits()));
+        }
+
+        // A cached spectrum is passed on in one piece
+        engine
+            .cached_spectrum_with_progress(&model(), &wavelengths, &mut |_, _| {}, &partial, &never)
+            .unwrap();
+        assert_eq!(chunks.lock().unwrap().len(), 1);
+    }
+}
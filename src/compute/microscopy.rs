@@ -0,0 +1,74 @@
+//! Collection efficiency of a microscope objective
+//!
+//! Integrates a normalized phase function over the collection cone of an
+//! objective with numerical aperture NA immersed in a medium of index n.
+
+use std::f64::consts::PI;
+
+/// Number of Simpson intervals used for the cone integral
+const INTEGRATION_STEPS: usize = 720;
+
+/// Half-angle of the collection cone in radians, θ_max = arcsin(NA/n)
+///
+/// An NA above the medium index cannot couple scattered light beyond the
+/// critical angle, so the cone is capped at the full hemisphere.
+pub fn collection_half_angle(numerical_aperture: f64, n_medium: f64) -> f64 {
+    if numerical_aperture <= 0.0 || n_medium <= 0.0 {
+        return 0.0;
+    }
+    (numerical_aperture / n_medium).min(1.0).asin()
+}
+
+/// Fraction of scattered power collected in a forward cone of half-angle arcsin(NA/n)
+///
+/// `phase_function` maps the scattering angle θ (radians) to p(θ), normalized
+/// so that ∫ p dΩ = 1 over the full sphere.
+pub fn collected_fraction<F>(phase_function: F, numerical_aperture: f64, n_medium: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let theta_max = collection_half_angle(numerical_aperture, n_medium);
+    if theta_max <= 0.0 {
+        return 0.0;
+    }
+
+    // Composite Simpson rule for 2π ∫ p(θ) sin θ dθ
+    let h = theta_max / INTEGRATION_STEPS as f64;
+    let integrand = |theta: f64| phase_function(theta) * theta.sin();
+    let mut sum = integrand(0.0) + integrand(theta_max);
+    for i in 1..INTEGRATION_STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(i as f64 * h);
+    }
+
+    (2.0 * PI * sum * h / 3.0).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::optical::mie::MieCoefficients;
+    use num_complex::Complex64;
+
+    #[test]
+    fn test_full_hemisphere_collects_forward_scatterer() {
+        // Large dielectric sphere scatters strongly forward
+        let coeffs = MieCoefficients::compute(Complex64::new(1.2, 0.0), 8.0).unwrap();
+        let fraction = collected_fraction(|t| coeffs.phase_function(t), 1.33, 1.33);
+        assert!(fraction >= 0.5, "collected {}", fraction);
+    }
+
+    #[test]
+    fn test_zero_na_collects_nothing() {
+        let coeffs = MieCoefficients::compute(Complex64::new(1.2, 0.0), 8.0).unwrap();
+        assert!(collected_fraction(|t| coeffs.phase_function(t), 1e-6, 1.33) < 1e-6);
+        assert_eq!(collected_fraction(|t| coeffs.phase_function(t), 0.0, 1.33), 0.0);
+    }
+
+    #[test]
+    fn test_isotropic_hemisphere_is_half() {
+        let isotropic = |_: f64| 1.0 / (4.0 * PI);
+        let fraction = collected_fraction(isotropic, 1.5, 1.0);
+        assert!((fraction - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,137 @@
+//! Mixed-population ensembles
+//!
+//! A sample holding several particle populations is described by number
+//! fractions fᵢ. Ensemble cross sections are C = Σ fᵢ Cᵢ, i.e. per average
+//! particle, and efficiencies are taken relative to the fraction-weighted
+//! geometric area G = Σ fᵢ Gᵢ so that Q = C / G.
+
+use crate::core::{CalcResult, CalculationError, OpticalMetadata, OpticalModel, OpticalResult};
+
+/// Allowed deviation of the fraction sum from 1 before it is rejected
+pub const FRACTION_SUM_TOLERANCE: f64 = 1e-3;
+
+/// Number-fraction-weighted spectrum of a mixture of particle populations
+///
+/// Fractions must be non-negative and sum to 1 within
+/// [`FRACTION_SUM_TOLERANCE`]; they are renormalized to sum exactly to 1.
+pub fn mixture_spectrum(
+    components: &[(Box<dyn OpticalModel>, f64)],
+    wavelengths: &[f64],
+) -> CalcResult<Vec<OpticalResult>> {
+    if components.is_empty() {
+        return Err(CalculationError::InvalidInput(
+            "Mixture needs at least one component".to_string(),
+        ));
+    }
+    if components.iter().any(|(_, f)| !(*f >= 0.0 && f.is_finite())) {
+        return Err(CalculationError::InvalidInput(
+            "Number fractions must be non-negative".to_string(),
+        ));
+    }
+    let total: f64 = components.iter().map(|(_, f)| f).sum();
+    if (total - 1.0).abs() > FRACTION_SUM_TOLERANCE {
+        return Err(CalculationError::InvalidInput(format!(
+            "Number fractions sum to {:.4}, expected 1",
+            total
+        )));
+    }
+
+    let fractions: Vec<f64> = components.iter().map(|(_, f)| f / total).collect();
+    let geometric_area: f64 = components
+        .iter()
+        .zip(&fractions)
+        .map(|((model, _), f)| f * model.geometric_cross_section())
+        .sum();
+    let spectra = components
+        .iter()
+        .map(|(model, _)| model.calculate_spectrum(wavelengths))
+        .collect::<CalcResult<Vec<_>>>()?;
+
+    Ok(wavelengths
+        .iter()
+        .enumerate()
+        .map(|(i, &wavelength)| {
+            let weighted = |field: fn(&OpticalResult) -> f64| -> f64 {
+                spectra.iter().zip(&fractions).map(|(s, f)| f * field(&s[i])).sum()
+            };
+            let c_sca = weighted(|r| r.c_sca);
+            let c_abs = weighted(|r| r.c_abs);
+            let c_ext = weighted(|r| r.c_ext);
+            // g averaged over the scattered power
+            let g = if c_sca > 0.0 { weighted(|r| r.g * r.c_sca) / c_sca } else { 0.0 };
+            let (q_sca, q_ext) = (c_sca / geometric_area, c_ext / geometric_area);
+
+            OpticalResult {
+                wavelength,
+                q_sca,
+                q_abs: c_abs / geometric_area,
+                q_ext,
+                c_sca,
+                c_abs,
+                c_ext,
+                g,
+                q_pr: q_ext - g * q_sca,
+                metadata: OpticalMetadata {
+                    num_terms: None,
+                    converged: spectra.iter().all(|s| s[i].metadata.converged),
+                    size_parameter: weighted(|r| r.metadata.size_parameter),
+                    notes: vec![format!(
+                        "Mixture of {} components, efficiencies per mean geometric area {:.2} nm²",
+                        components.len(),
+                        geometric_area
+                    )],
+                },
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+    use crate::physics::optical::mie::MieModel;
+
+    fn gold(radius: f64) -> Box<dyn OpticalModel> {
+        Box::new(MieModel::new(radius, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33))
+    }
+
+    fn wavelengths() -> Vec<f64> {
+        (400..=700).step_by(50).map(|w| w as f64).collect()
+    }
+
+    #[test]
+    fn test_single_component_matches_model() {
+        let direct = gold(30.0).calculate_spectrum(&wavelengths()).unwrap();
+        let mixed = mixture_spectrum(&[(gold(30.0), 1.0)], &wavelengths()).unwrap();
+
+        for (a, b) in direct.iter().zip(&mixed) {
+            assert!((a.q_ext - b.q_ext).abs() < 1e-12);
+            assert!((a.c_sca - b.c_sca).abs() < 1e-9);
+            assert!((a.c_abs - b.c_abs).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fractions_are_normalized() {
+        let small = gold(20.0).calculate_spectrum(&wavelengths()).unwrap();
+        let large = gold(40.0).calculate_spectrum(&wavelengths()).unwrap();
+        // Sum slightly off 1 is accepted and rescaled
+        let mixed = mixture_spectrum(&[(gold(20.0), 0.7), (gold(40.0), 0.3004)], &wavelengths()).unwrap();
+
+        let (f_small, f_large) = (0.7 / 1.0004, 0.3004 / 1.0004);
+        for ((s, l), m) in small.iter().zip(&large).zip(&mixed) {
+            let expected = f_small * s.c_ext + f_large * l.c_ext;
+            assert!((m.c_ext - expected).abs() < 1e-9 * expected);
+        }
+    }
+
+    #[test]
+    fn test_fractions_must_sum_to_one() {
+        let err = mixture_spectrum(&[(gold(20.0), 0.5), (gold(40.0), 0.3)], &wavelengths());
+        assert!(matches!(err, Err(CalculationError::InvalidInput(_))));
+        let err = mixture_spectrum(&[(gold(20.0), 1.5), (gold(40.0), -0.5)], &wavelengths());
+        assert!(err.is_err());
+        assert!(mixture_spectrum(&[], &wavelengths()).is_err());
+    }
+}
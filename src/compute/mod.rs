@@ -1,3 +1,47 @@
 //! Compute engine
 
+pub mod background;
+pub mod cache;
+pub mod columns;
+pub mod comparison;
 pub mod engine;
+pub mod microscopy;
+pub mod mixture;
+pub mod moments;
+pub mod multipole;
+pub mod paradox;
+pub mod peaks;
+pub mod polydisperse;
+pub mod refine;
+pub mod reference;
+pub mod residuals;
+pub mod sensitivity;
+pub mod spectral;
+pub mod sweep;
+pub mod typed;
+pub mod universal;
+pub mod wavelength_list;
+
+pub use background::{BackgroundRun, RunProgress};
+pub use cache::{spectrum_key, ComputeCache, DEFAULT_CACHE_CAPACITY};
+pub use columns::SpectrumColumns;
+pub use comparison::align_spectra;
+pub use engine::{serial_spectrum, ComputeEngine};
+pub use microscopy::collected_fraction;
+pub use mixture::mixture_spectrum;
+pub use moments::{spectral_moments, SpectralMoments};
+pub use multipole::{multipole_spectrum, MultipoleDecomposition};
+pub use paradox::{extinction_paradox, ExtinctionParadox, EXTINCTION_LIMIT};
+pub use peaks::{dominant_peak, refined_peak_wavelength, Peak};
+pub use polydisperse::{band_edges, monte_carlo_band, monte_carlo_band_with_progress, PolydisperseConfig, SpectralBand};
+pub use refine::refine_until;
+pub use reference::{
+    against_geometric_area, against_reference, bandgap_against_bulk, thermal_against_bulk, ReferenceComparison,
+};
+pub use residuals::{q_ext_residuals, residual_stats, Residual, ResidualStats};
+pub use sensitivity::{peak_sensitivities, Parameter, Sensitivity};
+pub use sweep::{ParameterSweep, SweepAxis};
+pub use spectral::{c_ext_vs_energy, integrated_cross_section};
+pub use typed::{calculate_optical, OpticalParams, TypedOpticalResult};
+pub use universal::{efficiency_at_x, efficiency_vs_x};
+pub use wavelength_list::{at_wavelengths, parse_wavelength_list, WavelengthList};
@@ -0,0 +1,83 @@
+//! Spectral moments of the extinction curve
+//!
+//! Centroid and RMS width summarize a Q_ext spectrum without assuming a
+//! single symmetric peak, so they stay meaningful for asymmetric or
+//! multi-peak spectra where peak position and FWHM do not.
+
+use super::spectral::trapezoid_window;
+use crate::core::OpticalResult;
+
+/// Zeroth to second moments of Q_ext(λ)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralMoments {
+    /// ∫ Q_ext dλ, in nm
+    pub area: f64,
+    /// First moment ∫ λ·Q_ext dλ / area, in nm
+    pub centroid: f64,
+    /// Standard deviation √(∫ (λ − centroid)²·Q_ext dλ / area), in nm
+    pub width: f64,
+}
+
+/// Moments of Q_ext over the spectrum's wavelength range
+///
+/// `results` must be sorted by wavelength. `None` for fewer than two points
+/// or a non-positive area.
+pub fn spectral_moments(results: &[OpticalResult]) -> Option<SpectralMoments> {
+    let (first, last) = (results.first()?.wavelength, results.last()?.wavelength);
+    let integrate = |weight: &dyn Fn(f64) -> f64| {
+        let points: Vec<(f64, f64)> = results.iter().map(|r| (r.wavelength, weight(r.wavelength) * r.q_ext)).collect();
+        trapezoid_window(&points, first, last)
+    };
+
+    let area = integrate(&|_| 1.0);
+    if area.is_nan() || area <= 0.0 {
+        return None;
+    }
+    let centroid = integrate(&|wl| wl) / area;
+    let variance = integrate(&|wl| (wl - centroid).powi(2)) / area;
+    Some(SpectralMoments {
+        area,
+        centroid,
+        width: variance.max(0.0).sqrt(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spectrum(q: impl Fn(f64) -> f64, min: f64, max: f64) -> Vec<OpticalResult> {
+        (0..=((max - min) as usize))
+            .map(|i| {
+                let wavelength = min + i as f64;
+                OpticalResult::for_test(wavelength, q(wavelength), 0.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gaussian_moments() {
+        let sigma = 30.0;
+        let results = spectrum(|wl| 2.0 * (-0.5 * ((wl - 520.0) / sigma).powi(2)).exp(), 300.0, 800.0);
+        let m = spectral_moments(&results).unwrap();
+        assert!((m.centroid - 520.0).abs() < 1e-6);
+        assert!((m.width - sigma).abs() < 1e-2);
+        assert!((m.area - 2.0 * sigma * (2.0 * std::f64::consts::PI).sqrt()).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_asymmetric_ramp_moments() {
+        // Q rising linearly over 100 nm: mean at 2/3, variance L²/18
+        let results = spectrum(|wl| wl - 300.0, 300.0, 400.0);
+        let m = spectral_moments(&results).unwrap();
+        assert!((m.centroid - (300.0 + 200.0 / 3.0)).abs() < 0.05);
+        assert!((m.width - (100.0f64.powi(2) / 18.0).sqrt()).abs() < 0.05);
+        assert!((m.area - 5000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_or_zero_spectrum() {
+        assert!(spectral_moments(&[]).is_none());
+        assert!(spectral_moments(&spectrum(|_| 0.0, 300.0, 310.0)).is_none());
+    }
+}
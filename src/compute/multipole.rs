@@ -0,0 +1,100 @@
+//! Multipole decomposition of Mie spectra
+//!
+//! Splits the efficiencies into per-order electric (aₙ) and magnetic (bₙ)
+//! contributions, e.g. to locate electric/magnetic dipole crossings.
+
+use crate::core::CalcResult;
+use crate::physics::optical::mie::{MieCoefficients, MieModel};
+use serde::Serialize;
+
+/// Contributions of one multipole order n
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MultipoleOrder {
+    /// Order n (1 = dipole, 2 = quadrupole, ...)
+    pub order: usize,
+    pub a_abs: f64,
+    pub b_abs: f64,
+    /// (2/x²)(2n+1)|aₙ|²
+    pub q_sca_electric: f64,
+    /// (2/x²)(2n+1)|bₙ|²
+    pub q_sca_magnetic: f64,
+    /// (2/x²)(2n+1) Re(aₙ)
+    pub q_ext_electric: f64,
+    /// (2/x²)(2n+1) Re(bₙ)
+    pub q_ext_magnetic: f64,
+}
+
+/// Per-order contributions at one wavelength
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MultipoleDecomposition {
+    pub wavelength: f64,
+    pub size_parameter: f64,
+    pub orders: Vec<MultipoleOrder>,
+}
+
+impl MultipoleDecomposition {
+    pub fn from_coefficients(wavelength: f64, coeffs: &MieCoefficients) -> Self {
+        let norm = 2.0 / (coeffs.x * coeffs.x);
+        let orders = coeffs
+            .a
+            .iter()
+            .zip(&coeffs.b)
+            .enumerate()
+            .map(|(i, (an, bn))| {
+                let order = i + 1;
+                let weight = norm * (2 * order + 1) as f64;
+                MultipoleOrder {
+                    order,
+                    a_abs: an.norm(),
+                    b_abs: bn.norm(),
+                    q_sca_electric: weight * an.norm_sqr(),
+                    q_sca_magnetic: weight * bn.norm_sqr(),
+                    q_ext_electric: weight * an.re,
+                    q_ext_magnetic: weight * bn.re,
+                }
+            })
+            .collect();
+
+        Self {
+            wavelength,
+            size_parameter: coeffs.x,
+            orders,
+        }
+    }
+
+    /// Highest order retained in the series
+    pub fn max_order(&self) -> usize {
+        self.orders.len()
+    }
+}
+
+/// Multipole decomposition of `model` at each wavelength
+pub fn multipole_spectrum(model: &MieModel, wavelengths: &[f64]) -> CalcResult<Vec<MultipoleDecomposition>> {
+    wavelengths
+        .iter()
+        .map(|&wl| {
+            let mut point = model.clone();
+            point.wavelength = wl;
+            Ok(MultipoleDecomposition::from_coefficients(wl, &point.coefficients()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+
+    #[test]
+    fn test_orders_sum_to_totals() {
+        let model = MieModel::new(120.0, 500.0, RefractiveIndex::new(3.5, 0.01), 1.0);
+        let coeffs = model.coefficients().unwrap();
+        let decomposition = MultipoleDecomposition::from_coefficients(500.0, &coeffs);
+
+        assert_eq!(decomposition.max_order(), coeffs.num_terms());
+        let q_sca: f64 = decomposition.orders.iter().map(|o| o.q_sca_electric + o.q_sca_magnetic).sum();
+        let q_ext: f64 = decomposition.orders.iter().map(|o| o.q_ext_electric + o.q_ext_magnetic).sum();
+        assert!((q_sca - coeffs.q_sca()).abs() < 1e-12);
+        assert!((q_ext - coeffs.q_ext()).abs() < 1e-12);
+    }
+}
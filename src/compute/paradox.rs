@@ -0,0 +1,73 @@
+//! Extinction paradox indicator
+//!
+//! For a particle much larger than the wavelength, Q_ext tends to 2 rather
+//! than 1: the geometric shadow removes πr² of the beam and diffraction
+//! around the edge removes as much again.
+
+use crate::core::OpticalResult;
+use serde::{Deserialize, Serialize};
+
+/// Large-particle limit of Q_ext
+pub const EXTINCTION_LIMIT: f64 = 2.0;
+
+/// Size parameter above which the indicator is shown
+pub const PARADOX_MIN_X: f64 = 10.0;
+
+/// How close a large-particle result is to the extinction limit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExtinctionParadox {
+    pub size_parameter: f64,
+    pub q_ext: f64,
+}
+
+impl ExtinctionParadox {
+    /// Q_ext as a fraction of the limit
+    pub fn ratio(&self) -> f64 {
+        self.q_ext / EXTINCTION_LIMIT
+    }
+
+    /// Signed relative deviation from the limit
+    pub fn deviation(&self) -> f64 {
+        self.ratio() - 1.0
+    }
+}
+
+/// Indicator for `result`, if it is a full Mie series result at large x
+///
+/// Single-term results come from the Rayleigh approximation, whose Q_ext
+/// grows without bound and says nothing about the limit.
+pub fn extinction_paradox(result: &OpticalResult) -> Option<ExtinctionParadox> {
+    let series = result.metadata.num_terms.is_some_and(|n| n > 1);
+    let x = result.metadata.size_parameter;
+    (series && x >= PARADOX_MIN_X).then_some(ExtinctionParadox {
+        size_parameter: x,
+        q_ext: result.q_ext,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::universal::efficiency_at_x;
+    use num_complex::Complex64;
+
+    #[test]
+    fn test_indicator_only_above_threshold() {
+        let m = Complex64::new(1.33, 0.0);
+        assert!(extinction_paradox(&efficiency_at_x(m, 2.0).unwrap()).is_none());
+        assert!(extinction_paradox(&efficiency_at_x(m, PARADOX_MIN_X - 0.1).unwrap()).is_none());
+
+        let result = efficiency_at_x(m, 80.0).unwrap();
+        let paradox = extinction_paradox(&result).unwrap();
+        assert_eq!(paradox.q_ext, result.q_ext);
+        assert_eq!(paradox.size_parameter, 80.0);
+        assert!(paradox.deviation().abs() < 0.1, "Q_ext = {}", paradox.q_ext);
+    }
+
+    #[test]
+    fn test_rayleigh_result_is_ignored() {
+        let mut result = efficiency_at_x(Complex64::new(1.5, 0.0), 20.0).unwrap();
+        result.metadata.num_terms = Some(1);
+        assert!(extinction_paradox(&result).is_none());
+    }
+}
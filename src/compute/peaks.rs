@@ -0,0 +1,125 @@
+//! Resonance peak detection on computed spectra
+
+use super::columns::SpectrumColumns;
+use crate::core::OpticalResult;
+use crate::utils::features::level_crossing;
+
+/// A local maximum of Q_ext
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Peak {
+    /// Index into the spectrum
+    pub index: usize,
+    pub wavelength: f64,
+    /// Q_ext at the peak
+    pub value: f64,
+    /// Full width at half maximum in nm, if both (or one) half-maximum
+    /// crossings lie inside the data
+    pub fwhm: Option<f64>,
+}
+
+/// The highest interior maximum of Q_ext, with its FWHM
+///
+/// A maximum at either end of the range is not a resolved resonance, so it
+/// gives `None`. When only one half-maximum crossing is inside the data the
+/// width is estimated as twice the half width on that side.
+pub fn dominant_peak(results: &[OpticalResult]) -> Option<Peak> {
+    let (index, peak) = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.q_ext.is_finite())
+        .max_by(|(_, a), (_, b)| a.q_ext.total_cmp(&b.q_ext))?;
+    if index == 0 || index + 1 == results.len() || peak.q_ext <= 0.0 {
+        return None;
+    }
+
+    let columns = SpectrumColumns::from(results);
+    let level = 0.5 * peak.q_ext;
+    let crossing = |forward| level_crossing(&columns.wavelength, &columns.q_ext, index, level, forward);
+    let (left, right) = (crossing(false), crossing(true));
+    let fwhm = match (left, right) {
+        (Some(l), Some(r)) => Some(r - l),
+        (Some(l), None) => Some(2.0 * (peak.wavelength - l)),
+        (None, Some(r)) => Some(2.0 * (r - peak.wavelength)),
+        (None, None) => None,
+    };
+
+    Some(Peak {
+        index,
+        wavelength: peak.wavelength,
+        value: peak.q_ext,
+        fwhm,
+    })
+}
+
+/// Peak wavelength refined to sub-grid precision
+///
+/// Fits a parabola through the peak sample and its two neighbours, so the
+/// position moves continuously as inputs change instead of jumping between
+/// grid points.
+pub fn refined_peak_wavelength(results: &[OpticalResult], peak: &Peak) -> f64 {
+    let i = peak.index;
+    let (Some(a), Some(c)) = (i.checked_sub(1).and_then(|j| results.get(j)), results.get(i + 1)) else {
+        return peak.wavelength;
+    };
+    let b = &results[i];
+    let (h1, h2) = (b.wavelength - a.wavelength, c.wavelength - b.wavelength);
+    // Vertex of the parabola through three unevenly spaced points
+    let s1 = (b.q_ext - a.q_ext) / h1;
+    let s2 = (c.q_ext - b.q_ext) / h2;
+    let curvature = (s2 - s1) / (h1 + h2);
+    if curvature >= 0.0 {
+        return peak.wavelength;
+    }
+    let slope_at_b = (s1 * h2 + s2 * h1) / (h1 + h2);
+    (b.wavelength - slope_at_b / (2.0 * curvature)).clamp(a.wavelength, c.wavelength)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Lorentzian Q_ext on a 1 nm grid from `min` to `max`
+    pub(crate) fn lorentzian(center: f64, fwhm: f64, min: f64, max: f64) -> Vec<OpticalResult> {
+        let half = 0.5 * fwhm;
+        (0..=((max - min) as usize))
+            .map(|i| {
+                let wavelength = min + i as f64;
+                let q = half * half / ((wavelength - center).powi(2) + half * half);
+                OpticalResult {
+                    c_sca: q,
+                    c_ext: q,
+                    ..OpticalResult::for_test(wavelength, q, 0.0)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lorentzian_peak_and_width() {
+        let peak = dominant_peak(&lorentzian(520.0, 40.0, 300.0, 800.0)).unwrap();
+        assert_eq!(peak.wavelength, 520.0);
+        assert!((peak.value - 1.0).abs() < 1e-12);
+        assert!((peak.fwhm.unwrap() - 40.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_refined_wavelength_between_grid_points() {
+        let results = lorentzian(520.3, 40.0, 300.0, 800.0);
+        let peak = dominant_peak(&results).unwrap();
+        assert_eq!(peak.wavelength, 520.0);
+        assert!((refined_peak_wavelength(&results, &peak) - 520.3).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_one_sided_width_is_mirrored() {
+        let peak = dominant_peak(&lorentzian(310.0, 40.0, 300.0, 800.0)).unwrap();
+        assert!((peak.fwhm.unwrap() - 40.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_monotonic_spectrum_has_no_peak() {
+        let results = lorentzian(200.0, 40.0, 300.0, 800.0);
+        assert!(dominant_peak(&results).is_none());
+        assert!(dominant_peak(&[]).is_none());
+    }
+}
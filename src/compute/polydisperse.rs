@@ -0,0 +1,254 @@
+//! Polydisperse ensembles by Monte-Carlo sampling of particle radii
+//!
+//! Radii are drawn from a normal distribution around the nominal radius and
+//! the spread of Q_ext is reported as a mean ± σ band per wavelength.
+
+use super::engine::ComputeEngine;
+use crate::core::{CalcResult, CalculationError, OpticalModel};
+use crate::physics::optical::mie::MieModel;
+use crate::utils::moving_average;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Monte-Carlo sampling settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolydisperseConfig {
+    /// Standard deviation of the radius relative to the nominal radius
+    pub relative_sigma: f64,
+    /// Number of sampled particles
+    pub samples: usize,
+    /// RNG seed, for reproducible bands
+    pub seed: u64,
+}
+
+impl Default for PolydisperseConfig {
+    fn default() -> Self {
+        Self {
+            relative_sigma: 0.1,
+            samples: 64,
+            seed: 42,
+        }
+    }
+}
+
+/// Ensemble Q_ext statistics at one wavelength
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralBand {
+    pub wavelength: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// SplitMix64 generator, enough for reproducible size sampling
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal deviate (Box-Muller)
+    fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// Sample `config.samples` positive radii around `radius`
+fn sample_radii(radius: f64, config: &PolydisperseConfig) -> Vec<f64> {
+    let mut rng = SplitMix64(config.seed);
+    let mut radii = Vec::with_capacity(config.samples);
+    while radii.len() < config.samples {
+        let r = radius * (1.0 + config.relative_sigma * rng.next_normal());
+        if r > 0.0 {
+            radii.push(r);
+        }
+    }
+    radii
+}
+
+/// Mean ± σ of Q_ext across a sampled size distribution
+pub fn monte_carlo_band(
+    engine: &ComputeEngine,
+    model: &MieModel,
+    wavelengths: &[f64],
+    config: &PolydisperseConfig,
+) -> CalcResult<Vec<SpectralBand>> {
+    monte_carlo_band_with_progress(engine, model, wavelengths, config, &|_, _| {}, &AtomicBool::new(false))
+}
+
+/// [`monte_carlo_band`], reporting progress and stopping when asked
+///
+/// Samples run in parallel, each through
+/// [`OpticalModel::calculate_spectrum_with_progress`]. `progress(done, total)`
+/// counts points over all samples. Once `cancel` is set the run fails with
+/// [`CalculationError::Cancelled`]: a band from the samples that happened
+/// to finish would not follow the configured distribution.
+pub fn monte_carlo_band_with_progress(
+    engine: &ComputeEngine,
+    model: &MieModel,
+    wavelengths: &[f64],
+    config: &PolydisperseConfig,
+    progress: &(dyn Fn(usize, usize) + Sync),
+    cancel: &AtomicBool,
+) -> CalcResult<Vec<SpectralBand>> {
+    if config.samples < 2 {
+        return Err(CalculationError::InvalidInput(
+            "Polydisperse band needs at least 2 samples".to_string(),
+        ));
+    }
+    if !(config.relative_sigma >= 0.0 && config.relative_sigma.is_finite()) {
+        return Err(CalculationError::InvalidInput(
+            "Relative size spread must be non-negative".to_string(),
+        ));
+    }
+
+    let radii = sample_radii(model.radius, config);
+    let total = radii.len() * wavelengths.len();
+    let finished = AtomicUsize::new(0);
+    let spectra = engine
+        .par_map(&radii, |&r| {
+            let mut sample = model.clone();
+            sample.radius = r;
+            let mut reported = 0;
+            let mut report = |done: usize, _| {
+                let all = finished.fetch_add(done - reported, Ordering::Relaxed) + done - reported;
+                reported = done;
+                progress(all, total);
+            };
+            sample.calculate_spectrum_with_progress(wavelengths, &mut report, cancel)
+        })
+        .into_iter()
+        .collect::<CalcResult<Vec<_>>>()?;
+    if cancel.load(Ordering::Relaxed) {
+        return Err(CalculationError::Cancelled);
+    }
+
+    let n = spectra.len() as f64;
+    Ok(wavelengths
+        .iter()
+        .enumerate()
+        .map(|(i, &wavelength)| {
+            let mean = spectra.iter().map(|s| s[i].q_ext).sum::<f64>() / n;
+            let var = spectra.iter().map(|s| (s[i].q_ext - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            SpectralBand {
+                wavelength,
+                mean,
+                std_dev: var.sqrt(),
+            }
+        })
+        .collect())
+}
+
+/// Lower and upper band edges (mean ∓ σ), optionally smoothed
+///
+/// Smoothing applies only to the edges; the mean is never altered. Edges
+/// are clamped so that 0 ≤ lower ≤ mean ≤ upper for non-negative Q_ext.
+pub fn band_edges(band: &[SpectralBand], smoothing_window: Option<usize>) -> Vec<(f64, f64)> {
+    let lower: Vec<f64> = band.iter().map(|b| b.mean - b.std_dev).collect();
+    let upper: Vec<f64> = band.iter().map(|b| b.mean + b.std_dev).collect();
+    let (lower, upper) = match smoothing_window {
+        Some(window) => (moving_average(&lower, window), moving_average(&upper, window)),
+        None => (lower, upper),
+    };
+
+    band.iter()
+        .zip(lower.into_iter().zip(upper))
+        .map(|(b, (lo, hi))| (lo.max(0.0).min(b.mean), hi.max(b.mean)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+
+    fn band() -> Vec<SpectralBand> {
+        let engine = ComputeEngine::new(Some(2)).unwrap();
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let wavelengths: Vec<f64> = (400..=700).step_by(10).map(|w| w as f64).collect();
+        let config = PolydisperseConfig {
+            relative_sigma: 0.3,
+            samples: 32,
+            seed: 7,
+        };
+        monte_carlo_band(&engine, &model, &wavelengths, &config).unwrap()
+    }
+
+    #[test]
+    fn test_smoothing_preserves_mean_and_order() {
+        let band = band();
+        let means: Vec<f64> = band.iter().map(|b| b.mean).collect();
+
+        let edges = band_edges(&band, Some(5));
+        assert_eq!(edges.len(), band.len());
+        for ((lo, hi), b) in edges.iter().zip(&band) {
+            assert!(*lo >= 0.0);
+            assert!(*lo <= b.mean && b.mean <= *hi);
+        }
+        // Mean is untouched by smoothing
+        assert_eq!(band.iter().map(|b| b.mean).collect::<Vec<_>>(), means);
+    }
+
+    #[test]
+    fn test_lower_edge_clamped_at_zero() {
+        let wide = [SpectralBand {
+            wavelength: 500.0,
+            mean: 0.1,
+            std_dev: 1.0,
+        }];
+        assert_eq!(band_edges(&wide, None)[0], (0.0, 1.1));
+    }
+
+    #[test]
+    fn test_progress_covers_every_point_and_cancel_stops() {
+        let engine = ComputeEngine::new(Some(2)).unwrap();
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let wavelengths: Vec<f64> = (400..=700).map(|w| w as f64).collect();
+        let config = PolydisperseConfig::default();
+
+        let last = AtomicUsize::new(0);
+        let report = |done: usize, total: usize| {
+            assert_eq!(total, config.samples * wavelengths.len());
+            last.fetch_max(done, Ordering::Relaxed);
+        };
+        let never = AtomicBool::new(false);
+        let band = monte_carlo_band_with_progress(&engine, &model, &wavelengths, &config, &report, &never).unwrap();
+        assert_eq!(last.into_inner(), config.samples * wavelengths.len());
+        assert_eq!(band.len(), wavelengths.len());
+
+        let cancelled = AtomicBool::new(true);
+        let result = monte_carlo_band_with_progress(&engine, &model, &wavelengths, &config, &|_, _| {}, &cancelled);
+        assert!(matches!(result, Err(CalculationError::Cancelled)));
+    }
+
+    #[test]
+    fn test_two_samples_give_their_average_and_spread() {
+        let engine = ComputeEngine::new(Some(2)).unwrap();
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let config = PolydisperseConfig {
+            relative_sigma: 0.3,
+            samples: 2,
+            seed: 7,
+        };
+        let band = monte_carlo_band(&engine, &model, &[520.0], &config).unwrap();
+
+        // Each sampled particle carries weight 1/2
+        let radii = sample_radii(model.radius, &config);
+        let q: Vec<f64> = radii
+            .iter()
+            .map(|&r| MieModel::new(r, 520.0, RefractiveIndex::new(0.47, 2.40), 1.33).calculate().unwrap().q_ext)
+            .collect();
+        assert_ne!(q[0], q[1]);
+        let mean = 0.5 * q[0] + 0.5 * q[1];
+        // Sample standard deviation of two values, |a − b| / √2
+        let std_dev = (q[0] - q[1]).abs() / 2f64.sqrt();
+        assert!((band[0].mean - mean).abs() < 1e-12 * mean);
+        assert!((band[0].std_dev - std_dev).abs() < 1e-12 * std_dev);
+    }
+}
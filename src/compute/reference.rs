@@ -0,0 +1,130 @@
+//! Quantities set against a reference value
+//!
+//! One shape for every "compared to X" readout: a value, the reference it
+//! is measured against, and their ratio, guarded against a zero reference.
+
+use super::comparison::percent_change;
+use crate::core::{ElectronicResult, OpticalResult, ThermalResult};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceComparison {
+    /// Display name of the quantity, e.g. "C_ext"
+    pub quantity: &'static str,
+    pub value: f64,
+    pub reference: f64,
+}
+
+impl ReferenceComparison {
+    pub fn new(quantity: &'static str, value: f64, reference: f64) -> Self {
+        Self { quantity, value, reference }
+    }
+
+    /// value / reference, `None` when the reference is zero or not finite
+    pub fn ratio(&self) -> Option<f64> {
+        (self.reference != 0.0 && self.reference.is_finite()).then(|| self.value / self.reference)
+    }
+
+    /// Percent change from the reference, `None` when the reference is zero
+    pub fn percent_change(&self) -> Option<f64> {
+        percent_change(self.reference, self.value)
+    }
+}
+
+/// C_ext against the geometric cross section `area` in nm²
+///
+/// Pass the model's [`geometric_cross_section`](crate::core::OpticalModel::geometric_cross_section)
+/// so the ratio is the Q_ext that model reports.
+pub fn against_geometric_area(result: &OpticalResult, area: f64) -> ReferenceComparison {
+    ReferenceComparison::new("C_ext", result.c_ext, area)
+}
+
+/// Each cross section against the same cross section of a pinned particle
+pub fn against_reference(result: &OpticalResult, reference: &OpticalResult) -> [ReferenceComparison; 3] {
+    [
+        ReferenceComparison::new("C_sca", result.c_sca, reference.c_sca),
+        ReferenceComparison::new("C_abs", result.c_abs, reference.c_abs),
+        ReferenceComparison::new("C_ext", result.c_ext, reference.c_ext),
+    ]
+}
+
+/// Effective thermal conductivity against the bulk value
+///
+/// The ratio is the model's reduction factor, recomputed with the zero guard.
+pub fn thermal_against_bulk(result: &ThermalResult) -> ReferenceComparison {
+    ReferenceComparison::new("κ_eff", result.kappa_eff, result.kappa_bulk)
+}
+
+/// Confined bandgap against the bulk bandgap
+pub fn bandgap_against_bulk(result: &ElectronicResult) -> ReferenceComparison {
+    ReferenceComparison::new("E_g", result.bandgap, result.bulk_bandgap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConfinementRegime, ElectronicMetadata, ParticleShape, ThermalMetadata};
+
+    fn result(c_sca: f64, c_abs: f64) -> OpticalResult {
+        OpticalResult {
+            c_sca,
+            c_abs,
+            c_ext: c_sca + c_abs,
+            ..OpticalResult::for_test(500.0, 0.0, 0.0)
+        }
+    }
+
+    #[test]
+    fn test_ratios_against_reference_particle() {
+        let comparisons = against_reference(&result(30.0, 10.0), &result(10.0, 20.0));
+        let ratios: Vec<_> = comparisons.iter().map(|c| c.ratio().unwrap()).collect();
+        assert_eq!(ratios, [3.0, 0.5, 40.0 / 30.0]);
+        assert_eq!(comparisons[0].percent_change(), Some(200.0));
+        assert_eq!(comparisons[1].percent_change(), Some(-50.0));
+    }
+
+    #[test]
+    fn test_geometric_ratio_is_q_ext() {
+        let area = ParticleShape::Sphere { radius: 25.0 }.projected_area();
+        let comparison = against_geometric_area(&result(1.5 * area, 0.5 * area), area);
+        assert!((comparison.ratio().unwrap() - 2.0).abs() < 1e-12);
+        assert!(against_geometric_area(&result(1.0, 0.0), 0.0).ratio().is_none());
+    }
+
+    #[test]
+    fn test_zero_reference_has_no_ratio() {
+        for comparison in against_reference(&result(1.0, 1.0), &result(0.0, 0.0)) {
+            assert_eq!(comparison.ratio(), None, "{}", comparison.quantity);
+            assert_eq!(comparison.percent_change(), None, "{}", comparison.quantity);
+        }
+        assert_eq!(ReferenceComparison::new("x", 1.0, f64::NAN).ratio(), None);
+    }
+
+    #[test]
+    fn test_thermal_and_electronic_against_bulk() {
+        let mut thermal = ThermalResult {
+            temperature: 300.0,
+            kappa_eff: 50.0,
+            kappa_bulk: 150.0,
+            reduction_factor: 1.0 / 3.0,
+            mfp: Some(40.0),
+            metadata: ThermalMetadata::default(),
+        };
+        assert_eq!(thermal_against_bulk(&thermal).ratio(), Some(thermal.reduction_factor));
+        thermal.kappa_bulk = 0.0;
+        assert_eq!(thermal_against_bulk(&thermal).ratio(), None);
+
+        let mut dot = ElectronicResult {
+            diameter: 4.0,
+            bandgap: 2.1,
+            bulk_bandgap: 1.75,
+            confinement_energy: 0.5,
+            coulomb_correction: 0.15,
+            bohr_radius: None,
+            regime: ConfinementRegime::Strong,
+            metadata: ElectronicMetadata::default(),
+        };
+        assert!((bandgap_against_bulk(&dot).ratio().unwrap() - 1.2).abs() < 1e-12);
+        dot.bulk_bandgap = 0.0;
+        assert_eq!(bandgap_against_bulk(&dot).ratio(), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Spectra computed to a target curve resolution
+//!
+//! Instead of a fixed grid, the sampling density is doubled until linear
+//! interpolation of the coarser grid predicts the new midpoints to within the
+//! requested accuracy. Points already computed are reused at every level.
+
+use crate::core::{CalcResult, CalculationError, OpticalModel, OpticalResult};
+
+/// Intervals in the starting grid
+pub const INITIAL_INTERVALS: usize = 16;
+/// Doublings attempted before giving up (16·2¹⁰ = 16384 intervals)
+pub const MAX_REFINEMENT_ITERATIONS: usize = 10;
+
+/// Spectrum over `range` (nm) resolved to `max_relative_error`
+///
+/// The error of a grid is the largest deviation between a newly computed
+/// midpoint Q_ext and the linear interpolation of its neighbours, relative to
+/// the peak |Q_ext|. Returns the finest grid once that error is below the
+/// target, or [`CalculationError::ConvergenceFailed`] after
+/// [`MAX_REFINEMENT_ITERATIONS`] doublings.
+pub fn refine_until(
+    model: &dyn OpticalModel,
+    range: (f64, f64),
+    max_relative_error: f64,
+) -> CalcResult<Vec<OpticalResult>> {
+    let (min, max) = range;
+    if !(min.is_finite() && max.is_finite() && max > min) {
+        return Err(CalculationError::InvalidInput(format!(
+            "Invalid wavelength range {}–{} nm",
+            min, max
+        )));
+    }
+    if !(max_relative_error > 0.0 && max_relative_error.is_finite()) {
+        return Err(CalculationError::InvalidInput(
+            "Target accuracy must be positive".to_string(),
+        ));
+    }
+
+    let step = (max - min) / INITIAL_INTERVALS as f64;
+    let grid: Vec<f64> = (0..=INITIAL_INTERVALS).map(|i| min + i as f64 * step).collect();
+    let mut results = model.calculate_spectrum(&grid)?;
+
+    for _ in 0..MAX_REFINEMENT_ITERATIONS {
+        let midpoints: Vec<f64> = results
+            .windows(2)
+            .map(|w| 0.5 * (w[0].wavelength + w[1].wavelength))
+            .collect();
+        let new_points = model.calculate_spectrum(&midpoints)?;
+
+        let peak = results
+            .iter()
+            .chain(&new_points)
+            .map(|r| r.q_ext.abs())
+            .fold(0.0, f64::max);
+        let error = results
+            .windows(2)
+            .zip(&new_points)
+            .map(|(w, mid)| (mid.q_ext - 0.5 * (w[0].q_ext + w[1].q_ext)).abs())
+            .fold(0.0, f64::max);
+
+        let mut merged = Vec::with_capacity(results.len() + new_points.len());
+        let mut new_points = new_points.into_iter();
+        for r in results {
+            merged.push(r);
+            merged.extend(new_points.next());
+        }
+        results = merged;
+
+        if peak == 0.0 || error <= max_relative_error * peak {
+            return Ok(results);
+        }
+    }
+
+    Err(CalculationError::ConvergenceFailed {
+        iterations: MAX_REFINEMENT_ITERATIONS,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        ParticleShape, PhysicsModel, RefractiveIndex, ValidationResult, ValidityFlag,
+    };
+    use crate::physics::optical::mie::MieModel;
+
+    /// Pseudo-random Q_ext in [0, 1): no grid resolves it
+    struct Noise;
+
+    impl PhysicsModel for Noise {
+        fn name(&self) -> &str {
+            "Noise"
+        }
+
+        fn description(&self) -> &str {
+            "Unresolvable test curve"
+        }
+
+        fn validate(&self) -> ValidationResult<()> {
+            Ok(())
+        }
+    }
+
+    impl OpticalModel for Noise {
+        fn calculate(&self) -> CalcResult<OpticalResult> {
+            unreachable!("refine_until only requests spectra")
+        }
+
+        fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+            Ok(wavelengths
+                .iter()
+                .map(|&wavelength| {
+                    let q = ((wavelength * 12.9898).sin() * 43758.5453).fract().abs();
+                    OpticalResult {
+                        c_sca: q,
+                        c_ext: q,
+                        ..OpticalResult::for_test(wavelength, q, 0.0)
+                    }
+                })
+                .collect())
+        }
+
+        fn shape(&self) -> ParticleShape {
+            ParticleShape::Sphere { radius: 1.0 }
+        }
+
+        fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+            vec![ValidityFlag::Valid; wavelengths.len()]
+        }
+    }
+
+    #[test]
+    fn test_smooth_curve_converges() {
+        let model = MieModel::new(30.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let coarse = refine_until(&model, (300.0, 800.0), 1e-2).unwrap();
+        let fine = refine_until(&model, (300.0, 800.0), 1e-4).unwrap();
+
+        assert!(fine.len() > coarse.len());
+        assert!(fine.len() < INITIAL_INTERVALS << MAX_REFINEMENT_ITERATIONS);
+        assert_eq!(fine.first().unwrap().wavelength, 300.0);
+        assert_eq!(fine.last().unwrap().wavelength, 800.0);
+        assert!(fine.windows(2).all(|w| w[0].wavelength < w[1].wavelength));
+    }
+
+    #[test]
+    fn test_unresolvable_curve_hits_iteration_cap() {
+        match refine_until(&Noise, (300.0, 800.0), 1e-3) {
+            Err(CalculationError::ConvergenceFailed { iterations }) => {
+                assert_eq!(iterations, MAX_REFINEMENT_ITERATIONS)
+            }
+            other => panic!("expected convergence failure, got {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        let model = MieModel::new(30.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        assert!(refine_until(&model, (800.0, 300.0), 1e-3).is_err());
+        assert!(refine_until(&model, (300.0, 800.0), 0.0).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Computed vs measured residuals
+//!
+//! The computed spectrum is interpolated onto the measured wavelengths, so a
+//! sparse or irregular measurement grid is compared point by point.
+
+use super::columns::SpectrumColumns;
+use super::comparison::interpolate;
+use crate::core::OpticalResult;
+
+/// Computed − measured Q_ext at one measured wavelength
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Residual {
+    pub wavelength: f64,
+    pub residual: f64,
+}
+
+/// Summary of a residual set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResidualStats {
+    /// Root-mean-square residual
+    pub rms: f64,
+    /// Largest |residual|
+    pub max_abs: f64,
+    pub count: usize,
+}
+
+/// Residuals of `computed` (sorted by wavelength) against measured
+/// (wavelength nm, Q_ext) pairs
+///
+/// Measured points outside the computed range, or with non-finite values,
+/// are skipped.
+pub fn q_ext_residuals(computed: &[OpticalResult], measured: &[(f64, f64)]) -> Vec<Residual> {
+    let computed = SpectrumColumns::from(computed);
+    measured
+        .iter()
+        .filter(|(wl, q)| wl.is_finite() && q.is_finite())
+        .filter_map(|&(wavelength, q)| {
+            interpolate(&computed, wavelength).map(|c| Residual {
+                wavelength,
+                residual: c.q_ext - q,
+            })
+        })
+        .collect()
+}
+
+/// RMS and maximum absolute residual, `None` for an empty set
+pub fn residual_stats(residuals: &[Residual]) -> Option<ResidualStats> {
+    if residuals.is_empty() {
+        return None;
+    }
+    let sum_sq: f64 = residuals.iter().map(|r| r.residual * r.residual).sum();
+    Some(ResidualStats {
+        rms: (sum_sq / residuals.len() as f64).sqrt(),
+        max_abs: residuals.iter().map(|r| r.residual.abs()).fold(0.0, f64::max),
+        count: residuals.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn computed() -> Vec<OpticalResult> {
+        (0..=50)
+            .map(|i| {
+                let wavelength = 300.0 + 10.0 * i as f64;
+                let q = wavelength / 100.0;
+                OpticalResult::for_test(wavelength, q, 0.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_constant_offset_gives_matching_rms() {
+        // Measured on an off-grid set, 0.25 below the computed line
+        let measured: Vec<(f64, f64)> = (0..20).map(|i| 305.0 + 23.0 * i as f64).map(|wl| (wl, wl / 100.0 - 0.25)).collect();
+        let residuals = q_ext_residuals(&computed(), &measured);
+        assert_eq!(residuals.len(), measured.len());
+        assert!(residuals.iter().all(|r| (r.residual - 0.25).abs() < 1e-12));
+
+        let stats = residual_stats(&residuals).unwrap();
+        assert!((stats.rms - 0.25).abs() < 1e-12);
+        assert!((stats.max_abs - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sparse_and_out_of_range_measurements() {
+        let measured = [(250.0, 1.0), (500.0, 4.0), (f64::NAN, 1.0), (900.0, 1.0)];
+        let residuals = q_ext_residuals(&computed(), &measured);
+        assert_eq!(residuals, vec![Residual { wavelength: 500.0, residual: 1.0 }]);
+        assert_eq!(residual_stats(&residuals).unwrap().count, 1);
+
+        assert!(q_ext_residuals(&[], &measured).is_empty());
+        assert_eq!(residual_stats(&[]), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Sensitivity of the resonance wavelength to the inputs
+//!
+//! Each input is perturbed in turn and the shift of the dominant Q_ext peak
+//! is measured by central finite differences. Sensitivities are reported as
+//! elasticities (p/λ)·∂λ/∂p, so inputs with different units can be ranked.
+
+use super::peaks::{dominant_peak, refined_peak_wavelength};
+use crate::core::{CalcResult, OpticalResult, RunInputs};
+
+/// Default perturbation, relative to the input value
+pub const DEFAULT_RELATIVE_STEP: f64 = 0.01;
+
+/// An input whose effect on the resonance is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parameter {
+    Radius,
+    IndexReal,
+    IndexImag,
+    MediumIndex,
+}
+
+impl Parameter {
+    pub const ALL: [Parameter; 4] = [
+        Parameter::Radius,
+        Parameter::IndexReal,
+        Parameter::IndexImag,
+        Parameter::MediumIndex,
+    ];
+
+    /// Short symbol for plots and tables
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Parameter::Radius => "r",
+            Parameter::IndexReal => "n",
+            Parameter::IndexImag => "k",
+            Parameter::MediumIndex => "n_medium",
+        }
+    }
+
+    pub fn value(self, inputs: &RunInputs) -> f64 {
+        match self {
+            Parameter::Radius => inputs.particle_radius,
+            Parameter::IndexReal => inputs.n_particle_real,
+            Parameter::IndexImag => inputs.n_particle_imag,
+            Parameter::MediumIndex => inputs.n_medium,
+        }
+    }
+
+    /// Copy of `inputs` with this parameter set to `value`
+    pub fn with_value(self, inputs: &RunInputs, value: f64) -> RunInputs {
+        let mut perturbed = *inputs;
+        match self {
+            Parameter::Radius => perturbed.particle_radius = value,
+            Parameter::IndexReal => perturbed.n_particle_real = value,
+            Parameter::IndexImag => perturbed.n_particle_imag = value,
+            Parameter::MediumIndex => perturbed.n_medium = value,
+        }
+        perturbed
+    }
+}
+
+/// Resonance shift per unit change of one input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sensitivity {
+    pub parameter: Parameter,
+    /// ∂λ_peak/∂p, in nm per unit of the parameter
+    pub derivative: f64,
+    /// Elasticity (p/λ_peak)·∂λ_peak/∂p; zero for a parameter at zero
+    pub normalized: f64,
+}
+
+/// Sub-grid wavelength of the dominant Q_ext peak, if there is one
+pub fn peak_wavelength(results: &[OpticalResult]) -> Option<f64> {
+    dominant_peak(results).map(|peak| refined_peak_wavelength(results, &peak))
+}
+
+/// Sensitivities of the peak wavelength, ranked by |normalized| (largest first)
+///
+/// `spectrum` computes the spectrum for a set of inputs. Each parameter is
+/// stepped by `relative_step` of its value (or by `relative_step` itself at
+/// zero); a step that would make it negative falls back to a forward
+/// difference. `Ok(None)` when the unperturbed spectrum has no resolved
+/// peak; parameters whose perturbed spectra lose the peak are left out.
+pub fn peak_sensitivities<F>(
+    inputs: &RunInputs,
+    relative_step: f64,
+    spectrum: F,
+) -> CalcResult<Option<Vec<Sensitivity>>>
+where
+    F: Fn(&RunInputs) -> CalcResult<Vec<OpticalResult>>,
+{
+    let Some(center) = peak_wavelength(&spectrum(inputs)?) else {
+        return Ok(None);
+    };
+
+    let mut sensitivities = Vec::with_capacity(Parameter::ALL.len());
+    for parameter in Parameter::ALL {
+        let value = parameter.value(inputs);
+        let step = if value == 0.0 { relative_step } else { relative_step * value.abs() };
+        let lower = if value - step < 0.0 { value } else { value - step };
+        let upper = value + step;
+
+        let shifted = |v: f64| -> CalcResult<Option<f64>> {
+            if v == value {
+                return Ok(Some(center));
+            }
+            Ok(peak_wavelength(&spectrum(&parameter.with_value(inputs, v))?))
+        };
+        let (Some(lo), Some(hi)) = (shifted(lower)?, shifted(upper)?) else {
+            continue;
+        };
+        let derivative = (hi - lo) / (upper - lower);
+        sensitivities.push(Sensitivity {
+            parameter,
+            derivative,
+            normalized: derivative * value / center,
+        });
+    }
+    sensitivities.sort_by(|a, b| b.normalized.abs().total_cmp(&a.normalized.abs()));
+    Ok(Some(sensitivities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::peaks::tests::lorentzian;
+    use crate::compute::universal::efficiency_at_x;
+    use crate::core::OpticalMetadata;
+    use num_complex::Complex64;
+    use std::f64::consts::PI;
+
+    /// Full Mie spectrum of a Drude sphere (gold-like, λp = 139 nm);
+    /// the particle index inputs are ignored in favour of the dispersion
+    fn drude_sphere(inputs: &RunInputs) -> CalcResult<Vec<OpticalResult>> {
+        (400..=700)
+            .map(|wl| {
+                let wavelength = wl as f64;
+                let omega = 139.0 / wavelength; // ω/ωp
+                let eps = 9.5 - 1.0 / (omega * Complex64::new(omega, 0.008));
+                let m = eps.sqrt() / inputs.n_medium;
+                let x = 2.0 * PI * inputs.n_medium * inputs.particle_radius / wavelength;
+                let q = efficiency_at_x(m, x)?;
+                Ok(OpticalResult { wavelength, metadata: OpticalMetadata::default(), ..q })
+            })
+            .collect()
+    }
+
+    fn gold_in_water() -> RunInputs {
+        RunInputs {
+            particle_radius: 40.0,
+            wavelength: 500.0,
+            n_particle_real: 0.5,
+            n_particle_imag: 2.5,
+            n_medium: 1.33,
+        }
+    }
+
+    #[test]
+    fn test_plasmon_redshifts_with_medium_and_radius() {
+        let ranked = peak_sensitivities(&gold_in_water(), DEFAULT_RELATIVE_STEP, drude_sphere)
+            .unwrap()
+            .unwrap();
+        let find = |p: Parameter| ranked.iter().find(|s| s.parameter == p).unwrap();
+
+        let medium = find(Parameter::MediumIndex);
+        let radius = find(Parameter::Radius);
+        assert!(medium.derivative > 0.0 && medium.normalized > 0.1, "{:?}", medium);
+        assert!(radius.derivative > 0.0 && radius.normalized > 0.01, "{:?}", radius);
+
+        // The Drude index replaces n, k: they cannot move the peak
+        assert_eq!(find(Parameter::IndexReal).derivative, 0.0);
+        assert_eq!(ranked[0].parameter, Parameter::MediumIndex);
+        assert_eq!(ranked[1].parameter, Parameter::Radius);
+    }
+
+    #[test]
+    fn test_no_peak_gives_none() {
+        let monotonic = peak_sensitivities(&gold_in_water(), DEFAULT_RELATIVE_STEP, |_| {
+            Ok(lorentzian(200.0, 40.0, 300.0, 800.0))
+        });
+        assert_eq!(monotonic.unwrap(), None);
+    }
+}
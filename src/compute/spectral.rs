@@ -0,0 +1,110 @@
+//! Spectra on a photon-energy axis
+//!
+//! Extinction reframed as a spectral rate: C_ext(E) with E = hc/λ, and its
+//! integral over an energy window as an effective oscillator strength.
+
+use crate::core::units::Wavelength;
+use crate::core::OpticalResult;
+
+/// (photon energy in eV, C_ext in nm²) pairs sorted by increasing energy
+///
+/// Points below the minimum convertible wavelength are dropped.
+pub fn c_ext_vs_energy(results: &[OpticalResult]) -> Vec<(f64, f64)> {
+    let mut points: Vec<(f64, f64)> = results
+        .iter()
+        .filter_map(|r| Some((Wavelength(r.wavelength).to_energy_ev().ok()?.0, r.c_ext)))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    points
+}
+
+/// Trapezoidal integral of sorted (x, y) samples over [lo, hi]
+///
+/// Window edges falling between samples are linearly interpolated.
+pub fn trapezoid_window(points: &[(f64, f64)], lo: f64, hi: f64) -> f64 {
+    if points.len() < 2 || hi <= lo {
+        return 0.0;
+    }
+    let (x_first, x_last) = (points[0].0, points[points.len() - 1].0);
+    let lo = lo.max(x_first);
+    let hi = hi.min(x_last);
+    if hi <= lo {
+        return 0.0;
+    }
+
+    let value_at = |x: f64| -> f64 {
+        let i = points.partition_point(|p| p.0 < x).clamp(1, points.len() - 1);
+        let (x0, y0) = points[i - 1];
+        let (x1, y1) = points[i];
+        y0 + (x - x0) / (x1 - x0) * (y1 - y0)
+    };
+
+    let mut nodes = vec![(lo, value_at(lo))];
+    nodes.extend(points.iter().copied().filter(|p| p.0 > lo && p.0 < hi));
+    nodes.push((hi, value_at(hi)));
+
+    nodes
+        .windows(2)
+        .map(|w| 0.5 * (w[1].0 - w[0].0) * (w[0].1 + w[1].1))
+        .sum()
+}
+
+/// Integrated extinction cross section ∫ C_ext dE over [e_min, e_max] in eV·nm²
+pub fn integrated_cross_section(results: &[OpticalResult], e_min: f64, e_max: f64) -> f64 {
+    trapezoid_window(&c_ext_vs_energy(results), e_min, e_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(wavelength: f64, c_ext: f64) -> OpticalResult {
+        OpticalResult {
+            c_ext,
+            ..OpticalResult::for_test(wavelength, 0.0, 0.0)
+        }
+    }
+
+    #[test]
+    fn test_matches_manual_trapezoid() {
+        let results: Vec<_> = (300..=800)
+            .step_by(10)
+            .map(|wl| {
+                let wl = wl as f64;
+                result(wl, 1000.0 * (-(wl - 520.0).powi(2) / 2000.0).exp())
+            })
+            .collect();
+
+        // Window on sample energies so no edge interpolation is involved
+        let points = c_ext_vs_energy(&results);
+        let (lo, hi) = (points[10].0, points[30].0);
+        let manual: f64 = points[10..=30]
+            .windows(2)
+            .map(|w| 0.5 * (w[1].0 - w[0].0) * (w[0].1 + w[1].1))
+            .sum();
+
+        let integral = integrated_cross_section(&results, lo, hi);
+        assert!((integral - manual).abs() < 1e-9 * manual.abs());
+    }
+
+    #[test]
+    fn test_constant_cross_section_with_interpolated_edges() {
+        let results: Vec<_> = (300..=800).step_by(50).map(|wl| result(wl as f64, 5.0)).collect();
+        let integral = integrated_cross_section(&results, 2.0, 3.0);
+        assert!((integral - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wavelengths_below_minimum_are_dropped() {
+        let results = vec![result(0.0, 1.0), result(0.5, 1.0), result(500.0, 2.0)];
+        let points = c_ext_vs_energy(&results);
+        assert_eq!(points.len(), 1);
+        assert!(points[0].0.is_finite());
+    }
+
+    #[test]
+    fn test_window_outside_data_is_zero() {
+        let results = vec![result(500.0, 1.0), result(600.0, 1.0)];
+        assert_eq!(integrated_cross_section(&results, 5.0, 6.0), 0.0);
+    }
+}
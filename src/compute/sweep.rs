@@ -0,0 +1,153 @@
+//! Parameter sweeps
+//!
+//! Runs one Mie model repeatedly with a single input stepped through a list
+//! of values, e.g. to compare cross sections across particle sizes.
+
+use super::engine::ComputeEngine;
+use crate::core::{CalcResult, CalculationError, OpticalModel, OpticalResult};
+use crate::physics::optical::mie::MieModel;
+
+/// Input stepped by a [`ParameterSweep`] and the values it takes
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepAxis {
+    /// Particle radius in nm
+    Radius(Vec<f64>),
+    /// Refractive index of the medium
+    MediumIndex(Vec<f64>),
+    /// Vacuum wavelength in nm
+    Wavelength(Vec<f64>),
+}
+
+impl SweepAxis {
+    pub fn values(&self) -> &[f64] {
+        match self {
+            SweepAxis::Radius(values) | SweepAxis::MediumIndex(values) | SweepAxis::Wavelength(values) => values,
+        }
+    }
+
+    /// Axis label for plots and tables
+    pub fn label(&self) -> &'static str {
+        match self {
+            SweepAxis::Radius(_) => "Radius (nm)",
+            SweepAxis::MediumIndex(_) => "Medium index",
+            SweepAxis::Wavelength(_) => "Wavelength (nm)",
+        }
+    }
+
+    /// Copy of `model` with this axis set to `value`
+    fn apply(&self, model: &MieModel, value: f64) -> MieModel {
+        let mut point = model.clone();
+        match self {
+            SweepAxis::Radius(_) => point.radius = value,
+            SweepAxis::MediumIndex(_) => point.n_medium = value,
+            SweepAxis::Wavelength(_) => point.wavelength = value,
+        }
+        point
+    }
+}
+
+/// A base model and the axis swept over it
+#[derive(Clone)]
+pub struct ParameterSweep {
+    pub model: MieModel,
+    pub axis: SweepAxis,
+}
+
+impl ParameterSweep {
+    pub fn new(model: MieModel, axis: SweepAxis) -> Self {
+        Self { model, axis }
+    }
+
+    /// The sweep must have at least one value and all of them finite; each
+    /// point is further checked by the model itself
+    pub fn validate(&self) -> CalcResult<()> {
+        let values = self.axis.values();
+        if values.is_empty() {
+            return Err(CalculationError::InvalidInput(format!(
+                "{} sweep needs at least one value",
+                self.axis.label()
+            )));
+        }
+        if let Some(value) = values.iter().find(|v| !v.is_finite()) {
+            return Err(CalculationError::InvalidInput(format!(
+                "{} sweep value {} is not finite",
+                self.axis.label(),
+                value
+            )));
+        }
+        Ok(())
+    }
+
+    /// Each swept value paired with its result, in sweep order
+    pub fn run(&self) -> CalcResult<Vec<(f64, OpticalResult)>> {
+        self.validate()?;
+        self.axis
+            .values()
+            .iter()
+            .map(|&value| Ok((value, self.axis.apply(&self.model, value).calculate()?)))
+            .collect()
+    }
+
+    /// [`Self::run`] with the points computed in parallel on `engine`
+    pub fn run_on(&self, engine: &ComputeEngine) -> CalcResult<Vec<(f64, OpticalResult)>> {
+        self.validate()?;
+        engine
+            .par_map(self.axis.values(), |&value| {
+                Ok((value, self.axis.apply(&self.model, value).calculate()?))
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+
+    fn model() -> MieModel {
+        MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33)
+    }
+
+    #[test]
+    fn test_radius_sweep_grows_cross_sections() {
+        let radii = vec![5.0, 10.0, 20.0, 40.0, 80.0];
+        let sweep = ParameterSweep::new(model(), SweepAxis::Radius(radii.clone()));
+        let results = sweep.run().unwrap();
+
+        assert_eq!(results.iter().map(|(r, _)| *r).collect::<Vec<_>>(), radii);
+        assert!(results.iter().all(|(_, result)| result.wavelength == 500.0));
+        assert!(results.windows(2).all(|w| w[1].1.c_ext > w[0].1.c_ext));
+        assert!(results.windows(2).all(|w| w[1].1.c_sca > w[0].1.c_sca));
+
+        let parallel = sweep.run_on(&ComputeEngine::new(Some(2)).unwrap()).unwrap();
+        for ((a, ra), (b, rb)) in results.iter().zip(&parallel) {
+            assert_eq!(a, b);
+            assert_eq!(ra.c_ext.to_bits(), rb.c_ext.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_wavelength_and_medium_axes() {
+        let by_wavelength = ParameterSweep::new(model(), SweepAxis::Wavelength(vec![450.0, 550.0]))
+            .run()
+            .unwrap();
+        assert_eq!(by_wavelength[1].1.wavelength, 550.0);
+        let spectrum = model().calculate_spectrum(&[450.0, 550.0]).unwrap();
+        assert_eq!(by_wavelength[1].1.q_ext.to_bits(), spectrum[1].q_ext.to_bits());
+
+        let by_medium = ParameterSweep::new(model(), SweepAxis::MediumIndex(vec![1.0, 1.5]))
+            .run()
+            .unwrap();
+        assert_ne!(by_medium[0].1.q_ext, by_medium[1].1.q_ext);
+    }
+
+    #[test]
+    fn test_empty_or_invalid_sweeps_are_rejected() {
+        assert!(ParameterSweep::new(model(), SweepAxis::Radius(vec![])).run().is_err());
+        assert!(ParameterSweep::new(model(), SweepAxis::MediumIndex(vec![1.33, f64::NAN]))
+            .run()
+            .is_err());
+        assert!(ParameterSweep::new(model(), SweepAxis::Radius(vec![10.0, -1.0])).run().is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Unit-typed calculation API
+//!
+//! A thin layer over the f64 models for library users: wavelengths and cross
+//! sections come back as [`units`](crate::core::units) newtypes so nm² cannot
+//! be mistaken for m². The models themselves keep working on bare f64s.
+
+use crate::core::units::{Area, Nanometer, Wavelength};
+use crate::core::{CalcResult, OpticalMetadata, OpticalModel, OpticalResult, RefractiveIndex};
+use crate::physics::optical::mie::MieModel;
+use serde::{Deserialize, Serialize};
+
+/// Inputs of a single-wavelength optical calculation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpticalParams {
+    pub radius: Nanometer,
+    pub wavelength: Wavelength,
+    pub n_particle: RefractiveIndex,
+    /// Refractive index of the surrounding medium
+    pub n_medium: f64,
+}
+
+/// [`OpticalResult`] with typed wavelength and cross sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypedOpticalResult {
+    pub wavelength: Wavelength,
+    pub q_sca: f64,
+    pub q_abs: f64,
+    pub q_ext: f64,
+    pub c_sca: Area,
+    pub c_abs: Area,
+    pub c_ext: Area,
+    /// Asymmetry parameter ⟨cos θ⟩
+    #[serde(default)]
+    pub g: f64,
+    /// Radiation-pressure efficiency
+    #[serde(default)]
+    pub q_pr: f64,
+    pub metadata: OpticalMetadata,
+}
+
+impl From<OpticalResult> for TypedOpticalResult {
+    fn from(r: OpticalResult) -> Self {
+        Self {
+            wavelength: Wavelength(r.wavelength),
+            q_sca: r.q_sca,
+            q_abs: r.q_abs,
+            q_ext: r.q_ext,
+            c_sca: Area(r.c_sca),
+            c_abs: Area(r.c_abs),
+            c_ext: Area(r.c_ext),
+            g: r.g,
+            q_pr: r.q_pr,
+            metadata: r.metadata,
+        }
+    }
+}
+
+impl From<TypedOpticalResult> for OpticalResult {
+    fn from(r: TypedOpticalResult) -> Self {
+        Self {
+            wavelength: r.wavelength.0,
+            q_sca: r.q_sca,
+            q_abs: r.q_abs,
+            q_ext: r.q_ext,
+            c_sca: r.c_sca.0,
+            c_abs: r.c_abs.0,
+            c_ext: r.c_ext.0,
+            g: r.g,
+            q_pr: r.q_pr,
+            metadata: r.metadata,
+        }
+    }
+}
+
+/// Efficiencies and cross sections of a sphere at one wavelength
+pub fn calculate_optical(params: OpticalParams) -> CalcResult<TypedOpticalResult> {
+    MieModel::new(params.radius.0, params.wavelength.0, params.n_particle, params.n_medium)
+        .calculate()
+        .map(TypedOpticalResult::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> OpticalParams {
+        OpticalParams {
+            radius: Nanometer(20.0),
+            wavelength: Wavelength(520.0),
+            n_particle: RefractiveIndex::new(0.47, 2.40),
+            n_medium: 1.33,
+        }
+    }
+
+    #[test]
+    fn test_typed_api_matches_bare_model() {
+        let typed = calculate_optical(params()).unwrap();
+        let bare = MieModel::new(20.0, 520.0, RefractiveIndex::new(0.47, 2.40), 1.33)
+            .calculate()
+            .unwrap();
+        assert_eq!(typed.wavelength, Wavelength(bare.wavelength));
+        assert_eq!(typed.q_ext, bare.q_ext);
+        assert_eq!(typed.c_ext, Area(bare.c_ext));
+        assert_eq!(typed.c_sca.to_square_meters(), bare.c_sca * 1e-18);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let bare = calculate_optical(params()).map(OpticalResult::from).unwrap();
+        let back = OpticalResult::from(TypedOpticalResult::from(bare.clone()));
+        assert_eq!(back.wavelength, bare.wavelength);
+        assert_eq!(
+            [back.q_sca, back.q_abs, back.q_ext, back.c_sca, back.c_abs, back.c_ext],
+            [bare.q_sca, bare.q_abs, bare.q_ext, bare.c_sca, bare.c_abs, bare.c_ext]
+        );
+        let area = Area(bare.c_ext);
+        assert!((Area::from_square_meters(area.to_square_meters()).0 - area.0).abs() < 1e-9 * area.0);
+    }
+
+    #[test]
+    fn test_invalid_params_error() {
+        let bad = OpticalParams { radius: Nanometer(-1.0), ..params() };
+        assert!(calculate_optical(bad).is_err());
+    }
+}
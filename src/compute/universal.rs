@@ -0,0 +1,72 @@
+//! Universal efficiency curves Q(x) at fixed relative index
+//!
+//! Sweeps the size parameter directly, independent of absolute radius and
+//! wavelength, reproducing the textbook ripple/interference structure.
+
+use crate::core::{CalcResult, OpticalMetadata, OpticalResult};
+use crate::physics::optical::mie::MieCoefficients;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// Calculate efficiencies versus size parameter for a fixed relative index `m`
+///
+/// Results are expressed in reduced units where λ/2π = 1: `wavelength` is 2π
+/// and cross sections are in units of (λ/2π)², so C = Q·πx².
+pub fn efficiency_vs_x(m: Complex64, x_values: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+    x_values.iter().map(|&x| efficiency_at_x(m, x)).collect()
+}
+
+/// Full Mie series efficiencies at a single size parameter, in reduced units
+pub fn efficiency_at_x(m: Complex64, x: f64) -> CalcResult<OpticalResult> {
+    let coeffs = MieCoefficients::compute(m, x)?;
+    let q_ext = coeffs.q_ext();
+    let q_sca = coeffs.q_sca();
+    let q_abs = coeffs.q_abs();
+    let geometric_area = PI * x * x;
+
+    Ok(OpticalResult {
+        wavelength: 2.0 * PI,
+        q_sca,
+        q_abs,
+        q_ext,
+        c_sca: q_sca * geometric_area,
+        c_abs: q_abs * geometric_area,
+        c_ext: q_ext * geometric_area,
+        g: coeffs.g(),
+        q_pr: coeffs.q_pr(),
+        metadata: OpticalMetadata {
+            num_terms: Some(coeffs.num_terms()),
+            converged: true,
+            size_parameter: x,
+            notes: vec!["Universal curve (reduced units λ/2π = 1)".to_string()],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_absorbing_oscillates_around_two() {
+        let x_values: Vec<f64> = (0..=400).map(|i| 60.0 + i as f64 * 0.1).collect();
+        let results = efficiency_vs_x(Complex64::new(1.33, 0.0), &x_values).unwrap();
+
+        let mean = results.iter().map(|r| r.q_ext).sum::<f64>() / results.len() as f64;
+        assert!((mean - 2.0).abs() < 0.15, "mean Q_ext = {}", mean);
+
+        // Ripple structure: the curve is not flat
+        let max = results.iter().map(|r| r.q_ext).fold(f64::NEG_INFINITY, f64::max);
+        let min = results.iter().map(|r| r.q_ext).fold(f64::INFINITY, f64::min);
+        assert!(max - min > 1e-3);
+
+        for r in &results {
+            assert!(r.q_abs.abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_positive_x() {
+        assert!(efficiency_vs_x(Complex64::new(1.5, 0.0), &[0.0]).is_err());
+    }
+}
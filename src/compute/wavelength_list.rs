@@ -0,0 +1,69 @@
+//! Results at a handful of chosen wavelengths
+//!
+//! For laser lines and other discrete sources (405, 532, 633, 785 nm, ...)
+//! the model is evaluated at exactly the listed wavelengths, independent of
+//! the spectrum grid.
+
+use crate::core::units::Wavelength;
+use crate::core::{CalcResult, OpticalModel, OpticalResult};
+
+/// A parsed wavelength list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WavelengthList {
+    /// Valid wavelengths in nm, in the order given
+    pub wavelengths: Vec<f64>,
+    /// Entries that were not valid wavelengths, as typed
+    pub skipped: Vec<String>,
+}
+
+/// Parse a comma-separated list of wavelengths in nm
+///
+/// Empty entries are ignored; entries that are not numbers, or are below
+/// [`MIN_WAVELENGTH_NM`](crate::core::units::MIN_WAVELENGTH_NM), are skipped
+/// and reported in [`WavelengthList::skipped`].
+pub fn parse_wavelength_list(text: &str) -> WavelengthList {
+    let mut list = WavelengthList::default();
+    for entry in text.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        match entry.parse::<f64>() {
+            Ok(value) if value.is_finite() && Wavelength(value).checked().is_ok() => list.wavelengths.push(value),
+            _ => list.skipped.push(entry.to_string()),
+        }
+    }
+    list
+}
+
+/// `model` evaluated at each of `wavelengths`, one result per wavelength
+pub fn at_wavelengths(model: &dyn OpticalModel, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+    model.calculate_spectrum(wavelengths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::RefractiveIndex;
+    use crate::physics::optical::mie::MieModel;
+
+    #[test]
+    fn test_parse_skips_invalid_entries() {
+        let list = parse_wavelength_list(" 405, 532 ,abc,633,, -10, 0.5, inf, 785.5 ");
+        assert_eq!(list.wavelengths, vec![405.0, 532.0, 633.0, 785.5]);
+        assert_eq!(list.skipped, vec!["abc", "-10", "0.5", "inf"]);
+
+        assert_eq!(parse_wavelength_list(""), WavelengthList::default());
+    }
+
+    #[test]
+    fn test_one_row_per_valid_wavelength() {
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.4), 1.33);
+        let list = parse_wavelength_list("405, 532, x, 633, 785");
+        let rows = at_wavelengths(&model, &list.wavelengths).unwrap();
+
+        assert_eq!(rows.len(), list.wavelengths.len());
+        for (row, &wl) in rows.iter().zip(&list.wavelengths) {
+            assert_eq!(row.wavelength, wl);
+            let mut single = model.clone();
+            single.wavelength = wl;
+            assert_eq!(row.q_ext, single.calculate().unwrap().q_ext);
+        }
+    }
+}
@@ -4,81 +4,74 @@
 
 use std::f64::consts::PI;
 
-/// Speed of light in vacuum [m/s]
-pub const C: f64 = 2.99792458e8;
-
-/// Speed of light in nm/s
-pub const C_NM_S: f64 = 2.99792458e17;
-
-/// Planck constant [J·s]
-pub const H: f64 = 6.62607015e-34;
-
-/// Reduced Planck constant ℏ [J·s]
-pub const HBAR: f64 = 1.054571817e-34;
-
-/// Boltzmann constant [J/K]
-pub const K_B: f64 = 1.380649e-23;
-
-/// Elementary charge [C]
-pub const E: f64 = 1.602176634e-19;
-
-/// Electron mass [kg]
-pub const M_E: f64 = 9.1093837015e-31;
-
-/// Proton mass [kg]
-pub const M_P: f64 = 1.67262192369e-27;
-
-/// Avogadro constant [mol⁻¹]
-pub const N_A: f64 = 6.02214076e23;
-
-/// Vacuum permittivity ε₀ [F/m]
-pub const EPSILON_0: f64 = 8.8541878128e-12;
-
-/// Vacuum permeability μ₀ [H/m]
-pub const MU_0: f64 = 1.25663706212e-6;
-
-/// Fine structure constant α (dimensionless)
-pub const ALPHA: f64 = 7.2973525693e-3;
-
-/// Rydberg constant [eV]
-pub const RY: f64 = 13.605693122994;
-
-/// Bohr radius [m]
-pub const BOHR_RADIUS: f64 = 5.29177210903e-11;
+/// Declare `f64` constants together with their [`ConstantInfo`] rows
+///
+/// Each entry is `NAME = value, symbol, unit, description;`. The doc
+/// comment of each constant and the `TABLE` read by [`describe`] are both
+/// generated from it, so a constant cannot be left out of the table.
+macro_rules! constant_table {
+    ($vis:vis TABLE, $prefix:literal; $($name:ident = $value:expr, $symbol:literal, $unit:literal, $description:literal;)*) => {
+        $(
+            #[doc = concat!($description, " (", $unit, ")")]
+            pub const $name: f64 = $value;
+        )*
+
+        /// Every constant declared here, in declaration order
+        $vis const TABLE: &[ConstantInfo] = &[$(ConstantInfo {
+            name: concat!($prefix, stringify!($name)),
+            symbol: $symbol,
+            description: $description,
+            value: $name,
+            unit: $unit,
+        }),*];
+    };
+}
 
-/// Bohr radius [nm]
-pub const BOHR_RADIUS_NM: f64 = 0.05291772109;
+constant_table! {
+    TABLE, "";
+    C = 2.99792458e8, "c", "m/s", "Speed of light in vacuum";
+    C_NM_S = 2.99792458e17, "c", "nm/s", "Speed of light in vacuum";
+    H = 6.62607015e-34, "h", "J·s", "Planck constant";
+    HBAR = 1.054571817e-34, "ℏ", "J·s", "Reduced Planck constant";
+    K_B = 1.380649e-23, "k_B", "J/K", "Boltzmann constant";
+    E = 1.602176634e-19, "e", "C", "Elementary charge";
+    M_E = 9.1093837015e-31, "m_e", "kg", "Electron mass";
+    M_P = 1.67262192369e-27, "m_p", "kg", "Proton mass";
+    N_A = 6.02214076e23, "N_A", "mol⁻¹", "Avogadro constant";
+    EPSILON_0 = 8.8541878128e-12, "ε₀", "F/m", "Vacuum permittivity";
+    MU_0 = 1.25663706212e-6, "μ₀", "H/m", "Vacuum permeability";
+    ALPHA = 7.2973525693e-3, "α", "1", "Fine structure constant";
+    RY = 13.605693122994, "Ry", "eV", "Rydberg energy";
+    BOHR_RADIUS = 5.29177210903e-11, "a₀", "m", "Bohr radius";
+    BOHR_RADIUS_NM = 0.05291772109, "a₀", "nm", "Bohr radius";
+}
 
 /// Conversion factors
 pub mod conversions {
-    /// Electron volt to Joule
-    pub const EV_TO_J: f64 = 1.602176634e-19;
-
-    /// Joule to electron volt
-    pub const J_TO_EV: f64 = 6.241509074e18;
-
-    /// Nanometer to meter
-    pub const NM_TO_M: f64 = 1e-9;
-
-    /// Meter to nanometer
-    pub const M_TO_NM: f64 = 1e9;
-
-    /// h*c product in eV·nm (useful for photon energy)
-    pub const HC_EV_NM: f64 = 1239.84193;
-
-    /// Atomic mass unit to kg
-    pub const AMU_TO_KG: f64 = 1.66053906660e-27;
+    use super::ConstantInfo;
+
+    constant_table! {
+        pub(super) TABLE, "conversions::";
+        EV_TO_J = 1.602176634e-19, "eV→J", "J/eV", "Electron volt to joule";
+        J_TO_EV = 6.241509074e18, "J→eV", "eV/J", "Joule to electron volt";
+        NM_TO_M = 1e-9, "nm→m", "m/nm", "Nanometer to meter";
+        M_TO_NM = 1e9, "m→nm", "nm/m", "Meter to nanometer";
+        NM_TO_CM = 1e-7, "nm→cm", "cm/nm", "Nanometer to centimeter";
+        ZERO_CELSIUS_K = 273.15, "T₀", "K", "0 °C in kelvin";
+        HC_EV_NM = 1239.84193, "hc", "eV·nm", "Planck constant × speed of light";
+        AMU_TO_KG = 1.66053906660e-27, "u", "kg", "Atomic mass unit";
+    }
 }
 
 /// Useful compound constants
 pub mod compound {
     use super::*;
 
-    /// Thermal energy at 300 K in eV
-    pub const K_B_T_300K_EV: f64 = 0.02585;
-
-    /// Thermal energy at 300 K in Joules
-    pub const K_B_T_300K_J: f64 = 4.14e-21;
+    constant_table! {
+        pub(super) TABLE, "compound::";
+        K_B_T_300K_EV = 0.02585, "k_BT", "eV", "Thermal energy at 300 K";
+        K_B_T_300K_J = 4.14e-21, "k_BT", "J", "Thermal energy at 300 K";
+    }
 
     /// Characteristic length in nm for thermal de Broglie wavelength at 300K
     pub fn thermal_de_broglie_nm(mass_kg: f64) -> f64 {
@@ -92,6 +85,23 @@ pub mod compound {
     }
 }
 
+/// Description of a physical constant for display
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantInfo {
+    /// Rust identifier (e.g. `HBAR`, `conversions::EV_TO_J`)
+    pub name: &'static str,
+    /// Conventional symbol
+    pub symbol: &'static str,
+    pub description: &'static str,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+/// All public constants of this module, in declaration order
+pub fn describe() -> Vec<ConstantInfo> {
+    [TABLE, conversions::TABLE, compound::TABLE].concat()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +135,20 @@ mod tests {
         let a0_calc = 4.0 * PI * EPSILON_0 * HBAR.powi(2) / (M_E * E.powi(2));
         assert!((a0_calc - BOHR_RADIUS).abs() / BOHR_RADIUS < 1e-6);
     }
+
+    #[test]
+    fn test_describe_lists_every_constant() {
+        let table = describe();
+        assert_eq!(table.len(), TABLE.len() + conversions::TABLE.len() + compound::TABLE.len());
+        for (i, info) in table.iter().enumerate() {
+            assert!(table[..i].iter().all(|other| other.name != info.name), "{} listed twice", info.name);
+            assert!(!info.unit.is_empty() && !info.symbol.is_empty(), "{}", info.name);
+        }
+
+        let value = |name: &str| table.iter().find(|c| c.name == name).expect(name).value;
+        assert_eq!(value("HBAR"), HBAR);
+        assert_eq!(value("conversions::NM_TO_CM"), conversions::NM_TO_CM);
+        assert_eq!(value("conversions::ZERO_CELSIUS_K"), conversions::ZERO_CELSIUS_K);
+        assert_eq!(value("compound::K_B_T_300K_J"), compound::K_B_T_300K_J);
+    }
 }
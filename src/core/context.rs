@@ -0,0 +1,201 @@
+//! Physical constants as seen by a calculation
+//!
+//! Calculations read c, h, e and k_B through a [`PhysicsContext`] instead of
+//! the `const`s in [`constants`], so that a "what-if" session can scale
+//! them. Photon energies, k_B·T, the Brus quantum-dot gap and the Callaway
+//! phonon conductivity all follow the context. Each constant carries a scale
+//! factor; with every factor at exactly 1 the derived values are
+//! bit-identical to CODATA.
+
+use super::constants::{self, conversions};
+use super::units::Wavelength;
+use super::ValidationResult;
+use serde::{Deserialize, Serialize};
+
+/// A constant that can be scaled in a [`PhysicsContext`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalableConstant {
+    SpeedOfLight,
+    Planck,
+    ElementaryCharge,
+    Boltzmann,
+}
+
+impl ScalableConstant {
+    pub const ALL: [ScalableConstant; 4] = [
+        ScalableConstant::SpeedOfLight,
+        ScalableConstant::Planck,
+        ScalableConstant::ElementaryCharge,
+        ScalableConstant::Boltzmann,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ScalableConstant::SpeedOfLight => "c",
+            ScalableConstant::Planck => "h",
+            ScalableConstant::ElementaryCharge => "e",
+            ScalableConstant::Boltzmann => "k_B",
+        }
+    }
+
+    /// (English, Spanish) name
+    pub fn name(self) -> (&'static str, &'static str) {
+        match self {
+            ScalableConstant::SpeedOfLight => ("Speed of light", "Velocidad de la luz"),
+            ScalableConstant::Planck => ("Planck constant", "Constante de Planck"),
+            ScalableConstant::ElementaryCharge => ("Elementary charge", "Carga elemental"),
+            ScalableConstant::Boltzmann => ("Boltzmann constant", "Constante de Boltzmann"),
+        }
+    }
+
+    /// CODATA 2018 value in SI units
+    pub fn codata(self) -> f64 {
+        match self {
+            ScalableConstant::SpeedOfLight => constants::C,
+            ScalableConstant::Planck => constants::H,
+            ScalableConstant::ElementaryCharge => constants::E,
+            ScalableConstant::Boltzmann => constants::K_B,
+        }
+    }
+}
+
+/// Scale factors applied to the CODATA constants
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhysicsContext {
+    pub c_scale: f64,
+    pub h_scale: f64,
+    pub e_scale: f64,
+    pub k_b_scale: f64,
+}
+
+impl Default for PhysicsContext {
+    fn default() -> Self {
+        Self::CODATA
+    }
+}
+
+impl PhysicsContext {
+    /// Every constant at its CODATA value
+    pub const CODATA: Self = Self {
+        c_scale: 1.0,
+        h_scale: 1.0,
+        e_scale: 1.0,
+        k_b_scale: 1.0,
+    };
+
+    pub fn scale(&self, constant: ScalableConstant) -> f64 {
+        match constant {
+            ScalableConstant::SpeedOfLight => self.c_scale,
+            ScalableConstant::Planck => self.h_scale,
+            ScalableConstant::ElementaryCharge => self.e_scale,
+            ScalableConstant::Boltzmann => self.k_b_scale,
+        }
+    }
+
+    pub fn scale_mut(&mut self, constant: ScalableConstant) -> &mut f64 {
+        match constant {
+            ScalableConstant::SpeedOfLight => &mut self.c_scale,
+            ScalableConstant::Planck => &mut self.h_scale,
+            ScalableConstant::ElementaryCharge => &mut self.e_scale,
+            ScalableConstant::Boltzmann => &mut self.k_b_scale,
+        }
+    }
+
+    /// Whether any constant is scaled away from CODATA
+    pub fn is_overridden(&self) -> bool {
+        *self != Self::CODATA
+    }
+
+    /// Restore every constant to CODATA
+    pub fn reset(&mut self) {
+        *self = Self::CODATA;
+    }
+
+    /// Value of `constant` in SI units under this context
+    pub fn value(&self, constant: ScalableConstant) -> f64 {
+        constant.codata() * self.scale(constant)
+    }
+
+    /// Reduced Planck constant ħ in J·s
+    pub fn hbar(&self) -> f64 {
+        constants::HBAR * self.h_scale
+    }
+
+    /// Factor on the Bohr radius a₀ = 4πε₀ħ²/(m_e e²)
+    pub fn bohr_radius_scale(&self) -> f64 {
+        (self.h_scale / self.e_scale).powi(2)
+    }
+
+    /// Factor on the Rydberg energy in eV, m_e e³/(2(4πε₀ħ)²)
+    pub fn rydberg_scale(&self) -> f64 {
+        self.e_scale.powi(3) / self.h_scale.powi(2)
+    }
+
+    /// h·c in eV·nm
+    ///
+    /// The electron volt is defined through e, so scaling e changes it too.
+    pub fn hc_ev_nm(&self) -> f64 {
+        conversions::HC_EV_NM * (self.h_scale * self.c_scale / self.e_scale)
+    }
+
+    /// Photon energy E = hc/λ in eV
+    pub fn photon_energy_ev(&self, wavelength: Wavelength) -> ValidationResult<f64> {
+        Ok(self.hc_ev_nm() / wavelength.checked()?.0)
+    }
+
+    /// Frequency ν = c/λ in Hz
+    pub fn frequency_hz(&self, wavelength: Wavelength) -> ValidationResult<f64> {
+        Ok(constants::C_NM_S * self.c_scale / wavelength.checked()?.0)
+    }
+
+    /// Thermal energy k_B·T in eV
+    pub fn thermal_energy_ev(&self, temperature: f64) -> f64 {
+        self.value(ScalableConstant::Boltzmann) * temperature / self.value(ScalableConstant::ElementaryCharge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_scales_dependent_results() {
+        let green = Wavelength(500.0);
+        let codata = PhysicsContext::CODATA;
+        let mut what_if = codata;
+        *what_if.scale_mut(ScalableConstant::SpeedOfLight) = 2.0;
+        assert!(what_if.is_overridden());
+
+        // E ∝ hc/e and ν ∝ c: doubling c doubles both
+        let ratio = what_if.photon_energy_ev(green).unwrap() / codata.photon_energy_ev(green).unwrap();
+        assert!((ratio - 2.0).abs() < 1e-15);
+        assert_eq!(what_if.frequency_hz(green).unwrap(), 2.0 * codata.frequency_hz(green).unwrap());
+        // k_B·T does not involve c
+        assert_eq!(what_if.thermal_energy_ev(300.0), codata.thermal_energy_ev(300.0));
+
+        *what_if.scale_mut(ScalableConstant::ElementaryCharge) = 2.0;
+        assert!((what_if.hc_ev_nm() - conversions::HC_EV_NM).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reset_restores_exact_codata() {
+        let mut context = PhysicsContext {
+            c_scale: 0.5,
+            h_scale: 3.0,
+            e_scale: 1.1,
+            k_b_scale: 7.0,
+        };
+        context.reset();
+        assert!(!context.is_overridden());
+
+        for wl in [250.0, 500.0, 1234.5] {
+            let wl = Wavelength(wl);
+            assert_eq!(context.photon_energy_ev(wl).unwrap(), wl.to_energy_ev().unwrap().0);
+            assert_eq!(context.frequency_hz(wl).unwrap(), wl.to_frequency_hz().unwrap());
+        }
+        for constant in ScalableConstant::ALL {
+            assert_eq!(context.value(constant), constant.codata());
+        }
+        assert_eq!(context.hc_ev_nm(), conversions::HC_EV_NM);
+    }
+}
@@ -0,0 +1,24 @@
+//! Display language for user-facing messages
+//!
+//! Physics code keeps messages as structured codes with (English, Spanish)
+//! text pairs; they are rendered in the current language only when shown,
+//! so switching language never requires recomputing results.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// Pick the text for this language from an (English, Spanish) pair
+    pub fn pick<'a>(self, en: &'a str, es: &'a str) -> &'a str {
+        match self {
+            Language::English => en,
+            Language::Spanish => es,
+        }
+    }
+}
@@ -3,9 +3,19 @@
 //! This module provides the foundation for all physics calculations in NanoCalc.
 
 pub mod constants;
+pub mod context;
+pub mod i18n;
+pub mod model_kind;
+pub mod shape;
 pub mod traits;
 pub mod types;
+pub mod warnings;
 
 pub use constants::*;
+pub use context::*;
+pub use i18n::*;
+pub use model_kind::*;
+pub use shape::*;
 pub use traits::*;
 pub use types::*;
+pub use warnings::*;
@@ -0,0 +1,35 @@
+//! Selectable optical models
+//!
+//! [`OpticalModelKind`] names each model the GUI can switch between; the
+//! app registry turns a kind and the inputs into a model.
+
+use serde::{Deserialize, Serialize};
+
+/// Optical model used for single points and spectra
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OpticalModelKind {
+    /// Mie series, with Rayleigh below the solver's threshold
+    #[default]
+    Mie,
+    /// Rayleigh-Gans-Debye for weakly scattering spheres
+    RayleighGansDebye,
+    /// van de Hulst anomalous diffraction for large, low-contrast spheres
+    AnomalousDiffraction,
+}
+
+impl OpticalModelKind {
+    pub const ALL: [OpticalModelKind; 3] = [
+        OpticalModelKind::Mie,
+        OpticalModelKind::RayleighGansDebye,
+        OpticalModelKind::AnomalousDiffraction,
+    ];
+
+    /// Display name (English, Spanish)
+    pub fn name(self) -> (&'static str, &'static str) {
+        match self {
+            OpticalModelKind::Mie => ("Mie", "Mie"),
+            OpticalModelKind::RayleighGansDebye => ("Rayleigh-Gans-Debye", "Rayleigh-Gans-Debye"),
+            OpticalModelKind::AnomalousDiffraction => ("Anomalous diffraction", "Difracción anómala"),
+        }
+    }
+}
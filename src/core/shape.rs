@@ -0,0 +1,108 @@
+//! Particle shapes and their geometric cross sections
+//!
+//! Efficiencies are normalized by the orientation-averaged projected area.
+//! For convex bodies Cauchy's theorem gives this as one quarter of the
+//! surface area, which reduces to πr² for a sphere.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Particle geometry (all lengths in nm)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ParticleShape {
+    Sphere {
+        radius: f64,
+    },
+    /// Spheroid with equatorial semi-axis a and polar semi-axis c
+    Spheroid {
+        equatorial_radius: f64,
+        polar_radius: f64,
+    },
+    /// Right circular cylinder with flat end caps
+    Cylinder {
+        radius: f64,
+        length: f64,
+    },
+}
+
+impl ParticleShape {
+    /// Total surface area in nm²
+    pub fn surface_area(&self) -> f64 {
+        match *self {
+            ParticleShape::Sphere { radius } => 4.0 * PI * radius * radius,
+            ParticleShape::Spheroid {
+                equatorial_radius: a,
+                polar_radius: c,
+            } => {
+                if (a - c).abs() <= 1e-12 * a.max(c) {
+                    4.0 * PI * a * a
+                } else if c > a {
+                    // Prolate
+                    let e = (1.0 - a * a / (c * c)).sqrt();
+                    2.0 * PI * a * a * (1.0 + c / (a * e) * e.asin())
+                } else {
+                    // Oblate
+                    let e = (1.0 - c * c / (a * a)).sqrt();
+                    2.0 * PI * a * a * (1.0 + (1.0 - e * e) / e * e.atanh())
+                }
+            }
+            ParticleShape::Cylinder { radius, length } => {
+                2.0 * PI * radius * radius + 2.0 * PI * radius * length
+            }
+        }
+    }
+
+    /// Orientation-averaged projected area in nm² (surface area / 4)
+    pub fn projected_area(&self) -> f64 {
+        self.surface_area() / 4.0
+    }
+
+    /// Short formula label for the geometric area
+    pub fn area_label(&self) -> &'static str {
+        match self {
+            ParticleShape::Sphere { .. } => "πr²",
+            ParticleShape::Spheroid { .. } => "⟨A⟩ spheroid",
+            ParticleShape::Cylinder { .. } => "⟨A⟩ cylinder",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_area_is_pi_r_squared() {
+        let shape = ParticleShape::Sphere { radius: 25.0 };
+        assert!((shape.projected_area() - PI * 625.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_prolate_spheroid_area() {
+        // a = 10, c = 20: S = 2πa²(1 + c/(a e) asin e), e = √(1 − a²/c²)
+        let shape = ParticleShape::Spheroid {
+            equatorial_radius: 10.0,
+            polar_radius: 20.0,
+        };
+        let e: f64 = (1.0f64 - 0.25).sqrt();
+        let expected = 2.0 * PI * 100.0 * (1.0 + 2.0 / e * e.asin()) / 4.0;
+        assert!((shape.projected_area() - expected).abs() < 1e-9);
+        // Lies between the two principal projections πa² and πac
+        assert!(shape.projected_area() > PI * 100.0 && shape.projected_area() < PI * 200.0);
+    }
+
+    #[test]
+    fn test_spheroid_limits_to_sphere() {
+        let nearly = ParticleShape::Spheroid {
+            equatorial_radius: 10.0,
+            polar_radius: 10.0 + 1e-6,
+        };
+        let oblate = ParticleShape::Spheroid {
+            equatorial_radius: 10.0 + 1e-6,
+            polar_radius: 10.0,
+        };
+        let sphere = ParticleShape::Sphere { radius: 10.0 }.projected_area();
+        assert!((nearly.projected_area() - sphere).abs() / sphere < 1e-5);
+        assert!((oblate.projected_area() - sphere).abs() / sphere < 1e-5);
+    }
+}
@@ -3,8 +3,15 @@
 //! These traits provide extensibility: new physical models can be added
 //! by implementing these traits without modifying existing code.
 
-use crate::core::types::{CalcResult, ValidationResult};
+use crate::core::shape::ParticleShape;
+use crate::core::types::{CalcResult, ValidationError, ValidationResult};
+use crate::core::warnings::Warning;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Wavelengths per chunk, and so between calls of the progress callback,
+/// in [`OpticalModel::calculate_spectrum_with_progress`]
+pub const PROGRESS_INTERVAL: usize = 32;
 
 /// Base trait for all physics models
 ///
@@ -25,7 +32,7 @@ pub trait PhysicsModel: Send + Sync {
     }
 
     /// Get warnings about parameter ranges (non-fatal)
-    fn warnings(&self) -> Vec<String> {
+    fn warnings(&self) -> Vec<Warning> {
         Vec::new()
     }
 }
@@ -43,6 +50,71 @@ pub trait OpticalModel: PhysicsModel {
         &self,
         wavelengths: &[f64], // nm
     ) -> CalcResult<Vec<OpticalResult>>;
+
+    /// [`Self::calculate_spectrum`] in chunks, reporting progress and
+    /// stopping early when asked
+    ///
+    /// The wavelengths are passed to [`Self::calculate_spectrum`]
+    /// [`PROGRESS_INTERVAL`] at a time, so models that batch their points
+    /// keep doing so. `progress(done, total)` is called after each chunk.
+    /// `cancel` is checked before each chunk; once it is set the results
+    /// computed so far are returned.
+    fn calculate_spectrum_with_progress(
+        &self,
+        wavelengths: &[f64],
+        progress: &mut dyn FnMut(usize, usize),
+        cancel: &AtomicBool,
+    ) -> CalcResult<Vec<OpticalResult>> {
+        let total = wavelengths.len();
+        let mut results = Vec::with_capacity(total);
+        for chunk in wavelengths.chunks(PROGRESS_INTERVAL) {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            results.extend(self.calculate_spectrum(chunk)?);
+            progress(results.len(), total);
+        }
+        if total == 0 {
+            progress(0, 0);
+        }
+        Ok(results)
+    }
+
+    /// Particle geometry used by the model
+    fn shape(&self) -> ParticleShape;
+
+    /// Geometric area in nm² used to convert cross sections to efficiencies
+    fn geometric_cross_section(&self) -> f64 {
+        self.shape().projected_area()
+    }
+
+    /// How well the model's approximations hold at each wavelength
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag>;
+}
+
+/// Whether a model's approximations hold at a given point
+///
+/// Ordered from best to worst, so `max` combines several criteria.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ValidityFlag {
+    Valid,
+    Marginal,
+    Invalid,
+}
+
+impl ValidityFlag {
+    /// Classify a regime parameter that must be small for the model to hold
+    ///
+    /// Valid below `valid_below`, Marginal below `marginal_below`, else Invalid.
+    pub fn from_parameter(value: f64, valid_below: f64, marginal_below: f64) -> Self {
+        if value < valid_below {
+            ValidityFlag::Valid
+        } else if value < marginal_below {
+            ValidityFlag::Marginal
+        } else {
+            ValidityFlag::Invalid
+        }
+    }
 }
 
 /// Result of optical calculations
@@ -69,11 +141,20 @@ pub struct OpticalResult {
     /// Extinction cross-section in nm²
     pub c_ext: f64,
 
+    /// Asymmetry parameter g = ⟨cos θ⟩ (0 for models that do not compute it)
+    #[serde(default)]
+    pub g: f64,
+
+    /// Radiation-pressure efficiency Q_pr = Q_ext − g·Q_sca (0 for models
+    /// that do not compute it)
+    #[serde(default)]
+    pub q_pr: f64,
+
     /// Additional metadata
     pub metadata: OpticalMetadata,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct OpticalMetadata {
     /// Number of terms used in series expansion (for Mie)
     pub num_terms: Option<usize>,
@@ -81,7 +162,7 @@ pub struct OpticalMetadata {
     /// Convergence achieved
     pub converged: bool,
 
-    /// Size parameter x = 2πr/λ
+    /// Size parameter x = 2π·n_medium·r/λ
     pub size_parameter: f64,
 
     /// Model-specific notes
@@ -89,10 +170,52 @@ pub struct OpticalMetadata {
 }
 
 impl OpticalResult {
+    /// Efficiencies at one wavelength with Q_ext = Q_sca + Q_abs and every
+    /// other field zero, for building spectra in tests
+    #[cfg(test)]
+    pub(crate) fn for_test(wavelength: f64, q_sca: f64, q_abs: f64) -> Self {
+        Self {
+            wavelength,
+            q_sca,
+            q_abs,
+            q_ext: q_sca + q_abs,
+            c_sca: 0.0,
+            c_abs: 0.0,
+            c_ext: 0.0,
+            g: 0.0,
+            q_pr: 0.0,
+            metadata: OpticalMetadata::default(),
+        }
+    }
+
     /// Conservation check: Q_ext should equal Q_sca + Q_abs
     pub fn check_conservation(&self) -> f64 {
         (self.q_ext - (self.q_sca + self.q_abs)).abs()
     }
+
+    /// Absorption cross-section per particle volume C_abs / (4/3·πr³), in nm⁻¹
+    pub fn c_abs_per_volume(&self, radius: f64) -> ValidationResult<f64> {
+        let volume = 4.0 / 3.0 * std::f64::consts::PI * radius.powi(3);
+        if !(volume > 0.0 && volume.is_finite()) {
+            return Err(ValidationError::InvalidParameter(format!(
+                "Particle volume must be positive (radius = {} nm)",
+                radius
+            )));
+        }
+        Ok(self.c_abs / volume)
+    }
+
+    /// Absorption cross-section per particle mass in m²/g, for a density in g/cm³
+    pub fn c_abs_per_mass(&self, radius: f64, density: f64) -> ValidationResult<f64> {
+        if !(density > 0.0 && density.is_finite()) {
+            return Err(ValidationError::InvalidParameter(format!(
+                "Density must be positive (got {} g/cm³)",
+                density
+            )));
+        }
+        // nm²/nm³ → m²/cm³ is 1e-18 / 1e-21
+        Ok(self.c_abs_per_volume(radius)? * 1e3 / density)
+    }
 }
 
 /// Thermal model trait for calculating thermal properties
@@ -209,6 +332,11 @@ pub trait Cacheable {
     fn cache_key(&self) -> String;
 }
 
+/// An optical model the compute engine can run and cache
+pub trait CacheableOpticalModel: OpticalModel + Cacheable {}
+
+impl<T: OpticalModel + Cacheable + ?Sized> CacheableOpticalModel for T {}
+
 /// Trait for models that can be parallelized
 pub trait Parallelizable {
     /// Can this calculation be split across multiple threads?
@@ -229,16 +357,43 @@ mod tests {
     #[test]
     fn test_optical_result_conservation() {
         let result = OpticalResult {
-            wavelength: 500.0,
-            q_sca: 1.5,
-            q_abs: 0.5,
-            q_ext: 2.0,
             c_sca: 100.0,
             c_abs: 33.33,
             c_ext: 133.33,
-            metadata: OpticalMetadata::default(),
+            ..OpticalResult::for_test(500.0, 1.5, 0.5)
         };
 
         assert!(result.check_conservation() < 1e-10);
     }
+
+    #[test]
+    fn test_absorption_per_volume_scales_as_inverse_radius() {
+        // Fixed Q_abs: C_abs = Q_abs·πr² ∝ r², V ∝ r³
+        let at_radius = |r: f64| OpticalResult {
+            c_abs: 2.0 * std::f64::consts::PI * r * r,
+            c_ext: 2.0 * std::f64::consts::PI * r * r,
+            ..OpticalResult::for_test(520.0, 0.0, 2.0)
+        };
+        let small = at_radius(10.0).c_abs_per_volume(10.0).unwrap();
+        let large = at_radius(40.0).c_abs_per_volume(40.0).unwrap();
+        assert!((small / large - 4.0).abs() < 1e-12);
+        // Q_abs·πr² / (4/3·πr³) = 3·Q_abs / (4r)
+        assert!((small - 3.0 * 2.0 / 40.0).abs() < 1e-12);
+
+        let per_mass = at_radius(10.0).c_abs_per_mass(10.0, 19.3).unwrap();
+        assert!((per_mass - small * 1e3 / 19.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normalization_rejects_non_positive_inputs() {
+        let result = OpticalResult {
+            c_abs: 1.0,
+            c_ext: 1.0,
+            ..OpticalResult::for_test(520.0, 0.0, 1.0)
+        };
+        assert!(result.c_abs_per_volume(0.0).is_err());
+        assert!(result.c_abs_per_volume(-5.0).is_err());
+        assert!(result.c_abs_per_mass(10.0, 0.0).is_err());
+        assert!(result.c_abs_per_mass(10.0, f64::NAN).is_err());
+    }
 }
@@ -3,12 +3,18 @@
 //! This module defines the fundamental abstractions used throughout the system.
 
 use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Physical units using newtype pattern for type safety
 pub mod units {
+    use super::{ValidationError, ValidationResult};
+    use crate::core::context::PhysicsContext;
     use serde::{Deserialize, Serialize};
 
+    /// Shortest wavelength (nm) the energy and frequency conversions accept
+    pub const MIN_WAVELENGTH_NM: f64 = 1.0;
+
     /// Nanometers
     #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
     pub struct Nanometer(pub f64);
@@ -21,6 +27,10 @@ pub mod units {
     #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
     pub struct Wavelength(pub f64);
 
+    /// Area in square nanometers
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+    pub struct Area(pub f64);
+
     /// Temperature in Kelvin
     #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
     pub struct Kelvin(pub f64);
@@ -40,20 +50,45 @@ pub mod units {
     }
 
     impl Wavelength {
-        pub fn to_energy_ev(self) -> ElectronVolt {
-            const HC: f64 = 1239.84193; // h*c in eV·nm
-            ElectronVolt(HC / self.0)
+        /// This wavelength, or an error if it is below [`MIN_WAVELENGTH_NM`]
+        ///
+        /// Both conversions divide by λ, so a zero or tiny wavelength would
+        /// otherwise come out as an infinite or absurd energy.
+        pub fn checked(self) -> ValidationResult<Self> {
+            if self.0.is_nan() || self.0 < MIN_WAVELENGTH_NM {
+                return Err(ValidationError::OutOfRange {
+                    value: self.0,
+                    min: MIN_WAVELENGTH_NM,
+                    max: f64::INFINITY,
+                });
+            }
+            Ok(self)
+        }
+
+        /// Photon energy with CODATA constants
+        pub fn to_energy_ev(self) -> ValidationResult<ElectronVolt> {
+            Ok(ElectronVolt(PhysicsContext::CODATA.photon_energy_ev(self)?))
+        }
+
+        /// Frequency with the CODATA speed of light
+        pub fn to_frequency_hz(self) -> ValidationResult<f64> {
+            PhysicsContext::CODATA.frequency_hz(self)
+        }
+    }
+
+    impl Area {
+        pub fn to_square_meters(self) -> f64 {
+            self.0 * 1e-18
         }
 
-        pub fn to_frequency_hz(self) -> f64 {
-            const C: f64 = 2.99792458e17; // speed of light in nm/s
-            C / self.0
+        pub fn from_square_meters(m2: f64) -> Self {
+            Area(m2 * 1e18)
         }
     }
 
     impl Kelvin {
         pub fn to_celsius(self) -> f64 {
-            self.0 - 273.15
+            self.0 - crate::core::conversions::ZERO_CELSIUS_K
         }
     }
 }
@@ -81,6 +116,12 @@ impl RefractiveIndex {
         let n = self.to_complex();
         n * n
     }
+
+    /// Index n + ik = √ε on the principal branch, so k ≥ 0 for ε'' ≥ 0
+    pub fn from_permittivity(eps: Complex64) -> Self {
+        let n = eps.sqrt();
+        Self::new(n.re, n.im)
+    }
 }
 
 impl fmt::Display for RefractiveIndex {
@@ -89,6 +130,16 @@ impl fmt::Display for RefractiveIndex {
     }
 }
 
+/// Particle and medium inputs of a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunInputs {
+    pub particle_radius: f64,
+    pub wavelength: f64,
+    pub n_particle_real: f64,
+    pub n_particle_imag: f64,
+    pub n_medium: f64,
+}
+
 /// Parameter validation result
 pub type ValidationResult<T> = Result<T, ValidationError>;
 
@@ -120,8 +171,36 @@ pub enum CalculationError {
     #[error("Model not applicable: {0}")]
     ModelNotApplicable(String),
 
+    /// Stopped through a cancel flag before it finished
+    #[error("Calculation cancelled")]
+    Cancelled,
+
     #[error("Validation error: {0}")]
     Validation(#[from] ValidationError),
 }
 
 pub type CalcResult<T> = Result<T, CalculationError>;
+
+#[cfg(test)]
+mod tests {
+    use super::units::*;
+
+    #[test]
+    fn test_conversions_at_normal_wavelengths() {
+        let green = Wavelength(500.0);
+        assert!((green.to_energy_ev().unwrap().0 - 2.4796839).abs() < 1e-6);
+        assert!((green.to_frequency_hz().unwrap() - 5.99584916e14).abs() < 1e6);
+    }
+
+    #[test]
+    fn test_conversions_reject_wavelengths_below_minimum() {
+        let edge = Wavelength(MIN_WAVELENGTH_NM);
+        assert!(edge.to_energy_ev().unwrap().0.is_finite());
+        assert!(edge.to_frequency_hz().unwrap().is_finite());
+
+        for wl in [MIN_WAVELENGTH_NM * 0.999, 1e-12, 0.0, -500.0, f64::NAN] {
+            assert!(Wavelength(wl).to_energy_ev().is_err(), "λ = {}", wl);
+            assert!(Wavelength(wl).to_frequency_hz().is_err(), "λ = {}", wl);
+        }
+    }
+}
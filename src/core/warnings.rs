@@ -0,0 +1,156 @@
+//! Structured, localizable model warnings
+//!
+//! Models report non-fatal issues as [`Warning`] values carrying a stable
+//! [`WarningCode`], so front-ends can categorize and translate them.
+
+use super::i18n::Language;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How serious a warning is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WarningSeverity {
+    /// Informational note, results are reliable
+    Info,
+    /// Results may be inaccurate
+    Caution,
+    /// Results are likely wrong
+    Severe,
+}
+
+/// Stable identifier of a warning condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WarningCode {
+    /// Size parameter exceeds the validity of the Rayleigh approximation
+    SizeParameterTooLarge,
+    /// The Mie series reached its term cap before converging
+    SeriesNotConverged,
+    /// Phase shift |m−1|·x exceeds the validity of Rayleigh-Gans-Debye
+    PhaseShiftTooLarge,
+    /// Outside x ≫ 1, |m−1| ≪ 1 where anomalous diffraction holds
+    AnomalousDiffractionRegime,
+    /// Wavelengths past the tabulated data were clamped to its edge
+    WavelengthOutOfDatasetRange,
+    /// An absorbing material was applied as the medium; its k was dropped
+    MediumAbsorptionDiscarded,
+}
+
+impl WarningCode {
+    /// All known codes
+    pub const ALL: &'static [WarningCode] = &[
+        WarningCode::SizeParameterTooLarge,
+        WarningCode::SeriesNotConverged,
+        WarningCode::PhaseShiftTooLarge,
+        WarningCode::AnomalousDiffractionRegime,
+        WarningCode::WavelengthOutOfDatasetRange,
+        WarningCode::MediumAbsorptionDiscarded,
+    ];
+
+    /// Localized message as an (English, Spanish) pair
+    pub fn message(self) -> (&'static str, &'static str) {
+        match self {
+            WarningCode::SizeParameterTooLarge => (
+                "Size parameter is large: Rayleigh approximation may be inaccurate. Full Mie theory recommended.",
+                "Parámetro de tamaño grande: la aproximación de Rayleigh puede ser inexacta. Se recomienda teoría de Mie completa.",
+            ),
+            WarningCode::SeriesNotConverged => (
+                "The Mie series did not converge within the term cap: raise the cap or loosen the tolerance.",
+                "La serie de Mie no convergió dentro del límite de términos: aumente el límite o relaje la tolerancia.",
+            ),
+            WarningCode::PhaseShiftTooLarge => (
+                "Phase shift |m−1|·x is large: Rayleigh-Gans-Debye may be inaccurate. Full Mie theory recommended.",
+                "Desfase |m−1|·x grande: Rayleigh-Gans-Debye puede ser inexacto. Se recomienda teoría de Mie completa.",
+            ),
+            WarningCode::AnomalousDiffractionRegime => (
+                "Anomalous diffraction needs a large (x ≫ 1), low-contrast (|m−1| ≪ 1) particle. Full Mie theory recommended.",
+                "La difracción anómala requiere una partícula grande (x ≫ 1) y de bajo contraste (|m−1| ≪ 1). Se recomienda teoría de Mie completa.",
+            ),
+            WarningCode::WavelengthOutOfDatasetRange => (
+                "Wavelengths outside the tabulated data use the nearest edge values, not extrapolation.",
+                "Las longitudes de onda fuera de los datos tabulados usan los valores del borde más cercano, sin extrapolar.",
+            ),
+            WarningCode::MediumAbsorptionDiscarded => (
+                "The medium must be non-absorbing: only the real part of its index was applied.",
+                "El medio debe ser no absorbente: solo se aplicó la parte real de su índice.",
+            ),
+        }
+    }
+}
+
+/// A non-fatal warning emitted by a physics model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Warning {
+    pub code: WarningCode,
+    pub severity: WarningSeverity,
+    /// Condition-specific detail (e.g. the offending value)
+    pub detail: String,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode, severity: WarningSeverity, detail: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl Warning {
+    /// Message rendered in `language`, with the detail appended
+    pub fn localized(&self, language: Language) -> String {
+        let (en, es) = self.code.message();
+        let message = language.pick(en, es);
+        if self.detail.is_empty() {
+            message.to_string()
+        } else {
+            format!("{} ({})", message, self.detail)
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localized(Language::English))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_code_has_localized_text() {
+        for code in WarningCode::ALL {
+            let (en, es) = code.message();
+            assert!(!en.is_empty());
+            assert!(!es.is_empty());
+            assert_ne!(en, es);
+        }
+    }
+
+    #[test]
+    fn test_display_includes_detail() {
+        let w = Warning::new(
+            WarningCode::SizeParameterTooLarge,
+            WarningSeverity::Caution,
+            "x = 2.50",
+        );
+        assert!(w.to_string().ends_with("(x = 2.50)"));
+    }
+
+    #[test]
+    fn test_language_switch_rerenders_without_recompute() {
+        let w = Warning::new(
+            WarningCode::SizeParameterTooLarge,
+            WarningSeverity::Caution,
+            "x = 2.50",
+        );
+        let (en, es) = WarningCode::SizeParameterTooLarge.message();
+
+        let mut language = Language::English;
+        assert_eq!(w.localized(language), format!("{} (x = 2.50)", en));
+        language = Language::Spanish;
+        assert_eq!(w.localized(language), format!("{} (x = 2.50)", es));
+    }
+}
@@ -0,0 +1,205 @@
+//! Batch input CSV: one calculation per row
+//!
+//! The template and the importer are both generated from [`BatchColumn::ALL`],
+//! so a filled-in template always has the header the importer expects.
+//! Lines starting with `#` are comments.
+
+use super::csv::push_row;
+use super::result_set::{ExportError, ExportResult};
+use crate::core::RunInputs;
+
+/// A column of the batch input CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchColumn {
+    Radius,
+    Wavelength,
+    NParticleReal,
+    NParticleImag,
+    NMedium,
+}
+
+impl BatchColumn {
+    pub const ALL: [BatchColumn; 5] = [
+        BatchColumn::Radius,
+        BatchColumn::Wavelength,
+        BatchColumn::NParticleReal,
+        BatchColumn::NParticleImag,
+        BatchColumn::NMedium,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            BatchColumn::Radius => "particle_radius_nm",
+            BatchColumn::Wavelength => "wavelength_nm",
+            BatchColumn::NParticleReal => "n_particle_real",
+            BatchColumn::NParticleImag => "n_particle_imag",
+            BatchColumn::NMedium => "n_medium",
+        }
+    }
+
+    /// Meaning and unit, written as a comment in the template
+    pub fn description(self) -> &'static str {
+        match self {
+            BatchColumn::Radius => "particle radius in nm",
+            BatchColumn::Wavelength => "vacuum wavelength in nm",
+            BatchColumn::NParticleReal => "particle refractive index n (dimensionless)",
+            BatchColumn::NParticleImag => "particle extinction coefficient k (dimensionless, ≥ 0)",
+            BatchColumn::NMedium => "medium refractive index (dimensionless, real)",
+        }
+    }
+
+    fn example(self) -> f64 {
+        match self {
+            BatchColumn::Radius => 50.0,
+            BatchColumn::Wavelength => 520.0,
+            BatchColumn::NParticleReal => 0.47,
+            BatchColumn::NParticleImag => 2.4,
+            BatchColumn::NMedium => 1.33,
+        }
+    }
+
+    fn value(self, inputs: &RunInputs) -> f64 {
+        match self {
+            BatchColumn::Radius => inputs.particle_radius,
+            BatchColumn::Wavelength => inputs.wavelength,
+            BatchColumn::NParticleReal => inputs.n_particle_real,
+            BatchColumn::NParticleImag => inputs.n_particle_imag,
+            BatchColumn::NMedium => inputs.n_medium,
+        }
+    }
+
+    fn set(self, inputs: &mut RunInputs, value: f64) {
+        let field = match self {
+            BatchColumn::Radius => &mut inputs.particle_radius,
+            BatchColumn::Wavelength => &mut inputs.wavelength,
+            BatchColumn::NParticleReal => &mut inputs.n_particle_real,
+            BatchColumn::NParticleImag => &mut inputs.n_particle_imag,
+            BatchColumn::NMedium => &mut inputs.n_medium,
+        };
+        *field = value;
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Empty batch CSV with unit comments, the header and a commented example row
+pub fn batch_template() -> String {
+    let mut csv = String::from("# nanocalc batch input: one calculation per row\n");
+    for column in BatchColumn::ALL {
+        csv.push_str(&format!("# {}: {}\n", column.header(), column.description()));
+    }
+    push_row(&mut csv, BatchColumn::ALL.iter().map(|c| c.header()));
+    csv.push_str("# ");
+    push_row(&mut csv, BatchColumn::ALL.iter().map(|c| c.example()));
+    csv
+}
+
+/// Render `rows` as a batch CSV the importer reads back
+pub fn batch_csv(rows: &[RunInputs]) -> String {
+    let mut csv = String::new();
+    push_row(&mut csv, BatchColumn::ALL.iter().map(|c| c.header()));
+    for row in rows {
+        push_row(&mut csv, BatchColumn::ALL.iter().map(|c| c.value(row)));
+    }
+    csv
+}
+
+/// Parse a batch CSV into one set of inputs per data row
+///
+/// The header must name every column of [`BatchColumn::ALL`], in any order.
+pub fn read_batch_csv(text: &str) -> ExportResult<Vec<RunInputs>> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !is_comment(line));
+    let header = lines.next().ok_or_else(|| ExportError::Parse("missing header row".to_string()))?;
+    let fields: Vec<&str> = header.split(',').map(str::trim).collect();
+    let columns = fields
+        .iter()
+        .map(|h| {
+            BatchColumn::ALL
+                .into_iter()
+                .find(|c| c.header() == *h)
+                .ok_or_else(|| ExportError::Parse(format!("unknown column '{}'", h)))
+        })
+        .collect::<ExportResult<Vec<_>>>()?;
+    if let Some(missing) = BatchColumn::ALL.into_iter().find(|c| !columns.contains(c)) {
+        return Err(ExportError::Parse(format!("missing column '{}'", missing.header())));
+    }
+
+    let rows = lines
+        .enumerate()
+        .map(|(i, line)| {
+            let values = line
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| ExportError::Parse(format!("invalid number on data row {}: '{}'", i + 1, line)))?;
+            if values.len() != columns.len() {
+                return Err(ExportError::Parse(format!(
+                    "data row {} has {} fields, expected {}",
+                    i + 1,
+                    values.len(),
+                    columns.len()
+                )));
+            }
+            let mut inputs = RunInputs::default();
+            for (column, value) in columns.iter().zip(values) {
+                column.set(&mut inputs, value);
+            }
+            Ok(inputs)
+        })
+        .collect::<ExportResult<Vec<_>>>()?;
+    if rows.is_empty() {
+        return Err(ExportError::Parse("no data rows".to_string()));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filled_template_parses() {
+        let template = batch_template();
+        // The commented example row is not data
+        assert!(matches!(read_batch_csv(&template), Err(ExportError::Parse(_))));
+
+        let filled = format!("{}40,530,0.47,2.4,1.33\n", template);
+        let rows = read_batch_csv(&filled).unwrap();
+        assert_eq!(
+            rows,
+            vec![RunInputs {
+                particle_radius: 40.0,
+                wavelength: 530.0,
+                n_particle_real: 0.47,
+                n_particle_imag: 2.4,
+                n_medium: 1.33,
+            }]
+        );
+        for column in BatchColumn::ALL {
+            assert!(template.contains(column.description()));
+        }
+    }
+
+    #[test]
+    fn test_reordered_columns_round_trip() {
+        let text = "n_medium,wavelength_nm,particle_radius_nm,n_particle_imag,n_particle_real\n1,500,20,0,1.5\n";
+        let rows = read_batch_csv(text).unwrap();
+        assert_eq!((rows[0].n_medium, rows[0].particle_radius, rows[0].n_particle_real), (1.0, 20.0, 1.5));
+        assert_eq!(read_batch_csv(&batch_csv(&rows)).unwrap(), rows);
+    }
+
+    #[test]
+    fn test_malformed_batch_is_rejected() {
+        for text in [
+            "",
+            "particle_radius_nm,wavelength_nm\n50,500\n",
+            "particle_radius_nm,wavelength_nm,n_particle_real,n_particle_imag,n_medium,extra\n",
+            "particle_radius_nm,wavelength_nm,n_particle_real,n_particle_imag,n_medium\n50,500,abc,0,1\n",
+            "particle_radius_nm,wavelength_nm,n_particle_real,n_particle_imag,n_medium\n50,500,1.5\n",
+        ] {
+            assert!(matches!(read_batch_csv(text), Err(ExportError::Parse(_))), "{:?}", text);
+        }
+    }
+}
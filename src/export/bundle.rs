@@ -0,0 +1,217 @@
+//! Self-contained export bundles
+//!
+//! A bundle is a directory holding the project file, data exports and plot
+//! image, plus a `manifest.json` listing every file with its SHA-256
+//! checksum and a provenance block, so a shared analysis can be verified.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::utils::clock::{Clock, SystemClock};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Name of the manifest written at the bundle root
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// Where and how a bundle was produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// NanoCalc version that wrote the bundle
+    pub crate_version: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Name of the model that produced the data
+    pub model: String,
+}
+
+impl Provenance {
+    /// Provenance stamped with the running crate version and current time
+    pub fn now(model: impl Into<String>) -> Self {
+        Self::stamped(model, &SystemClock)
+    }
+
+    /// Provenance stamped with the time read from `clock`
+    pub fn stamped(model: impl Into<String>, clock: &dyn Clock) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: clock.now_unix_secs(),
+            model: model.into(),
+        }
+    }
+}
+
+/// A file to place in the bundle
+#[derive(Debug, Clone)]
+pub struct BundleFile {
+    /// File name relative to the bundle directory
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl BundleFile {
+    pub fn new(name: impl Into<String>, bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+/// Everything written into a bundle
+#[derive(Debug, Clone)]
+pub struct BundleContents {
+    pub provenance: Provenance,
+    pub files: Vec<BundleFile>,
+}
+
+/// Manifest entry for one bundled file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub size: u64,
+    /// Lower-case hex SHA-256 of the file contents
+    pub sha256: String,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub provenance: Provenance,
+    pub files: Vec<ManifestEntry>,
+}
+
+/// Bundle errors
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Cannot serialize manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+
+    #[error("Invalid bundle file name: {0}")]
+    InvalidName(String),
+
+    #[error("Duplicate bundle file name: {0}")]
+    DuplicateName(String),
+}
+
+/// Lower-case hex SHA-256 digest of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Write `contents` and its manifest into the directory `path`
+///
+/// The directory is created if needed. File names must be plain names
+/// (no path separators), unique, and must not clash with the manifest.
+pub fn write(path: impl AsRef<Path>, contents: &BundleContents) -> Result<Manifest, BundleError> {
+    let dir = path.as_ref();
+    let mut seen = HashSet::new();
+    for file in &contents.files {
+        let plain = Path::new(&file.name).file_name().and_then(|n| n.to_str()) == Some(file.name.as_str());
+        if !plain || file.name == MANIFEST_NAME {
+            return Err(BundleError::InvalidName(file.name.clone()));
+        }
+        if !seen.insert(file.name.as_str()) {
+            return Err(BundleError::DuplicateName(file.name.clone()));
+        }
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let mut entries = Vec::with_capacity(contents.files.len());
+    for file in &contents.files {
+        std::fs::write(dir.join(&file.name), &file.bytes)?;
+        entries.push(ManifestEntry {
+            name: file.name.clone(),
+            size: file.bytes.len() as u64,
+            sha256: sha256_hex(&file.bytes),
+        });
+    }
+
+    let manifest = Manifest {
+        provenance: contents.provenance.clone(),
+        files: entries,
+    };
+    std::fs::write(dir.join(MANIFEST_NAME), serde_json::to_string_pretty(&manifest)?)?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::FixedClock;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("nanocalc_bundle_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_sha256_known_vector() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_manifest_lists_files_with_matching_checksums() {
+        let dir = temp_dir("manifest");
+        let contents = BundleContents {
+            provenance: Provenance::stamped("Mie", &FixedClock(1_700_000_000)),
+            files: vec![
+                BundleFile::new("spectrum.csv", "Wavelength (nm),Q_sca,Q_abs,Q_ext\n500,1,2,3\n"),
+                BundleFile::new("spectrum.json", "{}"),
+                BundleFile::new("plot.png", vec![0x89, b'P', b'N', b'G']),
+            ],
+        };
+        write(&dir, &contents).unwrap();
+
+        let manifest: Manifest =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(MANIFEST_NAME)).unwrap()).unwrap();
+        let names: Vec<&str> = manifest.files.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["spectrum.csv", "spectrum.json", "plot.png"]);
+        for entry in &manifest.files {
+            let bytes = std::fs::read(dir.join(&entry.name)).unwrap();
+            assert_eq!(entry.sha256, sha256_hex(&bytes));
+            assert_eq!(entry.size, bytes.len() as u64);
+        }
+        assert_eq!(manifest.provenance.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.provenance.timestamp, 1_700_000_000);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_paths_and_manifest_name() {
+        let dir = temp_dir("names");
+        for name in ["../escape.csv", "sub/file.csv", MANIFEST_NAME] {
+            let contents = BundleContents {
+                provenance: Provenance::now("Mie"),
+                files: vec![BundleFile::new(name, "x")],
+            };
+            assert!(matches!(write(&dir, &contents), Err(BundleError::InvalidName(_))));
+        }
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_rejects_duplicate_names() {
+        let dir = temp_dir("duplicates");
+        let contents = BundleContents {
+            provenance: Provenance::now("Mie"),
+            files: vec![
+                BundleFile::new("spectrum.csv", "first"),
+                BundleFile::new("plot.png", "png"),
+                BundleFile::new("spectrum.csv", "second"),
+            ],
+        };
+        match write(&dir, &contents) {
+            Err(BundleError::DuplicateName(name)) => assert_eq!(name, "spectrum.csv"),
+            other => panic!("expected a duplicate-name error, got {:?}", other),
+        }
+        assert!(!dir.exists());
+    }
+}
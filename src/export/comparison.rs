@@ -0,0 +1,65 @@
+//! Baseline vs current comparison export
+
+use crate::compute::comparison::{percent_change, AlignedPoint};
+
+fn format_percent(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Render aligned spectra as CSV with baseline, current, delta and percent change columns
+///
+/// Percent change is left empty where the baseline value is zero.
+pub fn comparison_csv(points: &[AlignedPoint]) -> String {
+    let mut csv = String::from("Wavelength (nm)");
+    for q in ["Q_ext", "Q_sca", "Q_abs"] {
+        csv.push_str(&format!(
+            ",baseline_{q},current_{q},delta_{q},percent_change_{q}"
+        ));
+    }
+    csv.push('\n');
+
+    for p in points {
+        csv.push_str(&p.wavelength.to_string());
+        for (b, c) in [
+            (p.baseline.q_ext, p.current.q_ext),
+            (p.baseline.q_sca, p.current.q_sca),
+            (p.baseline.q_abs, p.current.q_abs),
+        ] {
+            csv.push_str(&format!(",{},{},{},{}", b, c, c - b, format_percent(percent_change(b, c))));
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::comparison::{align_spectra, Efficiencies};
+    use crate::core::OpticalResult;
+
+    fn point(wavelength: f64, q_ext: f64) -> OpticalResult {
+        OpticalResult::for_test(wavelength, q_ext / 2.0, q_ext / 2.0)
+    }
+
+    #[test]
+    fn test_aligned_csv_on_different_grids() {
+        let baseline = vec![point(400.0, 1.0), point(410.0, 2.0), point(420.0, 3.0)];
+        let current = vec![point(395.0, 9.0), point(405.0, 3.0), point(415.0, 5.0)];
+
+        let csv = comparison_csv(&align_spectra(&baseline, &current));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].starts_with("Wavelength (nm),baseline_Q_ext,current_Q_ext,delta_Q_ext,percent_change_Q_ext"));
+        assert_eq!(lines.len(), 3); // header + 405, 415 (395 is outside the baseline)
+        assert!(lines[1].starts_with("405,1.5,3,1.5,100,"));
+        assert!(lines[2].starts_with("415,2.5,5,2.5,100,"));
+    }
+
+    #[test]
+    fn test_zero_baseline_leaves_percent_empty() {
+        let zero = Efficiencies { q_sca: 0.0, q_abs: 0.0, q_ext: 0.0 };
+        let one = Efficiencies { q_sca: 1.0, q_abs: 1.0, q_ext: 1.0 };
+        let csv = comparison_csv(&[AlignedPoint { wavelength: 500.0, baseline: zero, current: one }]);
+        assert!(csv.lines().nth(1).unwrap().starts_with("500,0,1,1,,"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Shared CSV writing
+
+use std::fmt::Display;
+
+/// Append one comma-separated row terminated by a newline
+pub fn push_row<I>(csv: &mut String, fields: I)
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    push_delimited(csv, fields, ',');
+}
+
+/// Append one row with fields separated by `delimiter`, terminated by a newline
+pub fn push_delimited<I>(text: &mut String, fields: I, delimiter: char)
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    let mut first = true;
+    for field in fields {
+        if !first {
+            text.push(delimiter);
+        }
+        text.push_str(&field.to_string());
+        first = false;
+    }
+    text.push('\n');
+}
@@ -0,0 +1,203 @@
+//! Spectrum exporters behind one trait
+//!
+//! Each format renders a spectrum into any [`Write`], so the same code
+//! writes files on the desktop, fills a download buffer on the web and
+//! writes into a `Vec<u8>` in tests.
+
+use super::bundle::Provenance;
+use super::result_set::{optical_csv, ExportError, OpticalColumn};
+use super::spectrum_json::{SpectrumFile, SpectrumMetadata};
+use crate::app::AppState;
+use crate::core::OpticalResult;
+use std::io::{self, Write};
+
+/// Reads one quantity from a result
+pub type FieldGetter = fn(&OpticalResult) -> f64;
+
+/// Per-point quantities written as named arrays by the binary formats
+pub const ARRAY_FIELDS: [(&str, FieldGetter); 8] = [
+    ("wavelength", |r| r.wavelength),
+    ("q_sca", |r| r.q_sca),
+    ("q_abs", |r| r.q_abs),
+    ("q_ext", |r| r.q_ext),
+    ("c_sca", |r| r.c_sca),
+    ("c_abs", |r| r.c_abs),
+    ("c_ext", |r| r.c_ext),
+    ("size_parameter", |r| r.metadata.size_parameter),
+];
+
+/// A spectrum file format
+pub trait Exporter {
+    /// File extension, without the dot
+    fn extension(&self) -> &'static str;
+
+    /// Write `results` to `writer` in this format
+    ///
+    /// Data the format cannot represent, e.g. an empty CSV, fails with an
+    /// [`io::ErrorKind::InvalidInput`] error wrapping an [`ExportError`];
+    /// see [`export_error`].
+    fn export(&self, results: &[OpticalResult], writer: &mut dyn Write) -> io::Result<()>;
+
+    /// [`Self::export`] into a new buffer
+    fn export_to_vec(&self, results: &[OpticalResult]) -> io::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        self.export(results, &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl From<ExportError> for io::Error {
+    fn from(e: ExportError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, e)
+    }
+}
+
+/// The [`ExportError`] behind an exporter's I/O error, if that was the cause
+pub fn export_error(e: &io::Error) -> Option<&ExportError> {
+    e.get_ref().and_then(|inner| inner.downcast_ref::<ExportError>())
+}
+
+/// CSV with the wavelength followed by the chosen columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvExporter {
+    pub columns: Vec<OpticalColumn>,
+}
+
+impl CsvExporter {
+    pub fn new(columns: impl IntoIterator<Item = OpticalColumn>) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+        }
+    }
+}
+
+impl Default for CsvExporter {
+    fn default() -> Self {
+        Self::new(OpticalColumn::DEFAULT)
+    }
+}
+
+impl Exporter for CsvExporter {
+    fn extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn export(&self, results: &[OpticalResult], writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(optical_csv(results, &self.columns)?.as_bytes())
+    }
+}
+
+/// JSON spectrum file, see [`SpectrumFile`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonExporter {
+    /// Inputs recorded next to the spectrum
+    pub metadata: SpectrumMetadata,
+    pub provenance: Option<Provenance>,
+}
+
+impl JsonExporter {
+    /// Exporter recording the inputs in `state` and where the data came from
+    pub fn new(state: &AppState, provenance: Provenance) -> Self {
+        Self {
+            metadata: SpectrumMetadata::from(state),
+            provenance: Some(provenance),
+        }
+    }
+}
+
+impl Exporter for JsonExporter {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn export(&self, results: &[OpticalResult], writer: &mut dyn Write) -> io::Result<()> {
+        let file = SpectrumFile {
+            provenance: self.provenance.clone(),
+            ..SpectrumFile::with_metadata(self.metadata.clone(), results)
+        };
+        writer.write_all(file.to_json()?.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::OpticalMetadata;
+    use crate::export::ResultKind;
+    use crate::utils::clock::FixedClock;
+
+    #[test]
+    fn test_csv_written_to_memory() {
+        let results = [OpticalResult::for_test(400.0, 0.5, 1.25), OpticalResult::for_test(500.0, 1.0, 2.0)];
+        let mut buffer = Vec::new();
+        CsvExporter::default().export(&results, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Wavelength (nm),Q_sca,Q_abs,Q_ext,C_sca (nm²),C_abs (nm²),C_ext (nm²),x\n\
+             400,0.5,1.25,1.75,0,0,0,0\n\
+             500,1,2,3,0,0,0,0\n"
+        );
+
+        let error = CsvExporter::default().export_to_vec(&[]).unwrap_err();
+        assert_eq!(export_error(&error), Some(&ExportError::Empty(ResultKind::Optical)));
+        let error = CsvExporter::new([]).export_to_vec(&results).unwrap_err();
+        assert_eq!(export_error(&error), Some(&ExportError::NoColumns));
+    }
+
+    #[test]
+    fn test_json_written_to_memory() {
+        let state = AppState {
+            particle_radius: 20.0,
+            ..Default::default()
+        };
+        let provenance = Provenance::stamped("Mie Scattering", &FixedClock(1_700_000_000));
+        let exporter = JsonExporter::new(&state, provenance.clone());
+        assert_eq!(exporter.extension(), "json");
+        let results = [OpticalResult::for_test(450.0, 0.25, 0.5)];
+        let text = String::from_utf8(exporter.export_to_vec(&results).unwrap()).unwrap();
+
+        let file = SpectrumFile::from_json(&text).unwrap();
+        assert_eq!(file.metadata.particle_radius, Some(20.0));
+        assert_eq!(file.provenance, Some(provenance));
+        assert_eq!(file.spectrum_data[0].q_ext, 0.75);
+    }
+
+    #[test]
+    fn test_json_carries_every_result_field() {
+        let result = OpticalResult {
+            wavelength: 520.0,
+            q_sca: 0.4,
+            q_abs: 2.1,
+            q_ext: 2.5,
+            c_sca: 1256.6,
+            c_abs: 6597.3,
+            c_ext: 7853.9,
+            g: 0.12,
+            q_pr: 2.45,
+            metadata: OpticalMetadata {
+                num_terms: Some(4),
+                converged: true,
+                size_parameter: 0.6,
+                notes: vec!["Au in water".to_string()],
+            },
+        };
+        let text = JsonExporter::new(&AppState::default(), Provenance::now("Mie Scattering"))
+            .export_to_vec(std::slice::from_ref(&result))
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&text).unwrap();
+
+        let point = &json["spectrum_data"][0];
+        let fields = serde_json::to_value(&result).unwrap();
+        for (key, value) in fields.as_object().unwrap() {
+            let name = if key == "wavelength" { "wavelength_nm" } else { key.as_str() };
+            assert_eq!(&point[name], value, "{}", key);
+        }
+        for key in ["crate_version", "timestamp", "model"] {
+            assert!(json["provenance"].get(key).is_some(), "{}", key);
+        }
+
+        let file = SpectrumFile::from_json(std::str::from_utf8(&text).unwrap()).unwrap();
+        assert_eq!(file.results()[0].metadata, result.metadata);
+        assert_eq!(file.results()[0].c_ext, result.c_ext);
+    }
+}
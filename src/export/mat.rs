@@ -0,0 +1,300 @@
+//! MATLAB .mat export
+//!
+//! Writes an uncompressed Level-5 MAT-file, which every MATLAB release and
+//! `scipy.io.loadmat` read. Each of the [`ARRAY_FIELDS`] becomes a double
+//! column vector named after it, and a `metadata` struct holds the inputs and provenance.
+
+use super::bundle::Provenance;
+use super::exporter::{Exporter, ARRAY_FIELDS};
+use super::spectrum_json::SpectrumMetadata;
+use crate::app::AppState;
+use crate::core::OpticalResult;
+use std::io::{self, Write};
+
+/// Name of the struct holding the inputs and provenance
+pub const MAT_METADATA: &str = "metadata";
+
+// Data types and array classes from the MAT-file Level 5 format reference
+const MI_INT8: u32 = 1;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_DOUBLE: u32 = 9;
+const MI_MATRIX: u32 = 14;
+const MX_STRUCT_CLASS: u8 = 2;
+const MX_CHAR_CLASS: u8 = 4;
+const MX_DOUBLE_CLASS: u8 = 6;
+
+/// Bytes reserved per struct field name, including the terminating NUL
+const FIELD_NAME_LENGTH: usize = 32;
+
+/// A MATLAB array as written by [`MatExporter`]
+enum MatValue {
+    /// Column vector of doubles
+    Column(Vec<f64>),
+    /// Character row vector
+    Text(String),
+    /// 1×1 struct
+    Struct(Vec<(&'static str, MatValue)>),
+}
+
+/// Data element: tag, data, then padding to the next 8-byte boundary
+fn push_element(out: &mut Vec<u8>, data_type: u32, data: &[u8]) {
+    out.extend_from_slice(&data_type.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(8), 0);
+}
+
+/// miMATRIX element for `value` named `name`; struct fields have empty names
+fn matrix(name: &str, value: &MatValue) -> Vec<u8> {
+    let (class, rows, cols) = match value {
+        MatValue::Column(values) => (MX_DOUBLE_CLASS, values.len(), 1),
+        MatValue::Text(text) => (MX_CHAR_CLASS, 1, text.encode_utf16().count()),
+        MatValue::Struct(_) => (MX_STRUCT_CLASS, 1, 1),
+    };
+
+    let mut body = Vec::new();
+    push_element(&mut body, MI_UINT32, &[class, 0, 0, 0, 0, 0, 0, 0]);
+    let dims: Vec<u8> = [rows as i32, cols as i32].iter().flat_map(|d| d.to_le_bytes()).collect();
+    push_element(&mut body, MI_INT32, &dims);
+    push_element(&mut body, MI_INT8, name.as_bytes());
+
+    match value {
+        MatValue::Column(values) => {
+            let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            push_element(&mut body, MI_DOUBLE, &data);
+        }
+        MatValue::Text(text) => {
+            let data: Vec<u8> = text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+            push_element(&mut body, MI_UINT16, &data);
+        }
+        MatValue::Struct(fields) => {
+            // Field name length goes in the small element format: size in the
+            // upper half of the type word, data in the tag's second word
+            body.extend_from_slice(&((4 << 16) | MI_INT32).to_le_bytes());
+            body.extend_from_slice(&(FIELD_NAME_LENGTH as i32).to_le_bytes());
+            let mut names = Vec::with_capacity(fields.len() * FIELD_NAME_LENGTH);
+            for (field, _) in fields {
+                let start = names.len();
+                names.extend_from_slice(field.as_bytes());
+                names.resize(start + FIELD_NAME_LENGTH, 0);
+            }
+            push_element(&mut body, MI_INT8, &names);
+            for (_, field) in fields {
+                body.extend(matrix("", field));
+            }
+        }
+    }
+
+    let mut element = Vec::with_capacity(body.len() + 8);
+    push_element(&mut element, MI_MATRIX, &body);
+    element
+}
+
+/// 128-byte file header: description, no subsystem data, version 0x0100
+/// and the endian indicator
+fn header() -> Vec<u8> {
+    let mut header = format!("MATLAB 5.0 MAT-file, written by NanoCalc {}", env!("CARGO_PKG_VERSION")).into_bytes();
+    header.resize(116, b' ');
+    header.extend_from_slice(&[0; 8]);
+    header.extend_from_slice(&0x0100u16.to_le_bytes());
+    header.extend_from_slice(b"IM");
+    header
+}
+
+/// Level-5 MAT-file with one column vector per quantity and a metadata struct
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatExporter {
+    /// Inputs recorded in the metadata struct
+    pub metadata: SpectrumMetadata,
+    pub provenance: Option<Provenance>,
+}
+
+impl MatExporter {
+    /// Exporter recording the inputs in `state` and where the data came from
+    pub fn new(state: &AppState, provenance: Provenance) -> Self {
+        Self {
+            metadata: SpectrumMetadata::from(state),
+            provenance: Some(provenance),
+        }
+    }
+
+    /// Fields of the metadata struct; inputs not recorded are left out
+    fn metadata_fields(&self) -> Vec<(&'static str, MatValue)> {
+        let inputs = [
+            ("particle_radius_nm", self.metadata.particle_radius),
+            ("n_particle_real", self.metadata.n_particle_real),
+            ("n_particle_imag", self.metadata.n_particle_imag),
+            ("n_medium", self.metadata.n_medium),
+            ("wavelength_nm", self.metadata.wavelength),
+        ];
+        let mut fields: Vec<_> = inputs
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|v| (name, MatValue::Column(vec![v]))))
+            .collect();
+        if let Some(provenance) = &self.provenance {
+            fields.push(("crate_version", MatValue::Text(provenance.crate_version.clone())));
+            fields.push(("timestamp", MatValue::Column(vec![provenance.timestamp as f64])));
+            fields.push(("model", MatValue::Text(provenance.model.clone())));
+        }
+        fields
+    }
+}
+
+impl Exporter for MatExporter {
+    fn extension(&self) -> &'static str {
+        "mat"
+    }
+
+    fn export(&self, results: &[OpticalResult], writer: &mut dyn Write) -> io::Result<()> {
+        let mut file = header();
+        for (name, column) in ARRAY_FIELDS {
+            file.extend(matrix(name, &MatValue::Column(results.iter().map(column).collect())));
+        }
+        file.extend(matrix(MAT_METADATA, &MatValue::Struct(self.metadata_fields())));
+        writer.write_all(&file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::FixedClock;
+
+    /// A top-level or struct-field array read back from a MAT-file
+    #[derive(Debug)]
+    struct Array {
+        name: String,
+        class: u8,
+        dims: Vec<i32>,
+        doubles: Vec<f64>,
+        fields: Vec<(String, Array)>,
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    /// Next data element in `bytes` from `*at` as (type, data), advancing past it
+    fn element<'a>(bytes: &'a [u8], at: &mut usize) -> (u32, &'a [u8]) {
+        let word = u32_at(bytes, *at);
+        if word >> 16 != 0 {
+            let data = &bytes[*at + 4..*at + 4 + (word >> 16) as usize];
+            *at += 8;
+            return (word & 0xffff, data);
+        }
+        let size = u32_at(bytes, *at + 4) as usize;
+        let data = &bytes[*at + 8..*at + 8 + size];
+        *at += (8 + size).next_multiple_of(8);
+        (word, data)
+    }
+
+    fn array(body: &[u8]) -> Array {
+        let mut at = 0;
+        let (_, flags) = element(body, &mut at);
+        let (_, dims) = element(body, &mut at);
+        let (_, name) = element(body, &mut at);
+        let mut array = Array {
+            name: String::from_utf8(name.to_vec()).unwrap(),
+            class: flags[0],
+            dims: dims.chunks(4).map(|d| i32::from_le_bytes(d.try_into().unwrap())).collect(),
+            doubles: Vec::new(),
+            fields: Vec::new(),
+        };
+        match array.class {
+            MX_DOUBLE_CLASS => {
+                let (data_type, data) = element(body, &mut at);
+                assert_eq!(data_type, MI_DOUBLE);
+                array.doubles = data.chunks(8).map(|d| f64::from_le_bytes(d.try_into().unwrap())).collect();
+            }
+            MX_STRUCT_CLASS => {
+                let (_, length) = element(body, &mut at);
+                let length = u32_at(length, 0) as usize;
+                let (_, names) = element(body, &mut at);
+                for name in names.chunks(length) {
+                    let name = String::from_utf8(name.iter().take_while(|&&b| b != 0).copied().collect()).unwrap();
+                    let (data_type, field) = element(body, &mut at);
+                    assert_eq!(data_type, MI_MATRIX);
+                    array.fields.push((name, self::array(field)));
+                }
+            }
+            _ => {}
+        }
+        array
+    }
+
+    fn read_mat(bytes: &[u8]) -> Vec<Array> {
+        assert!(bytes.starts_with(b"MATLAB 5.0 MAT-file"));
+        assert_eq!(&bytes[124..128], &[0x00, 0x01, b'I', b'M']);
+        let mut at = 128;
+        let mut arrays = Vec::new();
+        while at < bytes.len() {
+            assert_eq!(at % 8, 0);
+            let (data_type, body) = element(bytes, &mut at);
+            assert_eq!(data_type, MI_MATRIX);
+            arrays.push(array(body));
+        }
+        arrays
+    }
+
+    fn result(wavelength: f64) -> OpticalResult {
+        let mut result = OpticalResult {
+            c_sca: 10.0,
+            c_abs: 20.0,
+            c_ext: 30.0,
+            ..OpticalResult::for_test(wavelength, wavelength / 1000.0, 1.0)
+        };
+        result.metadata.size_parameter = 0.5;
+        result
+    }
+
+    #[test]
+    fn test_variables_read_back() {
+        let results: Vec<_> = [400.0, 450.0, 500.0].into_iter().map(result).collect();
+        let provenance = Provenance::stamped("Mie Scattering", &FixedClock(1_700_000_000));
+        let exporter = MatExporter::new(&AppState::default(), provenance);
+        assert_eq!(exporter.extension(), "mat");
+        let arrays = read_mat(&exporter.export_to_vec(&results).unwrap());
+
+        let names: Vec<_> = arrays.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names[..8], ARRAY_FIELDS.map(|(name, _)| name));
+        assert_eq!(names[8], MAT_METADATA);
+        for array in &arrays[..8] {
+            assert_eq!(array.class, MX_DOUBLE_CLASS);
+            assert_eq!(array.dims, [3, 1], "{}", array.name);
+            assert_eq!(array.doubles.len(), 3);
+        }
+        assert_eq!(arrays[0].doubles, [400.0, 450.0, 500.0]);
+        assert_eq!(arrays[3].doubles, results.iter().map(|r| r.q_ext).collect::<Vec<_>>());
+
+        let metadata = &arrays[8];
+        assert_eq!(metadata.class, MX_STRUCT_CLASS);
+        let fields: Vec<_> = metadata.fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "particle_radius_nm",
+                "n_particle_real",
+                "n_particle_imag",
+                "n_medium",
+                "wavelength_nm",
+                "crate_version",
+                "timestamp",
+                "model"
+            ]
+        );
+        assert_eq!(metadata.fields[0].1.doubles, [AppState::default().particle_radius]);
+        assert_eq!(metadata.fields[6].1.doubles, [1_700_000_000.0]);
+        let model = &metadata.fields[7].1;
+        assert_eq!((model.class, model.dims.as_slice()), (MX_CHAR_CLASS, &[1, 14][..]));
+    }
+
+    #[test]
+    fn test_empty_spectrum_and_metadata() {
+        let arrays = read_mat(&MatExporter::default().export_to_vec(&[]).unwrap());
+        assert_eq!(arrays.len(), 9);
+        assert!(arrays[..8].iter().all(|a| a.dims == [0, 1] && a.doubles.is_empty()));
+        assert!(arrays[8].fields.is_empty());
+    }
+}
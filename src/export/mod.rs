@@ -1,3 +1,28 @@
 //! Export functionality
 
-// Placeholder for MVP
+pub mod batch;
+pub mod bundle;
+pub mod comparison;
+pub mod csv;
+pub mod exporter;
+#[cfg(feature = "matlab")]
+pub mod mat;
+pub mod multipole;
+pub mod nk;
+pub mod normalized;
+pub mod npy;
+pub mod result_set;
+pub mod spectrum_json;
+
+pub use batch::{batch_template, read_batch_csv, BatchColumn};
+pub use bundle::{BundleContents, BundleFile, Manifest, Provenance};
+pub use comparison::comparison_csv;
+pub use exporter::{export_error, CsvExporter, Exporter, FieldGetter, JsonExporter, ARRAY_FIELDS};
+#[cfg(feature = "matlab")]
+pub use mat::MatExporter;
+pub use multipole::{multipole_csv, multipole_json};
+pub use nk::{nk_text, WavelengthUnit};
+pub use normalized::normalized_csv;
+pub use npy::NpyExporter;
+pub use result_set::{optical_csv, optical_tsv, read_optical_csv, to_csv, ExportError, OpticalColumn, ResultKind, ResultSet};
+pub use spectrum_json::{ImportError, SpectrumFile, SpectrumMetadata, SpectrumPoint};
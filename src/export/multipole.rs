@@ -0,0 +1,83 @@
+//! Multipole decomposition export
+
+use super::csv::push_row;
+use crate::compute::multipole::MultipoleDecomposition;
+
+pub const MULTIPOLE_CSV_HEADER: [&str; 8] = [
+    "Wavelength (nm)",
+    "Order",
+    "|a_n|",
+    "|b_n|",
+    "Q_sca_electric",
+    "Q_sca_magnetic",
+    "Q_ext_electric",
+    "Q_ext_magnetic",
+];
+
+/// Render decompositions as CSV: one block of rows per wavelength, one row per order
+pub fn multipole_csv(decompositions: &[MultipoleDecomposition]) -> String {
+    let mut csv = String::new();
+    push_row(&mut csv, MULTIPOLE_CSV_HEADER);
+    for d in decompositions {
+        for o in &d.orders {
+            push_row(
+                &mut csv,
+                [
+                    d.wavelength.to_string(),
+                    o.order.to_string(),
+                    o.a_abs.to_string(),
+                    o.b_abs.to_string(),
+                    o.q_sca_electric.to_string(),
+                    o.q_sca_magnetic.to_string(),
+                    o.q_ext_electric.to_string(),
+                    o.q_ext_magnetic.to_string(),
+                ],
+            );
+        }
+    }
+    csv
+}
+
+/// Render decompositions as pretty-printed JSON
+pub fn multipole_json(decompositions: &[MultipoleDecomposition]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(decompositions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::multipole::multipole_spectrum;
+    use crate::core::RefractiveIndex;
+    use crate::physics::optical::mie::MieModel;
+
+    #[test]
+    fn test_one_block_per_wavelength_up_to_used_order() {
+        let model = MieModel::new(100.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.0);
+        let decompositions = multipole_spectrum(&model, &[400.0, 600.0, 800.0]).unwrap();
+        let csv = multipole_csv(&decompositions);
+        let mut rows = csv.lines();
+        assert_eq!(rows.next().unwrap(), MULTIPOLE_CSV_HEADER.join(","));
+
+        let rows: Vec<Vec<&str>> = rows.map(|r| r.split(',').collect()).collect();
+        let mut offset = 0;
+        for d in &decompositions {
+            let block = &rows[offset..offset + d.max_order()];
+            for (i, row) in block.iter().enumerate() {
+                assert_eq!(row[0], d.wavelength.to_string());
+                assert_eq!(row[1], (i + 1).to_string());
+            }
+            offset += d.max_order();
+        }
+        assert_eq!(offset, rows.len());
+        // Larger size parameter (shorter λ) needs more orders
+        assert!(decompositions[0].max_order() > decompositions[2].max_order());
+    }
+
+    #[test]
+    fn test_json_lists_orders() {
+        let model = MieModel::new(50.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.0);
+        let decompositions = multipole_spectrum(&model, &[500.0]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&multipole_json(&decompositions).unwrap()).unwrap();
+        assert_eq!(value[0]["orders"][0]["order"], 1);
+    }
+}
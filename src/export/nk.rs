@@ -0,0 +1,84 @@
+//! Material n, k text export for FDTD solvers
+//!
+//! FDTD packages (Lumerical, MEEP fitting scripts) import sampled optical
+//! constants as plain whitespace-separated `wavelength n k` columns with no
+//! header, ordered by increasing wavelength.
+
+use crate::physics::materials::Dispersion;
+use serde::{Deserialize, Serialize};
+
+/// Unit of the wavelength column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WavelengthUnit {
+    Nanometer,
+    /// Lumerical's default for nk imports
+    #[default]
+    Micrometer,
+}
+
+impl WavelengthUnit {
+    pub const ALL: [WavelengthUnit; 2] = [WavelengthUnit::Nanometer, WavelengthUnit::Micrometer];
+
+    /// Convert a wavelength in nm to this unit
+    pub fn from_nm(self, wavelength_nm: f64) -> f64 {
+        match self {
+            WavelengthUnit::Nanometer => wavelength_nm,
+            WavelengthUnit::Micrometer => wavelength_nm * 1e-3,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            WavelengthUnit::Nanometer => "nm",
+            WavelengthUnit::Micrometer => "µm",
+        }
+    }
+}
+
+/// Sample `material` at `wavelengths` (nm) as `wavelength n k` lines
+///
+/// Rows are sorted by wavelength regardless of the order of `wavelengths`.
+pub fn nk_text(material: &dyn Dispersion, wavelengths: &[f64], unit: WavelengthUnit) -> String {
+    let mut sorted = wavelengths.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let mut text = String::new();
+    for wavelength in sorted {
+        let n = material.refractive_index_at(wavelength);
+        text.push_str(&format!("{}\t{}\t{}\n", unit.from_nm(wavelength), n.real, n.imaginary));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::materials::FormulaDispersion;
+
+    fn rows(text: &str) -> Vec<[f64; 3]> {
+        text.lines()
+            .map(|line| {
+                let cols: Vec<f64> = line.split_whitespace().map(|c| c.parse().unwrap()).collect();
+                [cols[0], cols[1], cols[2]]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_columns_are_ordered_and_converted() {
+        let material = FormulaDispersion::new("Cauchy", "1.5 + 10000/lambda^2", "0.001*lambda/500").unwrap();
+        let wavelengths = [800.0, 400.0, 600.0];
+
+        let um = rows(&nk_text(&material, &wavelengths, WavelengthUnit::Micrometer));
+        assert_eq!(um.len(), 3);
+        for (row, wavelength) in um.iter().zip([400.0, 600.0, 800.0]) {
+            assert!((row[0] - wavelength / 1000.0).abs() < 1e-12);
+            assert!((row[1] - (1.5 + 1e4 / (wavelength * wavelength))).abs() < 1e-12);
+            assert!((row[2] - 0.001 * wavelength / 500.0).abs() < 1e-12);
+        }
+
+        let nm = rows(&nk_text(&material, &wavelengths, WavelengthUnit::Nanometer));
+        assert_eq!(nm.iter().map(|r| r[0]).collect::<Vec<_>>(), [400.0, 600.0, 800.0]);
+        assert_eq!(nm.iter().map(|r| r[1]).collect::<Vec<_>>(), um.iter().map(|r| r[1]).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,34 @@
+//! Size-normalized absorption export
+//!
+//! Absorption per unit volume and per unit mass are the usual figures of
+//! merit for photothermal materials, comparable across particle sizes.
+
+use super::csv::push_row;
+use crate::core::{OpticalResult, ValidationResult};
+
+/// CSV header written by [`normalized_csv`]
+pub const NORMALIZED_CSV_HEADER: &str = "Wavelength (nm),C_abs (nm²),C_abs/V (1/nm),C_abs/m (m²/g)";
+
+/// Render C_abs, C_abs/V and (with a density in g/cm³) C_abs per mass as CSV
+///
+/// The mass column is left empty when no density is given.
+pub fn normalized_csv(results: &[OpticalResult], radius: f64, density: Option<f64>) -> ValidationResult<String> {
+    let mut csv = String::from(NORMALIZED_CSV_HEADER);
+    csv.push('\n');
+    for r in results {
+        let per_mass = match density {
+            Some(rho) => r.c_abs_per_mass(radius, rho)?.to_string(),
+            None => String::new(),
+        };
+        push_row(
+            &mut csv,
+            [
+                r.wavelength.to_string(),
+                r.c_abs.to_string(),
+                r.c_abs_per_volume(radius)?.to_string(),
+                per_mass,
+            ],
+        );
+    }
+    Ok(csv)
+}
@@ -0,0 +1,125 @@
+//! NumPy .npy export
+//!
+//! Writes the spectrum as a one-dimensional structured array with one
+//! little-endian float64 field per [`ARRAY_FIELDS`] entry, so
+//! `np.load(path)["q_ext"]` returns the column at full precision.
+//! Follows version 1.0 of the NPY format.
+
+use super::exporter::{Exporter, ARRAY_FIELDS};
+use crate::core::OpticalResult;
+use std::io::{self, Write};
+
+/// Magic string opening every .npy file
+pub const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Magic, version and header length come before the header dictionary
+const PREAMBLE_LEN: usize = NPY_MAGIC.len() + 2 + 2;
+
+/// Data starts on a multiple of this many bytes
+const NPY_ALIGNMENT: usize = 64;
+
+/// Header dictionary for `len` records, space-padded and ending in a newline
+/// so the data starts on an [`NPY_ALIGNMENT`] boundary
+fn header(len: usize) -> String {
+    let descr = ARRAY_FIELDS
+        .iter()
+        .map(|(name, _)| format!("('{}', '<f8')", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut header = format!("{{'descr': [{}], 'fortran_order': False, 'shape': ({},), }}", descr, len);
+    let padded = (PREAMBLE_LEN + header.len() + 1).next_multiple_of(NPY_ALIGNMENT);
+    let spaces = padded - PREAMBLE_LEN - header.len() - 1;
+    header.extend(std::iter::repeat_n(' ', spaces));
+    header.push('\n');
+    header
+}
+
+/// Structured-array .npy file, one record per wavelength
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NpyExporter;
+
+impl Exporter for NpyExporter {
+    fn extension(&self) -> &'static str {
+        "npy"
+    }
+
+    fn export(&self, results: &[OpticalResult], writer: &mut dyn Write) -> io::Result<()> {
+        let header = header(results.len());
+        let header_len = u16::try_from(header.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "NPY header exceeds 64 KiB"))?;
+
+        let mut file = Vec::with_capacity(PREAMBLE_LEN + header.len() + results.len() * ARRAY_FIELDS.len() * 8);
+        file.extend_from_slice(NPY_MAGIC);
+        file.extend_from_slice(&[1, 0]);
+        file.extend_from_slice(&header_len.to_le_bytes());
+        file.extend_from_slice(header.as_bytes());
+        for r in results {
+            for (_, field) in ARRAY_FIELDS {
+                file.extend_from_slice(&field(r).to_le_bytes());
+            }
+        }
+        writer.write_all(&file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(wavelength: f64) -> OpticalResult {
+        let mut result = OpticalResult {
+            c_sca: 10.0,
+            c_abs: 20.0,
+            c_ext: 30.0,
+            ..OpticalResult::for_test(wavelength, 0.1 * wavelength, 0.2)
+        };
+        result.metadata.size_parameter = 0.5;
+        result
+    }
+
+    /// Header dictionary and data bytes of a version 1.0 .npy file
+    fn read_npy(bytes: &[u8]) -> (&str, &[u8]) {
+        assert_eq!(&bytes[..6], NPY_MAGIC);
+        assert_eq!(&bytes[6..8], &[1, 0]);
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        let data_start = PREAMBLE_LEN + header_len;
+        assert_eq!(data_start % NPY_ALIGNMENT, 0);
+        let header = std::str::from_utf8(&bytes[PREAMBLE_LEN..data_start]).unwrap();
+        assert!(header.ends_with('\n'));
+        (header.trim_end(), &bytes[data_start..])
+    }
+
+    #[test]
+    fn test_header_shape_and_dtype_read_back() {
+        let results: Vec<_> = [400.0, 500.0, 600.0].into_iter().map(result).collect();
+        let bytes = NpyExporter.export_to_vec(&results).unwrap();
+        let (header, data) = read_npy(&bytes);
+
+        assert_eq!(
+            header,
+            "{'descr': [('wavelength', '<f8'), ('q_sca', '<f8'), ('q_abs', '<f8'), ('q_ext', '<f8'), \
+             ('c_sca', '<f8'), ('c_abs', '<f8'), ('c_ext', '<f8'), ('size_parameter', '<f8')], \
+             'fortran_order': False, 'shape': (3,), }"
+        );
+        assert_eq!(data.len(), 3 * ARRAY_FIELDS.len() * 8);
+
+        let values: Vec<f64> = data.chunks(8).map(|v| f64::from_le_bytes(v.try_into().unwrap())).collect();
+        let records: Vec<&[f64]> = values.chunks(ARRAY_FIELDS.len()).collect();
+        assert_eq!(records[1][0], 500.0);
+        assert_eq!(records[1][3].to_bits(), results[1].q_ext.to_bits());
+        assert_eq!(records[2][7], 0.5);
+    }
+
+    #[test]
+    fn test_padding_for_any_length() {
+        for len in [0, 1, 9, 10, 1000, 123_456] {
+            let header = header(len);
+            assert_eq!((PREAMBLE_LEN + header.len()) % NPY_ALIGNMENT, 0, "{}", len);
+            assert!(header.contains(&format!("'shape': ({},)", len)));
+        }
+        let empty = NpyExporter.export_to_vec(&[]).unwrap();
+        let (header, data) = read_npy(&empty);
+        assert!(header.contains("'shape': (0,)"));
+        assert!(data.is_empty());
+    }
+}
@@ -0,0 +1,381 @@
+//! Typed result sets for export
+//!
+//! Results are exported together with their kind so that headers and columns
+//! always match the data, even when the active mode and stored results differ.
+
+use super::csv::{push_delimited, push_row};
+use crate::core::{ElectronicResult, OpticalResult, ThermalResult};
+use std::fmt;
+
+/// Kind of results held by a [`ResultSet`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultKind {
+    Optical,
+    Thermal,
+    Electronic,
+}
+
+impl fmt::Display for ResultKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResultKind::Optical => write!(f, "optical"),
+            ResultKind::Thermal => write!(f, "thermal"),
+            ResultKind::Electronic => write!(f, "electronic"),
+        }
+    }
+}
+
+/// Results of a single kind, ready for export
+#[derive(Debug, Clone)]
+pub enum ResultSet {
+    Optical(Vec<OpticalResult>),
+    Thermal(Vec<ThermalResult>),
+    Electronic(Vec<ElectronicResult>),
+}
+
+/// Export errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ExportError {
+    #[error("Nothing to export: the {0} result set is empty")]
+    Empty(ResultKind),
+
+    #[error("Cannot write {expected} columns for a {found} result set")]
+    Mismatch {
+        expected: ResultKind,
+        found: ResultKind,
+    },
+
+    #[error("No columns selected for export")]
+    NoColumns,
+
+    #[error("Invalid CSV: {0}")]
+    Parse(String),
+}
+
+pub type ExportResult<T> = Result<T, ExportError>;
+
+impl ResultSet {
+    pub fn kind(&self) -> ResultKind {
+        match self {
+            ResultSet::Optical(_) => ResultKind::Optical,
+            ResultSet::Thermal(_) => ResultKind::Thermal,
+            ResultSet::Electronic(_) => ResultKind::Electronic,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ResultSet::Optical(r) => r.len(),
+            ResultSet::Thermal(r) => r.len(),
+            ResultSet::Electronic(r) => r.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check the set is non-empty and of the `expected` kind
+    pub fn ensure(&self, expected: ResultKind) -> ExportResult<()> {
+        if self.kind() != expected {
+            return Err(ExportError::Mismatch {
+                expected,
+                found: self.kind(),
+            });
+        }
+        if self.is_empty() {
+            return Err(ExportError::Empty(expected));
+        }
+        Ok(())
+    }
+}
+
+/// CSV header row for a result kind
+pub fn csv_header(kind: ResultKind) -> &'static str {
+    match kind {
+        ResultKind::Optical => "Wavelength (nm),Q_sca,Q_abs,Q_ext,C_sca (nm²),C_abs (nm²),C_ext (nm²),x",
+        ResultKind::Thermal => "Temperature (K),kappa_eff (W/m·K),kappa_bulk (W/m·K),reduction_factor,mfp (nm)",
+        ResultKind::Electronic => {
+            "Diameter (nm),Bandgap (eV),Bulk bandgap (eV),Confinement (eV),Coulomb (eV)"
+        }
+    }
+}
+
+/// A selectable quantity in an optical CSV export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpticalColumn {
+    QSca,
+    QAbs,
+    QExt,
+    CSca,
+    CAbs,
+    CExt,
+    QPr,
+    G,
+    SizeParameter,
+}
+
+impl OpticalColumn {
+    pub const ALL: [OpticalColumn; 9] = [
+        OpticalColumn::QSca,
+        OpticalColumn::QAbs,
+        OpticalColumn::QExt,
+        OpticalColumn::CSca,
+        OpticalColumn::CAbs,
+        OpticalColumn::CExt,
+        OpticalColumn::QPr,
+        OpticalColumn::G,
+        OpticalColumn::SizeParameter,
+    ];
+
+    /// Columns of the standard optical export: efficiencies, cross sections
+    /// and the size parameter
+    pub const DEFAULT: [OpticalColumn; 7] = [
+        OpticalColumn::QSca,
+        OpticalColumn::QAbs,
+        OpticalColumn::QExt,
+        OpticalColumn::CSca,
+        OpticalColumn::CAbs,
+        OpticalColumn::CExt,
+        OpticalColumn::SizeParameter,
+    ];
+
+    /// Column whose header is `header`
+    pub fn from_header(header: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.header() == header)
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            OpticalColumn::QSca => "Q_sca",
+            OpticalColumn::QAbs => "Q_abs",
+            OpticalColumn::QExt => "Q_ext",
+            OpticalColumn::CSca => "C_sca (nm²)",
+            OpticalColumn::CAbs => "C_abs (nm²)",
+            OpticalColumn::CExt => "C_ext (nm²)",
+            OpticalColumn::QPr => "Q_pr",
+            OpticalColumn::G => "g",
+            OpticalColumn::SizeParameter => "x",
+        }
+    }
+
+    pub fn value(self, result: &OpticalResult) -> f64 {
+        match self {
+            OpticalColumn::QSca => result.q_sca,
+            OpticalColumn::QAbs => result.q_abs,
+            OpticalColumn::QExt => result.q_ext,
+            OpticalColumn::CSca => result.c_sca,
+            OpticalColumn::CAbs => result.c_abs,
+            OpticalColumn::CExt => result.c_ext,
+            OpticalColumn::QPr => result.q_pr,
+            OpticalColumn::G => result.g,
+            OpticalColumn::SizeParameter => result.metadata.size_parameter,
+        }
+    }
+}
+
+/// Render optical results as CSV with the wavelength followed by `columns`, in order
+pub fn optical_csv(results: &[OpticalResult], columns: &[OpticalColumn]) -> ExportResult<String> {
+    optical_table(results, columns, ',')
+}
+
+/// [`optical_csv`] with tab separators, for pasting into a spreadsheet
+pub fn optical_tsv(results: &[OpticalResult], columns: &[OpticalColumn]) -> ExportResult<String> {
+    optical_table(results, columns, '\t')
+}
+
+fn optical_table(results: &[OpticalResult], columns: &[OpticalColumn], delimiter: char) -> ExportResult<String> {
+    if columns.is_empty() {
+        return Err(ExportError::NoColumns);
+    }
+    if results.is_empty() {
+        return Err(ExportError::Empty(ResultKind::Optical));
+    }
+
+    let mut text = String::new();
+    push_delimited(
+        &mut text,
+        std::iter::once("Wavelength (nm)").chain(columns.iter().map(|c| c.header())),
+        delimiter,
+    );
+    for r in results {
+        push_delimited(
+            &mut text,
+            std::iter::once(r.wavelength).chain(columns.iter().map(|c| c.value(r))),
+            delimiter,
+        );
+    }
+    Ok(text)
+}
+
+/// Parse CSV written by [`optical_csv`] into its columns and numeric rows
+///
+/// Each row starts with the wavelength, followed by one value per column.
+pub fn read_optical_csv(text: &str) -> ExportResult<(Vec<OpticalColumn>, Vec<Vec<f64>>)> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or_else(|| ExportError::Parse("missing header row".to_string()))?;
+    let mut fields = header.split(',').map(str::trim);
+    if fields.next() != Some("Wavelength (nm)") {
+        return Err(ExportError::Parse(format!("first column must be the wavelength: '{}'", header)));
+    }
+    let columns = fields
+        .map(|h| OpticalColumn::from_header(h).ok_or_else(|| ExportError::Parse(format!("unknown column '{}'", h))))
+        .collect::<ExportResult<Vec<_>>>()?;
+
+    let rows = lines
+        .enumerate()
+        .map(|(i, line)| {
+            let row = line
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| ExportError::Parse(format!("invalid number on data row {}: '{}'", i + 1, line)))?;
+            if row.len() != columns.len() + 1 {
+                return Err(ExportError::Parse(format!(
+                    "data row {} has {} fields, expected {}",
+                    i + 1,
+                    row.len(),
+                    columns.len() + 1
+                )));
+            }
+            Ok(row)
+        })
+        .collect::<ExportResult<Vec<_>>>()?;
+    Ok((columns, rows))
+}
+
+/// Render a result set as CSV, refusing empty sets or a kind other than `expected`
+pub fn to_csv(set: &ResultSet, expected: ResultKind) -> ExportResult<String> {
+    set.ensure(expected)?;
+
+    let mut csv = String::from(csv_header(expected));
+    csv.push('\n');
+    match set {
+        ResultSet::Optical(results) => {
+            for r in results {
+                push_row(
+                    &mut csv,
+                    [
+                        r.wavelength,
+                        r.q_sca,
+                        r.q_abs,
+                        r.q_ext,
+                        r.c_sca,
+                        r.c_abs,
+                        r.c_ext,
+                        r.metadata.size_parameter,
+                    ],
+                );
+            }
+        }
+        ResultSet::Thermal(results) => {
+            for r in results {
+                let mfp = r.mfp.map(|v| v.to_string()).unwrap_or_default();
+                push_row(
+                    &mut csv,
+                    [
+                        r.temperature.to_string(),
+                        r.kappa_eff.to_string(),
+                        r.kappa_bulk.to_string(),
+                        r.reduction_factor.to_string(),
+                        mfp,
+                    ],
+                );
+            }
+        }
+        ResultSet::Electronic(results) => {
+            for r in results {
+                push_row(
+                    &mut csv,
+                    [r.diameter, r.bandgap, r.bulk_bandgap, r.confinement_energy, r.coulomb_correction],
+                );
+            }
+        }
+    }
+    Ok(csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ThermalMetadata;
+
+    fn thermal_set() -> ResultSet {
+        ResultSet::Thermal(vec![ThermalResult {
+            temperature: 300.0,
+            kappa_eff: 50.0,
+            kappa_bulk: 150.0,
+            reduction_factor: 1.0 / 3.0,
+            mfp: Some(40.0),
+            metadata: ThermalMetadata::default(),
+        }])
+    }
+
+    #[test]
+    fn test_thermal_set_writes_thermal_columns() {
+        let csv = to_csv(&thermal_set(), ResultKind::Thermal).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), csv_header(ResultKind::Thermal));
+        assert!(lines.next().unwrap().starts_with("300,50,150,"));
+    }
+
+    #[test]
+    fn test_optical_header_for_thermal_set_is_rejected() {
+        assert_eq!(
+            to_csv(&thermal_set(), ResultKind::Optical),
+            Err(ExportError::Mismatch {
+                expected: ResultKind::Optical,
+                found: ResultKind::Thermal,
+            })
+        );
+    }
+
+    #[test]
+    fn test_selected_columns_in_order() {
+        let mut result = OpticalResult {
+            c_sca: 10.0,
+            c_abs: 20.0,
+            c_ext: 30.0,
+            ..OpticalResult::for_test(500.0, 1.0, 2.0)
+        };
+        result.metadata.size_parameter = 0.5;
+        let columns = [OpticalColumn::CExt, OpticalColumn::QSca, OpticalColumn::SizeParameter];
+        let results = [result];
+        let csv = optical_csv(&results, &columns).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), "Wavelength (nm),C_ext (nm²),Q_sca,x");
+        assert_eq!(lines.next().unwrap(), "500,30,1,0.5");
+        assert_eq!(read_optical_csv(&csv).unwrap(), (columns.to_vec(), vec![vec![500.0, 30.0, 1.0, 0.5]]));
+        assert_eq!(optical_tsv(&results, &columns).unwrap(), "Wavelength (nm)\tC_ext (nm²)\tQ_sca\tx\n500\t30\t1\t0.5\n");
+
+        let default = optical_csv(&results, &OpticalColumn::DEFAULT).unwrap();
+        assert_eq!(default.lines().next().unwrap(), csv_header(ResultKind::Optical));
+        assert_eq!(optical_csv(&[], &columns), Err(ExportError::Empty(ResultKind::Optical)));
+    }
+
+    #[test]
+    fn test_malformed_csv_is_rejected() {
+        for text in [
+            "",
+            "Q_ext,Wavelength (nm)\n",
+            "Wavelength (nm),Q_what\n",
+            "Wavelength (nm),Q_ext\n500,abc\n",
+            "Wavelength (nm),Q_ext\n500,1,2\n",
+        ] {
+            assert!(matches!(read_optical_csv(text), Err(ExportError::Parse(_))), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_no_columns_is_rejected() {
+        assert_eq!(optical_csv(&[], &[]), Err(ExportError::NoColumns));
+    }
+
+    #[test]
+    fn test_empty_set_is_rejected() {
+        assert_eq!(
+            to_csv(&ResultSet::Optical(Vec::new()), ResultKind::Optical),
+            Err(ExportError::Empty(ResultKind::Optical))
+        );
+    }
+}
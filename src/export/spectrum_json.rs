@@ -0,0 +1,235 @@
+//! JSON spectrum files: export and re-import
+//!
+//! The file carries the input parameters as `metadata` and a `provenance`
+//! block next to the `spectrum_data` points, each holding every field of
+//! the [`OpticalResult`] it came from. Everything beyond the wavelength and
+//! Q values is optional on import so files written by other tools (or older
+//! versions) still load.
+
+use super::bundle::Provenance;
+use crate::app::AppState;
+use crate::core::{OpticalMetadata, OpticalResult};
+use serde::{Deserialize, Serialize};
+
+/// Input parameters recorded alongside a spectrum
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumMetadata {
+    #[serde(default, rename = "particle_radius_nm", skip_serializing_if = "Option::is_none")]
+    pub particle_radius: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_particle_real: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_particle_imag: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_medium: Option<f64>,
+    #[serde(default, rename = "wavelength_nm", skip_serializing_if = "Option::is_none")]
+    pub wavelength: Option<f64>,
+}
+
+/// One sample of a stored spectrum
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumPoint {
+    pub wavelength_nm: f64,
+    pub q_sca: f64,
+    pub q_abs: f64,
+    pub q_ext: f64,
+    #[serde(default)]
+    pub c_sca: f64,
+    #[serde(default)]
+    pub c_abs: f64,
+    #[serde(default)]
+    pub c_ext: f64,
+    #[serde(default)]
+    pub g: f64,
+    #[serde(default)]
+    pub q_pr: f64,
+    /// Convergence record of the calculation
+    #[serde(default)]
+    pub metadata: OpticalMetadata,
+}
+
+impl From<&OpticalResult> for SpectrumPoint {
+    fn from(r: &OpticalResult) -> Self {
+        Self {
+            wavelength_nm: r.wavelength,
+            q_sca: r.q_sca,
+            q_abs: r.q_abs,
+            q_ext: r.q_ext,
+            c_sca: r.c_sca,
+            c_abs: r.c_abs,
+            c_ext: r.c_ext,
+            g: r.g,
+            q_pr: r.q_pr,
+            metadata: r.metadata.clone(),
+        }
+    }
+}
+
+impl From<&SpectrumPoint> for OpticalResult {
+    fn from(p: &SpectrumPoint) -> Self {
+        Self {
+            wavelength: p.wavelength_nm,
+            q_sca: p.q_sca,
+            q_abs: p.q_abs,
+            q_ext: p.q_ext,
+            c_sca: p.c_sca,
+            c_abs: p.c_abs,
+            c_ext: p.c_ext,
+            g: p.g,
+            q_pr: p.q_pr,
+            metadata: p.metadata.clone(),
+        }
+    }
+}
+
+/// Contents of a JSON spectrum file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumFile {
+    #[serde(default)]
+    pub metadata: SpectrumMetadata,
+    /// Version, time and model that produced the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    pub spectrum_data: Vec<SpectrumPoint>,
+}
+
+/// Import errors
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Cannot read file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid spectrum JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+impl SpectrumFile {
+    /// Spectrum file for `results` computed with the inputs in `state`
+    pub fn new(state: &AppState, results: &[OpticalResult]) -> Self {
+        Self::with_metadata(SpectrumMetadata::from(state), results)
+    }
+
+    /// Spectrum file for `results` with the given input record
+    pub fn with_metadata(metadata: SpectrumMetadata, results: &[OpticalResult]) -> Self {
+        Self {
+            metadata,
+            provenance: None,
+            spectrum_data: results.iter().map(SpectrumPoint::from).collect(),
+        }
+    }
+
+    /// The stored points as results
+    pub fn results(&self) -> Vec<OpticalResult> {
+        self.spectrum_data.iter().map(OpticalResult::from).collect()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, ImportError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ImportError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+impl From<&AppState> for SpectrumMetadata {
+    fn from(state: &AppState) -> Self {
+        Self {
+            particle_radius: Some(state.particle_radius),
+            n_particle_real: Some(state.n_particle_real),
+            n_particle_imag: Some(state.n_particle_imag),
+            n_medium: Some(state.n_medium),
+            wavelength: Some(state.wavelength),
+        }
+    }
+}
+
+impl SpectrumMetadata {
+    /// Copy the recorded parameters into `state`
+    ///
+    /// Fields missing from the file leave the corresponding input unchanged;
+    /// their JSON names are returned so the caller can warn about them.
+    pub fn apply_to(&self, state: &mut AppState) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        let fields: [(Option<f64>, &mut f64, &'static str); 5] = [
+            (self.particle_radius, &mut state.particle_radius, "particle_radius_nm"),
+            (self.n_particle_real, &mut state.n_particle_real, "n_particle_real"),
+            (self.n_particle_imag, &mut state.n_particle_imag, "n_particle_imag"),
+            (self.n_medium, &mut state.n_medium, "n_medium"),
+            (self.wavelength, &mut state.wavelength, "wavelength_nm"),
+        ];
+        for (value, target, name) in fields {
+            match value {
+                Some(v) => *target = v,
+                None => missing.push(name),
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_state() -> AppState {
+        AppState {
+            particle_radius: 72.5,
+            wavelength: 633.0,
+            n_particle_real: 0.18,
+            n_particle_imag: 3.1,
+            n_medium: 1.5,
+            ..AppState::default()
+        }
+    }
+
+    #[test]
+    fn test_full_metadata_populates_all_fields() {
+        let file = SpectrumFile::new(&source_state(), &[]);
+        let loaded = SpectrumFile::from_json(&file.to_json().unwrap()).unwrap();
+
+        let mut state = AppState::default();
+        let missing = loaded.metadata.apply_to(&mut state);
+        assert!(missing.is_empty());
+
+        let expected = source_state();
+        assert_eq!(state.particle_radius, expected.particle_radius);
+        assert_eq!(state.wavelength, expected.wavelength);
+        assert_eq!(state.n_particle_real, expected.n_particle_real);
+        assert_eq!(state.n_particle_imag, expected.n_particle_imag);
+        assert_eq!(state.n_medium, expected.n_medium);
+    }
+
+    #[test]
+    fn test_missing_fields_leave_inputs_unchanged() {
+        let text = r#"{
+            "metadata": { "particle_radius_nm": 30.0 },
+            "spectrum_data": [
+                { "wavelength_nm": 500.0, "q_sca": 0.1, "q_abs": 0.2, "q_ext": 0.3 }
+            ]
+        }"#;
+        let loaded = SpectrumFile::from_json(text).unwrap();
+        assert_eq!(loaded.spectrum_data.len(), 1);
+
+        let mut state = AppState::default();
+        let missing = loaded.metadata.apply_to(&mut state);
+        assert_eq!(state.particle_radius, 30.0);
+        assert_eq!(state.n_medium, AppState::default().n_medium);
+        assert_eq!(
+            missing,
+            vec!["n_particle_real", "n_particle_imag", "n_medium", "wavelength_nm"]
+        );
+    }
+
+    #[test]
+    fn test_file_without_metadata_loads() {
+        let loaded = SpectrumFile::from_json(r#"{ "spectrum_data": [] }"#).unwrap();
+        assert_eq!(loaded.metadata, SpectrumMetadata::default());
+        assert!(SpectrumFile::from_json("{").is_err());
+    }
+}
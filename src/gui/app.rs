@@ -1,17 +1,52 @@
 //! Main GUI application with modern, intuitive interface
 
-use crate::app::AppState;
-use crate::core::{OpticalResult, RefractiveIndex};
+use crate::app::{build_optical_model, controller, AppState, ComputeProfile, InputField, MediumPreset, RunHistory, SolverMethod, DEFAULT_HISTORY_LIMIT};
+use crate::core::{CalculationError, Language, OpticalModelKind, OpticalResult, ScalableConstant, RefractiveIndex, RunInputs, ValidityFlag, Warning, WarningCode, WarningSeverity};
+use crate::physics::thermal::photothermal::{absorbed_power, steady_state_temperature};
+use crate::physics::electronic::BrusModel;
+use crate::physics::thermal::{CallawayModel, NanowireThermalModel};
+use crate::physics::materials::{
+    column_count, database, johnson_christy, parse_dispersion_table, ColumnRole, ColumnSpec, DatasetBounds,
+    Dispersion, DrudeModel, FormulaDispersion, Interpolation, LorentzDrudeModel, MaterialClass, XUnit,
+};
+use crate::physics::optical::array::{apply_structure_factor, rayleigh_anomalies, Lattice};
 use crate::physics::optical::mie::MieModel;
-use crate::core::OpticalModel;
+use crate::physics::optical::MediumSpec;
+use crate::physics::{classify_material, suggest_model};
+use crate::core::{ElectronicModel, OpticalModel, ThermalModel};
+use crate::compute::{
+    against_geometric_area, against_reference, align_spectra, band_edges, c_ext_vs_energy, collected_fraction, efficiency_vs_x, extinction_paradox,
+    bandgap_against_bulk, integrated_cross_section, mixture_spectrum, thermal_against_bulk, monte_carlo_band_with_progress, multipole_spectrum, peak_sensitivities,
+    parse_wavelength_list, q_ext_residuals, residual_stats, spectral_moments, spectrum_key, at_wavelengths, ComputeEngine,
+    BackgroundRun, MultipoleDecomposition, PolydisperseConfig, ReferenceComparison, Sensitivity, SpectralBand, SpectrumColumns,
+};
+use crate::compute::sensitivity::DEFAULT_RELATIVE_STEP;
+use crate::export::bundle::{self, BundleContents, BundleFile, Provenance};
+use crate::export::{
+    comparison_csv, multipole_csv, multipole_json, nk_text, normalized_csv, optical_tsv, to_csv, CsvExporter,
+    Exporter, JsonExporter, OpticalColumn, ResultKind, ResultSet, SpectrumFile, WavelengthUnit,
+};
+use crate::project::{Project, ProjectComparison, ProjectError, SchemaVersion, PROJECT_EXTENSION};
+use crate::gui::appearance::{Appearance, APPEARANCE_KEY, MAX_UI_SCALE, MIN_UI_SCALE};
+use crate::gui::file_dialog::{default_file_name, FileDialogs};
+use crate::gui::plot_view::{PlotMode, PlotView, PLOT_VIEW_KEY};
+use crate::gui::repaint::{schedule_repaint, PendingWork};
+use crate::gui::widgets::{drag_value_speed, mark_invalid};
+use crate::utils::clock::{timestamped, Clock, SystemClock};
+use crate::utils::convolve::gaussian_broaden;
+use crate::utils::features::{detect_with, FeatureKind};
+use crate::plotting::{
+    auto_log_y_bounds, auto_y_bounds, legend_keys, log_points, peak_zoom_window, validity_spans, wavelength_from_plot_x, Overlay,
+    ValiditySpan, VISIBLE_BAND,
+};
 use egui::{CentralPanel, Context, SidePanel, TopBottomPanel, Rounding, Color32};
-use egui_plot::{Line, Plot, PlotPoints, Legend, Corner};
+use egui_plot::{Bar, BarChart, Line, Plot, PlotPoints, Legend, Corner, MarkerShape, Points};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Language {
-    English,
-    Spanish,
-}
+/// Lattice, spacing (nm), incidence angle (°) and medium index of the array overlay
+type ArrayInputs = (Lattice, f64, f64, f64);
 
 #[derive(Debug, Clone)]
 pub struct ElementProperties {
@@ -20,31 +55,168 @@ pub struct ElementProperties {
     atomic_number: u32,
     n_real: f64,
     n_imag: f64,
+    wavelength: f64,      // nm at which n, k apply
+    from_database: bool,  // false = constante fija a 550 nm
+    dataset: Option<DatasetBounds>,
 }
 
 pub struct NanoCalcApp {
     state: AppState,
     result: Option<OpticalResult>,
+    warnings: Vec<Warning>,
+    /// Warnings from the model behind the last calculation
+    model_warnings: Vec<Warning>,
+    /// Input that failed validation, outlined in red until it is fixed
+    invalid_field: Option<InputField>,
+    /// Data range of the tabulated material last applied, if any
+    dataset_bounds: Option<DatasetBounds>,
+    /// Dispersion model behind the current n, k, if it came from one
+    active_dispersion: Option<Arc<dyn Dispersion>>,
+    nk_unit: WavelengthUnit,
+    /// Quantities written by the spectrum CSV export, in column order
+    csv_columns: BTreeSet<OpticalColumn>,
+    /// Render the parameter caption under exported PNG plots
+    png_caption: bool,
     spectrum_results: Vec<OpticalResult>,
+    /// Validity shading of the spectrum, with the model and grid it was computed for
+    validity_cache: Option<(String, Vec<ValiditySpan>)>,
+    /// Columnar copy of `spectrum_results` for the plot, rebuilt when they change
+    spectrum_columns: SpectrumColumns,
+    baseline_results: Vec<OpticalResult>,
+    /// Single-point result pinned for comparison, with the inputs that produced it
+    reference_result: Option<(OpticalResult, RunInputs)>,
     calculating: bool,
     error_message: Option<String>,
     show_about: bool,
     show_periodic_table: bool,
     show_element_properties: bool,
+    /// Element dialog applies to the medium instead of the particle
+    apply_as_medium: bool,
     selected_element: Option<ElementProperties>,
     language: Language,
     plot_reset_counter: u32,  // Para forzar reset del plot
+    zoom_request: Option<(f64, f64)>,
+    /// Plot appearance, and the saved default restored at launch
+    plot_view: PlotView,
+    default_plot_view: PlotView,
+    /// Overlay Q_ext multiplied by a periodic-array structure factor
+    show_array: bool,
+    array_lattice: Lattice,
+    array_spacing: f64,
+    array_angle_deg: f64,
+    /// Array-modified Q_ext of the spectrum, with the lattice, spacing, angle
+    /// and medium index it was computed for; cleared with the spectrum
+    array_cache: Option<(ArrayInputs, Vec<[f64; 2]>)>,
+    /// Ranked resonance sensitivities; Some(empty) when there was no peak
+    sensitivities: Option<Vec<Sensitivity>>,
     show_export_dialog: bool,
     export_filename: String,
     export_type: ExportType,
     log_messages: Vec<String>,  // Log de mensajes
+    universal_results: Vec<OpticalResult>,
+    universal_x_max: f64,
+    pick_wavelength_mode: bool,  // Clic en la gráfica fija λ
+    collection_na: f64,
+    engine: ComputeEngine,
+    /// Spectrum computing in the background, with the model name and inputs for its history record
+    spectrum_run: Option<(BackgroundRun<Vec<OpticalResult>>, String, RunInputs)>,
+    /// Polydisperse band computing in the background
+    band_run: Option<BackgroundRun<Vec<SpectralBand>>>,
+    /// Handle the streaming worker uses to request repaints
+    repaint_ctx: Option<Context>,
+    show_settings: bool,
+    show_compare_projects: bool,
+    compare_path_input: String,
+    /// Project saved by a newer format, awaiting a best-effort load decision
+    newer_project_prompt: Option<(String, SchemaVersion)>,
+    project_comparison: ProjectComparison,
+    show_constants_inspector: bool,
+    inspector_plasma_ev: f64,
+    inspector_mass_me: f64,
+    energy_window: (f64, f64),  // eV
+    polydisperse: PolydisperseConfig,
+    band_results: Vec<SpectralBand>,
+    smooth_band: bool,
+    band_smoothing_window: usize,
+    mixer_fraction_a: f64,  // Fracción numérica de la población A (partícula actual)
+    mixer_radius_b: f64,
+    mixer_n_b: (f64, f64),
+    mixture_results: Vec<OpticalResult>,
+    show_import: bool,
+    show_open_project: bool,
+    open_project_path_input: String,
+    import_path_input: String,
+    batch_path_input: String,
+    /// Comma-separated wavelengths for the wavelength-list table
+    wavelength_list_input: String,
+    wavelength_list_results: Vec<OpticalResult>,
+    imported_spectrum: Option<SpectrumFile>,
+    confirm_copy_parameters: bool,
+    multipole_results: Vec<MultipoleDecomposition>,
+    periodic_decimals: usize,
+    irradiance_mw_um2: f64,  // mW/µm² = 1e9 W/m²
+    kappa_medium: f64,       // W/(m·K)
+    particle_density: f64,   // g/cm³
+    nanowire_diameter: f64,  // nm
+    nanowire_callaway: bool,
+    run_history: RunHistory,
+    show_history: bool,
+    formula_n_input: String,
+    formula_k_input: String,
+    /// Pasted optical constant table and the role of each of its columns
+    table_input: String,
+    table_spec: ColumnSpec,
+    appearance: Appearance,
+    #[cfg_attr(not(all(feature = "file_dialog", not(target_arch = "wasm32"))), allow(dead_code))]
+    file_dialogs: FileDialogs,
+    clock: Arc<dyn Clock>,  // Marca temporal del log, historial y procedencia
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ExportType {
-    CSV,
-    JSON,
-    PNG,
+    Csv,
+    Json,
+    Png,
+    Comparison,
+    Project,
+    MultipoleCsv,
+    MultipoleJson,
+    Bundle,
+    Normalized,
+    NkText,
+}
+
+/// Periodic-table tints for elements with tabulated data
+const METALLIC_TINT: Color32 = Color32::from_rgb(110, 90, 40);
+const DIELECTRIC_TINT: Color32 = Color32::from_rgb(40, 70, 110);
+const ABSORBING_TINT: Color32 = Color32::from_rgb(90, 60, 90);
+
+/// Colors for overlaid comparison spectra
+const OVERLAY_COLORS: &[Color32] = &[
+    Color32::from_rgb(255, 200, 80),
+    Color32::from_rgb(200, 120, 255),
+    Color32::from_rgb(80, 220, 220),
+    Color32::from_rgb(255, 120, 180),
+    Color32::from_rgb(180, 220, 90),
+    Color32::from_rgb(255, 160, 90),
+];
+
+/// Link group sharing the wavelength axis between the spectrum and residual plots
+const SPECTRUM_AXIS_GROUP: &str = "spectrum_x_axis";
+
+/// Translucent fill for a background shading
+fn overlay_fill(overlay: Overlay) -> Color32 {
+    let [r, g, b, a] = overlay.rgba();
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Dashed line used for overlaying reference spectra on the main plot
+fn overlay_line(points: PlotPoints, color: Color32, name: String, width: f32) -> Line {
+    Line::new(points)
+        .color(color)
+        .width(width)
+        .style(egui_plot::LineStyle::Dashed { length: 8.0 })
+        .name(name)
 }
 
 // Material presets for quick access
@@ -84,46 +256,128 @@ const MATERIAL_PRESETS: &[MaterialPreset] = &[
 
 impl Default for NanoCalcApp {
     fn default() -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
         Self {
             state: AppState::default(),
             result: None,
+            warnings: Vec::new(),
+            model_warnings: Vec::new(),
+            invalid_field: None,
+            dataset_bounds: None,
+            active_dispersion: None,
+            nk_unit: WavelengthUnit::default(),
+            png_caption: false,
+            csv_columns: OpticalColumn::DEFAULT.into_iter().collect(),
             spectrum_results: Vec::new(),
+            validity_cache: None,
+            spectrum_columns: SpectrumColumns::default(),
+            baseline_results: Vec::new(),
+            reference_result: None,
             calculating: false,
             error_message: None,
             show_about: false,
             show_periodic_table: false,
             show_element_properties: false,
+            apply_as_medium: false,
             selected_element: None,
             language: Language::English,
             plot_reset_counter: 0,
+            zoom_request: None,
+            plot_view: PlotView::default(),
+            default_plot_view: PlotView::default(),
+            show_array: false,
+            array_lattice: Lattice::default(),
+            array_spacing: 400.0,
+            array_angle_deg: 0.0,
+            array_cache: None,
+            sensitivities: None,
             show_export_dialog: false,
             export_filename: String::from("nanocalc_spectrum"),
-            export_type: ExportType::CSV,
+            export_type: ExportType::Csv,
             log_messages: vec![String::from("✅ NanoCalc initialized")],
+            universal_results: Vec::new(),
+            universal_x_max: 30.0,
+            pick_wavelength_mode: false,
+            collection_na: 0.9,
+            engine: ComputeEngine::default(),
+            spectrum_run: None,
+            band_run: None,
+            repaint_ctx: None,
+            show_settings: false,
+            show_compare_projects: false,
+            compare_path_input: String::new(),
+            newer_project_prompt: None,
+            project_comparison: ProjectComparison::default(),
+            show_constants_inspector: false,
+            inspector_plasma_ev: 9.0,
+            inspector_mass_me: 1.0,
+            energy_window: (2.0, 2.6),
+            polydisperse: PolydisperseConfig::default(),
+            band_results: Vec::new(),
+            smooth_band: false,
+            band_smoothing_window: 5,
+            mixer_fraction_a: 0.5,
+            mixer_radius_b: 20.0,
+            mixer_n_b: (0.05, 3.00),
+            mixture_results: Vec::new(),
+            show_import: false,
+            show_open_project: false,
+            open_project_path_input: String::new(),
+            import_path_input: String::new(),
+            batch_path_input: String::new(),
+            wavelength_list_input: "405, 532, 633, 785".to_string(),
+            wavelength_list_results: Vec::new(),
+            imported_spectrum: None,
+            confirm_copy_parameters: false,
+            multipole_results: Vec::new(),
+            periodic_decimals: 2,
+            irradiance_mw_um2: 1.0,
+            kappa_medium: 0.6,  // agua
+            particle_density: 19.3,  // oro
+            nanowire_diameter: 50.0,
+            nanowire_callaway: false,
+            run_history: RunHistory::with_clock(DEFAULT_HISTORY_LIMIT, clock.clone()),
+            show_history: false,
+            formula_n_input: String::from("1.5 + 0.01/(lambda/1000)^2"),
+            formula_k_input: String::from("0"),
+            table_input: String::new(),
+            table_spec: ColumnSpec::guess(3),
+            appearance: Appearance::default(),
+            file_dialogs: FileDialogs::default(),
+            clock,
         }
     }
 }
 
 impl NanoCalcApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        // Restore accessibility settings from the last session
+        let appearance: Appearance = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, APPEARANCE_KEY))
+            .unwrap_or_default();
+        let default_plot_view: PlotView = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, PLOT_VIEW_KEY))
+            .unwrap_or_default();
+
         // Configure fonts and style
-        Self::configure_style(&cc.egui_ctx);
-        Self::default()
+        Self::configure_style(&cc.egui_ctx, &appearance);
+        Self {
+            appearance,
+            plot_view: default_plot_view.clone(),
+            default_plot_view,
+            repaint_ctx: Some(cc.egui_ctx.clone()),
+            ..Self::default()
+        }
     }
 
-    fn configure_style(ctx: &Context) {
-        use egui::{FontFamily, FontId, TextStyle, Visuals, Rounding};
+    fn configure_style(ctx: &Context, appearance: &Appearance) {
+        use egui::Rounding;
 
-        // Set modern fonts
+        // Fonts scaled by the accessibility setting
         let mut style = (*ctx.style()).clone();
-        style.text_styles = [
-            (TextStyle::Heading, FontId::new(26.0, FontFamily::Proportional)),
-            (TextStyle::Body, FontId::new(15.0, FontFamily::Proportional)),
-            (TextStyle::Monospace, FontId::new(14.0, FontFamily::Monospace)),
-            (TextStyle::Button, FontId::new(15.0, FontFamily::Proportional)),
-            (TextStyle::Small, FontId::new(13.0, FontFamily::Proportional)),
-        ]
-        .into();
+        style.text_styles = appearance.text_styles();
 
         // Modern rounded corners and spacing
         style.spacing.item_spacing = egui::vec2(8.0, 10.0);
@@ -137,27 +391,38 @@ impl NanoCalcApp {
         style.visuals.widgets.hovered.rounding = Rounding::same(6.0);
         style.visuals.widgets.active.rounding = Rounding::same(6.0);
 
-        // Color scheme - modern blue tones
-        let mut visuals = Visuals::dark();
-        visuals.widgets.inactive.bg_fill = Color32::from_rgb(45, 50, 65);
-        visuals.widgets.hovered.bg_fill = Color32::from_rgb(60, 65, 85);
-        visuals.widgets.active.bg_fill = Color32::from_rgb(70, 130, 180);
-        visuals.selection.bg_fill = Color32::from_rgba_premultiplied(70, 130, 180, 100);
-        visuals.window_fill = Color32::from_rgb(30, 33, 42);
-        visuals.panel_fill = Color32::from_rgb(35, 38, 48);
-        
+        // Color scheme - modern blue tones, or high contrast
+        let mut visuals = appearance.visuals();
+        visuals.window_rounding = style.visuals.window_rounding;
+        visuals.menu_rounding = style.visuals.menu_rounding;
+        visuals.widgets.noninteractive.rounding = style.visuals.widgets.noninteractive.rounding;
+        visuals.widgets.inactive.rounding = style.visuals.widgets.inactive.rounding;
+        visuals.widgets.hovered.rounding = style.visuals.widgets.hovered.rounding;
+        visuals.widgets.active.rounding = style.visuals.widgets.active.rounding;
+
         style.visuals = visuals;
         ctx.set_style(style);
     }
 
     fn t(&self, en: &str, es: &str) -> String {
-        match self.language {
-            Language::English => en.to_string(),
-            Language::Spanish => es.to_string(),
-        }
+        self.language.pick(en, es).to_string()
     }
 
-    fn get_element_properties(symbol: &str, name: &str, atomic_number: u32) -> ElementProperties {
+    fn get_element_properties(symbol: &str, name: &str, atomic_number: u32, wavelength: f64) -> ElementProperties {
+        if let Some(material) = database::element(symbol) {
+            let index = material.refractive_index_at(wavelength);
+            return ElementProperties {
+                symbol: symbol.to_string(),
+                name: name.to_string(),
+                atomic_number,
+                n_real: index.real,
+                n_imag: index.imaginary,
+                wavelength,
+                from_database: true,
+                dataset: Some(material.bounds()),
+            };
+        }
+
         // Propiedades ópticas aproximadas para elementos comunes (550 nm)
         let (n_real, n_imag) = match symbol {
             "Au" => (0.47, 2.40),  // Oro
@@ -182,17 +447,343 @@ impl NanoCalcApp {
             atomic_number,
             n_real,
             n_imag,
+            wavelength: 550.0,
+            from_database: false,
+            dataset: None,
         }
     }
 
+    /// Warnings are stored as codes and rendered in the current language each frame
+    fn warning_text(&self, warning: &Warning) -> String {
+        warning.localized(self.language)
+    }
+
+    /// Warnings from the selected model at the short end of the spectrum,
+    /// where the size parameter is largest
+    fn spectrum_model_warnings(&self) -> Vec<Warning> {
+        let state = AppState {
+            wavelength: self.state.spectrum_range().0,
+            ..self.state.clone()
+        };
+        controller::optical_model(&state, self.active_dispersion.as_ref())
+            .map(|model| model.warnings())
+            .unwrap_or_default()
+    }
+
     fn apply_material_preset(&mut self, preset: &MaterialPreset) {
         self.state.n_particle_real = preset.n_real;
         self.state.n_particle_imag = preset.n_imag;
+        self.active_dispersion = None;
+    }
+
+    /// Value, reference and ratio for each comparison, under a heading
+    fn draw_reference_comparison(&self, ui: &mut egui::Ui, id: &str, heading: &str, comparisons: &[ReferenceComparison]) {
+        ui.label(egui::RichText::new(heading).color(Color32::LIGHT_GRAY));
+        egui::Grid::new(id)
+            .num_columns(4)
+            .spacing([15.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for comparison in comparisons {
+                    ui.label(format!("{}:", comparison.quantity));
+                    ui.label(format!("{:.2}", comparison.value));
+                    ui.colored_label(Color32::GRAY, format!("{:.2}", comparison.reference));
+                    let ratio = comparison.ratio().map_or("—".to_string(), |r| format!("×{:.3}", r));
+                    ui.colored_label(Color32::from_rgb(255, 220, 100), ratio);
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Metal/dielectric class of the current index in the medium, with a model hint
+    fn draw_material_class(&self, ui: &mut egui::Ui) {
+        let index = RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag);
+        let (icon, label, tooltip) = match classify_material(index, self.state.n_medium) {
+            MaterialClass::Metallic => ("🔶", self.t("Metallic", "Metálico"), self.t(
+                "Re(ε) < 0 relative to the medium: supports localized plasmons",
+                "Re(ε) < 0 respecto al medio: soporta plasmones localizados"
+            )),
+            MaterialClass::Dielectric => ("💠", self.t("Dielectric", "Dieléctrico"), self.t(
+                "Re(ε) > 0 with weak absorption",
+                "Re(ε) > 0 con absorción débil"
+            )),
+            MaterialClass::AbsorbingDielectric => ("🟣", self.t("Absorbing dielectric", "Dieléctrico absorbente"), self.t(
+                "Re(ε) > 0 with significant absorption (k ≥ 0.05 relative to the medium)",
+                "Re(ε) > 0 con absorción significativa (k ≥ 0.05 respecto al medio)"
+            )),
+        };
+        let suggestion = suggest_model(index, self.state.n_medium).map(|kind| {
+            let (en, es) = kind.name();
+            self.t(&format!("Suggested: {}", en), &format!("Sugerido: {}", es))
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{} {}", icon, label)).on_hover_text(tooltip);
+            if let Some(suggestion) = suggestion {
+                ui.colored_label(Color32::GRAY, suggestion);
+            }
+        });
+    }
+
+    /// Typed n(λ), k(λ) formulas, evaluated at the current wavelength on demand
+    fn draw_formula_dispersion(&mut self, ui: &mut egui::Ui) {
+        let formula_label = self.t("ƒ Custom dispersion formula", "ƒ Fórmula de dispersión personalizada");
+        let mut apply = false;
+        ui.collapsing(formula_label, |ui| {
+            ui.colored_label(Color32::GRAY, self.t(
+                "Variable: lambda or λ (nm). Functions: sqrt, exp, ln, log10, abs, sin, cos",
+                "Variable: lambda o λ (nm). Funciones: sqrt, exp, ln, log10, abs, sin, cos"
+            ));
+            ui.horizontal(|ui| {
+                ui.label("n =");
+                ui.text_edit_singleline(&mut self.formula_n_input);
+            });
+            ui.horizontal(|ui| {
+                ui.label("k =");
+                ui.text_edit_singleline(&mut self.formula_k_input);
+            });
+            self.draw_reference_wavelength(ui);
+            if ui.button(self.t("Apply at reference λ", "Aplicar en λ de referencia")).clicked() {
+                apply = true;
+            }
+        });
+        if apply {
+            self.apply_formula_dispersion();
+        }
+    }
+
+    /// Advanced toggle describing the medium by ε and µ, with its derived n and Z
+    fn draw_magnetic_medium(&mut self, ui: &mut egui::Ui) {
+        let advanced_label = self.t("Advanced medium", "Medio avanzado");
+        let toggle_label = self.t("Magnetic host (µ≠1)", "Medio magnético (µ≠1)");
+        let toggle_hint = self.t(
+            "Replace n (medium) by independent permittivity and permeability, n = √ε·√µ. The host must be lossless with ε, µ > 0",
+            "Sustituir n (medio) por permitividad y permeabilidad independientes, n = √ε·√µ. El medio debe ser sin pérdidas con ε, µ > 0"
+        );
+        let was_on = self.state.magnetic_medium;
+        ui.collapsing(advanced_label, |ui| {
+            ui.checkbox(&mut self.state.magnetic_medium, toggle_label)
+                .on_hover_text(toggle_hint);
+            if self.state.magnetic_medium && !was_on {
+                self.state.medium_spec = MediumSpec::from_index(self.state.n_medium);
+            }
+            if !self.state.magnetic_medium {
+                return;
+            }
+
+            let spec = &mut self.state.medium_spec;
+            egui::Grid::new("magnetic_medium_grid")
+                .num_columns(3)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("ε =");
+                    ui.add(egui::DragValue::new(&mut spec.eps_real).speed(0.01).range(0.01..=20.0));
+                    ui.add(egui::DragValue::new(&mut spec.eps_imag).speed(0.001).range(0.0..=20.0).prefix("+ i"));
+                    ui.end_row();
+                    ui.label("µ =");
+                    ui.add(egui::DragValue::new(&mut spec.mu_real).speed(0.01).range(0.01..=20.0));
+                    ui.add(egui::DragValue::new(&mut spec.mu_imag).speed(0.001).range(0.0..=20.0).prefix("+ i"));
+                    ui.end_row();
+                });
+
+            let spec = self.state.medium_spec;
+            if spec.validate().is_ok() {
+                let (n, z) = (spec.refractive_index(), spec.impedance());
+                ui.colored_label(
+                    Color32::GRAY,
+                    format!("n = {:.4} {:+.4}i   Z = {:.4} {:+.4}i", n.re, n.im, z.re, z.im)
+                );
+                if let Err(e) = spec.host() {
+                    ui.colored_label(Color32::from_rgb(255, 200, 100), e.to_string());
+                }
+            }
+        });
+    }
+
+    /// Pasted (λ, n, k) or (E, ε', ε'') table with a role chosen for each column
+    fn draw_table_dispersion(&mut self, ui: &mut egui::Ui) {
+        let table_label = self.t("📋 Paste n,k or ε table", "📋 Pegar tabla n,k o ε");
+        let x_label = self.t("x unit:", "Unidad de x:");
+        let mut apply = false;
+        #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+        let mut load_csv: Option<std::path::PathBuf> = None;
+        ui.collapsing(table_label, |ui| {
+            ui.colored_label(Color32::GRAY, self.t(
+                "One row per line; columns separated by spaces, tabs, commas or semicolons",
+                "Una fila por línea; columnas separadas por espacios, tabulaciones, comas o punto y coma"
+            ));
+            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut self.table_input)
+                    .code_editor()
+                    .desired_rows(5)
+                    .desired_width(f32::INFINITY));
+            });
+            #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+            if ui.button(self.t("📂 Load CSV…", "📂 Cargar CSV…"))
+                .on_hover_text(self.t(
+                    "Read a wavelength (nm), n, k file and apply it at the reference λ",
+                    "Leer un archivo de longitud de onda (nm), n, k y aplicarlo en la λ de referencia"
+                ))
+                .clicked()
+            {
+                load_csv = self.file_dialogs.open("CSV", &["csv", "txt"]);
+            }
+
+            let Some(columns) = column_count(&self.table_input) else {
+                return;
+            };
+            if columns != self.table_spec.roles.len() {
+                self.table_spec = ColumnSpec { x_unit: self.table_spec.x_unit, ..ColumnSpec::guess(columns) };
+            }
+            ui.horizontal_wrapped(|ui| {
+                for (i, role) in self.table_spec.roles.iter_mut().enumerate() {
+                    egui::ComboBox::from_id_salt(("table_column", i))
+                        .width(50.0)
+                        .selected_text(role.symbol())
+                        .show_ui(ui, |ui| {
+                            for option in ColumnRole::ALL {
+                                ui.selectable_value(role, option, option.symbol());
+                            }
+                        });
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(x_label);
+                for unit in XUnit::ALL {
+                    ui.selectable_value(&mut self.table_spec.x_unit, unit, unit.symbol());
+                }
+            });
+            self.draw_reference_wavelength(ui);
+            if ui.button(self.t("Apply at reference λ", "Aplicar en λ de referencia")).clicked() {
+                apply = true;
+            }
+        });
+        if apply {
+            self.apply_table_dispersion();
+        }
+        #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+        if let Some(path) = load_csv {
+            self.load_csv_material(&path);
+        }
+    }
+
+    /// Make a (λ, n, k) CSV file the active material
+    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+    fn load_csv_material(&mut self, path: &std::path::Path) {
+        match crate::physics::materials::TabulatedMaterial::from_csv(path) {
+            Ok(material) => {
+                let bounds = material.bounds();
+                self.error_message = None;
+                self.apply_dispersive_material(material);
+                self.dataset_bounds = Some(bounds);
+            }
+            Err(e) => {
+                let error_msg = format!("CSV material error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    fn apply_table_dispersion(&mut self) {
+        match parse_dispersion_table(&self.table_input, &self.table_spec) {
+            Ok(material) => {
+                let n = self.state.snapshot_index(&material);
+                let wavelength = self.state.snapshot_wavelength();
+                let bounds = material.bounds();
+                self.state.n_particle_real = n.real;
+                self.state.n_particle_imag = n.imaginary;
+                self.dataset_bounds = Some(bounds);
+                self.active_dispersion = Some(Arc::new(material));
+                self.error_message = None;
+                let msg = self.t(
+                    &format!("📋 Table ({}) applied at {} nm: n = {:.4} + {:.4}i", bounds, wavelength, n.real, n.imaginary),
+                    &format!("📋 Tabla ({}) aplicada en {} nm: n = {:.4} + {:.4}i", bounds, wavelength, n.real, n.imaginary)
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Table error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    /// Reference wavelength for n,k snapshots: follows λ unless fixed by the user
+    fn draw_reference_wavelength(&mut self, ui: &mut egui::Ui) {
+        let follow_label = self.t("Follow working λ", "Seguir λ de trabajo");
+        let hint = self.t(
+            "Wavelength at which dispersive materials are reduced to a single n, k",
+            "Longitud de onda a la que los materiales dispersivos se reducen a un único n, k"
+        );
+        let invalid = self.invalid_field == Some(InputField::ReferenceWavelength);
+        ui.horizontal(|ui| {
+            mark_invalid(ui, invalid);
+            ui.label(self.t("Reference λ:", "λ de referencia:")).on_hover_text(&hint);
+            let mut follow = self.state.reference_wavelength.is_none();
+            let mut wavelength = self.state.snapshot_wavelength();
+            ui.add_enabled(!follow, egui::DragValue::new(&mut wavelength)
+                .speed(1.0)
+                .range(200.0..=2000.0)
+                .suffix(" nm"));
+            ui.checkbox(&mut follow, follow_label);
+            self.state.reference_wavelength = (!follow).then_some(wavelength);
+        });
+        let dispersive_label = self.t("Dispersive spectrum", "Espectro dispersivo");
+        let dispersive_hint = self.t(
+            "Use the active material's n, k at every spectrum wavelength instead of the snapshot",
+            "Usar n, k del material activo en cada longitud de onda del espectro en lugar de la instantánea"
+        );
+        ui.add_enabled(
+            self.active_dispersion.is_some(),
+            egui::Checkbox::new(&mut self.state.dispersive_spectrum, dispersive_label),
+        )
+        .on_hover_text(dispersive_hint);
+    }
+
+    /// Make a dispersion model the active material, with n, k at the reference λ
+    fn apply_dispersive_material(&mut self, material: impl Dispersion + 'static) {
+        let n = self.state.snapshot_index(&material);
+        let wavelength = self.state.snapshot_wavelength();
+        self.state.n_particle_real = n.real;
+        self.state.n_particle_imag = n.imaginary;
+        self.dataset_bounds = None;
+        let msg = self.t(
+            &format!("⚛ {} at {} nm: n = {:.4} + {:.4}i", material.name(), wavelength, n.real, n.imaginary),
+            &format!("⚛ {} en {} nm: n = {:.4} + {:.4}i", material.name(), wavelength, n.real, n.imaginary)
+        );
+        self.active_dispersion = Some(Arc::new(material));
+        self.add_log(&msg);
+    }
+
+    fn apply_formula_dispersion(&mut self) {
+        match FormulaDispersion::new("Custom formula", &self.formula_n_input, &self.formula_k_input) {
+            Ok(material) => {
+                let n = self.state.snapshot_index(&material);
+                let wavelength = self.state.snapshot_wavelength();
+                self.state.n_particle_real = n.real;
+                self.state.n_particle_imag = n.imaginary;
+                self.dataset_bounds = None;
+                self.active_dispersion = Some(Arc::new(material));
+                self.error_message = None;
+                let msg = self.t(
+                    &format!("ƒ Formula applied at {} nm: n = {:.4} + {:.4}i", wavelength, n.real, n.imaginary),
+                    &format!("ƒ Fórmula aplicada en {} nm: n = {:.4} + {:.4}i", wavelength, n.real, n.imaginary)
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Formula error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
     }
 
     fn calculate_single(&mut self) {
         self.calculating = true;
         self.error_message = None;
+        if !self.inputs_valid() { return; }
         
         let msg = self.t(
             &format!("🔬 Calculating at {} nm...", self.state.wavelength),
@@ -200,16 +791,23 @@ impl NanoCalcApp {
         );
         self.add_log(&msg);
 
-        let model = MieModel::new(
-            self.state.particle_radius,
-            self.state.wavelength,
-            RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
-            self.state.n_medium,
-        );
+        let model = match controller::optical_model(&self.state, self.active_dispersion.as_ref()) {
+            Ok(model) => model,
+            Err(e) => {
+                let error_msg = format!("Calculation error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                self.calculating = false;
+                return;
+            }
+        };
 
-        match model.calculate() {
-            Ok(result) => {
-                self.result = Some(result);
+        self.model_warnings = model.warnings();
+
+        let inputs = RunInputs::from(&self.state);
+        match self.run_history.record(model.name(), inputs, || model.calculate().map(|r| vec![r])) {
+            Ok(mut results) => {
+                self.result = results.pop();
                 self.add_log(&self.t("✅ Single point calculated", "✅ Punto único calculado"));
             }
             Err(e) => {
@@ -222,33 +820,46 @@ impl NanoCalcApp {
         self.calculating = false;
     }
 
-    fn calculate_spectrum(&mut self) {
+    /// Results at exactly the wavelengths in the wavelength list
+    fn calculate_wavelength_list(&mut self) {
         self.calculating = true;
         self.error_message = None;
-        
-        self.add_log(&self.t("📊 Calculating full spectrum (300-800 nm)...", "📊 Calculando espectro completo (300-800 nm)..."));
+        if !self.inputs_valid() { return; }
 
-        let wavelengths: Vec<f64> = (300..=800).step_by(5).map(|w| w as f64).collect();
+        let list = parse_wavelength_list(&self.wavelength_list_input);
+        if !list.skipped.is_empty() {
+            let skipped = list.skipped.join(", ");
+            let msg = self.t(
+                &format!("⚠ Skipped invalid wavelengths: {}", skipped),
+                &format!("⚠ Longitudes de onda inválidas omitidas: {}", skipped)
+            );
+            self.add_log(&msg);
+        }
+        if list.wavelengths.is_empty() {
+            let error_msg = self.t("Wavelength list has no valid entries", "La lista de longitudes de onda no tiene entradas válidas");
+            self.error_message = Some(error_msg.clone());
+            self.add_log(&format!("❌ {}", error_msg));
+            self.calculating = false;
+            return;
+        }
 
-        let model = MieModel::new(
-            self.state.particle_radius,
-            self.state.wavelength,
-            RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
-            self.state.n_medium,
+        let msg = self.t(
+            &format!("🔦 Calculating at {} wavelengths...", list.wavelengths.len()),
+            &format!("🔦 Calculando en {} longitudes de onda...", list.wavelengths.len())
         );
+        self.add_log(&msg);
 
-        match model.calculate_spectrum(&wavelengths) {
+        let inputs = RunInputs::from(&self.state);
+        let outcome = controller::optical_model(&self.state, self.active_dispersion.as_ref()).and_then(|model| {
+            self.run_history.record(model.name(), inputs, || at_wavelengths(model.as_ref(), &list.wavelengths))
+        });
+        match outcome {
             Ok(results) => {
-                self.spectrum_results = results;
-                self.plot_reset_counter += 1;  // Forzar reset del plot
-                let msg = self.t(
-                    &format!("✅ Spectrum calculated ({} points)", self.spectrum_results.len()),
-                    &format!("✅ Espectro calculado ({} puntos)", self.spectrum_results.len())
-                );
-                self.add_log(&msg);
+                self.wavelength_list_results = results;
+                self.add_log(&self.t("✅ Wavelength list calculated", "✅ Lista de longitudes de onda calculada"));
             }
             Err(e) => {
-                let error_msg = format!("Spectrum calculation error: {}", e);
+                let error_msg = format!("Calculation error: {}", e);
                 self.error_message = Some(error_msg.clone());
                 self.add_log(&format!("❌ {}", error_msg));
             }
@@ -257,183 +868,685 @@ impl NanoCalcApp {
         self.calculating = false;
     }
 
-    fn draw_input_panel(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(5.0);
-        ui.heading(&self.t("Input Parameters", "Parámetros de Entrada"))
-            .on_hover_text(&self.t(
-                "Configure the nanoparticle and environment properties for optical calculations",
-                "Configura las propiedades de la nanopartícula y el entorno para cálculos ópticos"
-            ));
-        ui.add_space(15.0);
-
-        // Material Presets Section
-        ui.group(|ui| {
-            ui.set_min_width(ui.available_width());
-            ui.horizontal(|ui| {
-                ui.strong(&self.t("Quick Presets", "Preajustes Rápidos"));
-            });
-            ui.add_space(5.0);
-
-            egui::Grid::new("preset_grid")
-                .num_columns(2)
-                .spacing([8.0, 8.0])
-                .show(ui, |ui| {
-                    for preset in MATERIAL_PRESETS {
-                        if ui.button(format!("{}", preset.name))
-                            .on_hover_text(preset.description)
-                            .clicked() 
-                        {
-                            self.apply_material_preset(preset);
-                        }
-                        if ui.available_width() < 50.0 {
-                            ui.end_row();
-                        }
-                    }
-                });
-        });
-
-        ui.add_space(12.0);
-
-        // Periodic Table Button
-        if ui.button("Select from Periodic Table").on_hover_text("Choose element optical properties").clicked() {
-            self.show_periodic_table = true;
-        }
-
-        ui.add_space(12.0);
-
-        // Particle Properties Card
+    /// Compact table of the wavelength-list results
+    fn draw_wavelength_list_table(&self, ui: &mut egui::Ui) {
         egui::Frame::none()
-            .fill(Color32::from_rgb(40, 43, 53))
+            .fill(Color32::from_rgb(45, 48, 58))
             .rounding(Rounding::same(8.0))
             .inner_margin(egui::Margin::same(12.0))
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
-                    ui.strong("Particle Properties");
-                    ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Physical size and optical properties of the nanoparticle",
-                            "Tamaño físico y propiedades ópticas de la nanopartícula"
-                        ));
-                });
-                ui.add_space(8.0);
-
-                // Radius input
-                ui.horizontal(|ui| {
-                    ui.label("Radius (r):");
-                    ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Particle radius in nanometers (1-1000 nm). Typical: 10-100 nm",
-                            "Radio de la partícula en nanómetros (1-1000 nm). Típico: 10-100 nm"
-                        ));
-                    ui.add(egui::DragValue::new(&mut self.state.particle_radius)
-                        .speed(1.0)
-                        .range(1.0..=1000.0)
-                        .suffix(" nm"));
+                    ui.label("🔦");
+                    ui.strong(self.t("Wavelength List", "Lista de Longitudes de Onda"));
                 });
-
                 ui.add_space(5.0);
+                egui::Grid::new("wavelength_list_table")
+                    .num_columns(5)
+                    .spacing([15.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["λ (nm)", "Q_sca", "Q_abs", "Q_ext", "C_ext (nm²)"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+                        for result in &self.wavelength_list_results {
+                            ui.label(format!("{:.1}", result.wavelength));
+                            ui.label(format!("{:.5}", result.q_sca));
+                            ui.label(format!("{:.5}", result.q_abs));
+                            ui.label(format!("{:.5}", result.q_ext));
+                            ui.label(format!("{:.2}", result.c_ext));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
 
-                // Refractive index inputs
-                ui.horizontal(|ui| {
-                    ui.label("n (real):");
-                    ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Real part of refractive index. Controls light velocity in material",
-                            "Parte real del índice de refracción. Controla la velocidad de la luz en el material"
-                        ));
-                    ui.add(egui::DragValue::new(&mut self.state.n_particle_real)
-                        .speed(0.01)
-                        .range(-10.0..=10.0)
-                        .fixed_decimals(2));
-                });
+    fn calculate_spectrum(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+        
+        let (wl_min, wl_max) = self.state.spectrum_range();
+        self.add_log(&self.t(
+            &format!("📊 Calculating full spectrum ({}-{} nm)...", wl_min, wl_max),
+            &format!("📊 Calculando espectro completo ({}-{} nm)...", wl_min, wl_max)
+        ));
+        // A new run supersedes any still in flight; dropping it stops the worker
+        self.spectrum_run = None;
 
-                ui.horizontal(|ui| {
-                    ui.label("k (imag):");
-                    ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Imaginary part (extinction coefficient). Controls light absorption",
-                            "Parte imaginaria (coeficiente de extinción). Controla la absorción de luz"
-                        ));
-                    ui.add(egui::DragValue::new(&mut self.state.n_particle_imag)
-                        .speed(0.01)
-                        .range(0.0..=10.0)
-                        .fixed_decimals(2));
-                });
+        let solver = self.state.solver;
+        if let Err(e) = solver.validate() {
+            let error_msg = format!("Invalid solver settings: {}", e);
+            self.error_message = Some(error_msg.clone());
+            self.add_log(&format!("❌ {}", error_msg));
+            self.calculating = false;
+            return;
+        }
+        if self.spectrum_grid().is_none() { return; }
 
-                // Show complex index
-                ui.add_space(5.0);
-                ui.horizontal(|ui| {
-                    ui.colored_label(
-                        Color32::from_rgb(100, 180, 255),
-                        format!("n = {:.2} + {:.2}i", 
-                            self.state.n_particle_real, 
-                            self.state.n_particle_imag)
-                    );
+        let model_name = match controller::optical_model(&self.state, self.active_dispersion.as_ref()) {
+            Ok(model) => model.name().to_string(),
+            Err(e) => {
+                let error_msg = format!("Spectrum calculation error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                self.calculating = false;
+                return;
+            }
+        };
+        self.model_warnings = self.spectrum_model_warnings();
+        let stream = self.state.stream_spectrum;
+        if stream {
+            self.spectrum_results.clear();
+            self.spectrum_columns = SpectrumColumns::default();
+            self.array_cache = None;
+            self.plot_reset_counter += 1;
+        }
+        let state = self.state.clone();
+        let material = self.active_dispersion.clone();
+        let engine = self.engine.clone();
+        let run = BackgroundRun::spawn_with_partials(
+            move |progress, partial| {
+                controller::spectrum_with_progress(
+                    &state,
+                    material.as_ref(),
+                    &engine,
+                    &mut |done, total| progress.report(done, total),
+                    &|chunk| {
+                        if stream {
+                            partial(chunk.to_vec());
+                        }
+                    },
+                    progress.cancel_flag(),
+                )
+            },
+            self.repaint_notifier(),
+        );
+        self.spectrum_run = Some((run, model_name, RunInputs::from(&self.state)));
+    }
+
+    /// Callback for background work to request a repaint
+    fn repaint_notifier(&self) -> impl Fn() + Send + Sync + 'static {
+        let ctx = self.repaint_ctx.clone();
+        move || {
+            if let Some(ctx) = &ctx {
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    /// Take the background spectrum once it is done or has stopped, showing
+    /// the chunks streamed in meanwhile
+    fn poll_spectrum_run(&mut self) {
+        if let Some((run, _, _)) = &self.spectrum_run {
+            let chunks = run.take_partials();
+            if !chunks.is_empty() {
+                self.spectrum_results.extend(chunks.into_iter().flatten());
+                self.spectrum_columns = SpectrumColumns::from(self.spectrum_results.as_slice());
+                self.array_cache = None;
+            }
+        }
+        let Some(outcome) = self.spectrum_run.as_ref().and_then(|(run, _, _)| run.poll()) else {
+            return;
+        };
+        let Some((run, model, inputs)) = self.spectrum_run.take() else {
+            return;
+        };
+        match outcome {
+            Ok(results) => {
+                self.spectrum_results = results;
+                self.spectrum_columns = SpectrumColumns::from(self.spectrum_results.as_slice());
+                self.array_cache = None;
+                self.plot_reset_counter += 1;  // Forzar reset del plot
+                if run.is_cancelled() {
+                    self.log_spectrum_stopped();
+                } else {
+                    self.run_history.record_finished(&model, inputs, &self.spectrum_results, run.elapsed());
+                    self.spectrum_finished();
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Spectrum calculation error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+        self.calculating = false;
+    }
+
+    /// Log a completed spectrum and check it against the dataset range
+    fn spectrum_finished(&mut self) {
+        let msg = self.t(
+            &format!("✅ Spectrum calculated ({} points)", self.spectrum_results.len()),
+            &format!("✅ Espectro calculado ({} puntos)", self.spectrum_results.len())
+        );
+        self.add_log(&msg);
+
+        self.warnings.retain(|w| w.code != WarningCode::WavelengthOutOfDatasetRange);
+        let computed: Vec<f64> = self.spectrum_results.iter().map(|r| r.wavelength).collect();
+        if let Some(warning) = self.dataset_bounds.and_then(|b| b.check(&computed)) {
+            self.add_log(&format!("⚠️ {}", self.warning_text(&warning)));
+            self.warnings.push(warning);
+        }
+    }
+
+    fn log_spectrum_stopped(&mut self) {
+        let msg = self.t(
+            &format!("⏹ Spectrum stopped after {} points", self.spectrum_results.len()),
+            &format!("⏹ Espectro detenido tras {} puntos", self.spectrum_results.len())
+        );
+        self.add_log(&msg);
+    }
+
+    /// False, with the error surfaced, if any input is NaN or infinite
+    fn inputs_valid(&mut self) -> bool {
+        match self.state.check_inputs() {
+            Ok(()) => {
+                self.invalid_field = None;
+                true
+            }
+            Err((field, e)) => {
+                self.invalid_field = Some(field);
+                let (en, es) = field.name();
+                let error_msg = format!("Invalid input: {}: {}", self.t(en, es), e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                self.calculating = false;
+                false
+            }
+        }
+    }
+
+    /// Spectrum wavelengths at the solver grid step; on an unusable range
+    /// the error is surfaced and None returned
+    fn spectrum_grid(&mut self) -> Option<Vec<f64>> {
+        let (wl_min, wl_max) = self.state.spectrum_range();
+        match self.state.solver.wavelength_grid(wl_min, wl_max) {
+            Ok(grid) => Some(grid),
+            Err(e) => {
+                let error_msg = format!("Invalid wavelength range: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                self.calculating = false;
+                None
+            }
+        }
+    }
+
+    fn calculate_polydisperse_band(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+
+        let Some(wavelengths) = self.spectrum_grid() else { return; };
+        let model = MieModel::new(
+            self.state.particle_radius,
+            self.state.wavelength,
+            RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
+            self.state.n_medium,
+        );
+        let engine = self.engine.clone();
+        let config = self.polydisperse;
+        self.band_run = Some(BackgroundRun::spawn(
+            move |progress| {
+                monte_carlo_band_with_progress(
+                    &engine,
+                    &model,
+                    &wavelengths,
+                    &config,
+                    &|done, total| progress.report(done, total),
+                    progress.cancel_flag(),
+                )
+            },
+            self.repaint_notifier(),
+        ));
+    }
+
+    /// Take the background polydisperse band once it is done or has stopped
+    fn poll_band_run(&mut self) {
+        let Some(outcome) = self.band_run.as_ref().and_then(|run| run.poll()) else {
+            return;
+        };
+        self.band_run = None;
+        match outcome {
+            Ok(band) => {
+                self.band_results = band;
+                let msg = self.t(
+                    &format!("🎲 Polydisperse band calculated ({} samples)", self.polydisperse.samples),
+                    &format!("🎲 Banda polidispersa calculada ({} muestras)", self.polydisperse.samples)
+                );
+                self.add_log(&msg);
+            }
+            Err(CalculationError::Cancelled) => {
+                self.add_log(&self.t("⏹ Polydisperse band stopped", "⏹ Banda polidispersa detenida"));
+            }
+            Err(e) => {
+                let error_msg = format!("Polydisperse band error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+        self.calculating = false;
+    }
+
+    fn calculate_mixture(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+
+        let Some(wavelengths) = self.spectrum_grid() else { return; };
+        let population_a: Box<dyn OpticalModel> = Box::new(MieModel::new(
+            self.state.particle_radius,
+            self.state.wavelength,
+            RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
+            self.state.n_medium,
+        ));
+        let population_b: Box<dyn OpticalModel> = Box::new(MieModel::new(
+            self.mixer_radius_b,
+            self.state.wavelength,
+            RefractiveIndex::new(self.mixer_n_b.0, self.mixer_n_b.1),
+            self.state.n_medium,
+        ));
+        let components = [
+            (population_a, self.mixer_fraction_a),
+            (population_b, 1.0 - self.mixer_fraction_a),
+        ];
+
+        match mixture_spectrum(&components, &wavelengths) {
+            Ok(results) => {
+                self.mixture_results = results;
+                let msg = self.t(
+                    &format!("🧪 Mixture spectrum calculated ({:.0}% A)", self.mixer_fraction_a * 100.0),
+                    &format!("🧪 Espectro de mezcla calculado ({:.0}% A)", self.mixer_fraction_a * 100.0)
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Mixture calculation error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+
+        self.calculating = false;
+    }
+
+    fn calculate_universal_curve(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+
+        let m = RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag)
+            .to_complex()
+            / self.state.n_medium;
+        let msg = self.t(
+            &format!("📐 Calculating Q(x) for m = {:.3} + {:.3}i...", m.re, m.im),
+            &format!("📐 Calculando Q(x) para m = {:.3} + {:.3}i...", m.re, m.im)
+        );
+        self.add_log(&msg);
+
+        let steps = 500;
+        let x_values: Vec<f64> = (1..=steps)
+            .map(|i| self.universal_x_max * i as f64 / steps as f64)
+            .collect();
+
+        match efficiency_vs_x(m, &x_values) {
+            Ok(results) => {
+                self.universal_results = results;
+                self.plot_reset_counter += 1;
+                let msg = self.t(
+                    &format!("✅ Universal curve calculated ({} points)", self.universal_results.len()),
+                    &format!("✅ Curva universal calculada ({} puntos)", self.universal_results.len())
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Universal curve error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+
+        self.calculating = false;
+    }
+
+    fn draw_input_panel(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(5.0);
+        ui.heading(self.t("Input Parameters", "Parámetros de Entrada"))
+            .on_hover_text(self.t(
+                "Configure the nanoparticle and environment properties for optical calculations",
+                "Configura las propiedades de la nanopartícula y el entorno para cálculos ópticos"
+            ));
+        ui.add_space(15.0);
+
+        // Once a field is flagged, follow the user's edits until it is fixed
+        if self.invalid_field.is_some() {
+            self.invalid_field = self.state.check_inputs().err().map(|(field, _)| field);
+        }
+        let invalid = self.invalid_field;
+
+        self.draw_model_selector(ui);
+        ui.add_space(10.0);
+
+        // Material Presets Section
+        ui.group(|ui| {
+            ui.set_min_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.strong(self.t("Quick Presets", "Preajustes Rápidos"));
+            });
+            ui.add_space(5.0);
+
+            egui::Grid::new("preset_grid")
+                .num_columns(2)
+                .spacing([8.0, 8.0])
+                .show(ui, |ui| {
+                    for preset in MATERIAL_PRESETS {
+                        if ui.button(preset.name.to_string())
+                            .on_hover_text(preset.description)
+                            .clicked() 
+                        {
+                            self.apply_material_preset(preset);
+                        }
+                        if ui.available_width() < 50.0 {
+                            ui.end_row();
+                        }
+                    }
                 });
+
+            ui.horizontal(|ui| {
+                ui.label(self.t("Drude metals:", "Metales de Drude:"))
+                    .on_hover_text(self.t(
+                        "Free-electron ε(λ) = ε_∞ − ω_p²/(ω² + iγω), evaluated at the reference wavelength",
+                        "ε(λ) de electrones libres = ε_∞ − ω_p²/(ω² + iγω), evaluada en la longitud de onda de referencia"
+                    ));
+                for material in [DrudeModel::gold(), DrudeModel::silver()] {
+                    let hint = format!(
+                        "ε_∞ = {}, ħω_p = {} eV, ħγ = {} eV",
+                        material.eps_inf, material.plasma_ev, material.damping_ev
+                    );
+                    if ui.button(material.name.clone()).on_hover_text(hint).clicked() {
+                        self.apply_dispersive_material(material);
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(self.t("Lorentz-Drude:", "Lorentz-Drude:"))
+                    .on_hover_text(self.t(
+                        "Drude term plus interband Lorentz oscillators (Rakić 1998)",
+                        "Término de Drude más osciladores de Lorentz interbanda (Rakić 1998)"
+                    ));
+                let metals = [
+                    LorentzDrudeModel::gold(),
+                    LorentzDrudeModel::silver(),
+                    LorentzDrudeModel::aluminium(),
+                    LorentzDrudeModel::copper(),
+                ];
+                for material in metals {
+                    let hint = format!(
+                        "ħω_p = {} eV, {} oscillators",
+                        material.plasma_ev,
+                        material.oscillators.len()
+                    );
+                    if ui.button(material.name.clone()).on_hover_text(hint).clicked() {
+                        self.apply_dispersive_material(material);
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(self.t("Johnson & Christy:", "Johnson y Christy:"))
+                    .on_hover_text(self.t(
+                        "Measured n, k (Phys. Rev. B 6, 4370, 1972), cubic-spline interpolated",
+                        "n, k medidos (Phys. Rev. B 6, 4370, 1972), interpolados con spline cúbico"
+                    ));
+                for material in [
+                    johnson_christy::gold(Interpolation::CubicSpline),
+                    johnson_christy::silver(Interpolation::CubicSpline),
+                ] {
+                    let bounds = material.bounds();
+                    if ui.button(material.name().to_string()).on_hover_text(bounds.to_string()).clicked() {
+                        self.apply_dispersive_material(material);
+                        self.dataset_bounds = Some(bounds);
+                    }
+                }
             });
+        });
 
         ui.add_space(12.0);
 
-        // Environment Card
+        // Periodic Table Button
+        if ui.button("Select from Periodic Table").on_hover_text("Choose element optical properties").clicked() {
+            self.show_periodic_table = true;
+        }
+
+        ui.add_space(5.0);
+        self.draw_formula_dispersion(ui);
+        self.draw_table_dispersion(ui);
+
+        ui.add_space(12.0);
+
+        // Particle Properties Card
         egui::Frame::none()
             .fill(Color32::from_rgb(40, 43, 53))
             .rounding(Rounding::same(8.0))
             .inner_margin(egui::Margin::same(12.0))
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
-                    ui.strong("Environment");
+                    ui.strong("Particle Properties");
                     ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Surrounding medium and incident light properties",
-                            "Propiedades del medio circundante y luz incidente"
+                        .on_hover_text(self.t(
+                            "Physical size and optical properties of the nanoparticle",
+                            "Tamaño físico y propiedades ópticas de la nanopartícula"
                         ));
                 });
                 ui.add_space(8.0);
 
+                // Shift = fine, Ctrl = coarse drag for every input below
+                let modifiers = ui.input(|i| i.modifiers);
+                let drag_hint = self.t("Drag: Shift = fine, Ctrl = coarse", "Arrastrar: Shift = fino, Ctrl = grueso");
+
+                // Radius input
                 ui.horizontal(|ui| {
-                    ui.label("Wavelength (λ):");
+                    mark_invalid(ui, invalid == Some(InputField::ParticleRadius));
+                    ui.label("Radius (r):");
                     ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Wavelength of incident light (200-2000 nm). Visible: 400-700 nm",
-                            "Longitud de onda de la luz incidente (200-2000 nm). Visible: 400-700 nm"
+                        .on_hover_text(self.t(
+                            "Particle radius in nanometers (1-1000 nm). Typical: 10-100 nm",
+                            "Radio de la partícula en nanómetros (1-1000 nm). Típico: 10-100 nm"
                         ));
-                    ui.add(egui::DragValue::new(&mut self.state.wavelength)
-                        .speed(1.0)
-                        .range(200.0..=2000.0)
-                        .suffix(" nm"));
+                    ui.add(egui::DragValue::new(&mut self.state.particle_radius)
+                        .speed(drag_value_speed(1.0, &modifiers))
+                        .range(1.0..=1000.0)
+                        .suffix(" nm"))
+                        .on_hover_text(&drag_hint);
                 });
 
                 ui.add_space(5.0);
 
+                // Refractive index inputs
                 ui.horizontal(|ui| {
-                    ui.label("n (medium):");
+                    mark_invalid(ui, invalid == Some(InputField::ParticleIndexN));
+                    ui.label("n (real):");
                     ui.label("ℹ️")
-                        .on_hover_text(&self.t(
-                            "Refractive index of surrounding medium (air=1.0, water=1.33, glass≈1.5)",
-                            "Índice de refracción del medio circundante (aire=1.0, agua=1.33, vidrio≈1.5)"
+                        .on_hover_text(self.t(
+                            "Real part of refractive index. Controls light velocity in material",
+                            "Parte real del índice de refracción. Controla la velocidad de la luz en el material"
                         ));
-                    ui.add(egui::DragValue::new(&mut self.state.n_medium)
-                        .speed(0.01)
-                        .range(1.0..=3.0)
-                        .fixed_decimals(2));
+                    ui.add(egui::DragValue::new(&mut self.state.n_particle_real)
+                        .speed(drag_value_speed(0.01, &modifiers))
+                        .range(-10.0..=10.0)
+                        .min_decimals(2))
+                        .on_hover_text(&drag_hint);
                 });
 
-                // Show photon energy
+                ui.horizontal(|ui| {
+                    mark_invalid(ui, invalid == Some(InputField::ParticleIndexK));
+                    ui.label("k (imag):");
+                    ui.label("ℹ️")
+                        .on_hover_text(self.t(
+                            "Imaginary part (extinction coefficient). Controls light absorption",
+                            "Parte imaginaria (coeficiente de extinción). Controla la absorción de luz"
+                        ));
+                    ui.add(egui::DragValue::new(&mut self.state.n_particle_imag)
+                        .speed(drag_value_speed(0.01, &modifiers))
+                        .range(0.0..=10.0)
+                        .min_decimals(2))
+                        .on_hover_text(&drag_hint);
+                });
+
+                // Show complex index
                 ui.add_space(5.0);
                 ui.horizontal(|ui| {
-                    ui.label("⚡");
-                    let energy_ev = 1239.84193 / self.state.wavelength;
                     ui.colored_label(
-                        Color32::from_rgb(100, 255, 180),
-                        format!("E = {:.2} eV", energy_ev)
+                        Color32::from_rgb(100, 180, 255),
+                        format!("n = {:.2} + {:.2}i", 
+                            self.state.n_particle_real, 
+                            self.state.n_particle_imag)
                     );
                 });
+                self.draw_material_class(ui);
             });
 
-        ui.add_space(20.0);
+        ui.add_space(12.0);
 
-        // Action Buttons
-        ui.vertical_centered(|ui| {
+        // Environment Card
+        egui::Frame::none()
+            .fill(Color32::from_rgb(40, 43, 53))
+            .rounding(Rounding::same(8.0))
+            .inner_margin(egui::Margin::same(12.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("Environment");
+                    ui.label("ℹ️")
+                        .on_hover_text(self.t(
+                            "Surrounding medium and incident light properties",
+                            "Propiedades del medio circundante y luz incidente"
+                        ));
+                });
+                ui.add_space(8.0);
+
+                let modifiers = ui.input(|i| i.modifiers);
+                let drag_hint = self.t("Drag: Shift = fine, Ctrl = coarse", "Arrastrar: Shift = fino, Ctrl = grueso");
+
+                ui.horizontal(|ui| {
+                    mark_invalid(ui, invalid == Some(InputField::Wavelength));
+                    ui.label("Wavelength (λ):");
+                    ui.label("ℹ️")
+                        .on_hover_text(self.t(
+                            "Wavelength of incident light (200-2000 nm). Visible: 400-700 nm",
+                            "Longitud de onda de la luz incidente (200-2000 nm). Visible: 400-700 nm"
+                        ));
+                    ui.add(egui::DragValue::new(&mut self.state.wavelength)
+                        .speed(drag_value_speed(1.0, &modifiers))
+                        .range(200.0..=2000.0)
+                        .suffix(" nm"))
+                        .on_hover_text(&drag_hint);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Wavelength List:", "Lista de λ:"))
+                        .on_hover_text(self.t(
+                            "Comma-separated wavelengths in nm, e.g. laser lines. Invalid entries are skipped",
+                            "Longitudes de onda en nm separadas por comas, p. ej. líneas láser. Las entradas inválidas se omiten"
+                        ));
+                    ui.add(egui::TextEdit::singleline(&mut self.wavelength_list_input).desired_width(140.0));
+                    if ui.button("🔦").on_hover_text(self.t(
+                        "Calculate at exactly these wavelengths",
+                        "Calcular exactamente en estas longitudes de onda"
+                    )).clicked() {
+                        self.calculate_wavelength_list();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    mark_invalid(ui, matches!(invalid, Some(InputField::SpectrumStart | InputField::SpectrumEnd)));
+                    ui.label(self.t("Spectrum range:", "Rango del espectro:"))
+                        .on_hover_text(self.t(
+                            "Wavelengths covered by the full spectrum; the step is the solver grid step",
+                            "Longitudes de onda del espectro completo; el paso es el paso de malla del solver"
+                        ));
+                    let max = self.state.wl_max;
+                    ui.add(egui::DragValue::new(&mut self.state.wl_min)
+                        .speed(drag_value_speed(1.0, &modifiers))
+                        .range(100.0..=(max - 1.0).max(100.0))
+                        .suffix(" nm"));
+                    ui.label("–");
+                    let min = self.state.wl_min;
+                    ui.add(egui::DragValue::new(&mut self.state.wl_max)
+                        .speed(drag_value_speed(1.0, &modifiers))
+                        .range((min + 1.0)..=5000.0)
+                        .suffix(" nm"));
+                    ui.label(self.t("step", "paso"));
+                    ui.add(egui::DragValue::new(&mut self.state.solver.grid_step_nm)
+                        .speed(drag_value_speed(0.5, &modifiers))
+                        .range(0.1..=50.0)
+                        .suffix(" nm"));
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    mark_invalid(ui, invalid == Some(InputField::MediumIndex));
+                    ui.label("n (medium):");
+                    ui.label("ℹ️")
+                        .on_hover_text(self.t(
+                            "Refractive index of surrounding medium (air=1.0, water=1.33, glass≈1.5)",
+                            "Índice de refracción del medio circundante (aire=1.0, agua=1.33, vidrio≈1.5)"
+                        ));
+                    ui.add(egui::DragValue::new(&mut self.state.n_medium)
+                        .speed(drag_value_speed(0.01, &modifiers))
+                        .range(1.0..=3.0)
+                        .min_decimals(2))
+                        .on_hover_text(&drag_hint);
+                });
+
+                ui.horizontal(|ui| {
+                    let active = self.state.medium_preset();
+                    for preset in MediumPreset::ALL {
+                        let (en, es) = preset.label();
+                        let label = format!("{} ({})", self.t(en, es), preset.index());
+                        if ui.selectable_label(active == Some(preset), label).clicked() {
+                            self.state.select_medium_preset(preset);
+                        }
+                    }
+                    let custom = self.state.custom_n_medium;
+                    let custom_label = match custom {
+                        Some(n) => format!("{} ({})", self.t("Custom", "Personalizado"), n),
+                        None => self.t("Custom", "Personalizado"),
+                    };
+                    if ui.add_enabled(
+                        custom.is_some(),
+                        egui::SelectableLabel::new(active.is_none(), custom_label)
+                    )
+                    .on_hover_text(self.t(
+                        "Restore the last custom medium index",
+                        "Restaurar el último índice del medio personalizado"
+                    ))
+                    .clicked()
+                    {
+                        self.state.restore_custom_medium();
+                    }
+                });
+
+                self.draw_magnetic_medium(ui);
+
+                // Show photon energy
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label("⚡");
+                    let energy = match self.state.working_point().photon_energy() {
+                        Ok(energy_ev) => format!("E = {:.2} eV", energy_ev),
+                        Err(_) => "E = —".to_string(),
+                    };
+                    ui.colored_label(Color32::from_rgb(100, 255, 180), energy);
+                });
+            });
+
+        ui.add_space(20.0);
+
+        // Action Buttons
+        ui.vertical_centered(|ui| {
             let btn_size = egui::vec2(ui.available_width() - 20.0, 40.0);
             
             if ui.add_sized(btn_size, egui::Button::new("🔬 Calculate Single Point"))
@@ -446,11 +1559,29 @@ impl NanoCalcApp {
             ui.add_space(8.0);
 
             if ui.add_sized(btn_size, egui::Button::new("📊 Calculate Full Spectrum"))
-                .on_hover_text("Calculate properties across wavelength range (300-800 nm)")
+                .on_hover_text(format!(
+                    "Calculate properties across wavelength range ({}-{} nm)",
+                    self.state.wl_min, self.state.wl_max
+                ))
                 .clicked() 
             {
                 self.calculate_spectrum();
             }
+
+            let progress = self.spectrum_run.as_ref().map(|(run, _, _)| run.progress());
+            if let Some(progress) = progress {
+                ui.add_space(5.0);
+                let stop_label = self.t("⏹ Stop", "⏹ Detener");
+                let stop_hint = self.t("Keep the points computed so far", "Conservar los puntos ya calculados");
+                ui.horizontal(|ui| {
+                    ui.add(egui::ProgressBar::new(progress as f32).show_percentage().desired_width(btn_size.x - 70.0));
+                    if ui.button(stop_label).on_hover_text(stop_hint).clicked() {
+                        if let Some((run, _, _)) = &self.spectrum_run {
+                            run.cancel();
+                        }
+                    }
+                });
+            }
         });
 
         // Error/Warning Messages
@@ -471,10 +1602,60 @@ impl NanoCalcApp {
         ui.add_space(10.0);
     }
 
+    /// One banner per model and input warning, above the results
+    fn draw_warning_banners(&self, ui: &mut egui::Ui) {
+        for warning in self.model_warnings.iter().chain(&self.warnings) {
+            let color = match warning.severity {
+                WarningSeverity::Info => Color32::from_rgb(150, 200, 255),
+                WarningSeverity::Caution => Color32::from_rgb(255, 220, 120),
+                WarningSeverity::Severe => Color32::from_rgb(255, 150, 150),
+            };
+            egui::Frame::none()
+                .fill(Color32::from_rgb(90, 75, 25))
+                .rounding(Rounding::same(6.0))
+                .inner_margin(egui::Margin::same(10.0))
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("⚠️");
+                        ui.colored_label(color, self.warning_text(warning));
+                    });
+                });
+            ui.add_space(6.0);
+        }
+    }
+
     fn draw_results_panel(&mut self, ui: &mut egui::Ui) {
         ui.add_space(5.0);
-        ui.heading(&self.t("Results", "Resultados"));
-        ui.add_space(15.0);
+        ui.heading(self.t("Results", "Resultados"));
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.result.is_some(), egui::Button::new(self.t("📋 Copy to Clipboard", "📋 Copiar al Portapapeles")))
+                .on_hover_text(self.t("Copy this point as tab-separated values", "Copiar este punto como valores separados por tabuladores"))
+                .clicked()
+            {
+                if let Some(result) = self.result.clone() {
+                    self.copy_results(ui.ctx(), &[result]);
+                }
+            }
+            if ui
+                .add_enabled(!self.spectrum_results.is_empty(), egui::Button::new(self.t("📋 Copy Spectrum", "📋 Copiar Espectro")))
+                .on_hover_text(self.t("Copy the whole spectrum as tab-separated values", "Copiar todo el espectro como valores separados por tabuladores"))
+                .clicked()
+            {
+                let results = self.spectrum_results.clone();
+                self.copy_results(ui.ctx(), &results);
+            }
+        });
+        ui.add_space(10.0);
+
+        self.draw_warning_banners(ui);
+
+        if !self.wavelength_list_results.is_empty() {
+            self.draw_wavelength_list_table(ui);
+            ui.add_space(12.0);
+        }
 
         if let Some(ref result) = self.result {
             // Main info card
@@ -490,13 +1671,6 @@ impl NanoCalcApp {
                     ui.add_space(5.0);
                     ui.label(format!("Wavelength: {:.1} nm", result.wavelength));
                     ui.label(format!("Size parameter: x = {:.4}", result.metadata.size_parameter));
-                    
-                    if result.metadata.size_parameter > 1.0 {
-                        ui.colored_label(
-                            Color32::from_rgb(255, 200, 100),
-                            "⚠ Note: Rayleigh approximation may be inaccurate (x > 1)"
-                        );
-                    }
                 });
 
             ui.add_space(12.0);
@@ -511,7 +1685,7 @@ impl NanoCalcApp {
                         ui.label("📈");
                         ui.strong("Efficiency Factors (Dimensionless)");
                         ui.label("ℹ️")
-                            .on_hover_text(&self.t(
+                            .on_hover_text(self.t(
                                 "Efficiency = Cross-section / Geometric area. Values >1 indicate resonance effects",
                                 "Eficiencia = Sección transversal / Área geométrica. Valores >1 indican efectos de resonancia"
                             ));
@@ -526,7 +1700,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("Q_sca:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Scattering efficiency: ratio of scattered power to incident power on geometric area",
                                         "Eficiencia de dispersión: razón de potencia dispersada a potencia incidente en área geométrica"
                                     ));
@@ -534,7 +1708,7 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(100, 180, 255),
                                 format!("{:.5}", result.q_sca)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "Light scattered in all directions",
                                 "Luz dispersada en todas direcciones"
                             ));
@@ -543,7 +1717,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("Q_abs:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Absorption efficiency: ratio of absorbed power to incident power on geometric area",
                                         "Eficiencia de absorción: razón de potencia absorbida a potencia incidente en área geométrica"
                                     ));
@@ -551,7 +1725,7 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(255, 140, 100),
                                 format!("{:.5}", result.q_abs)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "Light absorbed and converted to heat",
                                 "Luz absorbida y convertida en calor"
                             ));
@@ -560,7 +1734,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("Q_ext:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Extinction efficiency: total light removed from beam (Q_ext = Q_sca + Q_abs)",
                                         "Eficiencia de extinción: luz total removida del haz (Q_ext = Q_sca + Q_abs)"
                                     ));
@@ -568,12 +1742,52 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(100, 255, 150),
                                 format!("{:.5}", result.q_ext)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "Total light removed = scattering + absorption",
                                 "Luz total removida = dispersión + absorción"
                             ));
                             ui.end_row();
+
+                            ui.horizontal(|ui| {
+                                ui.label("g:");
+                                ui.label("ℹ️")
+                                    .on_hover_text(self.t(
+                                        "Asymmetry parameter ⟨cos θ⟩: 0 for symmetric scattering, → 1 for strongly forward",
+                                        "Parámetro de asimetría ⟨cos θ⟩: 0 para dispersión simétrica, → 1 muy hacia adelante"
+                                    ));
+                            });
+                            ui.label(format!("{:.4}", result.g));
+                            ui.end_row();
+
+                            ui.horizontal(|ui| {
+                                ui.label("Q_pr:");
+                                ui.label("ℹ️")
+                                    .on_hover_text(self.t(
+                                        "Radiation-pressure efficiency Q_pr = Q_ext − g·Q_sca: momentum transferred to the particle",
+                                        "Eficiencia de presión de radiación Q_pr = Q_ext − g·Q_sca: momento transferido a la partícula"
+                                    ));
+                            });
+                            ui.label(format!("{:.5}", result.q_pr));
+                            ui.end_row();
+                        });
+
+                    if let Some(paradox) = extinction_paradox(result) {
+                        ui.add_space(8.0);
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.label("🌗");
+                            ui.strong(self.t("Extinction paradox", "Paradoja de extinción"));
+                            ui.label("ℹ️")
+                                .on_hover_text(self.t(
+                                    "For x ≫ 1, Q_ext → 2: the particle blocks its geometric area and diffracts as much again around its edge",
+                                    "Para x ≫ 1, Q_ext → 2: la partícula bloquea su área geométrica y difracta otro tanto alrededor de su borde"
+                                ));
                         });
+                        ui.add(
+                            egui::ProgressBar::new(paradox.ratio().clamp(0.0, 1.0) as f32)
+                                .text(format!("Q_ext = {:.3} ({:+.1}% of 2)", paradox.q_ext, 100.0 * paradox.deviation()))
+                        );
+                    }
                 });
 
             ui.add_space(12.0);
@@ -588,7 +1802,7 @@ impl NanoCalcApp {
                         ui.label("🎯");
                         ui.strong("Cross Sections (nm²)");
                         ui.label("ℹ️")
-                            .on_hover_text(&self.t(
+                            .on_hover_text(self.t(
                                 "Effective areas for light-particle interactions in nm²",
                                 "Áreas efectivas para interacciones luz-partícula en nm²"
                             ));
@@ -603,7 +1817,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("C_sca:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Scattering cross-section: effective area for scattering",
                                         "Sección transversal de dispersión: área efectiva para dispersión"
                                     ));
@@ -611,7 +1825,7 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(100, 180, 255),
                                 format!("{:.2}", result.c_sca)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "C_sca = Q_sca × πr². Measure of scattering strength",
                                 "C_sca = Q_sca × πr². Medida de la fuerza de dispersión"
                             ));
@@ -620,7 +1834,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("C_abs:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Absorption cross-section: effective area for absorption",
                                         "Sección transversal de absorción: área efectiva para absorción"
                                     ));
@@ -628,7 +1842,7 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(255, 140, 100),
                                 format!("{:.2}", result.c_abs)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "C_abs = Q_abs × πr². Measure of absorption strength",
                                 "C_abs = Q_abs × πr². Medida de la fuerza de absorción"
                             ));
@@ -637,7 +1851,7 @@ impl NanoCalcApp {
                             ui.horizontal(|ui| {
                                 ui.label("C_ext:");
                                 ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
+                                    .on_hover_text(self.t(
                                         "Extinction cross-section: total effective area (C_sca + C_abs)",
                                         "Sección transversal de extinción: área total efectiva (C_sca + C_abs)"
                                     ));
@@ -645,30 +1859,326 @@ impl NanoCalcApp {
                             ui.colored_label(
                                 Color32::from_rgb(100, 255, 150),
                                 format!("{:.2}", result.c_ext)
-                            ).on_hover_text(&self.t(
+                            ).on_hover_text(self.t(
                                 "C_ext = C_sca + C_abs = Q_ext × πr²",
                                 "C_ext = C_sca + C_abs = Q_ext × πr²"
                             ));
                             ui.end_row();
+                        });
+                });
 
-                            let geometric = std::f64::consts::PI * self.state.particle_radius.powi(2);
-                            ui.horizontal(|ui| {
-                                ui.label("Geometric (πr²):");
-                                ui.label("ℹ️")
-                                    .on_hover_text(&self.t(
-                                        "Physical cross-sectional area of the particle. Compare with C_sca, C_abs, C_ext",
-                                        "Área transversal física de la partícula. Comparar con C_sca, C_abs, C_ext"
-                                    ));
-                            });
+            ui.add_space(12.0);
+
+            // Reference Comparison Card
+            let geometric = controller::optical_model(&self.state, self.active_dispersion.as_ref())
+                .ok()
+                .map(|model| (model.shape().area_label(), [against_geometric_area(result, model.geometric_cross_section())]));
+            let pinned = self.reference_result.as_ref().map(|(reference, inputs)| (against_reference(result, reference), *inputs));
+            let mut pin = false;
+            let mut unpin = false;
+            egui::Frame::none()
+                .fill(Color32::from_rgb(50, 55, 65))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("⚖");
+                        ui.strong(self.t("Compared to Reference", "Comparado con Referencia"));
+                    });
+                    ui.add_space(8.0);
+                    if let Some((area_label, geometric)) = &geometric {
+                        let heading = self.t(
+                            &format!("Geometric area {}", area_label),
+                            &format!("Área geométrica {}", area_label)
+                        );
+                        self.draw_reference_comparison(ui, "geometric_comparison", &heading, geometric);
+                    }
+                    if let Some((comparisons, inputs)) = &pinned {
+                        ui.add_space(5.0);
+                        let heading = self.t(
+                            &format!("Pinned particle (r = {} nm, n = {} + {}i)", inputs.particle_radius, inputs.n_particle_real, inputs.n_particle_imag),
+                            &format!("Partícula fijada (r = {} nm, n = {} + {}i)", inputs.particle_radius, inputs.n_particle_real, inputs.n_particle_imag)
+                        );
+                        self.draw_reference_comparison(ui, "pinned_comparison", &heading, comparisons);
+                    }
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        pin = ui.button(self.t("📌 Pin as reference", "📌 Fijar como referencia"))
+                            .on_hover_text(self.t(
+                                "Compare later results against this particle",
+                                "Comparar resultados posteriores con esta partícula"
+                            ))
+                            .clicked();
+                        if pinned.is_some() {
+                            unpin = ui.button(self.t("Unpin", "Soltar")).clicked();
+                        }
+                    });
+                });
+            if pin {
+                self.reference_result = Some((result.clone(), RunInputs::from(&self.state)));
+            }
+            if unpin {
+                self.reference_result = None;
+            }
+
+            ui.add_space(12.0);
+
+            // Microscopy Card
+            let model = MieModel::new(
+                self.state.particle_radius,
+                self.state.wavelength,
+                RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
+                self.state.n_medium,
+            );
+            let collected = model.coefficients().ok().map(|coeffs| {
+                collected_fraction(|theta| coeffs.phase_function(theta), self.collection_na, self.state.n_medium)
+            });
+            let mut collection_na = self.collection_na;
+            let na_tooltip = self.t(
+                "Numerical aperture of the collection objective. Capped at n_medium (total internal reflection)",
+                "Apertura numérica del objetivo colector. Limitada a n_medio (reflexión total interna)"
+            );
+            egui::Frame::none()
+                .fill(Color32::from_rgb(45, 60, 55))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("🔭");
+                        ui.strong(self.t("Microscopy Collection", "Colección en Microscopía"));
+                        ui.label("ℹ️")
+                            .on_hover_text(self.t(
+                                "Fraction of scattered light collected by a forward objective of the given NA",
+                                "Fracción de luz dispersada colectada por un objetivo frontal con la NA dada"
+                            ));
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("NA:");
+                        ui.add(egui::DragValue::new(&mut collection_na)
+                            .speed(0.01)
+                            .range(0.0..=1.7)
+                            .fixed_decimals(2))
+                            .on_hover_text(na_tooltip);
+                    });
+                    if let Some(fraction) = collected {
+                        ui.colored_label(
+                            Color32::from_rgb(150, 230, 200),
+                            format!("{} NA={:.2}: {:.1}%",
+                                self.t("Fraction collected at", "Fracción colectada con"),
+                                collection_na,
+                                fraction * 100.0)
+                        );
+                    }
+                });
+            self.collection_na = collection_na;
+
+            ui.add_space(12.0);
+
+            // Photothermal Card
+            let mut irradiance = self.irradiance_mw_um2;
+            let mut kappa = self.kappa_medium;
+            let mut ambient = self.state.working_point().temperature;
+            let ambient_hint = self.t("Ambient temperature", "Temperatura ambiente");
+            let ambient_invalid = self.invalid_field == Some(InputField::Temperature);
+            let irradiance_w_m2 = irradiance * 1e9;
+            let delta_t = steady_state_temperature(result.c_abs, irradiance_w_m2, kappa, self.state.particle_radius);
+            let p_abs = absorbed_power(result.c_abs, irradiance_w_m2);
+            egui::Frame::none()
+                .fill(Color32::from_rgb(65, 50, 45))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("🔥");
+                        ui.strong(self.t("Photothermal Heating", "Calentamiento Fototérmico"));
+                        ui.label("ℹ️")
+                            .on_hover_text(self.t(
+                                "Steady-state temperature rise ΔT = C_abs·I / (4π κ_medium r)",
+                                "Aumento de temperatura estacionario ΔT = C_abs·I / (4π κ_medio r)"
+                            ));
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("I:");
+                        ui.add(egui::DragValue::new(&mut irradiance)
+                            .speed(0.01)
+                            .range(0.0..=1000.0)
+                            .suffix(" mW/µm²"));
+                        ui.label("κ:");
+                        ui.add(egui::DragValue::new(&mut kappa)
+                            .speed(0.01)
+                            .range(0.01..=500.0)
+                            .suffix(" W/(m·K)"));
+                    });
+                    ui.horizontal(|ui| {
+                        mark_invalid(ui, ambient_invalid);
+                        ui.label("T₀:").on_hover_text(ambient_hint);
+                        ui.add(egui::DragValue::new(&mut ambient)
+                            .speed(0.5)
+                            .range(0.0..=2000.0)
+                            .suffix(" K"));
+                    });
+                    match delta_t {
+                        Ok(dt) => {
+                            ui.label(format!("P_abs = {:.3e} W", p_abs));
+                            ui.colored_label(
+                                Color32::from_rgb(255, 180, 130),
+                                egui::RichText::new(format!("ΔT = {:.2} K", dt)).strong()
+                            );
+                            ui.label(format!("T = {:.2} K", ambient + dt));
+                        }
+                        Err(e) => {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), e.to_string());
+                        }
+                    }
+                });
+            self.irradiance_mw_um2 = irradiance;
+            self.kappa_medium = kappa;
+            self.state.temperature = ambient;
+
+            ui.add_space(12.0);
+
+            // Size-normalized absorption card
+            let mut density = self.particle_density;
+            let per_volume = result.c_abs_per_volume(self.state.particle_radius);
+            let per_mass = result.c_abs_per_mass(self.state.particle_radius, density);
+            egui::Frame::none()
+                .fill(Color32::from_rgb(55, 50, 70))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("📐");
+                        ui.strong(self.t("Size-normalized Absorption", "Absorción Normalizada por Tamaño"));
+                        ui.label("ℹ️")
+                            .on_hover_text(self.t(
+                                "C_abs per particle volume and per mass, comparable across sizes",
+                                "C_abs por volumen y por masa de partícula, comparable entre tamaños"
+                            ));
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("ρ:");
+                        ui.add(egui::DragValue::new(&mut density)
+                            .speed(0.1)
+                            .range(0.01..=30.0)
+                            .suffix(" g/cm³"));
+                    });
+                    match (per_volume, per_mass) {
+                        (Ok(v), Ok(m)) => {
+                            ui.label(format!("C_abs/V = {:.4e} nm⁻¹", v));
                             ui.colored_label(
-                                Color32::LIGHT_GRAY,
-                                format!("{:.2}", geometric)
-                            ).on_hover_text(&self.t(
-                                "Reference area. If C > πr², particle interacts more than its physical size",
-                                "Área de referencia. Si C > πr², la partícula interactúa más que su tamaño físico"
+                                Color32::from_rgb(200, 170, 255),
+                                egui::RichText::new(format!("C_abs/m = {:.4} m²/g", m)).strong()
+                            );
+                        }
+                        (Err(e), _) | (_, Err(e)) => {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), e.to_string());
+                        }
+                    }
+                });
+            self.particle_density = density;
+
+            ui.add_space(12.0);
+
+            // Nanowire thermal conductivity card
+            let mut diameter = self.nanowire_diameter;
+            let mut callaway = self.nanowire_callaway;
+            let wire_temperature = self.state.working_point().temperature;
+            let wire = if callaway {
+                CallawayModel::silicon(diameter, wire_temperature)
+                    .with_context(self.state.constants)
+                    .calculate()
+            } else {
+                NanowireThermalModel::silicon(diameter, wire_temperature).calculate()
+            };
+            let callaway_hint = self.t(
+                "Integrate boundary, impurity and Umklapp scattering over the Debye phonon spectrum",
+                "Integrar la dispersión en la frontera, por impurezas y Umklapp sobre el espectro de fonones de Debye"
+            );
+            let wire_heading = self.t("Bulk Si at the same temperature", "Si masivo a la misma temperatura");
+            egui::Frame::none()
+                .fill(Color32::from_rgb(45, 55, 65))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("🧵");
+                        ui.strong(self.t("Si Nanowire Conductivity", "Conductividad de Nanohilo de Si"));
+                        ui.label("ℹ️")
+                            .on_hover_text(self.t(
+                                "Boundary-scattering suppression at the ambient temperature; gray model κ_eff = κ_bulk / (1 + λ_mfp/d) unless Callaway is on",
+                                "Supresión por dispersión en la frontera a la temperatura ambiente; modelo gris κ_eff = κ_masivo / (1 + λ_mfp/d) salvo con Callaway"
                             ));
-                            ui.end_row();
-                        });
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("d:");
+                        ui.add(egui::DragValue::new(&mut diameter)
+                            .speed(1.0)
+                            .range(1.0..=100_000.0)
+                            .suffix(" nm"));
+                        ui.checkbox(&mut callaway, "Callaway").on_hover_text(callaway_hint);
+                    });
+                    match &wire {
+                        Ok(wire) => {
+                            self.draw_reference_comparison(ui, "nanowire_comparison", &wire_heading, &[thermal_against_bulk(wire)]);
+                            if let Some(mfp) = wire.mfp {
+                                ui.label(format!("λ_mfp = {:.1} nm", mfp));
+                            }
+                            if let Some(mechanism) = &wire.metadata.dominant_mechanism {
+                                ui.colored_label(Color32::GRAY, mechanism);
+                            }
+                        }
+                        Err(e) => {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), e.to_string());
+                        }
+                    }
+                });
+            self.nanowire_diameter = diameter;
+            self.nanowire_callaway = callaway;
+
+            ui.add_space(12.0);
+
+            // Quantum-dot bandgap card
+            let point = self.state.working_point();
+            let dot = BrusModel::cdse(point.diameter()).with_context(point.constants).calculate();
+            let dot_heading = self.t("Bulk CdSe gap", "Gap de CdSe masivo");
+            egui::Frame::none()
+                .fill(Color32::from_rgb(60, 45, 65))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(12.0))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("🔮");
+                        ui.strong(self.t("CdSe Quantum-Dot Bandgap", "Gap de Punto Cuántico de CdSe"));
+                        ui.label("ℹ️")
+                            .on_hover_text(self.t(
+                                "Brus equation at the particle diameter: E_g = E_bulk + ħ²π²/(2R²)(1/m_e* + 1/m_h*) − 1.8e²/(4πε₀ε_r R)",
+                                "Ecuación de Brus al diámetro de la partícula: E_g = E_masivo + ħ²π²/(2R²)(1/m_e* + 1/m_h*) − 1.8e²/(4πε₀ε_r R)"
+                            ));
+                    });
+                    ui.add_space(8.0);
+                    match &dot {
+                        Ok(dot) => {
+                            self.draw_reference_comparison(ui, "bandgap_comparison", &dot_heading, &[bandgap_against_bulk(dot)]);
+                            if let Some(bohr) = dot.bohr_radius {
+                                ui.label(format!("a_B = {:.2} nm, {:?}", bohr, dot.regime));
+                            }
+                            if let Ok(gap) = point.compare_gap(dot) {
+                                let (color, text) = if gap.above_gap() {
+                                    (Color32::from_rgb(150, 255, 180), self.t("Photon above the gap", "Fotón por encima del gap"))
+                                } else {
+                                    (Color32::GRAY, self.t("Photon below the gap", "Fotón por debajo del gap"))
+                                };
+                                ui.colored_label(color, format!("{} ({:+.3} eV)", text, gap.excess()));
+                            }
+                        }
+                        Err(e) => {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), e.to_string());
+                        }
+                    }
                 });
 
             ui.add_space(12.0);
@@ -726,17 +2236,123 @@ impl NanoCalcApp {
         }
     }
 
-    fn draw_plot_panel(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(5.0);
-        ui.horizontal(|ui| {
-            ui.heading("📈 Optical Spectrum");
-            ui.label("ℹ️")
-                .on_hover_text(&self.t(
+    /// Put `results` on the clipboard as TSV with every optical column
+    fn copy_results(&mut self, ctx: &Context, results: &[OpticalResult]) {
+        match optical_tsv(results, &OpticalColumn::ALL) {
+            Ok(tsv) => {
+                ctx.copy_text(tsv);
+                let msg = self.t(
+                    &format!("📋 Copied {} rows to the clipboard", results.len()),
+                    &format!("📋 Copiadas {} filas al portapapeles", results.len())
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => self.add_log(&format!("❌ {}", e)),
+        }
+    }
+
+    /// Validity spans of the selected model over the spectrum's wavelengths,
+    /// recomputed only when the model or the grid changes
+    fn spectrum_validity(&mut self) -> Vec<ValiditySpan> {
+        let Ok(model) = controller::optical_model(&self.state, self.active_dispersion.as_ref()) else {
+            return Vec::new();
+        };
+        let wavelengths: Vec<f64> = self.spectrum_results.iter().map(|r| r.wavelength).collect();
+        let key = spectrum_key(model.as_ref(), &wavelengths);
+        match &self.validity_cache {
+            Some((cached, spans)) if *cached == key => spans.clone(),
+            _ => {
+                let spans = validity_spans(&wavelengths, &model.validity_region(&wavelengths));
+                self.validity_cache = Some((key, spans.clone()));
+                spans
+            }
+        }
+    }
+
+    /// (λ, Q_ext) of the spectrum under the array structure factor,
+    /// recomputed only when the array inputs or the spectrum change
+    fn array_series(&mut self) -> Vec<[f64; 2]> {
+        let key: ArrayInputs = (self.array_lattice, self.array_spacing, self.array_angle_deg, self.state.n_medium);
+        match &self.array_cache {
+            Some((cached, points)) if *cached == key => points.clone(),
+            _ => {
+                let points: Vec<[f64; 2]> = apply_structure_factor(
+                    &self.spectrum_results,
+                    self.array_lattice,
+                    self.array_spacing,
+                    self.array_angle_deg.to_radians(),
+                    self.state.n_medium,
+                )
+                .iter()
+                .map(|r| [r.wavelength, r.q_ext])
+                .collect();
+                self.array_cache = Some((key, points.clone()));
+                points
+            }
+        }
+    }
+
+    fn draw_plot_panel(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.heading("📈 Optical Spectrum");
+            ui.label("ℹ️")
+                .on_hover_text(self.t(
                     "Optical properties across wavelength range. Shows how particle interacts with different colors of light",
                     "Propiedades ópticas a lo largo del rango de longitud de onda. Muestra cómo la partícula interactúa con diferentes colores de luz"
                 ));
         });
-        ui.add_space(15.0);
+        ui.add_space(5.0);
+        let spectrum_label = self.t("Spectrum Q(λ)", "Espectro Q(λ)");
+        let universal_label = self.t("Universal Curve Q(x)", "Curva Universal Q(x)");
+        let energy_label = self.t("C_ext(E)", "C_ext(E)");
+        let energy_tooltip = self.t(
+            "Extinction cross section vs photon energy, with window integral",
+            "Sección transversal de extinción vs energía del fotón, con integral en ventana"
+        );
+        let multipole_label = self.t("Multipoles", "Multipolos");
+        let multipole_tooltip = self.t(
+            "Per-order electric (aₙ) and magnetic (bₙ) scattering contributions",
+            "Contribuciones de dispersión eléctricas (aₙ) y magnéticas (bₙ) por orden"
+        );
+        let sensitivity_label = self.t("Sensitivity", "Sensibilidad");
+        let sensitivity_tooltip = self.t(
+            "Which input moves the resonance wavelength the most",
+            "Qué entrada desplaza más la longitud de onda de resonancia"
+        );
+        let universal_tooltip = self.t(
+            "Efficiencies vs size parameter x = 2πr/λ at fixed relative index m",
+            "Eficiencias vs parámetro de tamaño x = 2πr/λ con índice relativo m fijo"
+        );
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.plot_view.mode, PlotMode::Spectrum, spectrum_label);
+            ui.selectable_value(&mut self.plot_view.mode, PlotMode::UniversalCurve, universal_label)
+                .on_hover_text(universal_tooltip);
+            ui.selectable_value(&mut self.plot_view.mode, PlotMode::EnergyRate, energy_label)
+                .on_hover_text(energy_tooltip);
+            ui.selectable_value(&mut self.plot_view.mode, PlotMode::Multipoles, multipole_label)
+                .on_hover_text(multipole_tooltip);
+            ui.selectable_value(&mut self.plot_view.mode, PlotMode::Sensitivity, sensitivity_label)
+                .on_hover_text(sensitivity_tooltip);
+        });
+        ui.add_space(10.0);
+
+        if self.plot_view.mode == PlotMode::UniversalCurve {
+            self.draw_universal_curve(ui);
+            return;
+        }
+        if self.plot_view.mode == PlotMode::Multipoles {
+            self.draw_multipoles(ui);
+            return;
+        }
+        if self.plot_view.mode == PlotMode::Sensitivity {
+            self.draw_sensitivity(ui);
+            return;
+        }
+        if self.plot_view.mode == PlotMode::EnergyRate && !self.spectrum_results.is_empty() {
+            self.draw_energy_rate(ui);
+            return;
+        }
 
         if self.spectrum_results.is_empty() {
             // Empty state for plot
@@ -768,6 +2384,7 @@ impl NanoCalcApp {
         let max_q_abs = self.spectrum_results.iter()
             .map(|r| r.q_abs)
             .fold(f64::NEG_INFINITY, f64::max);
+        let moments = spectral_moments(&self.spectrum_results);
         
         egui::Frame::none()
             .fill(Color32::from_rgb(45, 48, 58))
@@ -783,33 +2400,123 @@ impl NanoCalcApp {
                     ui.label(format!("Max Q_abs: {:.4}", max_q_abs));
                     ui.separator();
                     ui.label(format!("{} points", self.spectrum_results.len()));
+                    if let Some(m) = moments {
+                        ui.separator();
+                        ui.label(format!("{}: {:.1} ± {:.1} nm", self.t("Centroid", "Centroide"), m.centroid, m.width))
+                            .on_hover_text(format!(
+                                "{}\n∫Q_ext dλ = {:.2} nm",
+                                self.t(
+                                    "First moment of Q_ext(λ) ± its standard deviation",
+                                    "Primer momento de Q_ext(λ) ± su desviación estándar"
+                                ),
+                                m.area
+                            ));
+                    }
+                    ui.separator();
+                    ui.label(format!("{}: {}", self.t("Profile", "Perfil"), self.profile_label()));
                 });
             });
 
         ui.add_space(10.0);
 
-        // Prepare plot data
-        let q_sca_points: PlotPoints = self
-            .spectrum_results
-            .iter()
-            .map(|r| [r.wavelength, r.q_sca])
-            .collect();
+        // Prepare plot data, in log₁₀ Q when the log scale is on
+        let log_y = self.plot_view.log_y;
+        let scaled = |points: Vec<[f64; 2]>| -> Vec<[f64; 2]> {
+            if log_y { log_points(points) } else { points }
+        };
+        let array_series = (self.show_array && !self.spectrum_results.is_empty()).then(|| self.array_series());
+        let columns = &self.spectrum_columns;
+        let q_sca_points = PlotPoints::from(scaled(columns.series(&columns.q_sca)));
+        let q_abs_points = PlotPoints::from(scaled(columns.series(&columns.q_abs)));
+        let q_ext_points = PlotPoints::from(scaled(columns.series(&columns.q_ext)));
 
-        let q_abs_points: PlotPoints = self
-            .spectrum_results
-            .iter()
-            .map(|r| [r.wavelength, r.q_abs])
-            .collect();
+        let baseline_points: Option<PlotPoints> = (!self.baseline_results.is_empty()).then(|| {
+            PlotPoints::from(scaled(self.baseline_results.iter().map(|r| [r.wavelength, r.q_ext]).collect()))
+        });
 
-        let q_ext_points: PlotPoints = self
-            .spectrum_results
-            .iter()
-            .map(|r| [r.wavelength, r.q_ext])
-            .collect();
+        // Polydisperse ±σ band; smoothing only ever touches the edges
+        let band_window = self.smooth_band.then_some(self.band_smoothing_window);
+        let band_quads: Vec<Vec<[f64; 2]>> = {
+            let edges = band_edges(&self.band_results, band_window);
+            self.band_results
+                .windows(2)
+                .zip(edges.windows(2))
+                .map(|(b, e)| vec![
+                    [b[0].wavelength, e[0].0],
+                    [b[1].wavelength, e[1].0],
+                    [b[1].wavelength, e[1].1],
+                    [b[0].wavelength, e[0].1],
+                ])
+                // A quad with a corner at or below zero has no log-space shape
+                .map(&scaled)
+                .filter(|quad| quad.len() == 4)
+                .collect()
+        };
+        let imported_points: Option<PlotPoints> = self.imported_spectrum.as_ref().map(|file| {
+            PlotPoints::from(scaled(file.spectrum_data.iter().map(|p| [p.wavelength_nm, p.q_ext]).collect()))
+        });
+        let broadened_points: Option<PlotPoints> = (self.plot_view.show_broadened && !columns.is_empty()).then(|| {
+            let broadened = gaussian_broaden(&columns.wavelength, &columns.q_ext, self.plot_view.instrument_fwhm);
+            PlotPoints::from(scaled(columns.series(&broadened)))
+        });
+        let array_points: Option<PlotPoints> = array_series.map(|points| PlotPoints::from(scaled(points)));
+        let mixture_points: Option<PlotPoints> = (!self.mixture_results.is_empty()).then(|| {
+            PlotPoints::from(scaled(self.mixture_results.iter().map(|r| [r.wavelength, r.q_ext]).collect()))
+        });
+        let band_mean_points: Option<PlotPoints> = (!self.band_results.is_empty()).then(|| {
+            PlotPoints::from(scaled(self.band_results.iter().map(|b| [b.wavelength, b.mean]).collect()))
+        });
+        let band_name = if self.smooth_band {
+            self.t("Q_ext ±σ (smoothed)", "Q_ext ±σ (suavizada)")
+        } else {
+            self.t("Q_ext ±σ", "Q_ext ±σ")
+        };
+
+        // Shade wavelengths where the model's approximation is marginal or invalid
+        let spans = self.spectrum_validity();
+        let (marginal_en, marginal_es) = Overlay::MarginalRegion.label();
+        let marginal_name = self.t(marginal_en, marginal_es);
+        let (invalid_en, invalid_es) = Overlay::InvalidRegion.label();
+        let invalid_name = self.t(invalid_en, invalid_es);
+
+        // Single-point crosshair and pickable wavelength range
+        let crosshair = self
+            .result
+            .as_ref()
+            .and_then(|r| scaled(vec![[r.wavelength, r.q_ext]]).first().map(|&[x, y]| (x, y)));
+        let (range_min, range_max) = self.state.spectrum_range();
+        let wl_min = self.spectrum_results.first().map(|r| r.wavelength).unwrap_or(range_min);
+        let wl_max = self.spectrum_results.last().map(|r| r.wavelength).unwrap_or(range_max);
+        // Part of the visible band inside the chosen range, if any
+        let visible_band = Some((VISIBLE_BAND.0.max(range_min), VISIBLE_BAND.1.min(range_max)))
+            .filter(|(start, end)| start < end);
+        let pick_mode = self.pick_wavelength_mode;
+        let zoom_request = self.zoom_request.take();
+        let overlays = self.plot_view.overlays.clone();
+
+        // Peaks, dips and shoulders of Q_ext, one marker series per kind
+        let feature_markers: Vec<(Vec<[f64; 2]>, MarkerShape, Color32, String)> = if self.plot_view.show_features {
+            let xs: Vec<f64> = self.spectrum_results.iter().map(|r| r.wavelength).collect();
+            let ys: Vec<f64> = self.spectrum_results.iter().map(|r| r.q_ext).collect();
+            let features = detect_with(&xs, &ys, &self.plot_view.feature_config);
+            [
+                (FeatureKind::Peak, MarkerShape::Up, Color32::from_rgb(255, 230, 90), self.t("Peaks", "Picos")),
+                (FeatureKind::Dip, MarkerShape::Down, Color32::from_rgb(120, 200, 255), self.t("Dips", "Valles")),
+                (FeatureKind::Shoulder, MarkerShape::Diamond, Color32::from_rgb(255, 140, 220), self.t("Shoulders", "Hombros")),
+            ]
+            .into_iter()
+            .map(|(kind, shape, color, name)| {
+                let points = scaled(features.iter().filter(|f| f.kind == kind).map(|f| [f.x, f.y]).collect());
+                (points, shape, color, name)
+            })
+            .collect()
+        } else {
+            Vec::new()
+        };
 
         // Main plot
         // Contenedor con padding personalizado para el plot
-        egui::Frame::none()
+        let picked_wavelength = egui::Frame::none()
             .inner_margin(egui::Margin {
                 left: 20.0,   // Más espacio a la izquierda para el eje Y
                 right: 10.0,
@@ -817,92 +2524,248 @@ impl NanoCalcApp {
                 bottom: 10.0,
             })
             .show(ui, |ui| {
-                // Calcular los límites Y basados en los datos actuales
-                let mut y_min = f64::INFINITY;
-                let mut y_max = f64::NEG_INFINITY;
-                
-                for result in &self.spectrum_results {
-                    let vals = [result.q_sca, result.q_abs, result.q_ext];
-                    for &val in &vals {
-                        if val.is_finite() {
-                            y_min = y_min.min(val);
-                            y_max = y_max.max(val);
-                        }
-                    }
-                }
-                
-                // Agregar margen del 10% arriba y abajo
-                if y_min.is_finite() && y_max.is_finite() && y_max > y_min {
-                    let range = y_max - y_min;
-                    let margin = range * 0.1;
-                    y_min = (y_min - margin).max(0.0);
-                    y_max = y_max + margin;
+                // Calcular los límites Y basados en los datos actuales (con margen del 10%)
+                let bounds = if log_y { auto_log_y_bounds } else { auto_y_bounds };
+                let (y_min, y_max) = bounds(
+                    self.spectrum_results.iter().flat_map(|r| [r.q_sca, r.q_abs, r.q_ext]).collect::<Vec<_>>()
+                );
+                let y_label = if log_y {
+                    self.t("log₁₀ Efficiency Factor Q", "log₁₀ Factor de Eficiencia Q")
                 } else {
-                    // Valores por defecto si no hay datos válidos
-                    y_min = 0.0;
-                    y_max = 1.0;
-                }
+                    self.t("Efficiency Factor Q", "Factor de Eficiencia Q")
+                };
                 
                 // Main plot con ajuste automático robusto y límites
                 let plot_id = format!("spectrum_plot_{}", self.plot_reset_counter);
                 Plot::new(&plot_id)
                     .legend(Legend::default().position(Corner::RightTop))
-                    .x_axis_label(&self.t("Wavelength (nm)", "Longitud de onda (nm)"))
-                    .y_axis_label(&self.t("Efficiency Factor Q", "Factor de Eficiencia Q"))
-                    .label_formatter(|name, value| {
-                        format!("{}\nλ = {:.1} nm\nQ = {:.4}", name, value.x, value.y)
+                    .x_axis_label(self.t("Wavelength (nm)", "Longitud de onda (nm)"))
+                    .y_axis_label(y_label)
+                    .label_formatter(move |name, value| {
+                        let q = if log_y { 10f64.powf(value.y) } else { value.y };
+                        format!("{}\nλ = {:.1} nm\nQ = {:.4}", name, value.x, q)
                     })
                     .y_axis_min_width(30.0)
                     .height(450.0)  // Altura fija para asegurar visibilidad
-                    .include_x(300.0)  // Asegurar rango X completo
-                    .include_x(800.0)
+                    .include_x(range_min)  // Asegurar rango X completo
+                    .include_x(range_max)
                     .include_y(y_min)  // Límites Y calculados
                     .include_y(y_max)
                     .set_margin_fraction([0.05, 0.1].into())  // Márgenes para no permitir zoom out excesivo
                     .allow_boxed_zoom(true)
                     .allow_drag(true)
                     .allow_zoom(true)
+                    .link_axis(SPECTRUM_AXIS_GROUP, true, false)
                     .show(ui, |plot_ui| {
+                        if let Some((x0, x1)) = zoom_request {
+                            let (y0, y1) = bounds(
+                                self.spectrum_results
+                                    .iter()
+                                    .filter(|r| (x0..=x1).contains(&r.wavelength))
+                                    .flat_map(|r| [r.q_sca, r.q_abs, r.q_ext])
+                                    .collect::<Vec<_>>()
+                            );
+                            plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max([x0, y0], [x1, y1]));
+                        }
+
+                        if let Some((start, end)) = visible_band.filter(|_| overlays.contains(&Overlay::VisibleBand)) {
+                            plot_ui.polygon(
+                                egui_plot::Polygon::new(PlotPoints::from(vec![
+                                    [start, y_min],
+                                    [end, y_min],
+                                    [end, y_max],
+                                    [start, y_max],
+                                ]))
+                                .fill_color(overlay_fill(Overlay::VisibleBand))
+                                .stroke(egui::Stroke::NONE),
+                            );
+                        }
+
+                        for span in &spans {
+                            let (overlay, name) = match span.flag {
+                                ValidityFlag::Marginal => (Overlay::MarginalRegion, &marginal_name),
+                                _ => (Overlay::InvalidRegion, &invalid_name),
+                            };
+                            if !overlays.contains(&overlay) {
+                                continue;
+                            }
+                            let fill = overlay_fill(overlay);
+                            plot_ui.polygon(
+                                egui_plot::Polygon::new(PlotPoints::from(vec![
+                                    [span.start, y_min],
+                                    [span.end, y_min],
+                                    [span.end, y_max],
+                                    [span.start, y_max],
+                                ]))
+                                .fill_color(fill)
+                                .stroke(egui::Stroke::NONE)
+                                .name(name),
+                            );
+                        }
+
                         plot_ui.line(
                             Line::new(q_sca_points)
                                 .color(Color32::from_rgb(70, 160, 255))
-                                .width(2.5)
-                                .name(&self.t("Q_sca (Scattering)", "Q_sca (Dispersión)")),
+                                .width(self.appearance.line_width(2.5))
+                                .name(self.t("Q_sca (Scattering)", "Q_sca (Dispersión)")),
                         );
                         plot_ui.line(
                             Line::new(q_abs_points)
                                 .color(Color32::from_rgb(255, 120, 70))
-                                .width(2.5)
-                                .name(&self.t("Q_abs (Absorption)", "Q_abs (Absorción)")),
+                                .width(self.appearance.line_width(2.5))
+                                .name(self.t("Q_abs (Absorption)", "Q_abs (Absorción)")),
                         );
                         plot_ui.line(
                             Line::new(q_ext_points)
                                 .color(Color32::from_rgb(100, 220, 140))
-                                .width(2.5)
-                                .name(&self.t("Q_ext (Extinction)", "Q_ext (Extinción)")),
+                                .width(self.appearance.line_width(2.5))
+                                .name(self.t("Q_ext (Extinction)", "Q_ext (Extinción)")),
                         );
+
+                        // Each segment is its own quad: egui_plot only fills convex polygons reliably
+                        for quad in band_quads {
+                            plot_ui.polygon(
+                                egui_plot::Polygon::new(PlotPoints::from(quad))
+                                    .fill_color(Color32::from_rgba_unmultiplied(100, 220, 140, 40))
+                                    .stroke(egui::Stroke::NONE)
+                                    .name(&band_name),
+                            );
+                        }
+                        if let Some(points) = band_mean_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(60, 170, 100),
+                                self.t("Ensemble mean Q_ext", "Q_ext medio del conjunto"),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        if let Some(points) = imported_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(80, 220, 220),
+                                self.t("Imported Q_ext", "Q_ext importado"),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        if let Some(points) = broadened_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(255, 160, 90),
+                                format!("Q_ext ⊛ IRF ({:.1} nm)", self.plot_view.instrument_fwhm),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        if let Some(points) = array_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(180, 220, 90),
+                                self.t("Array Q_ext × S(λ)", "Q_ext de arreglo × S(λ)"),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        if let Some(points) = mixture_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(200, 120, 255),
+                                self.t("Mixture Q_ext", "Q_ext de mezcla"),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        if let Some(points) = baseline_points {
+                            plot_ui.line(overlay_line(
+                                points,
+                                Color32::from_rgb(180, 180, 180),
+                                self.t("Baseline Q_ext", "Q_ext de referencia"),
+                                self.appearance.line_width(1.5),
+                            ));
+                        }
+
+                        for (points, shape, color, name) in feature_markers {
+                            if !points.is_empty() {
+                                plot_ui.points(
+                                    Points::new(points)
+                                        .shape(shape)
+                                        .filled(true)
+                                        .radius(6.0 * self.appearance.scale())
+                                        .color(color)
+                                        .name(name),
+                                );
+                            }
+                        }
                         
                         // Mark visible spectrum region
-                        plot_ui.vline(egui_plot::VLine::new(380.0)
-                            .color(Color32::from_rgba_premultiplied(150, 150, 255, 50))
-                            .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
-                        plot_ui.vline(egui_plot::VLine::new(750.0)
-                            .color(Color32::from_rgba_premultiplied(255, 150, 150, 50))
-                            .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
-                    });
-            });
+                        if let Some((start, end)) = visible_band.filter(|_| overlays.contains(&Overlay::VisibleBand)) {
+                            plot_ui.vline(egui_plot::VLine::new(start)
+                                .color(Color32::from_rgba_premultiplied(150, 150, 255, 50))
+                                .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
+                            plot_ui.vline(egui_plot::VLine::new(end)
+                                .color(Color32::from_rgba_premultiplied(255, 150, 150, 50))
+                                .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
+                        }
+
+                        // Crosshair at the single-point wavelength
+                        if let Some((wl, q)) = crosshair {
+                            plot_ui.vline(egui_plot::VLine::new(wl)
+                                .color(Color32::from_rgb(255, 220, 100))
+                                .width(self.appearance.line_width(1.5)));
+                            plot_ui.hline(egui_plot::HLine::new(q)
+                                .color(Color32::from_rgba_premultiplied(255, 220, 100, 80))
+                                .width(self.appearance.line_width(1.0)));
+                        }
+
+                        // Pick only in pick mode or with Shift held, so drag-zoom clicks are ignored
+                        let shift = plot_ui.ctx().input(|i| i.modifiers.shift);
+                        if (pick_mode || shift) && plot_ui.response().clicked() {
+                            plot_ui.pointer_coordinate()
+                                .and_then(|p| wavelength_from_plot_x(p.x, wl_min, wl_max))
+                        } else {
+                            None
+                        }
+                    }).inner
+            }).inner;
+
+        if let Some(wl) = picked_wavelength {
+            self.state.wavelength = wl;
+            self.calculate_single();
+        }
+
+        if self.plot_view.show_residuals {
+            self.draw_residual_plot(ui);
+        }
 
         ui.add_space(5.0);
         ui.horizontal(|ui| {
-            ui.colored_label(Color32::GRAY, "|");
-            ui.colored_label(Color32::from_rgb(150, 150, 255), "380 nm");
-            ui.label("-");
-            ui.colored_label(Color32::from_rgb(255, 150, 150), "750 nm");
-            ui.colored_label(Color32::GRAY, "| = Visible spectrum range");
+            // Key for the shadings currently drawn
+            ui.menu_button(self.t("🎨 Shading", "🎨 Sombreado"), |ui| {
+                for overlay in Overlay::ALL {
+                    let (en, es) = overlay.label();
+                    let mut enabled = self.plot_view.overlays.contains(&overlay);
+                    if ui.checkbox(&mut enabled, self.t(en, es)).changed() {
+                        if enabled {
+                            self.plot_view.overlays.insert(overlay);
+                        } else {
+                            self.plot_view.overlays.remove(&overlay);
+                        }
+                    }
+                }
+            });
+            for overlay in legend_keys(&self.plot_view.overlays) {
+                let [r, g, b, _] = overlay.rgba();
+                let (en, es) = overlay.label();
+                let (meaning_en, meaning_es) = overlay.meaning();
+                ui.colored_label(Color32::from_rgb(r, g, b), "■");
+                ui.label(egui::RichText::new(self.t(en, es)).small())
+                    .on_hover_text(self.t(meaning_en, meaning_es));
+            }
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button(&self.t("🔄 Reset View", "🔄 Restablecer Vista"))
-                    .on_hover_text(&self.t(
+                if ui.button(self.t("🔄 Reset View", "🔄 Restablecer Vista"))
+                    .on_hover_text(self.t(
                         "Reset zoom to show full spectrum",
                         "Restablecer zoom para mostrar el espectro completo"
                     ))
@@ -911,138 +2774,1722 @@ impl NanoCalcApp {
                     // Incrementar contador para forzar recreación del plot
                     self.plot_reset_counter += 1;
                 }
+
+                let log_label = self.t("Log Y", "Y logarítmica");
+                if ui.checkbox(&mut self.plot_view.log_y, log_label)
+                    .on_hover_text(self.t(
+                        "Plot log₁₀ Q so weak features show next to strong peaks; Q ≤ 0 is not drawn",
+                        "Graficar log₁₀ Q para ver rasgos débiles junto a picos intensos; Q ≤ 0 no se dibuja"
+                    ))
+                    .changed()
+                {
+                    // New Y range, so rebuild the plot rather than keep the old zoom
+                    self.plot_reset_counter += 1;
+                }
+
+                let save_label = self.t("💾 Save as Default View", "💾 Guardar como Vista Predeterminada");
+                let save_hint = self.t(
+                    "Open with this plot mode, shading, markers and overlays on every launch",
+                    "Abrir con este modo de gráfica, sombreado, marcadores y superposiciones en cada inicio"
+                );
+                let factory_label = self.t("🏭 Reset View to Factory", "🏭 Restablecer Vista de Fábrica");
+                let factory_hint = self.t(
+                    "Restore the built-in plot appearance and forget the saved default",
+                    "Restaurar la apariencia de fábrica de la gráfica y olvidar la predeterminada guardada"
+                );
+                let mut view_action = None;
+                ui.menu_button(self.t("⭐ Default View", "⭐ Vista Predeterminada"), |ui| {
+                    if ui.add_enabled(self.plot_view != self.default_plot_view, egui::Button::new(save_label))
+                        .on_hover_text(save_hint)
+                        .clicked()
+                    {
+                        view_action = Some(true);
+                        ui.close_menu();
+                    }
+                    if ui.button(factory_label).on_hover_text(factory_hint).clicked() {
+                        view_action = Some(false);
+                        ui.close_menu();
+                    }
+                });
+                match view_action {
+                    Some(true) => {
+                        self.default_plot_view = self.plot_view.clone();
+                        self.add_log(&self.t("⭐ Plot view saved as default", "⭐ Vista de gráfica guardada como predeterminada"));
+                    }
+                    Some(false) => {
+                        self.plot_view = PlotView::default();
+                        self.default_plot_view = PlotView::default();
+                        self.plot_reset_counter += 1;
+                        self.add_log(&self.t("🏭 Plot view reset to factory", "🏭 Vista de gráfica restablecida de fábrica"));
+                    }
+                    None => {}
+                }
+
+                ui.add(
+                    egui::DragValue::new(&mut self.plot_view.zoom_fwhm_multiple)
+                        .range(0.5..=20.0)
+                        .speed(0.1)
+                        .prefix("± ")
+                        .suffix(" × FWHM"),
+                )
+                .on_hover_text(self.t(
+                    "Half-width of the zoom window around the peak",
+                    "Semiancho de la ventana de zoom alrededor del pico"
+                ));
+                if ui.button(self.t("🔍 Zoom to Peak", "🔍 Zoom al Pico"))
+                    .on_hover_text(self.t(
+                        "Zoom to the dominant Q_ext resonance",
+                        "Ampliar la resonancia dominante de Q_ext"
+                    ))
+                    .clicked()
+                {
+                    match peak_zoom_window(&self.spectrum_results, self.plot_view.zoom_fwhm_multiple) {
+                        Some((x0, x1)) => {
+                            self.zoom_request = Some((x0, x1));
+                            self.add_log(&format!(
+                                "{} {:.1}–{:.1} nm",
+                                self.t("🔍 Zoomed to", "🔍 Zoom a"),
+                                x0,
+                                x1
+                            ));
+                        }
+                        None => {
+                            self.plot_reset_counter += 1;
+                            self.add_log(&self.t(
+                                "⚠️ No resonance peak found, showing full range",
+                                "⚠️ No se encontró pico de resonancia, mostrando el rango completo"
+                            ));
+                        }
+                    }
+                }
                 
+                ui.separator();
+
+                let has_baseline = !self.baseline_results.is_empty();
+                let compare_tooltip = self.t(
+                    "Export baseline, current, delta and percent change in one CSV",
+                    "Exportar referencia, actual, diferencia y cambio porcentual en un CSV"
+                );
+                if ui.add_enabled(has_baseline, egui::Button::new(self.t("⚖ Compare CSV", "⚖ CSV Comparación")))
+                    .on_hover_text(compare_tooltip)
+                    .on_disabled_hover_text(self.t("Pin a baseline first", "Fije una referencia primero"))
+                    .clicked()
+                {
+                    self.export_type = ExportType::Comparison;
+                    self.show_export_dialog = true;
+                }
+
+                let pin_tooltip = self.t(
+                    "Keep the current spectrum as a baseline for comparison",
+                    "Guardar el espectro actual como referencia para comparar"
+                );
+                if ui.button(self.t("📌 Pin Baseline", "📌 Fijar Referencia"))
+                    .on_hover_text(pin_tooltip)
+                    .clicked()
+                {
+                    self.baseline_results = self.spectrum_results.clone();
+                    self.add_log(&self.t("📌 Baseline pinned", "📌 Referencia fijada"));
+                }
+
+                ui.separator();
+
+                let pick_label = self.t("🎯 Pick λ", "🎯 Elegir λ");
+                let pick_tooltip = self.t(
+                    "Click the plot to set the wavelength and recompute the single point (or Shift+click)",
+                    "Haga clic en la gráfica para fijar la longitud de onda y recalcular el punto único (o Shift+clic)"
+                );
+                ui.toggle_value(&mut self.pick_wavelength_mode, pick_label)
+                    .on_hover_text(pick_tooltip);
+
+                ui.separator();
+
+                let prominence_tooltip = self.t(
+                    "Minimum feature prominence, as a percentage of the Q_ext range",
+                    "Prominencia mínima de los rasgos, como porcentaje del rango de Q_ext"
+                );
+                let mut prominence_pct = self.plot_view.feature_config.min_prominence * 100.0;
+                if ui.add_enabled(
+                    self.plot_view.show_features,
+                    egui::DragValue::new(&mut prominence_pct)
+                        .range(0.0..=50.0)
+                        .speed(0.1)
+                        .suffix(" %"),
+                )
+                .on_hover_text(prominence_tooltip)
+                .changed()
+                {
+                    self.plot_view.feature_config.min_prominence = prominence_pct / 100.0;
+                }
+                let residuals_tooltip = self.t(
+                    "Show computed − measured Q_ext under the plot",
+                    "Mostrar Q_ext calculado − medido bajo la gráfica"
+                );
+                let has_measured = self.imported_spectrum.is_some();
+                let mut show_residuals = self.plot_view.show_residuals && has_measured;
+                if ui.add_enabled(
+                    has_measured,
+                    egui::SelectableLabel::new(show_residuals, self.t("📉 Residuals", "📉 Residuos"))
+                )
+                .on_hover_text(residuals_tooltip)
+                .on_disabled_hover_text(self.t("Import a measured spectrum first", "Importe primero un espectro medido"))
+                .clicked()
+                {
+                    show_residuals = !show_residuals;
+                }
+                self.plot_view.show_residuals = show_residuals;
+
+                let features_label = self.t("◆ Features", "◆ Rasgos");
+                let features_tooltip = self.t(
+                    "Mark peaks (▲), dips (▼) and shoulders (◆) of Q_ext",
+                    "Marcar picos (▲), valles (▼) y hombros (◆) de Q_ext"
+                );
+                ui.toggle_value(&mut self.plot_view.show_features, features_label)
+                    .on_hover_text(features_tooltip);
+
+                let broadened_label = self.t("〰 Instrument", "〰 Instrumento");
+                let broadened_tooltip = self.t(
+                    "Overlay Q_ext convolved with a Gaussian instrument response of this FWHM",
+                    "Superponer Q_ext convolucionado con una respuesta instrumental gaussiana de este FWHM"
+                );
+                ui.toggle_value(&mut self.plot_view.show_broadened, broadened_label)
+                    .on_hover_text(&broadened_tooltip);
+                ui.add_enabled(
+                    self.plot_view.show_broadened,
+                    egui::DragValue::new(&mut self.plot_view.instrument_fwhm)
+                        .range(0.0..=100.0)
+                        .speed(0.1)
+                        .prefix("FWHM ")
+                        .suffix(" nm"),
+                )
+                .on_hover_text(broadened_tooltip);
+
                 ui.separator();
                 
                 // Export buttons
-                if ui.button(&self.t("💾 CSV", "💾 CSV"))
-                    .on_hover_text(&self.t(
+                if ui.button(self.t("💾 CSV", "💾 CSV"))
+                    .on_hover_text(self.t(
                         "Export spectrum data to CSV file",
                         "Exportar datos del espectro a archivo CSV"
                     ))
                     .clicked() 
                 {
-                    self.export_type = ExportType::CSV;
+                    self.export_type = ExportType::Csv;
                     self.show_export_dialog = true;
                 }
                 
-                if ui.button(&self.t("📄 JSON", "📄 JSON"))
-                    .on_hover_text(&self.t(
+                if ui.button(self.t("📄 JSON", "📄 JSON"))
+                    .on_hover_text(self.t(
                         "Export spectrum data to JSON file",
                         "Exportar datos del espectro a archivo JSON"
                     ))
                     .clicked() 
                 {
-                    self.export_type = ExportType::JSON;
+                    self.export_type = ExportType::Json;
+                    self.show_export_dialog = true;
+                }
+
+                if ui.button(self.t("📐 Per Volume", "📐 Por Volumen"))
+                    .on_hover_text(self.t(
+                        "Export C_abs per particle volume and per mass to CSV",
+                        "Exportar C_abs por volumen y por masa de partícula a CSV"
+                    ))
+                    .clicked()
+                {
+                    self.export_type = ExportType::Normalized;
+                    self.show_export_dialog = true;
+                }
+
+                if self.active_dispersion.is_some()
+                    && ui.button(self.t("🧪 n,k Table", "🧪 Tabla n,k"))
+                        .on_hover_text(self.t(
+                            "Export the active material's n and k over the spectrum range for FDTD import",
+                            "Exportar n y k del material activo en el rango del espectro para importar en FDTD"
+                        ))
+                        .clicked()
+                {
+                    self.export_type = ExportType::NkText;
                     self.show_export_dialog = true;
                 }
                 
-                if ui.button(&self.t("🖼️ PNG", "🖼️ PNG"))
-                    .on_hover_text(&self.t(
+                if ui.button(self.t("📁 Project", "📁 Proyecto"))
+                    .on_hover_text(self.t(
+                        "Save inputs and spectrum as a .ncp project file",
+                        "Guardar entradas y espectro como archivo de proyecto .ncp"
+                    ))
+                    .clicked()
+                {
+                    self.export_type = ExportType::Project;
+                    self.show_export_dialog = true;
+                }
+
+                if ui.button(self.t("📦 Bundle", "📦 Paquete"))
+                    .on_hover_text(self.t(
+                        "Export project, data and plot into a folder with a checksummed manifest",
+                        "Exportar proyecto, datos y gráfica a una carpeta con un manifiesto con sumas de verificación"
+                    ))
+                    .clicked()
+                {
+                    self.export_type = ExportType::Bundle;
+                    self.show_export_dialog = true;
+                }
+
+                if ui.button(self.t("🖼️ PNG", "🖼️ PNG"))
+                    .on_hover_text(self.t(
                         "Export plot as PNG image",
                         "Exportar gráfica como imagen PNG"
                     ))
                     .clicked() 
                 {
-                    self.export_type = ExportType::PNG;
+                    self.export_type = ExportType::Png;
                     self.show_export_dialog = true;
                 }
             });
         });
-    }
-    
-    fn export_csv(&mut self) {
-        if self.spectrum_results.is_empty() {
-            return;
-        }
-        
-        self.add_log(&self.t("💾 Exporting CSV...", "💾 Exportando CSV..."));
-        
-        let mut csv_content = String::from("Wavelength (nm),Q_sca,Q_abs,Q_ext\n");
-        for result in &self.spectrum_results {
-            csv_content.push_str(&format!(
-                "{},{},{},{}\n",
-                result.wavelength, result.q_sca, result.q_abs, result.q_ext
-            ));
-        }
-        
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            use std::fs::File;
-            use std::io::Write;
-            use std::env;
-            
-            let filename = format!("{}.csv", self.export_filename);
-            
-            if let Ok(mut file) = File::create(&filename) {
-                let _ = file.write_all(csv_content.as_bytes());
-                if let Ok(current_dir) = env::current_dir() {
-                    let full_path = current_dir.join(&filename);
-                    let msg = format!("✅ CSV: {}", full_path.display());
-                    self.add_log(&msg);
-                } else {
-                    self.add_log(&format!("✅ CSV: {}", filename));
+
+        ui.add_space(5.0);
+        let spread_tooltip = self.t(
+            "Standard deviation of the particle radius, relative to the nominal radius",
+            "Desviación estándar del radio de la partícula, relativa al radio nominal"
+        );
+        let smooth_label = self.t("Smooth band", "Suavizar banda");
+        let smooth_tooltip = self.t(
+            "Moving average over the ±σ edges only; the mean curve is never smoothed",
+            "Media móvil solo sobre los bordes ±σ; la curva media nunca se suaviza"
+        );
+        ui.horizontal(|ui| {
+            ui.label(self.t("🎲 Size spread σ:", "🎲 Dispersión de tamaño σ:"))
+                .on_hover_text(spread_tooltip);
+            let mut sigma_percent = self.polydisperse.relative_sigma * 100.0;
+            if ui.add(egui::DragValue::new(&mut sigma_percent)
+                .speed(0.5)
+                .range(0.0..=50.0)
+                .suffix(" %"))
+                .changed()
+            {
+                self.polydisperse.relative_sigma = sigma_percent / 100.0;
+            }
+            ui.label(self.t("Samples:", "Muestras:"));
+            ui.add(egui::DragValue::new(&mut self.polydisperse.samples).range(2..=1000));
+
+            if ui.button(self.t("Calculate Band", "Calcular Banda")).clicked() {
+                self.calculate_polydisperse_band();
+            }
+            if let Some(run) = &self.band_run {
+                ui.add(egui::ProgressBar::new(run.progress() as f32).show_percentage().desired_width(120.0));
+                if ui.button(self.t("⏹ Stop", "⏹ Detener")).clicked() {
+                    run.cancel();
+                }
+            }
+
+            if !self.band_results.is_empty() {
+                ui.separator();
+                ui.checkbox(&mut self.smooth_band, smooth_label)
+                    .on_hover_text(smooth_tooltip);
+                if self.smooth_band {
+                    ui.add(egui::DragValue::new(&mut self.band_smoothing_window)
+                        .range(3..=21)
+                        .prefix("w = "));
+                    ui.colored_label(
+                        Color32::from_rgb(255, 200, 100),
+                        self.t("Band smoothed (mean unsmoothed)", "Banda suavizada (media sin suavizar)")
+                    );
+                }
+                if ui.button(self.t("✖ Clear", "✖ Borrar")).clicked() {
+                    self.band_results.clear();
                 }
-            } else {
-                self.add_log(&self.t("❌ Error exporting CSV", "❌ Error exportando CSV"));
             }
+        });
+
+        self.draw_array_controls(ui);
+
+        let mixer_label = self.t("🧪 Two-population mixture", "🧪 Mezcla de dos poblaciones");
+        let fraction_tooltip = self.t(
+            "Number fraction of population A (the current particle); B gets the rest",
+            "Fracción numérica de la población A (la partícula actual); B recibe el resto"
+        );
+        let radius_b_label = self.t("B radius:", "Radio B:");
+        let index_b_label = self.t("B index n + ik:", "Índice B n + ik:");
+        let calculate_label = self.t("Calculate Mixture", "Calcular Mezcla");
+        let clear_label = self.t("✖ Clear", "✖ Borrar");
+        let mut calculate = false;
+        ui.collapsing(mixer_label, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("f_A:").on_hover_text(fraction_tooltip);
+                ui.add(egui::Slider::new(&mut self.mixer_fraction_a, 0.0..=1.0).fixed_decimals(2));
+                ui.colored_label(Color32::GRAY, format!("f_B = {:.2}", 1.0 - self.mixer_fraction_a));
+            });
+            ui.horizontal(|ui| {
+                ui.label(radius_b_label);
+                ui.add(egui::DragValue::new(&mut self.mixer_radius_b)
+                    .speed(0.5)
+                    .range(1.0..=500.0)
+                    .suffix(" nm"));
+                ui.separator();
+                ui.label(index_b_label);
+                ui.add(egui::DragValue::new(&mut self.mixer_n_b.0).speed(0.01).range(0.01..=10.0));
+                ui.add(egui::DragValue::new(&mut self.mixer_n_b.1).speed(0.01).range(0.0..=10.0));
+            });
+            ui.horizontal(|ui| {
+                if ui.button(calculate_label).clicked() {
+                    calculate = true;
+                }
+                if !self.mixture_results.is_empty() && ui.button(clear_label).clicked() {
+                    self.mixture_results.clear();
+                }
+            });
+        });
+        if calculate {
+            self.calculate_mixture();
         }
     }
     
-    fn export_json(&mut self) {
-        if self.spectrum_results.is_empty() {
-            return;
-        }
-        
-        self.add_log(&self.t("💾 Exporting JSON...", "💾 Exportando JSON..."));
-        
-        let json_data = serde_json::json!({
-            "metadata": {
-                "particle_radius_nm": self.state.particle_radius,
-                "n_particle_real": self.state.n_particle_real,
-                "n_particle_imag": self.state.n_particle_imag,
-                "n_medium": self.state.n_medium,
-                "wavelength_nm": self.state.wavelength
-            },
-            "spectrum_data": self.spectrum_results.iter().map(|r| {
-                serde_json::json!({
-                    "wavelength_nm": r.wavelength,
-                    "q_sca": r.q_sca,
-                    "q_abs": r.q_abs,
-                    "q_ext": r.q_ext
-                })
-            }).collect::<Vec<_>>()
+    /// Periodic-array option: lattice, period and incidence angle
+    fn draw_array_controls(&mut self, ui: &mut egui::Ui) {
+        let array_label = self.t("▦ Periodic array", "▦ Arreglo periódico");
+        let show_label = self.t("Show Q_ext × S(λ)", "Mostrar Q_ext × S(λ)");
+        let show_tooltip = self.t(
+            "Single-scattering structure factor of the array; peaks near the Rayleigh anomalies λ = d(1 ± sinθ)/n_medium",
+            "Factor de estructura de dispersión simple del arreglo; picos cerca de las anomalías de Rayleigh λ = d(1 ± sinθ)/n_medio"
+        );
+        let chain_label = self.t("1D chain", "Cadena 1D");
+        let square_label = self.t("2D square", "Cuadrada 2D");
+        let spacing_label = self.t("Period d:", "Periodo d:");
+        let angle_label = self.t("Incidence θ:", "Incidencia θ:");
+        let (blue, red) = rayleigh_anomalies(self.array_spacing, self.array_angle_deg.to_radians(), 1);
+        let anomaly_text = self.t(
+            &format!("Rayleigh anomalies: {:.0} / {:.0} nm", blue * self.state.n_medium, red * self.state.n_medium),
+            &format!("Anomalías de Rayleigh: {:.0} / {:.0} nm", blue * self.state.n_medium, red * self.state.n_medium)
+        );
+        ui.collapsing(array_label, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_array, show_label).on_hover_text(show_tooltip);
+                ui.selectable_value(&mut self.array_lattice, Lattice::Chain, chain_label);
+                ui.selectable_value(&mut self.array_lattice, Lattice::Square, square_label);
+            });
+            ui.horizontal(|ui| {
+                ui.label(spacing_label);
+                ui.add(egui::DragValue::new(&mut self.array_spacing)
+                    .speed(1.0)
+                    .range(50.0..=5000.0)
+                    .suffix(" nm"));
+                ui.label(angle_label);
+                ui.add(egui::DragValue::new(&mut self.array_angle_deg)
+                    .speed(0.5)
+                    .range(0.0..=80.0)
+                    .suffix("°"));
+                ui.colored_label(Color32::GRAY, anomaly_text);
+            });
+        });
+    }
+
+    /// Computed − measured Q_ext on the measured grid, with RMS and max
+    fn draw_residual_plot(&self, ui: &mut egui::Ui) {
+        let Some(file) = &self.imported_spectrum else {
+            return;
+        };
+        let measured: Vec<(f64, f64)> = file.spectrum_data.iter().map(|p| (p.wavelength_nm, p.q_ext)).collect();
+        let residuals = q_ext_residuals(&self.spectrum_results, &measured);
+
+        ui.add_space(5.0);
+        match residual_stats(&residuals) {
+            Some(stats) => {
+                ui.horizontal(|ui| {
+                    ui.strong(self.t("Residuals (computed − measured)", "Residuos (calculado − medido)"));
+                    ui.label(format!(
+                        "RMS = {:.4}   max |r| = {:.4}   ({} {})",
+                        stats.rms,
+                        stats.max_abs,
+                        stats.count,
+                        self.t("points", "puntos")
+                    ));
+                });
+            }
+            None => {
+                ui.colored_label(
+                    Color32::GRAY,
+                    self.t(
+                        "No measured points inside the computed range",
+                        "Ningún punto medido dentro del rango calculado"
+                    ),
+                );
+                return;
+            }
+        }
+
+        let points: Vec<[f64; 2]> = residuals.iter().map(|r| [r.wavelength, r.residual]).collect();
+        let plot_id = format!("residual_plot_{}", self.plot_reset_counter);
+        Plot::new(&plot_id)
+            .height(150.0)
+            .x_axis_label(self.t("Wavelength (nm)", "Longitud de onda (nm)"))
+            .y_axis_label("ΔQ_ext")
+            .y_axis_min_width(30.0)
+            .link_axis(SPECTRUM_AXIS_GROUP, true, false)
+            .show(ui, |plot_ui| {
+                plot_ui.hline(egui_plot::HLine::new(0.0)
+                    .color(Color32::GRAY)
+                    .width(self.appearance.line_width(1.0)));
+                plot_ui.line(
+                    Line::new(PlotPoints::from(points.clone()))
+                        .color(Color32::from_rgb(80, 220, 220))
+                        .width(self.appearance.line_width(1.5)),
+                );
+                plot_ui.points(
+                    Points::new(points)
+                        .radius(3.0 * self.appearance.scale())
+                        .color(Color32::from_rgb(80, 220, 220)),
+                );
+            });
+    }
+
+    fn draw_universal_curve(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(self.t("x max:", "x máx:"));
+            ui.add(egui::DragValue::new(&mut self.universal_x_max)
+                .speed(0.5)
+                .range(1.0..=200.0)
+                .fixed_decimals(1));
+            if ui.button(self.t("📐 Calculate Q(x)", "📐 Calcular Q(x)"))
+                .on_hover_text(self.t(
+                    "Sweep the size parameter holding m = n_particle / n_medium constant",
+                    "Barrer el parámetro de tamaño manteniendo m = n_partícula / n_medio constante"
+                ))
+                .clicked()
+            {
+                self.calculate_universal_curve();
+            }
+        });
+        ui.add_space(10.0);
+
+        if self.universal_results.is_empty() {
+            egui::Frame::none()
+                .fill(Color32::from_rgb(40, 43, 53))
+                .rounding(Rounding::same(8.0))
+                .inner_margin(egui::Margin::same(20.0))
+                .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(60.0);
+                        ui.label(egui::RichText::new("📐").size(64.0));
+                        ui.add_space(15.0);
+                        ui.label(egui::RichText::new(self.t("No universal curve data", "Sin datos de curva universal")).size(18.0));
+                        ui.add_space(8.0);
+                        ui.colored_label(
+                            Color32::GRAY,
+                            self.t(
+                                "Click 'Calculate Q(x)' to sweep the size parameter",
+                                "Haga clic en 'Calcular Q(x)' para barrer el parámetro de tamaño"
+                            )
+                        );
+                        ui.add_space(60.0);
+                    });
+                });
+            return;
+        }
+
+        let q_sca_points: PlotPoints = self.universal_results.iter()
+            .map(|r| [r.metadata.size_parameter, r.q_sca])
+            .collect();
+        let q_abs_points: PlotPoints = self.universal_results.iter()
+            .map(|r| [r.metadata.size_parameter, r.q_abs])
+            .collect();
+        let q_ext_points: PlotPoints = self.universal_results.iter()
+            .map(|r| [r.metadata.size_parameter, r.q_ext])
+            .collect();
+
+        let plot_id = format!("universal_plot_{}", self.plot_reset_counter);
+        Plot::new(&plot_id)
+            .legend(Legend::default().position(Corner::RightTop))
+            .x_axis_label(self.t("Size parameter x = 2πr/λ", "Parámetro de tamaño x = 2πr/λ"))
+            .y_axis_label(self.t("Efficiency Factor Q", "Factor de Eficiencia Q"))
+            .label_formatter(|name, value| {
+                format!("{}\nx = {:.2}\nQ = {:.4}", name, value.x, value.y)
+            })
+            .y_axis_min_width(30.0)
+            .height(450.0)
+            .include_y(0.0)
+            .allow_boxed_zoom(true)
+            .allow_drag(true)
+            .allow_zoom(true)
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    Line::new(q_sca_points)
+                        .color(Color32::from_rgb(70, 160, 255))
+                        .width(self.appearance.line_width(2.0))
+                        .name(self.t("Q_sca (Scattering)", "Q_sca (Dispersión)")),
+                );
+                plot_ui.line(
+                    Line::new(q_abs_points)
+                        .color(Color32::from_rgb(255, 120, 70))
+                        .width(self.appearance.line_width(2.0))
+                        .name(self.t("Q_abs (Absorption)", "Q_abs (Absorción)")),
+                );
+                plot_ui.line(
+                    Line::new(q_ext_points)
+                        .color(Color32::from_rgb(100, 220, 140))
+                        .width(self.appearance.line_width(2.0))
+                        .name(self.t("Q_ext (Extinction)", "Q_ext (Extinción)")),
+                );
+
+                // Extinction paradox limit Q_ext → 2
+                plot_ui.hline(egui_plot::HLine::new(2.0)
+                    .color(Color32::from_rgba_premultiplied(200, 200, 200, 60))
+                    .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
+            });
+    }
+
+    fn calculate_multipoles(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+
+        let Some(wavelengths) = self.spectrum_grid() else { return; };
+        let model = MieModel::new(
+            self.state.particle_radius,
+            self.state.wavelength,
+            RefractiveIndex::new(self.state.n_particle_real, self.state.n_particle_imag),
+            self.state.n_medium,
+        );
+
+        match multipole_spectrum(&model, &wavelengths) {
+            Ok(results) => {
+                self.multipole_results = results;
+                let msg = self.t(
+                    &format!("🧩 Multipole decomposition calculated ({} wavelengths)", self.multipole_results.len()),
+                    &format!("🧩 Descomposición multipolar calculada ({} longitudes de onda)", self.multipole_results.len())
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Multipole decomposition error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+
+        self.calculating = false;
+    }
+
+    /// Ranked bar chart of resonance-wavelength elasticities
+    fn draw_sensitivity(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(self.t("🎯 Calculate Sensitivity", "🎯 Calcular Sensibilidad")).clicked() {
+                self.calculate_sensitivity();
+            }
+            ui.colored_label(Color32::GRAY, self.t(
+                "(p/λ)·∂λ_peak/∂p from ±1% perturbations",
+                "(p/λ)·∂λ_pico/∂p con perturbaciones de ±1%"
+            ));
+        });
+        ui.add_space(10.0);
+
+        let Some(sensitivities) = &self.sensitivities else {
+            ui.colored_label(Color32::GRAY, self.t(
+                "Click 'Calculate Sensitivity' to rank the inputs by their effect on the resonance",
+                "Haga clic en 'Calcular Sensibilidad' para ordenar las entradas por su efecto en la resonancia"
+            ));
+            return;
+        };
+        if sensitivities.is_empty() {
+            ui.colored_label(Color32::from_rgb(255, 200, 100), self.t(
+                "No resolved Q_ext resonance in range: nothing to shift",
+                "Ninguna resonancia de Q_ext resuelta en el rango: nada que desplazar"
+            ));
+            return;
+        }
+
+        // Largest at the top: bar i sits at y = n − 1 − i
+        let count = sensitivities.len();
+        let bars: Vec<Bar> = sensitivities
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let color = if s.normalized >= 0.0 {
+                    Color32::from_rgb(255, 140, 90)
+                } else {
+                    Color32::from_rgb(100, 180, 255)
+                };
+                Bar::new((count - 1 - i) as f64, s.normalized)
+                    .name(format!("{}: {:+.2} nm per unit", s.parameter.symbol(), s.derivative))
+                    .fill(color)
+            })
+            .collect();
+        let symbols: Vec<&'static str> = sensitivities.iter().rev().map(|s| s.parameter.symbol()).collect();
+
+        Plot::new(format!("sensitivity_plot_{}", self.plot_reset_counter))
+            .height(220.0)
+            .x_axis_label(self.t("Normalized sensitivity", "Sensibilidad normalizada"))
+            .y_axis_formatter(move |mark, _range| {
+                let i = mark.value.round();
+                if (mark.value - i).abs() < 1e-6 && i >= 0.0 {
+                    symbols.get(i as usize).map(|s| s.to_string()).unwrap_or_default()
+                } else {
+                    String::new()
+                }
+            })
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(bars).horizontal().width(0.6));
+            });
+        ui.colored_label(Color32::GRAY, self.t(
+            "Positive: the peak redshifts as the input grows",
+            "Positivo: el pico se desplaza al rojo al aumentar la entrada"
+        ));
+    }
+
+    fn calculate_sensitivity(&mut self) {
+        self.calculating = true;
+        self.error_message = None;
+        if !self.inputs_valid() { return; }
+
+        let Some(wavelengths) = self.spectrum_grid() else { return; };
+        let inputs = RunInputs::from(&self.state);
+        let engine = &self.engine;
+        let ranked = peak_sensitivities(&inputs, DEFAULT_RELATIVE_STEP, |p| {
+            let model = MieModel::new(
+                p.particle_radius,
+                p.wavelength,
+                RefractiveIndex::new(p.n_particle_real, p.n_particle_imag),
+                p.n_medium,
+            );
+            engine.spectrum(&model, &wavelengths)
+        });
+
+        match ranked {
+            Ok(Some(ranked)) => {
+                let msg = match ranked.first() {
+                    Some(top) => self.t(
+                        &format!("🎯 Sensitivity: {} moves the resonance most", top.parameter.symbol()),
+                        &format!("🎯 Sensibilidad: {} desplaza más la resonancia", top.parameter.symbol())
+                    ),
+                    None => self.t("🎯 Sensitivity calculated", "🎯 Sensibilidad calculada"),
+                };
+                self.sensitivities = Some(ranked);
+                self.add_log(&msg);
+            }
+            Ok(None) => {
+                self.sensitivities = Some(Vec::new());
+                self.add_log(&self.t(
+                    "⚠️ No resolved resonance: sensitivity undefined",
+                    "⚠️ Sin resonancia resuelta: sensibilidad indefinida"
+                ));
+            }
+            Err(e) => {
+                let error_msg = format!("Sensitivity error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+
+        self.calculating = false;
+    }
+
+    fn draw_multipoles(&mut self, ui: &mut egui::Ui) {
+        let has_results = !self.multipole_results.is_empty();
+        ui.horizontal(|ui| {
+            if ui.button(self.t("🧩 Calculate Decomposition", "🧩 Calcular Descomposición")).clicked() {
+                self.calculate_multipoles();
+            }
+            ui.separator();
+            let disabled_hint = self.t("Run a decomposition first", "Calcule una descomposición primero");
+            if ui.add_enabled(has_results, egui::Button::new(self.t("💾 CSV", "💾 CSV")))
+                .on_disabled_hover_text(&disabled_hint)
+                .clicked()
+            {
+                self.export_type = ExportType::MultipoleCsv;
+                self.show_export_dialog = true;
+            }
+            if ui.add_enabled(has_results, egui::Button::new(self.t("📄 JSON", "📄 JSON")))
+                .on_disabled_hover_text(&disabled_hint)
+                .clicked()
+            {
+                self.export_type = ExportType::MultipoleJson;
+                self.show_export_dialog = true;
+            }
+        });
+        ui.add_space(10.0);
+
+        if !has_results {
+            ui.colored_label(Color32::GRAY, self.t(
+                "Click 'Calculate Decomposition' to split the spectrum into multipole orders",
+                "Haga clic en 'Calcular Descomposición' para separar el espectro en órdenes multipolares"
+            ));
+            return;
+        }
+
+        // Dipole, quadrupole and octupole; higher orders are in the export
+        const ORDER_NAMES: [&str; 3] = ["dipole", "quadrupole", "octupole"];
+        let mut lines = Vec::new();
+        for (i, name) in ORDER_NAMES.iter().enumerate() {
+            let color = OVERLAY_COLORS[i % OVERLAY_COLORS.len()];
+            let electric: PlotPoints = self.multipole_results.iter()
+                .map(|d| [d.wavelength, d.orders.get(i).map_or(0.0, |o| o.q_sca_electric)])
+                .collect();
+            let magnetic: PlotPoints = self.multipole_results.iter()
+                .map(|d| [d.wavelength, d.orders.get(i).map_or(0.0, |o| o.q_sca_magnetic)])
+                .collect();
+            lines.push(
+                Line::new(electric)
+                    .color(color)
+                    .width(self.appearance.line_width(2.0))
+                    .name(format!("E {}", name)),
+            );
+            lines.push(overlay_line(magnetic, color, format!("M {}", name), self.appearance.line_width(1.5)));
+        }
+
+        let plot_id = format!("multipole_plot_{}", self.plot_reset_counter);
+        Plot::new(&plot_id)
+            .legend(Legend::default().position(Corner::RightTop))
+            .x_axis_label(self.t("Wavelength (nm)", "Longitud de onda (nm)"))
+            .y_axis_label(self.t("Q_sca contribution", "Contribución a Q_sca"))
+            .y_axis_min_width(30.0)
+            .height(450.0)
+            .include_y(0.0)
+            .show(ui, |plot_ui| {
+                for line in lines {
+                    plot_ui.line(line);
+                }
+            });
+    }
+
+    fn export_multipoles(&mut self, json: bool) {
+        if self.multipole_results.is_empty() {
+            return;
+        }
+
+        self.add_log(&self.t("💾 Exporting multipoles...", "💾 Exportando multipolos..."));
+
+        let (content, extension) = if json {
+            match multipole_json(&self.multipole_results) {
+                Ok(content) => (content, "json"),
+                Err(_) => {
+                    self.add_log(&self.t("❌ Error serializing JSON", "❌ Error serializando JSON"));
+                    return;
+                }
+            }
+        } else {
+            (multipole_csv(&self.multipole_results), "csv")
+        };
+
+        let error_label = self.t("Error exporting multipoles", "Error exportando multipolos");
+        self.write_text_export("_multipoles", extension, &content, &extension.to_uppercase(), &error_label);
+    }
+
+    fn draw_energy_rate(&mut self, ui: &mut egui::Ui) {
+        let points = c_ext_vs_energy(&self.spectrum_results);
+        let (e_lo, e_hi) = (points[0].0, points[points.len() - 1].0);
+
+        let (mut w_min, mut w_max) = self.energy_window;
+        ui.horizontal(|ui| {
+            ui.label(self.t("Energy window:", "Ventana de energía:"));
+            ui.add(egui::DragValue::new(&mut w_min)
+                .speed(0.01)
+                .range(e_lo..=e_hi)
+                .suffix(" eV"));
+            ui.label("–");
+            ui.add(egui::DragValue::new(&mut w_max)
+                .speed(0.01)
+                .range(e_lo..=e_hi)
+                .suffix(" eV"));
         });
+        if w_max < w_min {
+            std::mem::swap(&mut w_min, &mut w_max);
+        }
+        self.energy_window = (w_min, w_max);
+
+        let integral = integrated_cross_section(&self.spectrum_results, w_min, w_max);
+        egui::Frame::none()
+            .fill(Color32::from_rgb(45, 48, 58))
+            .rounding(Rounding::same(6.0))
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("∫ C_ext dE =");
+                    ui.colored_label(Color32::from_rgb(100, 255, 150), format!("{:.4e} eV·nm²", integral));
+                    ui.label("ℹ️")
+                        .on_hover_text(self.t(
+                            "Integrated extinction over the energy window: an effective oscillator strength of the resonance",
+                            "Extinción integrada en la ventana de energía: una fuerza de oscilador efectiva de la resonancia"
+                        ));
+                });
+            });
+        ui.add_space(10.0);
+
+        let line_points: PlotPoints = points.iter().map(|&(e, c)| [e, c]).collect();
+        let plot_id = format!("energy_plot_{}", self.plot_reset_counter);
+        Plot::new(&plot_id)
+            .legend(Legend::default().position(Corner::RightTop))
+            .x_axis_label(self.t("Photon energy (eV)", "Energía del fotón (eV)"))
+            .y_axis_label(self.t("C_ext (nm²)", "C_ext (nm²)"))
+            .label_formatter(|name, value| {
+                format!("{}\nE = {:.3} eV\nC = {:.2} nm²", name, value.x, value.y)
+            })
+            .y_axis_min_width(30.0)
+            .height(450.0)
+            .include_y(0.0)
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    Line::new(line_points)
+                        .color(Color32::from_rgb(100, 220, 140))
+                        .width(self.appearance.line_width(2.5))
+                        .name("C_ext"),
+                );
+                for e in [w_min, w_max] {
+                    plot_ui.vline(egui_plot::VLine::new(e)
+                        .color(Color32::from_rgb(255, 220, 100))
+                        .style(egui_plot::LineStyle::Dashed { length: 5.0 }));
+                }
+            });
+    }
+
+    /// Destination for an export: a native Save dialog when available,
+    /// otherwise `<export_filename><suffix>.<extension>` in the working directory.
+    /// `None` means the user cancelled and nothing should be written.
+    fn export_path(&mut self, suffix: &str, extension: &str) -> Option<PathBuf> {
+        let default_name = default_file_name(&self.export_filename, suffix, extension);
+
+        #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+        {
+            let path = self.file_dialogs.save(&default_name, extension);
+            if path.is_none() {
+                self.add_log(&self.t("↩ Export cancelled", "↩ Exportación cancelada"));
+            }
+            path
+        }
+
+        #[cfg(not(all(feature = "file_dialog", not(target_arch = "wasm32"))))]
+        {
+            Some(PathBuf::from(default_name))
+        }
+    }
+
+    fn export_csv(&mut self) {
+        if self.spectrum_results.is_empty() {
+            return;
+        }
         
+        self.add_log(&self.t("💾 Exporting CSV...", "💾 Exportando CSV..."));
+        let exporter = CsvExporter::new(self.csv_columns.iter().copied());
+        let error_label = self.t("Error exporting CSV", "Error exportando CSV");
+        self.write_spectrum_export(&exporter, "CSV", &error_label);
+    }
+
+    /// Write the spectrum through `exporter` to a path chosen by [`Self::export_path`]
+    fn write_spectrum_export(&mut self, exporter: &dyn Exporter, label: &str, error_label: &str) {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            use std::fs::File;
-            use std::io::Write;
             use std::env;
-            
-            let filename = format!("{}.json", self.export_filename);
-            
-            if let Ok(mut file) = File::create(&filename) {
-                if let Ok(json_string) = serde_json::to_string_pretty(&json_data) {
-                    let _ = file.write_all(json_string.as_bytes());
+
+            let Some(filename) = self.export_path("", exporter.extension()) else { return; };
+
+            match controller::write_export(&filename, exporter, &self.spectrum_results) {
+                Ok(()) => {
                     if let Ok(current_dir) = env::current_dir() {
                         let full_path = current_dir.join(&filename);
-                        let msg = format!("✅ JSON: {}", full_path.display());
+                        let msg = format!("✅ {}: {}", label, full_path.display());
                         self.add_log(&msg);
                     } else {
-                        self.add_log(&format!("✅ JSON: {}", filename));
+                        self.add_log(&format!("✅ {}: {}", label, filename.display()));
                     }
-                } else {
-                    self.add_log(&self.t("❌ Error serializing JSON", "❌ Error serializando JSON"));
                 }
-            } else {
-                self.add_log(&self.t("❌ Error exporting JSON", "❌ Error exportando JSON"));
+                Err(e) => {
+                    self.add_log(&format!("❌ {}: {}", error_label, e));
+                }
+            }
+        }
+    }
+    
+    /// Write `content` to an export file, logging where it went or why it failed
+    fn write_text_export(&mut self, suffix: &str, extension: &str, content: &str, label: &str, error_label: &str) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use std::env;
+
+            let Some(filename) = self.export_path(suffix, extension) else { return; };
+
+            match std::fs::write(&filename, content) {
+                Ok(()) => {
+                    if let Ok(current_dir) = env::current_dir() {
+                        let full_path = current_dir.join(&filename);
+                        self.add_log(&format!("✅ {}: {}", label, full_path.display()));
+                    } else {
+                        self.add_log(&format!("✅ {}: {}", label, filename.display()));
+                    }
+                }
+                Err(e) => {
+                    self.add_log(&format!("❌ {}: {}", error_label, e));
+                }
             }
         }
     }
 
+    fn export_normalized(&mut self) {
+        if self.spectrum_results.is_empty() {
+            return;
+        }
+
+        let csv_content = match normalized_csv(
+            &self.spectrum_results,
+            self.state.particle_radius,
+            Some(self.particle_density),
+        ) {
+            Ok(csv) => csv,
+            Err(e) => {
+                self.add_log(&format!("❌ {}", e));
+                return;
+            }
+        };
+
+        let error_label = self.t("Error exporting CSV", "Error exportando CSV");
+        self.write_text_export("_normalized", "csv", &csv_content, "Normalized CSV", &error_label);
+    }
+
+    fn export_nk_table(&mut self) {
+        let Some(material) = self.active_dispersion.clone() else { return; };
+        let Some(wavelengths) = self.spectrum_grid() else { return; };
+        let text = nk_text(material.as_ref(), &wavelengths, self.nk_unit);
+
+        let error_label = self.t("Error exporting n,k table", "Error exportando tabla n,k");
+        self.write_text_export("_nk", "txt", &text, &format!("n,k table ({})", material.name()), &error_label);
+    }
+
+    fn export_comparison(&mut self) {
+        if self.spectrum_results.is_empty() || self.baseline_results.is_empty() {
+            return;
+        }
+
+        self.add_log(&self.t("💾 Exporting comparison CSV...", "💾 Exportando CSV de comparación..."));
+
+        let aligned = align_spectra(&self.baseline_results, &self.spectrum_results);
+        let csv_content = comparison_csv(&aligned);
+
+        let error_label = self.t("Error exporting comparison CSV", "Error exportando CSV de comparación");
+        self.write_text_export("_comparison", "csv", &csv_content, "CSV", &error_label);
+    }
+
+    fn export_history(&mut self) {
+        self.add_log(&self.t("💾 Exporting run history...", "💾 Exportando historial de cálculos..."));
+
+        let csv_content = self.run_history.to_csv();
+
+        let error_label = self.t("Error exporting run history", "Error exportando historial de cálculos");
+        self.write_text_export("_history", "csv", &csv_content, "CSV", &error_label);
+    }
+
+    fn export_project(&mut self) {
+        self.add_log(&self.t("💾 Saving project...", "💾 Guardando proyecto..."));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use std::env;
+
+            let Some(filename) = self.export_path("", PROJECT_EXTENSION) else { return; };
+            let project = Project::new(self.state.clone(), self.spectrum_results.clone());
+
+            match project.save(&filename) {
+                Ok(()) => {
+                    if let Ok(current_dir) = env::current_dir() {
+                        let full_path = current_dir.join(&filename);
+                        let msg = format!("✅ Project: {}", full_path.display());
+                        self.add_log(&msg);
+                    } else {
+                        self.add_log(&format!("✅ Project: {}", filename.display()));
+                    }
+                }
+                Err(e) => {
+                    self.add_log(&format!("❌ {}: {}", self.t("Error saving project", "Error guardando proyecto"), e));
+                }
+            }
+        }
+    }
+
+    fn draw_open_project(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut load_path: Option<String> = None;
+
+        egui::Window::new(self.t("Open Project", "Abrir Proyecto"))
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Project file:", "Archivo de proyecto:"));
+                    ui.text_edit_singleline(&mut self.open_project_path_input)
+                        .on_hover_text(format!("*.{}", PROJECT_EXTENSION));
+                    if ui.button(self.t("Open", "Abrir")).clicked() && !self.open_project_path_input.trim().is_empty() {
+                        load_path = Some(self.open_project_path_input.trim().to_string());
+                    }
+                    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+                    if ui.button(self.t("📂 Browse…", "📂 Examinar…")).clicked() {
+                        let filter = self.t("NanoCalc project", "Proyecto NanoCalc");
+                        if let Some(path) = self.file_dialogs.open(&filter, &[PROJECT_EXTENSION]) {
+                            self.open_project_path_input = path.display().to_string();
+                            load_path = Some(self.open_project_path_input.clone());
+                        }
+                    }
+                });
+                ui.colored_label(Color32::GRAY, self.t(
+                    "Replaces the current inputs and spectrum",
+                    "Reemplaza las entradas y el espectro actuales"
+                ));
+            });
+
+        if let Some(path) = load_path {
+            if self.open_project(&path) {
+                open = false;
+            }
+        }
+        if !open {
+            self.show_open_project = false;
+        }
+    }
+
+    /// Replace the session with a saved project; false if it could not be loaded
+    fn open_project(&mut self, path: &str) -> bool {
+        match crate::project::load(path) {
+            Ok((state, spectrum)) => {
+                // A run still streaming in belongs to the old session
+                self.spectrum_run = None;
+                self.band_run = None;
+                self.calculating = false;
+                self.state = state;
+                self.run_history.set_limit(self.state.history_limit);
+                self.result = None;
+                self.spectrum_results = spectrum;
+                self.spectrum_columns = SpectrumColumns::from(self.spectrum_results.as_slice());
+                self.array_cache = None;
+                self.plot_reset_counter += 1;
+                self.error_message = None;
+                let msg = self.t(
+                    &format!("📂 Project opened: {} ({} points)", path, self.spectrum_results.len()),
+                    &format!("📂 Proyecto abierto: {} ({} puntos)", path, self.spectrum_results.len())
+                );
+                self.add_log(&msg);
+                true
+            }
+            Err(e) => {
+                let error_msg = format!("{} {}: {}", self.t("Cannot open", "No se puede abrir"), path, e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                false
+            }
+        }
+    }
+
+    fn draw_compare_projects(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut add_path: Option<String> = None;
+        let mut remove_index: Option<usize> = None;
+
+        egui::Window::new(self.t("Compare Projects", "Comparar Proyectos"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(800.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Project file:", "Archivo de proyecto:"));
+                    ui.text_edit_singleline(&mut self.compare_path_input)
+                        .on_hover_text(format!("*.{}", PROJECT_EXTENSION));
+                    if ui.button(self.t("➕ Add", "➕ Agregar")).clicked() && !self.compare_path_input.trim().is_empty() {
+                        add_path = Some(self.compare_path_input.trim().to_string());
+                    }
+                    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+                    if ui.button(self.t("📂 Browse…", "📂 Examinar…")).clicked() {
+                        let filter = self.t("NanoCalc project", "Proyecto NanoCalc");
+                        if let Some(path) = self.file_dialogs.open(&filter, &[PROJECT_EXTENSION]) {
+                            add_path = Some(path.display().to_string());
+                        }
+                    }
+                });
+                ui.add_space(10.0);
+
+                if self.project_comparison.projects.is_empty() {
+                    ui.colored_label(Color32::GRAY, self.t(
+                        "No projects loaded. Save projects from the export dialog, then add them here.",
+                        "No hay proyectos cargados. Guarde proyectos desde el diálogo de exportación y agréguelos aquí."
+                    ));
+                    return;
+                }
+
+                if !self.project_comparison.grids_match() {
+                    ui.colored_label(Color32::from_rgb(255, 200, 100), self.t(
+                        "⚠ Projects use different wavelength grids; each curve is drawn on its own grid",
+                        "⚠ Los proyectos usan mallas de longitud de onda distintas; cada curva se dibuja en su propia malla"
+                    ));
+                    ui.add_space(5.0);
+                }
+
+                // Parameters side by side
+                egui::Grid::new("compare_projects_grid")
+                    .num_columns(8)
+                    .spacing([12.0, 6.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(self.t("Project", "Proyecto"));
+                        ui.strong("r (nm)");
+                        ui.strong("n");
+                        ui.strong("k");
+                        ui.strong("n_med");
+                        ui.strong(self.t("λ range (nm)", "Rango λ (nm)"));
+                        ui.strong(self.t("Points", "Puntos"));
+                        ui.label("");
+                        ui.end_row();
+
+                        for (i, p) in self.project_comparison.projects.iter().enumerate() {
+                            let color = OVERLAY_COLORS[i % OVERLAY_COLORS.len()];
+                            ui.colored_label(color, &p.label);
+                            ui.label(format!("{:.1}", p.project.state.particle_radius));
+                            ui.label(format!("{:.2}", p.project.state.n_particle_real));
+                            ui.label(format!("{:.2}", p.project.state.n_particle_imag));
+                            ui.label(format!("{:.2}", p.project.state.n_medium));
+                            match p.grid() {
+                                Some((first, last, n)) => {
+                                    ui.label(format!("{:.0}–{:.0}", first, last));
+                                    ui.label(n.to_string());
+                                }
+                                None => {
+                                    ui.label("—");
+                                    ui.label("0");
+                                }
+                            }
+                            if ui.small_button("✖").clicked() {
+                                remove_index = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(10.0);
+
+                Plot::new("compare_projects_plot")
+                    .legend(Legend::default().position(Corner::RightTop))
+                    .x_axis_label(self.t("Wavelength (nm)", "Longitud de onda (nm)"))
+                    .y_axis_label("Q_ext")
+                    .height(350.0)
+                    .show(ui, |plot_ui| {
+                        for (i, p) in self.project_comparison.projects.iter().enumerate() {
+                            let color = OVERLAY_COLORS[i % OVERLAY_COLORS.len()];
+                            plot_ui.line(overlay_line(p.q_ext_points().into(), color, p.label.clone(), self.appearance.line_width(1.5)));
+                        }
+                    });
+            });
+
+        if let Some(path) = add_path {
+            let added = self.project_comparison.add_file(&path);
+            self.finish_project_add(path, added);
+        }
+        if let Some(i) = remove_index {
+            self.project_comparison.remove(i);
+        }
+        if !open {
+            self.show_compare_projects = false;
+        }
+
+        self.draw_newer_project_prompt(ctx);
+    }
+
+    fn finish_project_add(&mut self, path: String, added: Result<(), ProjectError>) {
+        match added {
+            Ok(()) => {
+                self.add_log(&format!("📂 {}: {}", self.t("Project added", "Proyecto agregado"), path));
+                self.compare_path_input.clear();
+            }
+            Err(ProjectError::NewerVersion { found, .. }) => {
+                self.newer_project_prompt = Some((path, found));
+            }
+            Err(e) => {
+                let error_msg = format!("{} {}: {}", self.t("Cannot load", "No se puede cargar"), path, e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    /// Ask whether to try loading a project written by a newer format
+    fn draw_newer_project_prompt(&mut self, ctx: &Context) {
+        let Some((path, found)) = self.newer_project_prompt.clone() else {
+            return;
+        };
+        let mut decision: Option<bool> = None;
+
+        egui::Window::new(self.t("⚠ Newer Project Format", "⚠ Formato de Proyecto Más Reciente"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} {} ({} {}, {} {}).",
+                    path,
+                    self.t(
+                        "was saved by a newer version of NanoCalc",
+                        "fue guardado por una versión más reciente de NanoCalc"
+                    ),
+                    self.t("format", "formato"),
+                    found,
+                    self.t("supported", "soportado"),
+                    SchemaVersion::CURRENT
+                ));
+                ui.label(self.t(
+                    "A best-effort load keeps the fields this version knows and drops the rest. Results may be incomplete.",
+                    "Una carga aproximada conserva los campos que esta versión conoce y descarta el resto. Los resultados pueden estar incompletos."
+                ));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button(self.t("Load anyway", "Cargar de todos modos")).clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button(self.t("Cancel", "Cancelar")).clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+
+        match decision {
+            Some(true) => {
+                self.newer_project_prompt = None;
+                let added = self.project_comparison.add_file_best_effort(&path);
+                self.finish_project_add(path, added);
+            }
+            Some(false) => self.newer_project_prompt = None,
+            None => {}
+        }
+    }
+
+    fn draw_constants_inspector(&mut self, ctx: &Context) {
+        use crate::core::constants::{self, compound, M_E};
+
+        let mut open = true;
+
+        egui::Window::new(self.t("Physics Constant Inspector", "Inspector de Constantes Físicas"))
+            .collapsible(true)
+            .resizable(true)
+            .default_width(700.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("constants_grid")
+                            .num_columns(5)
+                            .spacing([15.0, 6.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(self.t("Symbol", "Símbolo"));
+                                ui.strong(self.t("Name", "Nombre"));
+                                ui.strong(self.t("Value", "Valor"));
+                                ui.strong(self.t("Unit", "Unidad"));
+                                ui.strong(self.t("Description", "Descripción"));
+                                ui.end_row();
+
+                                for c in constants::describe() {
+                                    ui.label(c.symbol);
+                                    ui.monospace(c.name);
+                                    ui.monospace(format!("{:.10e}", c.value));
+                                    ui.label(c.unit);
+                                    ui.colored_label(Color32::GRAY, c.description);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(5.0);
+                ui.strong(self.t("Derived helpers", "Funciones derivadas"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("ℏω_p:");
+                    ui.add(egui::DragValue::new(&mut self.inspector_plasma_ev)
+                        .speed(0.05)
+                        .range(0.01..=50.0)
+                        .suffix(" eV"));
+                    ui.label("→ λ_p =");
+                    ui.colored_label(
+                        Color32::from_rgb(100, 255, 180),
+                        format!("{:.2} nm", compound::plasma_wavelength_nm(self.inspector_plasma_ev))
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Mass:", "Masa:"));
+                    ui.add(egui::DragValue::new(&mut self.inspector_mass_me)
+                        .speed(0.01)
+                        .range(0.001..=1.0e6)
+                        .suffix(" m_e"));
+                    ui.label("→ λ_th(300 K) =");
+                    ui.colored_label(
+                        Color32::from_rgb(100, 255, 180),
+                        format!("{:.3} nm", compound::thermal_de_broglie_nm(self.inspector_mass_me * M_E))
+                    );
+                });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(5.0);
+                self.draw_constant_overrides(ui);
+            });
+
+        if !open {
+            self.show_constants_inspector = false;
+        }
+    }
+
+    /// What-if scale factors for c, h, e and k_B, threaded through the state's constants
+    fn draw_constant_overrides(&mut self, ui: &mut egui::Ui) {
+        let heading = self.t("What-if constants (non-physical)", "Constantes hipotéticas (no físicas)");
+        let hint = self.t(
+            "Scale constants to explore dimensional reasoning. Photon energies, k_BT, the quantum-dot gap and the Callaway conductivity follow the scaled values; nothing is saved",
+            "Escalar constantes para explorar el razonamiento dimensional. Las energías de fotón, k_BT, el gap del punto cuántico y la conductividad de Callaway siguen los valores escalados; no se guarda nada"
+        );
+        let names: Vec<(ScalableConstant, String)> = ScalableConstant::ALL
+            .iter()
+            .map(|&c| {
+                let (en, es) = c.name();
+                (c, self.t(en, es))
+            })
+            .collect();
+        let reset_label = self.t("↺ Reset to CODATA", "↺ Restablecer CODATA");
+        let active_label = self.t(
+            "⚠ Non-physical overrides active",
+            "⚠ Modificaciones no físicas activas"
+        );
+
+        ui.strong(heading).on_hover_text(hint);
+        ui.add_space(5.0);
+        let constants = &mut self.state.constants;
+        egui::Grid::new("constant_overrides_grid")
+            .num_columns(4)
+            .spacing([15.0, 6.0])
+            .show(ui, |ui| {
+                for (constant, name) in names {
+                    ui.label(constant.symbol());
+                    ui.label(name);
+                    ui.add(egui::DragValue::new(constants.scale_mut(constant))
+                        .speed(0.01)
+                        .range(0.01..=100.0)
+                        .prefix("× "));
+                    ui.monospace(format!("{:.10e}", constants.value(constant)));
+                    ui.end_row();
+                }
+            });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(constants.is_overridden(), egui::Button::new(reset_label)).clicked() {
+                constants.reset();
+            }
+            if constants.is_overridden() {
+                ui.colored_label(Color32::from_rgb(255, 120, 120), active_label);
+            }
+        });
+    }
+
+    fn export_json(&mut self) {
+        if self.spectrum_results.is_empty() {
+            return;
+        }
+        
+        self.add_log(&self.t("💾 Exporting JSON...", "💾 Exportando JSON..."));
+        let exporter = JsonExporter::new(&self.state, self.provenance());
+        let error_label = self.t("Error exporting JSON", "Error exportando JSON");
+        self.write_spectrum_export(&exporter, "JSON", &error_label);
+    }
+
+    fn import_spectrum(&mut self, path: &str) {
+        match SpectrumFile::load(path) {
+            Ok(file) => {
+                let msg = self.t(
+                    &format!("📂 Imported {} points from {}", file.spectrum_data.len(), path),
+                    &format!("📂 Importados {} puntos de {}", file.spectrum_data.len(), path)
+                );
+                self.add_log(&msg);
+                self.imported_spectrum = Some(file);
+                self.confirm_copy_parameters = false;
+            }
+            Err(e) => {
+                let error_msg = format!("Import error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    /// Calculate every row of a batch CSV into the run history
+    fn run_batch(&mut self, path: &str) {
+        let rows = match controller::read_batch(path) {
+            Ok(rows) => rows,
+            Err(e) => {
+                let error_msg = format!("Batch import error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+                return;
+            }
+        };
+        match controller::calculate_batch(&self.state, &rows, &mut self.run_history) {
+            Ok(results) => {
+                let msg = self.t(
+                    &format!("✅ Batch: {} rows calculated, see Run History", results.len()),
+                    &format!("✅ Lote: {} filas calculadas, ver Historial", results.len())
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Batch calculation error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    fn export_batch_template(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use std::env;
+
+            let Some(filename) = self.export_path("_batch_template", "csv") else { return; };
+
+            match controller::write_batch_template(&filename) {
+                Ok(()) => {
+                    let full_path = env::current_dir().map(|dir| dir.join(&filename)).unwrap_or(filename);
+                    self.add_log(&format!("✅ {}: {}", self.t("Batch template", "Plantilla de lote"), full_path.display()));
+                }
+                Err(e) => {
+                    self.add_log(&format!("❌ {}: {}", self.t("Error writing batch template", "Error escribiendo plantilla de lote"), e));
+                }
+            }
+        }
+    }
+
+    /// Overwrite the inputs with the imported file's metadata
+    fn copy_imported_parameters(&mut self) {
+        let Some(file) = &self.imported_spectrum else {
+            return;
+        };
+        let missing = file.metadata.apply_to(&mut self.state);
+        self.add_log(&self.t(
+            "⬅ Inputs set from imported metadata",
+            "⬅ Entradas fijadas desde los metadatos importados"
+        ));
+        if !missing.is_empty() {
+            let msg = self.t(
+                &format!("⚠ Missing in file, left unchanged: {}", missing.join(", ")),
+                &format!("⚠ Ausentes en el archivo, sin cambios: {}", missing.join(", "))
+            );
+            self.add_log(&msg);
+        }
+    }
+
+    fn draw_history(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut export = false;
+
+        egui::Window::new(self.t("Run History", "Historial de Cálculos"))
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Keep last", "Conservar últimos"));
+                    let mut limit = self.state.history_limit;
+                    if ui.add(egui::DragValue::new(&mut limit).range(1..=10_000)).changed() {
+                        self.state.history_limit = limit;
+                        self.run_history.set_limit(limit);
+                    }
+                    ui.label(self.t("runs", "cálculos"));
+                    ui.separator();
+                    if ui.add_enabled(!self.run_history.is_empty(), egui::Button::new(self.t("💾 Export CSV", "💾 Exportar CSV"))).clicked() {
+                        export = true;
+                    }
+                    if ui.add_enabled(!self.run_history.is_empty(), egui::Button::new(self.t("Clear", "Borrar"))).clicked() {
+                        self.run_history.clear();
+                    }
+                });
+                ui.add_space(5.0);
+
+                if self.run_history.is_empty() {
+                    ui.colored_label(Color32::GRAY, self.t("No calculations yet", "Aún no hay cálculos"));
+                    return;
+                }
+
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("run_history_grid")
+                        .num_columns(8)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.strong(self.t("Time", "Hora"));
+                            ui.strong(self.t("Model", "Modelo"));
+                            ui.strong("r (nm)");
+                            ui.strong("λ (nm)");
+                            ui.strong("n + ik");
+                            ui.strong(self.t("Points", "Puntos"));
+                            ui.strong("ms");
+                            ui.strong(self.t("Peak (nm)", "Pico (nm)"));
+                            ui.end_row();
+
+                            for r in self.run_history.records().rev() {
+                                let i = &r.inputs;
+                                ui.label(r.time_of_day());
+                                ui.label(&r.model);
+                                ui.label(format!("{:.1}", i.particle_radius));
+                                ui.label(format!("{:.1}", i.wavelength));
+                                ui.label(format!("{:.3} + {:.3}i", i.n_particle_real, i.n_particle_imag));
+                                ui.label(r.n_points.to_string());
+                                ui.label(format!("{:.2}", r.duration_ms));
+                                ui.label(r.peak_wavelength.map(|w| format!("{:.1}", w)).unwrap_or_default());
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if export {
+            self.export_history();
+        }
+        if !open {
+            self.show_history = false;
+        }
+    }
+
+    fn draw_import_dialog(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut load_path: Option<String> = None;
+        let mut batch_path: Option<String> = None;
+        let mut template = false;
+        let mut copy = false;
+
+        egui::Window::new(self.t("Import Spectrum", "Importar Espectro"))
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.set_min_width(420.0);
+                ui.horizontal(|ui| {
+                    ui.label(self.t("JSON file:", "Archivo JSON:"));
+                    ui.text_edit_singleline(&mut self.import_path_input);
+                    if ui.button(self.t("Load", "Cargar")).clicked() && !self.import_path_input.trim().is_empty() {
+                        load_path = Some(self.import_path_input.trim().to_string());
+                    }
+                    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+                    if ui.button(self.t("📂 Browse…", "📂 Examinar…")).clicked() {
+                        if let Some(path) = self.file_dialogs.open("JSON", &["json"]) {
+                            self.import_path_input = path.display().to_string();
+                            load_path = Some(self.import_path_input.clone());
+                        }
+                    }
+                });
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(self.t("Batch CSV:", "CSV por lotes:"));
+                    ui.text_edit_singleline(&mut self.batch_path_input);
+                    if ui.button(self.t("▶ Run", "▶ Ejecutar"))
+                        .on_hover_text(self.t(
+                            "Calculate one point per row; results go to the run history",
+                            "Calcular un punto por fila; los resultados van al historial"
+                        ))
+                        .clicked() && !self.batch_path_input.trim().is_empty()
+                    {
+                        batch_path = Some(self.batch_path_input.trim().to_string());
+                    }
+                    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+                    if ui.button(self.t("📂 Browse…", "📂 Examinar…")).clicked() {
+                        if let Some(path) = self.file_dialogs.open("CSV", &["csv"]) {
+                            self.batch_path_input = path.display().to_string();
+                            batch_path = Some(self.batch_path_input.clone());
+                        }
+                    }
+                    if ui.button(self.t("📄 Download Template", "📄 Descargar Plantilla"))
+                        .on_hover_text(self.t(
+                            "Empty batch CSV with the expected header, unit comments and a commented example row",
+                            "CSV por lotes vacío con la cabecera esperada, comentarios de unidades y una fila de ejemplo comentada"
+                        ))
+                        .clicked()
+                    {
+                        template = true;
+                    }
+                });
+
+                let Some(file) = &self.imported_spectrum else {
+                    return;
+                };
+                ui.add_space(10.0);
+                ui.strong(self.t("File metadata", "Metadatos del archivo"));
+                let not_recorded = self.t("not recorded", "no registrado");
+                let show = |v: Option<f64>, unit: &str| match v {
+                    Some(v) => format!("{}{}", v, unit),
+                    None => not_recorded.clone(),
+                };
+                egui::Grid::new("import_metadata_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 4.0])
+                    .show(ui, |ui| {
+                        let meta = &file.metadata;
+                        ui.label(self.t("Radius:", "Radio:"));
+                        ui.label(show(meta.particle_radius, " nm"));
+                        ui.end_row();
+                        ui.label("n:");
+                        ui.label(show(meta.n_particle_real, ""));
+                        ui.end_row();
+                        ui.label("k:");
+                        ui.label(show(meta.n_particle_imag, ""));
+                        ui.end_row();
+                        ui.label("n_medium:");
+                        ui.label(show(meta.n_medium, ""));
+                        ui.end_row();
+                        ui.label("λ:");
+                        ui.label(show(meta.wavelength, " nm"));
+                        ui.end_row();
+                        ui.label(self.t("Points:", "Puntos:"));
+                        ui.label(file.spectrum_data.len().to_string());
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+                if !self.confirm_copy_parameters {
+                    if ui.button(self.t("⬅ Copy parameters to inputs", "⬅ Copiar parámetros a las entradas"))
+                        .on_hover_text(self.t(
+                            "Set radius, indices, medium and wavelength from this file",
+                            "Fijar radio, índices, medio y longitud de onda desde este archivo"
+                        ))
+                        .clicked()
+                    {
+                        self.confirm_copy_parameters = true;
+                    }
+                } else {
+                    ui.colored_label(Color32::from_rgb(255, 200, 100), self.t(
+                        "This overwrites the current inputs. Continue?",
+                        "Esto sobrescribe las entradas actuales. ¿Continuar?"
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button(self.t("Overwrite", "Sobrescribir")).clicked() {
+                            copy = true;
+                        }
+                        if ui.button(self.t("Cancel", "Cancelar")).clicked() {
+                            self.confirm_copy_parameters = false;
+                        }
+                    });
+                }
+            });
+
+        if let Some(path) = load_path {
+            self.import_spectrum(&path);
+        }
+        if let Some(path) = batch_path {
+            self.run_batch(&path);
+        }
+        if template {
+            self.export_batch_template();
+        }
+        if copy {
+            self.copy_imported_parameters();
+            self.confirm_copy_parameters = false;
+        }
+        if !open {
+            self.show_import = false;
+            self.confirm_copy_parameters = false;
+        }
+    }
+
     fn draw_about_dialog(&mut self, ctx: &Context) {
-        egui::Window::new(&self.t("About NanoCalc", "Acerca de NanoCalc"))
+        egui::Window::new(self.t("About NanoCalc", "Acerca de NanoCalc"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -1109,8 +4556,8 @@ impl NanoCalcApp {
                 
                 // Close button
                 ui.vertical_centered(|ui| {
-                    if ui.button(egui::RichText::new(&self.t("Close", "Cerrar")).size(14.0))
-                        .on_hover_text(&self.t("Close this dialog", "Cerrar este diálogo")).clicked() {
+                    if ui.button(egui::RichText::new(self.t("Close", "Cerrar")).size(14.0))
+                        .on_hover_text(self.t("Close this dialog", "Cerrar este diálogo")).clicked() {
                         self.show_about = false;
                     }
                 });
@@ -1138,11 +4585,21 @@ impl NanoCalcApp {
                         .color(Color32::GRAY));
                     
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button(&self.t("Close", "Cerrar")).clicked() {
+                        if ui.button(self.t("Close", "Cerrar")).clicked() {
                             self.show_periodic_table = false;
                         }
+                        ui.add(egui::DragValue::new(&mut self.periodic_decimals).range(1..=5));
+                        ui.label(self.t("Decimals:", "Decimales:"));
                     });
                 });
+                self.draw_reference_wavelength(ui);
+                ui.horizontal(|ui| {
+                    ui.colored_label(METALLIC_TINT, "■");
+                    ui.label(self.t("Metallic (Re ε < 0)", "Metálico (Re ε < 0)"));
+                    ui.colored_label(DIELECTRIC_TINT, "■");
+                    ui.label(self.t("Dielectric (Re ε ≥ 0)", "Dieléctrico (Re ε ≥ 0)"));
+                    ui.colored_label(Color32::GRAY, self.t("· untinted = fixed 550 nm constants", "· sin tinte = constantes fijas a 550 nm"));
+                });
                 
                 ui.add_space(10.0);
                 ui.separator();
@@ -1190,20 +4647,42 @@ impl NanoCalcApp {
                                  ("", 0, ""), ("", 0, ""), ("", 0, ""), ("", 0, "")],
                         ];
                         
+                        let wavelength = self.state.snapshot_wavelength();
+                        let decimals = self.periodic_decimals;
+                        let metallic_label = self.t("Metallic", "Metálico");
+                        let dielectric_label = self.t("Dielectric", "Dieléctrico");
+                        let absorbing_label = self.t("Absorbing dielectric", "Dieléctrico absorbente");
                         for row in &elements {
                             ui.horizontal(|ui| {
                                 for (symbol, atomic_num, name) in row {
                                     if !symbol.is_empty() && *atomic_num > 0 {
-                                        let button = egui::Button::new(
+                                        let props = Self::get_element_properties(symbol, name, *atomic_num, wavelength);
+                                        let class = MaterialClass::from_permittivity(
+                                            RefractiveIndex::new(props.n_real, props.n_imag).to_permittivity()
+                                        );
+                                        let mut button = egui::Button::new(
                                             egui::RichText::new(format!("{}\n{}", symbol, atomic_num))
                                                 .size(11.0)
                                         )
                                         .min_size(egui::vec2(45.0, 45.0));
+                                        let mut hover = format!("{} (Z={})", name, atomic_num);
+                                        if props.from_database {
+                                            let (tint, class_label) = match class {
+                                                MaterialClass::Metallic => (METALLIC_TINT, &metallic_label),
+                                                MaterialClass::Dielectric => (DIELECTRIC_TINT, &dielectric_label),
+                                                MaterialClass::AbsorbingDielectric => (ABSORBING_TINT, &absorbing_label),
+                                            };
+                                            button = button.fill(tint);
+                                            hover.push_str(&format!(
+                                                "\nn = {:.*}, k = {:.*} @ {:.1} nm\n{}",
+                                                decimals, props.n_real, decimals, props.n_imag, props.wavelength, class_label
+                                            ));
+                                        }
                                         
                                         if ui.add(button)
-                                            .on_hover_text(format!("{} (Z={})", name, atomic_num))
+                                            .on_hover_text(hover)
                                             .clicked() {
-                                            self.selected_element = Some(Self::get_element_properties(symbol, name, *atomic_num));
+                                            self.selected_element = Some(props);
                                             self.show_element_properties = true;
                                             self.show_periodic_table = false;
                                         }
@@ -1234,7 +4713,7 @@ impl NanoCalcApp {
 
     fn draw_element_properties(&mut self, ctx: &Context) {
         if let Some(element) = self.selected_element.clone() {
-            egui::Window::new(&self.t(
+            egui::Window::new(self.t(
                 &format!("Element Properties - {}", element.symbol),
                 &format!("Propiedades del Elemento - {}", element.symbol)
             ))
@@ -1257,7 +4736,7 @@ impl NanoCalcApp {
                             .size(20.0)
                             .color(Color32::GRAY));
                         
-                        ui.label(egui::RichText::new(&format!("{}: {}",
+                        ui.label(egui::RichText::new(format!("{}: {}",
                             self.t("Atomic Number", "Número Atómico"),
                             element.atomic_number
                         ))
@@ -1270,9 +4749,9 @@ impl NanoCalcApp {
                     });
                     
                     // Optical properties section
-                    ui.label(egui::RichText::new(&self.t(
-                        "Optical Properties (@ 550 nm):",
-                        "Propiedades Ópticas (@ 550 nm):"
+                    ui.label(egui::RichText::new(self.t(
+                        &format!("Optical Properties (@ {:.1} nm):", element.wavelength),
+                        &format!("Propiedades Ópticas (@ {:.1} nm):", element.wavelength)
                     )).strong().size(16.0));
                     
                     ui.add_space(10.0);
@@ -1281,20 +4760,20 @@ impl NanoCalcApp {
                         .num_columns(2)
                         .spacing([20.0, 12.0])
                         .show(ui, |ui| {
-                            ui.label(egui::RichText::new(&self.t(
+                            ui.label(egui::RichText::new(self.t(
                                 "Refractive Index (Real):",
                                 "Índice de Refracción (Real):"
                             )).size(14.0));
-                            ui.label(egui::RichText::new(&format!("{:.3}", element.n_real))
+                            ui.label(egui::RichText::new(format!("{:.3}", element.n_real))
                                 .size(14.0)
                                 .color(Color32::from_rgb(100, 255, 150)));
                             ui.end_row();
                             
-                            ui.label(egui::RichText::new(&self.t(
+                            ui.label(egui::RichText::new(self.t(
                                 "Refractive Index (Imaginary):",
                                 "Índice de Refracción (Imaginaria):"
                             )).size(14.0));
-                            ui.label(egui::RichText::new(&format!("{:.3}", element.n_imag))
+                            ui.label(egui::RichText::new(format!("{:.3}", element.n_imag))
                                 .size(14.0)
                                 .color(Color32::from_rgb(100, 255, 150)));
                             ui.end_row();
@@ -1308,10 +4787,18 @@ impl NanoCalcApp {
                         .rounding(Rounding::same(6.0))
                         .inner_margin(egui::Margin::same(12.0))
                         .show(ui, |ui| {
-                            ui.label(egui::RichText::new(&self.t(
-                                "Note: These are approximate optical properties at 550 nm wavelength. Actual values may vary with wavelength and material form.",
-                                "Nota: Estas son propiedades ópticas aproximadas a 550 nm de longitud de onda. Los valores reales pueden variar con la longitud de onda y la forma del material."
-                            ))
+                            let note = if element.from_database {
+                                self.t(
+                                    "Note: Interpolated from built-in tabulated data at the current wavelength. Values may vary with material form.",
+                                    "Nota: Interpolado de datos tabulados incorporados a la longitud de onda actual. Los valores pueden variar con la forma del material."
+                                )
+                            } else {
+                                self.t(
+                                    "Note: These are approximate optical properties at 550 nm wavelength. Actual values may vary with wavelength and material form.",
+                                    "Nota: Estas son propiedades ópticas aproximadas a 550 nm de longitud de onda. Los valores reales pueden variar con la longitud de onda y la forma del material."
+                                )
+                            };
+                            ui.label(egui::RichText::new(note)
                                 .size(12.0)
                                 .color(Color32::GRAY));
                         });
@@ -1320,16 +4807,40 @@ impl NanoCalcApp {
                     ui.separator();
                     ui.add_space(15.0);
                     
+                    let particle_label = self.t("Particle", "Partícula");
+                    let medium_label = self.t("Medium", "Medio");
+                    ui.horizontal(|ui| {
+                        ui.label(self.t("Apply to:", "Aplicar a:"));
+                        ui.selectable_value(&mut self.apply_as_medium, false, particle_label);
+                        ui.selectable_value(&mut self.apply_as_medium, true, medium_label);
+                    });
+                    if self.apply_as_medium && element.n_imag > 0.0 {
+                        ui.colored_label(Color32::from_rgb(255, 200, 100), self.t(
+                            "⚠ k will be discarded: the medium must be non-absorbing",
+                            "⚠ k se descartará: el medio debe ser no absorbente"
+                        ));
+                    }
+                    ui.add_space(10.0);
+
                     // Action buttons
                     ui.horizontal(|ui| {
-                        let apply_text = self.t(
-                            "Apply Properties",
-                            "Aplicar Propiedades"
-                        );
-                        let apply_tooltip = self.t(
-                            "Apply these optical properties to the particle",
-                            "Aplicar estas propiedades ópticas a la partícula"
-                        );
+                        let (apply_text, apply_tooltip) = if self.apply_as_medium {
+                            (
+                                self.t("Apply as Medium", "Aplicar como Medio"),
+                                self.t(
+                                    "Use the real part of this index as the surrounding medium",
+                                    "Usar la parte real de este índice como medio circundante"
+                                ),
+                            )
+                        } else {
+                            (
+                                self.t("Apply as Particle", "Aplicar a Partícula"),
+                                self.t(
+                                    "Apply these optical properties to the particle",
+                                    "Aplicar estas propiedades ópticas a la partícula"
+                                ),
+                            )
+                        };
                         
                         if ui.add_sized(
                             [200.0, 35.0],
@@ -1338,8 +4849,31 @@ impl NanoCalcApp {
                         .on_hover_text(&apply_tooltip)
                         .clicked()
                         {
-                            self.state.n_particle_real = element.n_real;
-                            self.state.n_particle_imag = element.n_imag;
+                            if self.apply_as_medium {
+                                let index = RefractiveIndex::new(element.n_real, element.n_imag);
+                                self.warnings.retain(|w| w.code != WarningCode::MediumAbsorptionDiscarded);
+                                match self.state.apply_medium_index(index) {
+                                    Ok(Some(warning)) => {
+                                        self.add_log(&format!("⚠️ {}", self.warning_text(&warning)));
+                                        self.warnings.push(warning);
+                                    }
+                                    Ok(None) => {}
+                                    Err(e) => {
+                                        let msg = self.t(
+                                            &format!("❌ {} cannot be the medium: {}", element.symbol, e),
+                                            &format!("❌ {} no puede ser el medio: {}", element.symbol, e),
+                                        );
+                                        self.error_message = Some(msg.clone());
+                                        self.add_log(&msg);
+                                    }
+                                }
+                            } else {
+                                self.state.n_particle_real = element.n_real;
+                                self.state.n_particle_imag = element.n_imag;
+                                self.dataset_bounds = element.dataset;
+                                self.active_dispersion = database::element(&element.symbol)
+                                    .map(|material| Arc::new(material) as Arc<dyn Dispersion>);
+                            }
                             self.show_element_properties = false;
                         }
                         
@@ -1361,10 +4895,255 @@ impl NanoCalcApp {
         }
     }
     
+    fn apply_compute_threads(&mut self) {
+        match ComputeEngine::new(self.state.compute_threads) {
+            Ok(engine) => {
+                self.engine = engine;
+                let msg = self.t(
+                    &format!("⚙ Compute threads: {}", self.engine.num_threads()),
+                    &format!("⚙ Hilos de cálculo: {}", self.engine.num_threads())
+                );
+                self.add_log(&msg);
+            }
+            Err(e) => {
+                let error_msg = format!("Thread pool error: {}", e);
+                self.error_message = Some(error_msg.clone());
+                self.add_log(&format!("❌ {}", error_msg));
+            }
+        }
+    }
+
+    /// Name of the active accuracy profile, or "Custom"
+    fn profile_label(&self) -> String {
+        match self.state.solver.active_profile() {
+            Some(profile) => {
+                let (en, es) = profile.name();
+                self.t(en, es)
+            }
+            None => self.t("Custom", "Personalizado"),
+        }
+    }
+
+    /// Pick the optical model used by Calculate and the spectrum
+    fn draw_model_selector(&mut self, ui: &mut egui::Ui) {
+        let models: Vec<(OpticalModelKind, String)> = OpticalModelKind::ALL
+            .iter()
+            .map(|&kind| {
+                let (en, es) = kind.name();
+                (kind, self.t(en, es))
+            })
+            .collect();
+        let (en, es) = self.state.model_kind.name();
+        let current_label = self.t(en, es);
+
+        ui.horizontal(|ui| {
+            ui.label(self.t("Model:", "Modelo:"));
+            egui::ComboBox::from_id_salt("optical_model")
+                .selected_text(current_label)
+                .show_ui(ui, |ui| {
+                    for (kind, label) in models {
+                        ui.selectable_value(&mut self.state.model_kind, kind, label);
+                    }
+                });
+        });
+    }
+
+    fn draw_profile_selector(&mut self, ui: &mut egui::Ui) {
+        let mut selected = self.state.solver.active_profile();
+        let current_label = self.profile_label();
+        let profiles: Vec<(ComputeProfile, String)> = ComputeProfile::ALL
+            .iter()
+            .map(|&p| {
+                let (en, es) = p.name();
+                (p, self.t(en, es))
+            })
+            .collect();
+
+        egui::ComboBox::from_id_salt("accuracy_profile")
+            .selected_text(current_label)
+            .show_ui(ui, |ui| {
+                for (profile, label) in profiles {
+                    ui.selectable_value(&mut selected, Some(profile), label);
+                }
+            });
+        if let Some(profile) = selected {
+            if self.state.solver.active_profile() != Some(profile) {
+                self.state.solver = profile.settings();
+            }
+            let (en, es) = profile.description();
+            ui.colored_label(Color32::GRAY, self.t(en, es));
+        } else {
+            ui.colored_label(
+                Color32::from_rgb(255, 200, 100),
+                self.t("Advanced settings edited by hand", "Configuración avanzada editada manualmente")
+            );
+        }
+
+        let advanced_label = self.t("Advanced", "Avanzado");
+        let method_labels = [
+            (SolverMethod::Rayleigh, self.t("Rayleigh", "Rayleigh")),
+            (SolverMethod::Auto, self.t("Auto", "Automático")),
+            (SolverMethod::FullMie, self.t("Full Mie", "Mie completo")),
+        ];
+        let method_label = self.t("Method:", "Método:");
+        let terms_label = self.t("Max terms (n_max):", "Términos máx. (n_max):");
+        let tolerance_label = self.t("Tolerance:", "Tolerancia:");
+        let step_label = self.t("Grid step:", "Paso de malla:");
+        let refine_label = self.t("Adaptive refinement", "Refinamiento adaptativo");
+        let target_label = self.t("Compute to target accuracy", "Calcular hasta precisión objetivo");
+        let target_hint = self.t(
+            "Doubles the sampling until the curve changes by less than this fraction of its peak. Replaces the grid step",
+            "Duplica el muestreo hasta que la curva cambie menos que esta fracción de su pico. Sustituye al paso de malla"
+        );
+        let solver = &mut self.state.solver;
+        ui.collapsing(advanced_label, |ui| {
+            egui::Grid::new("solver_settings_grid")
+                .num_columns(2)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(method_label);
+                    ui.horizontal(|ui| {
+                        for (method, label) in method_labels {
+                            ui.selectable_value(&mut solver.method, method, label);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(terms_label);
+                    ui.add(egui::DragValue::new(&mut solver.max_terms).range(1..=5000));
+                    ui.end_row();
+
+                    ui.label(tolerance_label);
+                    ui.add(egui::DragValue::new(&mut solver.tolerance)
+                        .speed(1e-7)
+                        .range(1e-14..=1e-2)
+                        .custom_formatter(|v, _| format!("{:.0e}", v)));
+                    ui.end_row();
+
+                    ui.label(step_label);
+                    ui.add(egui::DragValue::new(&mut solver.grid_step_nm)
+                        .speed(0.5)
+                        .range(0.1..=50.0)
+                        .suffix(" nm"));
+                    ui.end_row();
+                });
+            ui.checkbox(&mut solver.adaptive_refinement, refine_label);
+
+            let mut use_target = solver.target_accuracy.is_some();
+            let mut target = solver.target_accuracy.unwrap_or(1e-3);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut use_target, target_label).on_hover_text(&target_hint);
+                ui.add_enabled(use_target, egui::DragValue::new(&mut target)
+                    .speed(1e-5)
+                    .range(1e-6..=0.1)
+                    .custom_formatter(|v, _| format!("{:.0e}", v)));
+            });
+            solver.target_accuracy = use_target.then_some(target);
+        });
+    }
+
+    fn draw_settings_dialog(&mut self, ctx: &Context) {
+        let mut open = true;
+        let mut apply = false;
+
+        egui::Window::new(self.t("Settings", "Configuración"))
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.set_min_width(350.0);
+                ui.add_space(10.0);
+
+                ui.strong(self.t("Parallel Compute", "Cálculo Paralelo"));
+                ui.add_space(5.0);
+
+                let available = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+                let mut limit = self.state.compute_threads.is_some();
+                let limit_label = self.t("Limit worker threads", "Limitar hilos de trabajo");
+                ui.checkbox(&mut limit, limit_label)
+                    .on_hover_text(self.t(
+                        "Cap thread usage on shared machines. Unchecked = all cores",
+                        "Limitar el uso de hilos en máquinas compartidas. Sin marcar = todos los núcleos"
+                    ));
+
+                let mut threads = self.state.compute_threads.unwrap_or(available);
+                ui.add_enabled_ui(limit, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(self.t("Threads:", "Hilos:"));
+                        ui.add(egui::DragValue::new(&mut threads).range(1..=available.max(1)));
+                    });
+                });
+                self.state.compute_threads = if limit { Some(threads) } else { None };
+
+                ui.colored_label(
+                    Color32::GRAY,
+                    format!("{}: {}", self.t("Active threads", "Hilos activos"), self.engine.num_threads())
+                );
+
+                let stream_label = self.t("Stream spectrum as it computes", "Mostrar el espectro mientras se calcula");
+                ui.checkbox(&mut self.state.stream_spectrum, stream_label)
+                    .on_hover_text(self.t(
+                        "Fill the plot in chunks as the background run computes them. Not used with adaptive refinement or a target accuracy",
+                        "Rellenar el gráfico por bloques mientras se calcula en segundo plano. No se usa con refinamiento adaptativo ni precisión objetivo"
+                    ));
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.strong(self.t("Accuracy Profile", "Perfil de Precisión"));
+                ui.add_space(5.0);
+                self.draw_profile_selector(ui);
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.strong(self.t("Accessibility", "Accesibilidad"));
+                ui.add_space(5.0);
+                let mut appearance = self.appearance;
+                let contrast_label = self.t("High-contrast theme", "Tema de alto contraste");
+                ui.checkbox(&mut appearance.high_contrast, contrast_label);
+                ui.horizontal(|ui| {
+                    ui.label(self.t("UI scale:", "Escala de la interfaz:"));
+                    ui.add(egui::Slider::new(&mut appearance.ui_scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+                        .step_by(0.05)
+                        .suffix("×"))
+                        .on_hover_text(self.t(
+                            "Scales fonts and plot line widths. Saved between sessions",
+                            "Escala fuentes y grosor de líneas. Se guarda entre sesiones"
+                        ));
+                });
+                if appearance != self.appearance {
+                    self.appearance = appearance;
+                    Self::configure_style(ui.ctx(), &appearance);
+                }
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.vertical_centered(|ui| {
+                    if ui.button(self.t("Apply", "Aplicar")).clicked() {
+                        apply = true;
+                    }
+                });
+            });
+
+        if apply {
+            self.apply_compute_threads();
+            self.show_settings = false;
+        }
+        if !open {
+            self.show_settings = false;
+        }
+    }
+
     fn draw_export_dialog(&mut self, ctx: &Context) {
         let mut open = true;
         
-        egui::Window::new(&self.t("Export Data", "Exportar Datos"))
+        egui::Window::new(self.t("Export Data", "Exportar Datos"))
             .collapsible(false)
             .resizable(false)
             .open(&mut open)
@@ -1375,9 +5154,16 @@ impl NanoCalcApp {
                 ui.add_space(10.0);
                 
                 let export_label = match self.export_type {
-                    ExportType::CSV => self.t("Export to CSV", "Exportar a CSV"),
-                    ExportType::JSON => self.t("Export to JSON", "Exportar a JSON"),
-                    ExportType::PNG => self.t("Export to PNG", "Exportar a PNG"),
+                    ExportType::Csv => self.t("Export to CSV", "Exportar a CSV"),
+                    ExportType::Json => self.t("Export to JSON", "Exportar a JSON"),
+                    ExportType::Png => self.t("Export to PNG", "Exportar a PNG"),
+                    ExportType::Comparison => self.t("Export Comparison CSV", "Exportar CSV de Comparación"),
+                    ExportType::Project => self.t("Save Project", "Guardar Proyecto"),
+                    ExportType::MultipoleCsv => self.t("Export Multipoles CSV", "Exportar CSV de Multipolos"),
+                    ExportType::MultipoleJson => self.t("Export Multipoles JSON", "Exportar JSON de Multipolos"),
+                    ExportType::Bundle => self.t("Export Bundle", "Exportar Paquete"),
+                    ExportType::Normalized => self.t("Export Normalized Absorption", "Exportar Absorción Normalizada"),
+                    ExportType::NkText => self.t("Export n,k Table", "Exportar Tabla n,k"),
                 };
                 
                 ui.heading(export_label);
@@ -1385,7 +5171,7 @@ impl NanoCalcApp {
                 
                 // Filename input
                 ui.horizontal(|ui| {
-                    ui.label(&self.t("Filename:", "Nombre del archivo:"));
+                    ui.label(self.t("Filename:", "Nombre del archivo:"));
                     ui.text_edit_singleline(&mut self.export_filename);
                 });
                 
@@ -1393,9 +5179,16 @@ impl NanoCalcApp {
                 
                 // Show extension
                 let extension = match self.export_type {
-                    ExportType::CSV => ".csv",
-                    ExportType::JSON => ".json",
-                    ExportType::PNG => ".png",
+                    ExportType::Csv => ".csv",
+                    ExportType::Json => ".json",
+                    ExportType::Png => ".png",
+                    ExportType::Comparison => "_comparison.csv",
+                    ExportType::Project => ".ncp",
+                    ExportType::MultipoleCsv => "_multipoles.csv",
+                    ExportType::MultipoleJson => "_multipoles.json",
+                    ExportType::Bundle => "_bundle/",
+                    ExportType::Normalized => "_normalized.csv",
+                    ExportType::NkText => "_nk.txt",
                 };
                 
                 ui.colored_label(
@@ -1406,6 +5199,49 @@ impl NanoCalcApp {
                         extension
                     )
                 );
+
+                if self.export_type == ExportType::Csv {
+                    ui.add_space(5.0);
+                    ui.label(self.t("Columns:", "Columnas:"));
+                    ui.horizontal_wrapped(|ui| {
+                        for column in OpticalColumn::ALL {
+                            let mut selected = self.csv_columns.contains(&column);
+                            if ui.checkbox(&mut selected, column.header()).changed() {
+                                if selected {
+                                    self.csv_columns.insert(column);
+                                } else {
+                                    self.csv_columns.remove(&column);
+                                }
+                            }
+                        }
+                    });
+                    if self.csv_columns.is_empty() {
+                        ui.colored_label(
+                            Color32::from_rgb(255, 120, 120),
+                            self.t("Select at least one column", "Seleccione al menos una columna")
+                        );
+                    }
+                }
+
+                if self.export_type == ExportType::Png {
+                    ui.add_space(5.0);
+                    let caption_label = self.t(
+                        "Add parameter and peak caption below the plot",
+                        "Añadir leyenda de parámetros y pico bajo la gráfica"
+                    );
+                    ui.checkbox(&mut self.png_caption, caption_label);
+                }
+
+                if self.export_type == ExportType::NkText {
+                    ui.add_space(5.0);
+                    let unit_label = self.t("Wavelength column:", "Columna de longitud de onda:");
+                    ui.horizontal(|ui| {
+                        ui.label(unit_label);
+                        for unit in WavelengthUnit::ALL {
+                            ui.selectable_value(&mut self.nk_unit, unit, unit.symbol());
+                        }
+                    });
+                }
                 
                 ui.add_space(15.0);
                 ui.separator();
@@ -1413,12 +5249,13 @@ impl NanoCalcApp {
                 
                 // Buttons
                 ui.horizontal(|ui| {
-                    if ui.button(&self.t("Cancel", "Cancelar")).clicked() {
+                    if ui.button(self.t("Cancel", "Cancelar")).clicked() {
                         self.show_export_dialog = false;
                     }
                     
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button(&self.t("💾 Export", "💾 Exportar")).clicked() {
+                        let can_export = self.export_type != ExportType::Csv || !self.csv_columns.is_empty();
+                        if ui.add_enabled(can_export, egui::Button::new(self.t("💾 Export", "💾 Exportar"))).clicked() {
                             self.perform_export();
                             self.show_export_dialog = false;
                         }
@@ -1432,19 +5269,8 @@ impl NanoCalcApp {
     }
     
     fn add_log(&mut self, message: &str) {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        // Format timestamp as HH:MM:SS (UTC)
-        let secs = timestamp % 86400;
-        let hours = (secs / 3600) % 24;
-        let mins = (secs / 60) % 60;
-        let secs = secs % 60;
-        
-        let log_entry = format!("[{:02}:{:02}:{:02}] {}", hours, mins, secs, message);
+        // Timestamp as [HH:MM:SS] (UTC)
+        let log_entry = timestamped(self.clock.as_ref(), message);
         self.log_messages.push(log_entry);
         
         // Keep only last 100 messages
@@ -1455,9 +5281,16 @@ impl NanoCalcApp {
     
     fn perform_export(&mut self) {
         match self.export_type {
-            ExportType::CSV => self.export_csv(),
-            ExportType::JSON => self.export_json(),
-            ExportType::PNG => self.export_png(),
+            ExportType::Csv => self.export_csv(),
+            ExportType::Json => self.export_json(),
+            ExportType::Png => self.export_png(),
+            ExportType::Comparison => self.export_comparison(),
+            ExportType::Project => self.export_project(),
+            ExportType::MultipoleCsv => self.export_multipoles(false),
+            ExportType::MultipoleJson => self.export_multipoles(true),
+            ExportType::Bundle => self.export_bundle(),
+            ExportType::Normalized => self.export_normalized(),
+            ExportType::NkText => self.export_nk_table(),
         }
     }
     
@@ -1468,93 +5301,21 @@ impl NanoCalcApp {
         
         #[cfg(feature = "export_png")]
         {
-            use plotters::prelude::*;
+            use crate::plotting::caption_lines;
             use std::env;
             
             self.add_log(&self.t("📊 Generating PNG plot...", "📊 Generando gráfica PNG..."));
             
-            let filename = format!("{}.png", self.export_filename);
+            let Some(filename) = self.export_path("", "png") else { return; };
             
-            // Create drawing area
-            let root = BitMapBackend::new(&filename, (1200, 800)).into_drawing_area();
-            root.fill(&WHITE).ok();
-            
-            // Find min/max values for proper scaling
-            let mut y_min = f64::INFINITY;
-            let mut y_max = f64::NEG_INFINITY;
-            
-            for result in &self.spectrum_results {
-                y_min = y_min.min(result.q_sca).min(result.q_abs).min(result.q_ext);
-                y_max = y_max.max(result.q_sca).max(result.q_abs).max(result.q_ext);
-            }
-            
-            // Add 10% margin
-            let margin = (y_max - y_min) * 0.1;
-            y_min -= margin;
-            y_max += margin;
-            
-            let mut chart = ChartBuilder::on(&root)
-                .caption("Mie Scattering Spectrum", ("sans-serif", 40))
-                .margin(20)
-                .x_label_area_size(50)
-                .y_label_area_size(70)
-                .build_cartesian_2d(300.0..800.0, y_min..y_max)
-                .ok();
-            
-            if let Some(ref mut chart) = chart {
-                chart.configure_mesh()
-                    .x_desc("Wavelength (nm)")
-                    .y_desc("Efficiency Factor")
-                    .draw()
-                    .ok();
-                
-                // Draw Q_sca (blue)
-                chart.draw_series(LineSeries::new(
-                    self.spectrum_results.iter().map(|r| (r.wavelength, r.q_sca)),
-                    &BLUE,
-                )).ok()
-                    .and_then(|series| {
-                        series.label("Q_sca")
-                            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
-                        Some(())
-                    });
-                
-                // Draw Q_abs (red)
-                chart.draw_series(LineSeries::new(
-                    self.spectrum_results.iter().map(|r| (r.wavelength, r.q_abs)),
-                    &RED,
-                )).ok()
-                    .and_then(|series| {
-                        series.label("Q_abs")
-                            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
-                        Some(())
-                    });
-                
-                // Draw Q_ext (green)
-                chart.draw_series(LineSeries::new(
-                    self.spectrum_results.iter().map(|r| (r.wavelength, r.q_ext)),
-                    &GREEN,
-                )).ok()
-                    .and_then(|series| {
-                        series.label("Q_ext")
-                            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
-                        Some(())
-                    });
-                
-                chart.configure_series_labels()
-                    .background_style(&WHITE.mix(0.8))
-                    .border_style(&BLACK)
-                    .draw()
-                    .ok();
-                
-                root.present().ok();
-                
+            let caption = self.png_caption.then(|| caption_lines(&self.state, &self.spectrum_results));
+            if render_spectrum_png(&self.spectrum_results, &filename, caption.as_deref()) {
                 if let Ok(current_dir) = env::current_dir() {
                     let full_path = current_dir.join(&filename);
                     let msg = format!("✅ PNG: {}", full_path.display());
                     self.add_log(&msg);
                 } else {
-                    self.add_log(&format!("✅ PNG: {}", filename));
+                    self.add_log(&format!("✅ PNG: {}", filename.display()));
                 }
             } else {
                 self.add_log(&self.t("❌ Error creating PNG chart", "❌ Error creando gráfica PNG"));
@@ -1566,10 +5327,170 @@ impl NanoCalcApp {
             self.add_log(&self.t("📸 PNG export requires plotters crate", "📸 Exportar PNG requiere crate plotters"));
         }
     }
+
+    /// Provenance for exports of the current spectrum
+    fn provenance(&self) -> Provenance {
+        let model = build_optical_model(self.state.model_kind, &self.state, None)
+            .map(|model| model.name().to_string())
+            .unwrap_or_else(|_| self.state.model_kind.name().0.to_string());
+        Provenance::stamped(model, self.clock.as_ref())
+    }
+
+    fn export_bundle(&mut self) {
+        if self.spectrum_results.is_empty() {
+            return;
+        }
+
+        self.add_log(&self.t("📦 Writing export bundle...", "📦 Escribiendo paquete de exportación..."));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(dir) = self.export_path("_bundle", "") else { return; };
+            let project = Project::new(self.state.clone(), self.spectrum_results.clone());
+            let csv = to_csv(&ResultSet::Optical(self.spectrum_results.clone()), ResultKind::Optical);
+            let json = JsonExporter::new(&self.state, self.provenance()).export_to_vec(&self.spectrum_results);
+            let (project_json, csv, json) = match (project.to_json(), csv, json) {
+                (Ok(p), Ok(c), Ok(j)) => (p, c, j),
+                _ => {
+                    self.add_log(&self.t("❌ Error preparing bundle files", "❌ Error preparando archivos del paquete"));
+                    return;
+                }
+            };
+
+            #[cfg_attr(not(feature = "export_png"), allow(unused_mut))]
+            let mut files = vec![
+                BundleFile::new(format!("project.{}", PROJECT_EXTENSION), project_json),
+                BundleFile::new("spectrum.csv", csv),
+                BundleFile::new("spectrum.json", json),
+            ];
+
+            #[cfg(feature = "export_png")]
+            {
+                // Render to a scratch file, then bundle its bytes with the rest
+                let scratch = std::env::temp_dir().join(format!("nanocalc_bundle_{}.png", std::process::id()));
+                if render_spectrum_png(&self.spectrum_results, &scratch, None) {
+                    if let Ok(bytes) = std::fs::read(&scratch) {
+                        files.push(BundleFile::new("spectrum.png", bytes));
+                    }
+                }
+                let _ = std::fs::remove_file(&scratch);
+            }
+
+            let contents = BundleContents {
+                provenance: self.provenance(),
+                files,
+            };
+
+            match bundle::write(&dir, &contents) {
+                Ok(manifest) => {
+                    let shown = std::env::current_dir().map(|d| d.join(&dir)).unwrap_or(dir);
+                    let msg = self.t(
+                        &format!("✅ Bundle ({} files): {}", manifest.files.len(), shown.display()),
+                        &format!("✅ Paquete ({} archivos): {}", manifest.files.len(), shown.display())
+                    );
+                    self.add_log(&msg);
+                }
+                Err(e) => {
+                    self.add_log(&format!("❌ {}: {}", self.t("Error writing bundle", "Error escribiendo paquete"), e));
+                }
+            }
+        }
+    }
+}
+
+/// Draw the spectrum with plotters into a PNG at `path`, with optional
+/// caption lines laid out beneath the chart
+#[cfg(feature = "export_png")]
+fn render_spectrum_png(results: &[OpticalResult], path: &std::path::Path, caption: Option<&[String]>) -> bool {
+    use plotters::prelude::*;
+    use crate::plotting::caption::{image_size, CAPTION_LINE_HEIGHT, CAPTION_PADDING};
+
+    const CHART_SIZE: (u32, u32) = (1200, 800);
+
+    // Create drawing area, taller when a caption is requested
+    let image = BitMapBackend::new(path, image_size(CHART_SIZE, caption)).into_drawing_area();
+    image.fill(&WHITE).ok();
+    let (root, caption_area) = image.split_vertically(CHART_SIZE.1);
+
+    for (i, line) in caption.unwrap_or_default().iter().enumerate() {
+        let y = (CAPTION_PADDING + i as u32 * CAPTION_LINE_HEIGHT) as i32;
+        caption_area
+            .draw(&Text::new(line.as_str(), (40, y), ("sans-serif", 20).into_font().color(&BLACK)))
+            .ok();
+    }
+    
+    // Find min/max values for proper scaling (with 10% margin)
+    let (y_min, y_max) = auto_y_bounds(
+        results.iter().flat_map(|r| [r.q_sca, r.q_abs, r.q_ext])
+    );
+    let (Some(x_min), Some(x_max)) = (results.first().map(|r| r.wavelength), results.last().map(|r| r.wavelength)) else {
+        return false;
+    };
+    
+    let chart = ChartBuilder::on(&root)
+        .caption("Mie Scattering Spectrum", ("sans-serif", 40))
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(70)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .ok();
+    
+    let Some(mut chart) = chart else {
+        return false;
+    };
+
+    chart.configure_mesh()
+        .x_desc("Wavelength (nm)")
+        .y_desc("Efficiency Factor")
+        .draw()
+        .ok();
+    
+    // Draw Q_sca (blue)
+    if let Ok(series) = chart.draw_series(LineSeries::new(
+        results.iter().map(|r| (r.wavelength, r.q_sca)),
+        &BLUE,
+    )) {
+        series.label("Q_sca")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+    }
+    
+    // Draw Q_abs (red)
+    if let Ok(series) = chart.draw_series(LineSeries::new(
+        results.iter().map(|r| (r.wavelength, r.q_abs)),
+        &RED,
+    )) {
+        series.label("Q_abs")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+    }
+    
+    // Draw Q_ext (green)
+    if let Ok(series) = chart.draw_series(LineSeries::new(
+        results.iter().map(|r| (r.wavelength, r.q_ext)),
+        &GREEN,
+    )) {
+        series.label("Q_ext")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
+    }
+    
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .ok();
+    
+    image.present().is_ok()
 }
 
 impl eframe::App for NanoCalcApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, APPEARANCE_KEY, &self.appearance);
+        eframe::set_value(storage, PLOT_VIEW_KEY, &self.default_plot_view);
+    }
+
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        self.poll_spectrum_run();
+        self.poll_band_run();
+
         // Modern top panel with gradient-like effect
         TopBottomPanel::top("top_panel")
             .exact_height(70.0)
@@ -1612,6 +5533,32 @@ impl eframe::App for NanoCalcApp {
                                             .color(Color32::from_rgb(180, 200, 255)));
                                     });
                                 
+                                if self.state.constants.is_overridden() {
+                                    ui.add_space(10.0);
+                                    let banner = egui::Frame::none()
+                                        .fill(Color32::from_rgb(140, 40, 40))
+                                        .rounding(Rounding::same(4.0))
+                                        .inner_margin(egui::Margin::symmetric(8.0, 4.0))
+                                        .show(ui, |ui| {
+                                            ui.label(egui::RichText::new(self.t(
+                                                "⚠ Non-physical constants",
+                                                "⚠ Constantes no físicas"
+                                            ))
+                                                .size(11.0)
+                                                .color(Color32::WHITE)
+                                                .strong());
+                                        });
+                                    if banner.response.interact(egui::Sense::click())
+                                        .on_hover_text(self.t(
+                                            "Physical constants are scaled away from CODATA. Click to review",
+                                            "Las constantes físicas difieren de CODATA. Clic para revisar"
+                                        ))
+                                        .clicked()
+                                    {
+                                        self.show_constants_inspector = true;
+                                    }
+                                }
+
                                 ui.add_space(10.0);
 
                                 // Language selector
@@ -1628,17 +5575,77 @@ impl eframe::App for NanoCalcApp {
                                 ui.add_space(10.0);
 
                                 // Periodic Table button
-                                if ui.button(&self.t("Elements", "Elementos"))
-                                    .on_hover_text(&self.t("Open Periodic Table", "Abrir Tabla Periódica"))
+                                if ui.button(self.t("Elements", "Elementos"))
+                                    .on_hover_text(self.t("Open Periodic Table", "Abrir Tabla Periódica"))
                                     .clicked() {
                                     self.show_periodic_table = true;
                                 }
                                 
                                 ui.add_space(5.0);
 
+                                // Developer tools menu
+                                let inspector_label = self.t("Constant Inspector", "Inspector de Constantes");
+                                ui.menu_button("🛠", |ui| {
+                                    ui.checkbox(&mut self.show_constants_inspector, inspector_label);
+                                })
+                                .response
+                                .on_hover_text(self.t("Developer tools", "Herramientas de desarrollo"));
+
+                                ui.add_space(5.0);
+
+                                // Project buttons
+                                if ui.button(self.t("💾 Save Project", "💾 Guardar Proyecto"))
+                                    .on_hover_text(self.t("Save inputs and spectrum", "Guardar entradas y espectro"))
+                                    .clicked() {
+                                    self.export_project();
+                                }
+                                if ui.button(self.t("📂 Open Project", "📂 Abrir Proyecto"))
+                                    .on_hover_text(self.t("Restore a saved session", "Restaurar una sesión guardada"))
+                                    .clicked() {
+                                    self.show_open_project = true;
+                                }
+
+                                ui.add_space(5.0);
+
+                                // Import spectrum button
+                                if ui.button(self.t("📂 Import", "📂 Importar"))
+                                    .on_hover_text(self.t("Import a JSON spectrum", "Importar un espectro JSON"))
+                                    .clicked() {
+                                    self.show_import = true;
+                                }
+
+                                ui.add_space(5.0);
+
+                                // Run history button
+                                if ui.button(self.t("🕘 History", "🕘 Historial"))
+                                    .on_hover_text(self.t("Inputs and timing of every run", "Entradas y tiempos de cada cálculo"))
+                                    .clicked() {
+                                    self.show_history = true;
+                                }
+
+                                ui.add_space(5.0);
+
+                                // Compare Projects button
+                                if ui.button(self.t("Compare", "Comparar"))
+                                    .on_hover_text(self.t("Compare saved projects", "Comparar proyectos guardados"))
+                                    .clicked() {
+                                    self.show_compare_projects = true;
+                                }
+
+                                ui.add_space(5.0);
+
+                                // Settings button
+                                if ui.button(self.t("⚙ Settings", "⚙ Configuración"))
+                                    .on_hover_text(self.t("Application settings", "Configuración de la aplicación"))
+                                    .clicked() {
+                                    self.show_settings = true;
+                                }
+
+                                ui.add_space(5.0);
+
                                 // About button
-                                if ui.button(&self.t("About", "Acerca de"))
-                                    .on_hover_text(&self.t("About NanoCalc", "Acerca de NanoCalc"))
+                                if ui.button(self.t("About", "Acerca de"))
+                                    .on_hover_text(self.t("About NanoCalc", "Acerca de NanoCalc"))
                                     .clicked() {
                                     self.show_about = true;
                                 }
@@ -1699,19 +5706,19 @@ impl eframe::App for NanoCalcApp {
                             ui.separator();
                             if self.calculating {
                                 ui.spinner();
-                                ui.label(&self.t("Calculating...", "Calculando..."));
+                                ui.label(self.t("Calculating...", "Calculando..."));
                             } else {
                                 ui.colored_label(Color32::from_rgb(100, 255, 150), 
-                                    &self.t("Ready", "Listo"));
+                                    self.t("Ready", "Listo"));
                             }
                             
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if ui.button(&self.t("🗑 Clear Log", "🗑 Limpiar Log")).clicked() {
+                                if ui.button(self.t("🗑 Clear Log", "🗑 Limpiar Log")).clicked() {
                                     self.log_messages.clear();
                                     self.log_messages.push(self.t("✅ Log cleared", "✅ Log limpiado").to_string());
                                 }
                                 ui.separator();
-                                ui.hyperlink_to(&self.t("Documentation", "Documentación"), 
+                                ui.hyperlink_to(self.t("Documentation", "Documentación"), 
                                     "https://github.com/lexharden/nanocalc");
                                 ui.separator();
                                 ui.colored_label(Color32::GRAY, "MIT License © 2025");
@@ -1759,5 +5766,40 @@ impl eframe::App for NanoCalcApp {
         if self.show_export_dialog {
             self.draw_export_dialog(ctx);
         }
+
+        // Show Compare Projects window if requested
+        if self.show_compare_projects {
+            self.draw_compare_projects(ctx);
+        }
+
+        // Show Open Project window if requested
+        if self.show_open_project {
+            self.draw_open_project(ctx);
+        }
+
+        // Show Import window if requested
+        if self.show_import {
+            self.draw_import_dialog(ctx);
+        }
+
+        // Show run history if requested
+        if self.show_history {
+            self.draw_history(ctx);
+        }
+
+        // Show Constant Inspector if toggled from the debug menu
+        if self.show_constants_inspector {
+            self.draw_constants_inspector(ctx);
+        }
+
+        // Show Settings dialog if requested
+        if self.show_settings {
+            self.draw_settings_dialog(ctx);
+        }
+
+        schedule_repaint(ctx, PendingWork {
+            computing: self.calculating,
+            animating: self.zoom_request.is_some(),
+        });
     }
 }
\ No newline at end of file
@@ -0,0 +1,149 @@
+//! Accessibility appearance settings
+//!
+//! A global UI scale applied to every text style and plot line width, and a
+//! high-contrast palette for low-vision users. Persisted across sessions
+//! through eframe storage.
+
+use egui::{Color32, FontFamily, FontId, Stroke, TextStyle, Visuals};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// eframe storage key for [`Appearance`]
+pub const APPEARANCE_KEY: &str = "nanocalc_appearance";
+
+/// Allowed range of the UI scale factor
+pub const MIN_UI_SCALE: f32 = 0.75;
+pub const MAX_UI_SCALE: f32 = 2.0;
+
+/// Base font sizes at scale 1.0
+const BASE_TEXT_SIZES: [(TextStyle, f32, FontFamily); 5] = [
+    (TextStyle::Heading, 26.0, FontFamily::Proportional),
+    (TextStyle::Body, 15.0, FontFamily::Proportional),
+    (TextStyle::Monospace, 14.0, FontFamily::Monospace),
+    (TextStyle::Button, 15.0, FontFamily::Proportional),
+    (TextStyle::Small, 13.0, FontFamily::Proportional),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Appearance {
+    /// Black background, white text, bright outlines
+    pub high_contrast: bool,
+    /// Multiplier for font sizes and plot line widths
+    pub ui_scale: f32,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl Appearance {
+    /// Scale clamped to the supported range
+    pub fn scale(&self) -> f32 {
+        self.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+    }
+
+    /// Text styles with every base size multiplied by the scale
+    pub fn text_styles(&self) -> BTreeMap<TextStyle, FontId> {
+        BASE_TEXT_SIZES
+            .iter()
+            .map(|(style, size, family)| (style.clone(), FontId::new(size * self.scale(), family.clone())))
+            .collect()
+    }
+
+    /// Plot line width for a base width at scale 1.0
+    pub fn line_width(&self, base: f32) -> f32 {
+        base * self.scale()
+    }
+
+    /// Color palette for the current contrast mode
+    pub fn visuals(&self) -> Visuals {
+        let mut visuals = Visuals::dark();
+        if self.high_contrast {
+            visuals.override_text_color = Some(Color32::WHITE);
+            visuals.window_fill = Color32::BLACK;
+            visuals.panel_fill = Color32::BLACK;
+            visuals.extreme_bg_color = Color32::BLACK;
+            visuals.faint_bg_color = Color32::from_gray(20);
+            visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+            visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+            visuals.widgets.inactive.bg_fill = Color32::BLACK;
+            visuals.widgets.inactive.weak_bg_fill = Color32::BLACK;
+            visuals.widgets.inactive.bg_stroke = Stroke::new(1.5, Color32::WHITE);
+            visuals.widgets.hovered.bg_fill = Color32::from_gray(40);
+            visuals.widgets.hovered.weak_bg_fill = Color32::from_gray(40);
+            visuals.widgets.hovered.bg_stroke = Stroke::new(2.0, Color32::YELLOW);
+            visuals.widgets.active.bg_fill = Color32::from_rgb(0, 90, 200);
+            visuals.widgets.active.bg_stroke = Stroke::new(2.0, Color32::YELLOW);
+            visuals.selection.bg_fill = Color32::from_rgb(0, 90, 200);
+            visuals.selection.stroke = Stroke::new(2.0, Color32::YELLOW);
+            visuals.hyperlink_color = Color32::from_rgb(0, 255, 255);
+        } else {
+            // Modern blue tones
+            visuals.widgets.inactive.bg_fill = Color32::from_rgb(45, 50, 65);
+            visuals.widgets.hovered.bg_fill = Color32::from_rgb(60, 65, 85);
+            visuals.widgets.active.bg_fill = Color32::from_rgb(70, 130, 180);
+            visuals.selection.bg_fill = Color32::from_rgba_premultiplied(70, 130, 180, 100);
+            visuals.window_fill = Color32::from_rgb(30, 33, 42);
+            visuals.panel_fill = Color32::from_rgb(35, 38, 48);
+        }
+        visuals
+    }
+}
+
+/// WCAG relative luminance of an sRGB color
+fn relative_luminance(color: Color32) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r()) + 0.7152 * channel(color.g()) + 0.0722 * channel(color.b())
+}
+
+/// WCAG contrast ratio between two colors (1 to 21)
+pub fn contrast_ratio(a: Color32, b: Color32) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_propagates_to_text_styles() {
+        let base = Appearance::default().text_styles();
+        let large = Appearance { ui_scale: 1.5, ..Default::default() }.text_styles();
+        assert_eq!(base[&TextStyle::Body].size, 15.0);
+        for (style, font) in &large {
+            assert!((font.size - 1.5 * base[style].size).abs() < 1e-6, "{:?}", style);
+        }
+
+        let clamped = Appearance { ui_scale: 10.0, ..Default::default() };
+        assert_eq!(clamped.text_styles()[&TextStyle::Body].size, 15.0 * MAX_UI_SCALE);
+        assert_eq!(clamped.line_width(2.0), 2.0 * MAX_UI_SCALE);
+    }
+
+    #[test]
+    fn test_high_contrast_palette_is_measurably_stronger() {
+        let text_contrast = |a: &Appearance| {
+            let visuals = a.visuals();
+            contrast_ratio(visuals.text_color(), visuals.panel_fill)
+        };
+        let normal = Appearance::default();
+        let high = Appearance { high_contrast: true, ..Default::default() };
+
+        assert_ne!(normal.visuals(), high.visuals());
+        assert_eq!(text_contrast(&high), 21.0);
+        assert!(text_contrast(&high) > text_contrast(&normal) + 5.0);
+    }
+}
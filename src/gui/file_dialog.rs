@@ -0,0 +1,121 @@
+//! Native open/save dialogs
+//!
+//! With the `file_dialog` feature on desktop, exports and project files go
+//! through real Save/Open dialogs that start in the last-used directory. The
+//! defaulting helpers are plain functions so they work (and are tested)
+//! without a dialog; on wasm the text-field filename is used as before.
+
+use std::path::{Path, PathBuf};
+
+/// Stem used when the filename field is left blank
+pub const FALLBACK_STEM: &str = "nanocalc";
+
+/// Default file name `<stem><suffix>.<extension>`
+///
+/// An empty `extension` gives no dot, for directories such as bundles.
+pub fn default_file_name(stem: &str, suffix: &str, extension: &str) -> String {
+    let stem = stem.trim();
+    let stem = if stem.is_empty() { FALLBACK_STEM } else { stem };
+    if extension.is_empty() {
+        format!("{}{}", stem, suffix)
+    } else {
+        format!("{}{}.{}", stem, suffix, extension)
+    }
+}
+
+/// Append `extension` unless the path already has it (case-insensitive)
+pub fn ensure_extension(path: PathBuf, extension: &str) -> PathBuf {
+    if extension.is_empty() {
+        return path;
+    }
+    let matches = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension));
+    if matches {
+        path
+    } else {
+        let mut name = path.into_os_string();
+        name.push(".");
+        name.push(extension);
+        PathBuf::from(name)
+    }
+}
+
+/// Dialog state remembered between uses
+#[derive(Debug, Clone, Default)]
+pub struct FileDialogs {
+    last_dir: Option<PathBuf>,
+}
+
+impl FileDialogs {
+    /// Directory of the last file picked, if any
+    pub fn last_dir(&self) -> Option<&Path> {
+        self.last_dir.as_deref()
+    }
+
+    /// Remember the directory containing `path`
+    pub fn remember(&mut self, path: &Path) {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            self.last_dir = Some(dir.to_path_buf());
+        }
+    }
+
+    /// Ask for a save location; `None` if the user cancelled
+    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+    pub fn save(&mut self, default_name: &str, extension: &str) -> Option<PathBuf> {
+        let mut dialog = rfd::FileDialog::new().set_file_name(default_name);
+        if !extension.is_empty() {
+            dialog = dialog.add_filter(extension.to_uppercase(), &[extension]);
+        }
+        if let Some(dir) = &self.last_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        let path = ensure_extension(dialog.save_file()?, extension);
+        self.remember(&path);
+        Some(path)
+    }
+
+    /// Ask for a file to open; `None` if the user cancelled
+    #[cfg(all(feature = "file_dialog", not(target_arch = "wasm32")))]
+    pub fn open(&mut self, filter_name: &str, extensions: &[&str]) -> Option<PathBuf> {
+        let mut dialog = rfd::FileDialog::new().add_filter(filter_name, extensions);
+        if let Some(dir) = &self.last_dir {
+            dialog = dialog.set_directory(dir);
+        }
+        let path = dialog.pick_file()?;
+        self.remember(&path);
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_file_names() {
+        assert_eq!(default_file_name("spectrum", "", "csv"), "spectrum.csv");
+        assert_eq!(default_file_name("spectrum", "_comparison", "csv"), "spectrum_comparison.csv");
+        assert_eq!(default_file_name("  ", "_history", "csv"), "nanocalc_history.csv");
+        assert_eq!(default_file_name("spectrum", "_bundle", ""), "spectrum_bundle");
+    }
+
+    #[test]
+    fn test_extension_is_added_only_when_missing() {
+        assert_eq!(ensure_extension(PathBuf::from("/tmp/out"), "csv"), PathBuf::from("/tmp/out.csv"));
+        assert_eq!(ensure_extension(PathBuf::from("/tmp/out.CSV"), "csv"), PathBuf::from("/tmp/out.CSV"));
+        assert_eq!(ensure_extension(PathBuf::from("/tmp/out.v1"), "json"), PathBuf::from("/tmp/out.v1.json"));
+        assert_eq!(ensure_extension(PathBuf::from("/tmp/bundle"), ""), PathBuf::from("/tmp/bundle"));
+    }
+
+    #[test]
+    fn test_last_directory_is_remembered() {
+        let mut dialogs = FileDialogs::default();
+        assert!(dialogs.last_dir().is_none());
+        dialogs.remember(Path::new("relative.csv"));
+        assert!(dialogs.last_dir().is_none());
+        dialogs.remember(Path::new("/data/runs/a.csv"));
+        assert_eq!(dialogs.last_dir(), Some(Path::new("/data/runs")));
+    }
+}
@@ -1,6 +1,10 @@
 //! GUI module
 
 pub mod app;
+pub mod appearance;
+pub mod file_dialog;
+pub mod plot_view;
+pub mod repaint;
 pub mod views;
 pub mod widgets;
 
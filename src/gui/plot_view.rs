@@ -0,0 +1,103 @@
+//! Plot appearance as one persisted bundle
+//!
+//! Everything that changes how the results plot looks, as opposed to what
+//! was calculated: the plot mode, background shadings, feature markers,
+//! residual panel, broadened overlay, zoom width and Y scale. The user's saved default
+//! is restored at launch through eframe storage; "factory" is
+//! [`PlotView::default`].
+
+use crate::plotting::{Overlay, DEFAULT_ZOOM_FWHM};
+use crate::utils::features::FeatureConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// eframe storage key for the saved default [`PlotView`]
+pub const PLOT_VIEW_KEY: &str = "nanocalc_plot_view";
+
+/// What the results panel plots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PlotMode {
+    #[default]
+    Spectrum,
+    UniversalCurve,
+    EnergyRate,
+    Multipoles,
+    Sensitivity,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotView {
+    pub mode: PlotMode,
+    /// Background shadings drawn behind the spectrum
+    pub overlays: BTreeSet<Overlay>,
+    /// Mark detected peaks and shoulders
+    pub show_features: bool,
+    pub feature_config: FeatureConfig,
+    /// Residual panel under the spectrum
+    pub show_residuals: bool,
+    /// Overlay Q_ext broadened by a Gaussian instrument response
+    pub show_broadened: bool,
+    /// Instrument FWHM in nm
+    pub instrument_fwhm: f64,
+    /// Half-width of the zoom-to-peak window in FWHM
+    pub zoom_fwhm_multiple: f64,
+    /// Plot log₁₀ Q instead of Q
+    pub log_y: bool,
+}
+
+impl Default for PlotView {
+    fn default() -> Self {
+        Self {
+            mode: PlotMode::Spectrum,
+            overlays: Overlay::ALL.into_iter().collect(),
+            show_features: false,
+            feature_config: FeatureConfig::default(),
+            show_residuals: false,
+            show_broadened: false,
+            instrument_fwhm: 5.0,
+            zoom_fwhm_multiple: DEFAULT_ZOOM_FWHM,
+            log_y: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_view_reloads_exactly() {
+        let saved = PlotView {
+            mode: PlotMode::EnergyRate,
+            overlays: [Overlay::InvalidRegion].into_iter().collect(),
+            show_features: true,
+            feature_config: FeatureConfig {
+                min_prominence: 0.125,
+                shoulder_tolerance: 0.3,
+            },
+            show_residuals: true,
+            show_broadened: true,
+            instrument_fwhm: 7.3,
+            zoom_fwhm_multiple: 2.5,
+            log_y: true,
+        };
+        assert_ne!(saved, PlotView::default());
+
+        let stored = serde_json::to_string(&saved).unwrap();
+        let reloaded: PlotView = serde_json::from_str(&stored).unwrap();
+        assert_eq!(reloaded, saved);
+    }
+
+    #[test]
+    fn test_missing_fields_fall_back_to_factory() {
+        let reloaded: PlotView = serde_json::from_str(r#"{"show_residuals": true}"#).unwrap();
+        assert_eq!(
+            reloaded,
+            PlotView {
+                show_residuals: true,
+                ..PlotView::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Repaint scheduling
+//!
+//! egui already repaints on input. Beyond that the app only asks for frames
+//! while work is pending, and otherwise wakes at a long idle interval, so an
+//! idle window does not keep the CPU (and a laptop battery) busy.
+
+use egui::Context;
+use std::time::Duration;
+
+/// Frame interval while a calculation or plot update is pending
+pub const ACTIVE_REPAINT_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Wake-up interval with nothing pending
+pub const IDLE_REPAINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Work that needs further frames without user input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingWork {
+    /// A calculation is running and its progress is shown
+    pub computing: bool,
+    /// A view change (e.g. a requested zoom) is applied next frame
+    pub animating: bool,
+}
+
+impl PendingWork {
+    pub fn any(&self) -> bool {
+        self.computing || self.animating
+    }
+}
+
+/// Delay before the next unprompted frame
+pub fn repaint_interval(work: PendingWork) -> Duration {
+    if work.any() {
+        ACTIVE_REPAINT_INTERVAL
+    } else {
+        IDLE_REPAINT_INTERVAL
+    }
+}
+
+/// Request the next frame according to `work`
+pub fn schedule_repaint(ctx: &Context, work: PendingWork) {
+    ctx.request_repaint_after(repaint_interval(work));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{RawInput, ViewportId};
+
+    /// Repaint delay egui reports after a headless frame
+    fn frame_delay(ctx: &Context, work: PendingWork) -> Duration {
+        let output = ctx.run(RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| ui.label("idle"));
+            schedule_repaint(ctx, work);
+        });
+        output.viewport_output[&ViewportId::ROOT].repaint_delay
+    }
+
+    #[test]
+    fn test_idle_frames_repaint_rarely() {
+        let ctx = Context::default();
+        // Let egui settle its own first-frame layout passes
+        for _ in 0..3 {
+            frame_delay(&ctx, PendingWork::default());
+        }
+        // egui subtracts its expected frame time from the requested delay
+        let idle = frame_delay(&ctx, PendingWork::default());
+        assert!(idle > IDLE_REPAINT_INTERVAL - Duration::from_secs(1), "{:?}", idle);
+
+        let busy = PendingWork { computing: true, ..Default::default() };
+        assert!(frame_delay(&ctx, busy) <= ACTIVE_REPAINT_INTERVAL);
+        assert!(frame_delay(&ctx, PendingWork::default()) > IDLE_REPAINT_INTERVAL / 2);
+    }
+}
@@ -0,0 +1,64 @@
+//! Modifier-aware drag speed
+//!
+//! Holding Shift makes a drag 10× finer for tuning near sharp resonances;
+//! holding Ctrl makes it 10× coarser for sweeping large ranges.
+
+use egui::Modifiers;
+
+/// Factor applied by a fine (Shift) or coarse (Ctrl) drag
+pub const DRAG_SPEED_FACTOR: f64 = 10.0;
+
+/// Effective step per dragged point for a field with speed `base`
+///
+/// Shift wins when both modifiers are held, so the finer step is never lost.
+pub fn drag_speed(base: f64, modifiers: &Modifiers) -> f64 {
+    if modifiers.shift {
+        base / DRAG_SPEED_FACTOR
+    } else if modifiers.ctrl {
+        base * DRAG_SPEED_FACTOR
+    } else {
+        base
+    }
+}
+
+/// Speed to pass to `DragValue::speed` so the drag moves at [`drag_speed`]
+///
+/// egui already slows Shift-only drags by 10×, so that case is passed through
+/// unscaled rather than compounding to 100×.
+pub fn drag_value_speed(base: f64, modifiers: &Modifiers) -> f64 {
+    if modifiers.shift_only() {
+        base
+    } else {
+        drag_speed(base, modifiers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHIFT_CTRL: Modifiers = Modifiers {
+        alt: false,
+        ctrl: true,
+        shift: true,
+        mac_cmd: false,
+        command: true,
+    };
+
+    #[test]
+    fn test_speed_selection() {
+        assert_eq!(drag_speed(1.0, &Modifiers::NONE), 1.0);
+        assert_eq!(drag_speed(1.0, &Modifiers::SHIFT), 0.1);
+        assert_eq!(drag_speed(0.01, &Modifiers::CTRL), 0.1);
+        assert_eq!(drag_speed(1.0, &SHIFT_CTRL), 0.1);
+        assert_eq!(drag_speed(1.0, &Modifiers::ALT), 1.0);
+    }
+
+    #[test]
+    fn test_shift_only_is_left_to_egui() {
+        assert_eq!(drag_value_speed(1.0, &Modifiers::SHIFT), 1.0);
+        assert_eq!(drag_value_speed(1.0, &Modifiers::CTRL), 10.0);
+        assert_eq!(drag_value_speed(1.0, &SHIFT_CTRL), 0.1);
+        assert_eq!(drag_value_speed(1.0, &Modifiers::NONE), 1.0);
+    }
+}
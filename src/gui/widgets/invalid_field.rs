@@ -0,0 +1,20 @@
+//! Red outline for an input that failed validation
+
+use egui::{Color32, Stroke, Ui};
+
+/// Text and outline colour of an invalid input
+pub const INVALID_COLOR: Color32 = Color32::from_rgb(235, 90, 90);
+
+/// Draw the rest of `ui` in red if `invalid`
+///
+/// Call at the start of the row holding the field so only that row changes.
+pub fn mark_invalid(ui: &mut Ui, invalid: bool) {
+    if !invalid {
+        return;
+    }
+    let visuals = ui.visuals_mut();
+    visuals.override_text_color = Some(INVALID_COLOR);
+    for widget in [&mut visuals.widgets.inactive, &mut visuals.widgets.hovered, &mut visuals.widgets.active] {
+        widget.bg_stroke = Stroke::new(1.5, INVALID_COLOR);
+    }
+}
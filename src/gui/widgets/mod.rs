@@ -1,3 +1,7 @@
 //! GUI widgets module
 
-// Placeholder for future custom widgets
+pub mod drag_speed;
+pub mod invalid_field;
+
+pub use drag_speed::{drag_speed, drag_value_speed};
+pub use invalid_field::mark_invalid;
@@ -16,5 +16,7 @@ pub mod utils;
 // Re-export main types
 pub use core::{
     CalcResult, CalculationError, ElectronicModel, ElectronicResult, OpticalModel,
-    OpticalResult, PhysicsModel, ThermalModel, ThermalResult, ValidationError,
+    OpticalResult, PhysicsModel, ThermalModel, ThermalResult, ValidationError, Warning,
+    WarningCode, WarningSeverity,
 };
+pub use compute::{calculate_optical, OpticalParams, TypedOpticalResult};
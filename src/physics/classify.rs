@@ -0,0 +1,65 @@
+//! Material classification and model suggestions
+//!
+//! A quick read of the relative index tells which optical model is worth
+//! trying: plasmonic metals need the full Mie series, since both
+//! approximations miss the resonance, and optically soft dielectrics are the
+//! anomalous diffraction regime.
+
+use super::materials::MaterialClass;
+use super::optical::anomalous_diffraction::ADA_VALID_CONTRAST;
+use crate::core::{OpticalModelKind, RefractiveIndex};
+
+/// Class of a particle with index `index` embedded in a medium of index `n_medium`
+///
+/// Uses the relative permittivity (index / n_medium)², so k is judged
+/// against the medium. The sign of Re(ε) is unaffected by the medium.
+pub fn classify_material(index: RefractiveIndex, n_medium: f64) -> MaterialClass {
+    let relative = index.to_complex() / n_medium;
+    MaterialClass::from_permittivity(relative * relative)
+}
+
+/// Model to suggest for `index` in `n_medium`, if any stands out
+pub fn suggest_model(index: RefractiveIndex, n_medium: f64) -> Option<OpticalModelKind> {
+    match classify_material(index, n_medium) {
+        MaterialClass::Metallic => Some(OpticalModelKind::Mie),
+        MaterialClass::Dielectric if (index.to_complex() / n_medium - 1.0).norm() < ADA_VALID_CONTRAST => {
+            Some(OpticalModelKind::AnomalousDiffraction)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classification_thresholds() {
+        let gold = RefractiveIndex::new(0.47, 2.40);
+        let silicon_500 = RefractiveIndex::new(4.30, 0.073);
+        let silicon_800 = RefractiveIndex::new(3.69, 0.006);
+        let water = RefractiveIndex::new(1.33, 1e-8);
+
+        assert_eq!(classify_material(gold, 1.33), MaterialClass::Metallic);
+        assert_eq!(classify_material(silicon_500, 1.0), MaterialClass::AbsorbingDielectric);
+        assert_eq!(classify_material(silicon_800, 1.0), MaterialClass::Dielectric);
+        assert_eq!(classify_material(water, 1.0), MaterialClass::Dielectric);
+
+        // k is judged relative to the medium
+        let weak = RefractiveIndex::new(1.6, 0.06);
+        assert_eq!(classify_material(weak, 1.0), MaterialClass::AbsorbingDielectric);
+        assert_eq!(classify_material(weak, 1.5), MaterialClass::Dielectric);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(suggest_model(RefractiveIndex::new(0.47, 2.40), 1.33), Some(OpticalModelKind::Mie));
+        // Polystyrene in water: |m − 1| ≈ 0.19
+        assert_eq!(
+            suggest_model(RefractiveIndex::new(1.59, 0.0), 1.33),
+            Some(OpticalModelKind::AnomalousDiffraction)
+        );
+        assert_eq!(suggest_model(RefractiveIndex::new(2.5, 0.0), 1.33), None);
+        assert_eq!(suggest_model(RefractiveIndex::new(4.30, 0.073), 1.0), None);
+    }
+}
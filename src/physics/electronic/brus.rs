@@ -0,0 +1,222 @@
+//! Brus equation for the bandgap of a quantum dot
+//!
+//! A spherical dot of radius R = d/2 raises the gap by the particle-in-a-
+//! sphere kinetic energy of the electron and hole and lowers it by their
+//! screened Coulomb attraction:
+//!
+//! E_g(d) = E_bulk + ħ²π²/(2R²)·(1/m_e* + 1/m_h*) − 1.8 e²/(4πε₀ε_r R)
+
+use super::exciton::{exciton_binding_energy, exciton_bohr_radius};
+use crate::core::*;
+use std::f64::consts::PI;
+
+/// Below this R/a_B confinement is strong
+pub const STRONG_CONFINEMENT_RATIO: f64 = 1.0;
+/// Above this R/a_B confinement is weak
+pub const WEAK_CONFINEMENT_RATIO: f64 = 4.0;
+
+/// Brus model of a spherical semiconductor dot
+#[derive(Debug, Clone)]
+pub struct BrusModel {
+    /// Dot diameter in nm
+    pub diameter: f64,
+    /// Bulk bandgap in eV
+    pub bulk_bandgap: f64,
+    /// Electron effective mass in units of m_e
+    pub electron_mass: f64,
+    /// Hole effective mass in units of m_e
+    pub hole_mass: f64,
+    /// Static relative permittivity ε_r
+    pub dielectric_constant: f64,
+    /// Constants ħ and e are read from
+    pub context: PhysicsContext,
+}
+
+impl BrusModel {
+    pub fn new(diameter: f64, bulk_bandgap: f64, electron_mass: f64, hole_mass: f64, dielectric_constant: f64) -> Self {
+        Self {
+            diameter,
+            bulk_bandgap,
+            electron_mass,
+            hole_mass,
+            dielectric_constant,
+            context: PhysicsContext::CODATA,
+        }
+    }
+
+    /// Evaluate with the constants of `context`
+    pub fn with_context(mut self, context: PhysicsContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Wurtzite CdSe: E_g = 1.74 eV, m_e* = 0.13, m_h* = 0.45, ε_r = 10.6
+    pub fn cdse(diameter: f64) -> Self {
+        Self::new(diameter, 1.74, 0.13, 0.45, 10.6)
+    }
+
+    /// Reduced exciton mass µ = m_e*m_h*/(m_e* + m_h*) in units of m_e
+    pub fn reduced_mass(&self) -> f64 {
+        self.electron_mass * self.hole_mass / (self.electron_mass + self.hole_mass)
+    }
+
+    /// Exciton Bohr radius in nm, see [`exciton_bohr_radius`]
+    pub fn bohr_radius(&self) -> f64 {
+        exciton_bohr_radius(self.dielectric_constant, self.reduced_mass()) * self.context.bohr_radius_scale()
+    }
+
+    /// Bulk exciton binding energy in eV, see [`exciton_binding_energy`]
+    pub fn binding_energy(&self) -> f64 {
+        exciton_binding_energy(self.dielectric_constant, self.reduced_mass()) * self.context.rydberg_scale()
+    }
+
+    /// Kinetic confinement energy in eV
+    pub fn confinement_energy(&self) -> f64 {
+        let radius = self.diameter / 2.0 * conversions::NM_TO_M;
+        let hbar = self.context.hbar();
+        hbar * hbar * PI * PI / (2.0 * M_E * radius * radius) / self.reduced_mass()
+            / self.context.value(ScalableConstant::ElementaryCharge)
+    }
+
+    /// Electron-hole Coulomb energy in eV, subtracted from the gap
+    pub fn coulomb_correction(&self) -> f64 {
+        let radius = self.diameter / 2.0 * conversions::NM_TO_M;
+        1.8 * self.context.value(ScalableConstant::ElementaryCharge) / (4.0 * PI * EPSILON_0 * self.dielectric_constant * radius)
+    }
+
+    /// Confinement regime from the dot radius against the Bohr radius
+    pub fn regime(&self) -> ConfinementRegime {
+        let ratio = self.diameter / 2.0 / self.bohr_radius();
+        if ratio < STRONG_CONFINEMENT_RATIO {
+            ConfinementRegime::Strong
+        } else if ratio > WEAK_CONFINEMENT_RATIO {
+            ConfinementRegime::Weak
+        } else {
+            ConfinementRegime::Intermediate
+        }
+    }
+}
+
+impl PhysicsModel for BrusModel {
+    fn name(&self) -> &str {
+        "Brus Equation"
+    }
+
+    fn description(&self) -> &str {
+        "Size-dependent bandgap of a spherical quantum dot"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        let positive = [
+            ("Diameter", self.diameter),
+            ("Electron effective mass", self.electron_mass),
+            ("Hole effective mass", self.hole_mass),
+            ("Dielectric constant", self.dielectric_constant),
+        ];
+        for (name, value) in positive {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(ValidationError::InvalidParameter(format!("{} must be positive", name)));
+            }
+        }
+        if !(self.bulk_bandgap.is_finite() && self.bulk_bandgap >= 0.0) {
+            return Err(ValidationError::InvalidParameter(
+                "Bulk bandgap must be non-negative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ElectronicModel for BrusModel {
+    fn calculate(&self) -> CalcResult<ElectronicResult> {
+        self.validate()?;
+        let confinement_energy = self.confinement_energy();
+        let coulomb_correction = self.coulomb_correction();
+        let bohr_radius = self.bohr_radius();
+
+        Ok(ElectronicResult {
+            diameter: self.diameter,
+            bandgap: self.bulk_bandgap + confinement_energy - coulomb_correction,
+            bulk_bandgap: self.bulk_bandgap,
+            confinement_energy,
+            coulomb_correction,
+            bohr_radius: Some(bohr_radius),
+            regime: self.regime(),
+            metadata: ElectronicMetadata {
+                effective_mass: Some(self.reduced_mass()),
+                dielectric_constant: Some(self.dielectric_constant),
+                model_type: "Brus".to_string(),
+                notes: vec![
+                    format!("R/a_B = {:.3}", self.diameter / 2.0 / bohr_radius),
+                    format!("bulk exciton binding = {:.1} meV", self.binding_energy() * 1e3),
+                ],
+            },
+        })
+    }
+
+    fn calculate_size_sweep(&self, sizes: &[f64]) -> CalcResult<Vec<ElectronicResult>> {
+        sizes
+            .iter()
+            .map(|&d| {
+                let mut model = self.clone();
+                model.diameter = d;
+                model.calculate()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_five_nm_cdse_dot() {
+        // Measured first-exciton absorption of 5 nm CdSe is ~2.0–2.1 eV; the
+        // infinite-barrier Brus equation runs a little high
+        let dot = BrusModel::cdse(5.0).calculate().unwrap();
+        assert!((1.95..2.3).contains(&dot.bandgap), "{}", dot.bandgap);
+        assert!((dot.confinement_energy - 0.597).abs() < 0.005, "{}", dot.confinement_energy);
+        assert!((dot.coulomb_correction - 0.098).abs() < 0.002, "{}", dot.coulomb_correction);
+        assert!((dot.bohr_radius.unwrap() - 5.56).abs() < 0.02);
+        assert_eq!(dot.regime, ConfinementRegime::Strong);
+        assert_eq!(dot.bohr_radius, Some(exciton_bohr_radius(10.6, BrusModel::cdse(5.0).reduced_mass())));
+    }
+
+    #[test]
+    fn test_size_sweep_approaches_bulk() {
+        let sizes = [2.0, 5.0, 15.0, 60.0, 1000.0];
+        let sweep = BrusModel::cdse(5.0).calculate_size_sweep(&sizes).unwrap();
+        // Confinement falls as 1/R², the Coulomb term only as 1/R, so past a
+        // few Bohr radii the gap dips just below bulk before returning to it
+        assert!(sweep[..3].windows(2).all(|w| w[1].bandgap < w[0].bandgap));
+        assert!(sweep[3].bandgap < 1.74);
+        assert!((sweep[4].bandgap - 1.74).abs() < 1e-3);
+        let regimes: Vec<_> = sweep.iter().map(|r| r.regime).collect();
+        assert_eq!(
+            regimes,
+            [
+                ConfinementRegime::Strong,
+                ConfinementRegime::Strong,
+                ConfinementRegime::Intermediate,
+                ConfinementRegime::Weak,
+                ConfinementRegime::Weak
+            ]
+        );
+        assert!(BrusModel::cdse(0.0).calculate().is_err());
+    }
+
+    #[test]
+    fn test_scaling_hbar_scales_the_confinement() {
+        let codata = BrusModel::cdse(5.0).calculate().unwrap();
+        let mut context = PhysicsContext::CODATA;
+        *context.scale_mut(ScalableConstant::Planck) = 2.0;
+        let what_if = BrusModel::cdse(5.0).with_context(context).calculate().unwrap();
+
+        // Confinement goes as ħ², the Coulomb term does not involve ħ
+        assert!((what_if.confinement_energy / codata.confinement_energy - 4.0).abs() < 1e-12);
+        assert_eq!(what_if.coulomb_correction, codata.coulomb_correction);
+        assert!(what_if.bandgap > codata.bandgap + 1.0, "{}", what_if.bandgap);
+        assert!((what_if.bohr_radius.unwrap() / codata.bohr_radius.unwrap() - 4.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,45 @@
+//! Hydrogenic (Wannier-Mott) exciton
+//!
+//! An electron-hole pair in a semiconductor is a hydrogen atom with the
+//! reduced mass µ in place of m_e and the Coulomb interaction screened by
+//! ε_r, so both its size and its binding scale from the atomic values.
+
+use crate::core::{BOHR_RADIUS_NM, RY};
+
+/// Exciton Bohr radius a_B = ε_r·(m_e/µ)·a₀ in nm
+///
+/// `reduced_mass_ratio` is µ/m_e.
+pub fn exciton_bohr_radius(eps_r: f64, reduced_mass_ratio: f64) -> f64 {
+    eps_r / reduced_mass_ratio * BOHR_RADIUS_NM
+}
+
+/// Exciton binding energy E_b = Ry·(µ/m_e)/ε_r² in eV
+pub fn exciton_binding_energy(eps_r: f64, reduced_mass_ratio: f64) -> f64 {
+    RY * reduced_mass_ratio / (eps_r * eps_r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_gaas_exciton() {
+        // GaAs: ε_r = 12.9, m_e* = 0.067, m_h* = 0.45
+        let mu = 0.067 * 0.45 / (0.067 + 0.45);
+        let radius = exciton_bohr_radius(12.9, mu);
+        assert!((11.0..13.0).contains(&radius), "{}", radius);
+        // Measured binding energy is ~4.2 meV
+        let binding = exciton_binding_energy(12.9, mu);
+        assert!((0.004..0.0055).contains(&binding), "{}", binding);
+    }
+
+    #[test]
+    fn test_hydrogen_limit() {
+        assert_eq!(exciton_bohr_radius(1.0, 1.0), BOHR_RADIUS_NM);
+        assert_eq!(exciton_binding_energy(1.0, 1.0), RY);
+        // E_b·a_B is fixed by e²/(8πε₀ε_r): halving µ doubles the radius
+        // and halves the binding
+        assert!((exciton_bohr_radius(10.0, 0.05) - 2.0 * exciton_bohr_radius(10.0, 0.1)).abs() < 1e-12);
+        assert!((exciton_binding_energy(10.0, 0.05) - 0.5 * exciton_binding_energy(10.0, 0.1)).abs() < 1e-15);
+    }
+}
@@ -1,5 +1,9 @@
 //! Electronic physics models
 
+pub mod brus;
+pub mod exciton;
 pub mod traits;
 
+pub use brus::BrusModel;
+pub use exciton::{exciton_binding_energy, exciton_bohr_radius};
 pub use traits::*;
@@ -0,0 +1,82 @@
+//! Built-in optical constants for common elements
+//!
+//! Au and Ag use the full [`johnson_christy`] tables. Cu (after Johnson &
+//! Christy), Al (after Rakić) and Si (after Aspnes & Studna) are approximate
+//! room-temperature n, k over the visible and near infrared. Elements not
+//! listed here have no built-in data.
+
+use super::johnson_christy;
+use super::tabulated::TabulatedMaterial;
+
+/// (symbol, name, rows of (wavelength nm, n, k))
+type ElementTable = (&'static str, &'static str, &'static [(f64, f64, f64)]);
+
+const ELEMENTS: &[ElementTable] = &[
+    ("Au", "Gold", johnson_christy::GOLD),
+    ("Ag", "Silver", johnson_christy::SILVER),
+    ("Cu", "Copper", &[
+        (400.0, 1.18, 2.21),
+        (500.0, 1.12, 2.60),
+        (550.0, 0.96, 2.58),
+        (600.0, 0.27, 3.41),
+        (700.0, 0.21, 4.20),
+        (800.0, 0.26, 5.00),
+    ]),
+    ("Al", "Aluminum", &[
+        (400.0, 0.49, 4.86),
+        (500.0, 0.77, 6.08),
+        (600.0, 1.20, 7.26),
+        (700.0, 1.83, 8.31),
+        (800.0, 2.80, 8.45),
+    ]),
+    ("Si", "Silicon", &[
+        (400.0, 5.57, 0.387),
+        (500.0, 4.30, 0.073),
+        (600.0, 3.94, 0.020),
+        (700.0, 3.78, 0.007),
+        (800.0, 3.69, 0.006),
+    ]),
+];
+
+/// Symbols with built-in data
+pub fn element_symbols() -> impl Iterator<Item = &'static str> {
+    ELEMENTS.iter().map(|(symbol, _, _)| *symbol)
+}
+
+/// Tabulated optical constants for the element `symbol`, if built in
+pub fn element(symbol: &str) -> Option<TabulatedMaterial> {
+    ELEMENTS
+        .iter()
+        .find(|(s, _, _)| *s == symbol)
+        .map(|(_, name, rows)| {
+            TabulatedMaterial::new(*name, rows).expect("built-in tables are sorted and finite")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::materials::{Dispersion, MaterialClass};
+
+    #[test]
+    fn test_every_builtin_table_loads() {
+        for symbol in element_symbols() {
+            assert!(element(symbol).is_some(), "{}", symbol);
+        }
+        assert!(element("Xx").is_none());
+    }
+
+    #[test]
+    fn test_gold_metallic_silicon_dielectric() {
+        let gold = element("Au").unwrap();
+        assert_eq!(MaterialClass::from_permittivity(gold.permittivity_at(650.0)), MaterialClass::Metallic);
+        let silicon = element("Si").unwrap();
+        assert_eq!(MaterialClass::from_permittivity(silicon.permittivity_at(650.0)), MaterialClass::Dielectric);
+    }
+
+    #[test]
+    fn test_noble_metals_are_the_johnson_christy_tables() {
+        assert_eq!(element("Au").unwrap().rows(), johnson_christy::GOLD);
+        assert_eq!(element("Ag").unwrap().rows(), johnson_christy::SILVER);
+    }
+}
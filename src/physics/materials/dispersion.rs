@@ -0,0 +1,88 @@
+//! Wavelength-dependent optical constants
+
+use crate::core::RefractiveIndex;
+use num_complex::Complex64;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A material whose refractive index depends on wavelength
+pub trait Dispersion: Send + Sync + fmt::Debug {
+    /// Human-readable material name
+    fn name(&self) -> &str;
+
+    /// Complex refractive index n + ik at `wavelength_nm`
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex;
+
+    /// Complex permittivity ε = (n + ik)² at `wavelength_nm`
+    fn permittivity_at(&self, wavelength_nm: f64) -> Complex64 {
+        self.refractive_index_at(wavelength_nm).to_permittivity()
+    }
+
+    /// Valid wavelength range in nm, if the data is bounded
+    fn wavelength_range(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Check whether `wavelength_nm` lies inside the valid range
+    fn contains(&self, wavelength_nm: f64) -> bool {
+        match self.wavelength_range() {
+            Some((min, max)) => wavelength_nm >= min && wavelength_nm <= max,
+            None => true,
+        }
+    }
+
+    /// Fingerprint of the data behind n(λ): equal parameters or tables give
+    /// equal keys, whatever the allocation
+    fn cache_key(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        format!("{self:?}").hash(&mut hasher);
+        format!("{}#{:016x}", self.name(), hasher.finish())
+    }
+}
+
+/// Extinction coefficient k at or above which a dielectric counts as absorbing
+pub const ABSORBING_K_THRESHOLD: f64 = 0.05;
+
+/// Optical character of a material at one wavelength
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialClass {
+    /// Re(ε) < 0: free-carrier response, supports plasmons
+    Metallic,
+    /// Re(ε) ≥ 0 and k below [`ABSORBING_K_THRESHOLD`]
+    Dielectric,
+    /// Re(ε) ≥ 0 with k of at least [`ABSORBING_K_THRESHOLD`]
+    AbsorbingDielectric,
+}
+
+impl MaterialClass {
+    /// Classify from the sign of Re(ε) and, for dielectrics, k = Im(√ε)
+    pub fn from_permittivity(eps: Complex64) -> Self {
+        if eps.re < 0.0 {
+            MaterialClass::Metallic
+        } else if eps.sqrt().im >= ABSORBING_K_THRESHOLD {
+            MaterialClass::AbsorbingDielectric
+        } else {
+            MaterialClass::Dielectric
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classification_from_permittivity() {
+        // Au near 600 nm: n + ik ≈ 0.25 + 3.0i → ε ≈ -8.9 + 1.5i
+        let gold = RefractiveIndex::new(0.25, 3.0).to_permittivity();
+        assert_eq!(MaterialClass::from_permittivity(gold), MaterialClass::Metallic);
+
+        let silica = RefractiveIndex::new(1.46, 0.0).to_permittivity();
+        assert_eq!(MaterialClass::from_permittivity(silica), MaterialClass::Dielectric);
+
+        // n = k is the boundary: Re(ε) = n² − k² = 0 is not metallic
+        let edge = RefractiveIndex::new(2.0, 2.0).to_permittivity();
+        assert_eq!(MaterialClass::from_permittivity(edge), MaterialClass::AbsorbingDielectric);
+    }
+}
@@ -0,0 +1,99 @@
+//! Drude free-electron dispersion for metals
+
+use super::dispersion::Dispersion;
+use crate::core::constants::{compound, conversions};
+use crate::core::RefractiveIndex;
+use num_complex::Complex64;
+
+/// Drude permittivity ε(ω) = ε_∞ − ω_p² / (ω² + iγω)
+///
+/// Frequencies are photon energies ħω in eV. Interband transitions are only
+/// represented by the constant background ε_∞.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrudeModel {
+    pub name: String,
+    /// Background permittivity ε_∞
+    pub eps_inf: f64,
+    /// Plasma energy ħω_p in eV
+    pub plasma_ev: f64,
+    /// Damping ħγ in eV
+    pub damping_ev: f64,
+}
+
+impl DrudeModel {
+    pub fn new(name: impl Into<String>, eps_inf: f64, plasma_ev: f64, damping_ev: f64) -> Self {
+        Self {
+            name: name.into(),
+            eps_inf,
+            plasma_ev,
+            damping_ev,
+        }
+    }
+
+    /// Gold, fitted to Johnson & Christy above the interband edge
+    pub fn gold() -> Self {
+        Self::new("Au (Drude)", 9.84, 9.03, 0.067)
+    }
+
+    /// Silver, fitted to Johnson & Christy above the interband edge
+    pub fn silver() -> Self {
+        Self::new("Ag (Drude)", 3.7, 8.9, 0.021)
+    }
+
+    /// Screened plasma wavelength in nm, where Re ε = 0
+    ///
+    /// Re ε vanishes at ω² = ω_p²/ε_∞ − γ²; the bare
+    /// [`plasma_wavelength_nm`](compound::plasma_wavelength_nm) is the
+    /// ε_∞ = 1, γ = 0 case.
+    pub fn plasma_wavelength_nm(&self) -> Option<f64> {
+        let omega_sq = self.plasma_ev.powi(2) / self.eps_inf - self.damping_ev.powi(2);
+        (omega_sq > 0.0).then(|| compound::plasma_wavelength_nm(omega_sq.sqrt()))
+    }
+}
+
+impl Dispersion for DrudeModel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex {
+        RefractiveIndex::from_permittivity(self.permittivity_at(wavelength_nm))
+    }
+
+    fn permittivity_at(&self, wavelength_nm: f64) -> Complex64 {
+        let omega = conversions::HC_EV_NM / wavelength_nm;
+        let wp2 = self.plasma_ev * self.plasma_ev;
+        self.eps_inf - wp2 / Complex64::new(omega * omega, self.damping_ev * omega)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permittivity_crosses_zero_at_plasma_wavelength() {
+        for metal in [DrudeModel::gold(), DrudeModel::silver(), DrudeModel::new("free", 1.0, 9.0, 0.0)] {
+            let plasma = metal.plasma_wavelength_nm().unwrap();
+            assert!(metal.permittivity_at(plasma * 0.99).re > 0.0, "{}", metal.name);
+            assert!(metal.permittivity_at(plasma * 1.01).re < 0.0, "{}", metal.name);
+            assert!(metal.permittivity_at(plasma).re.abs() < 1e-9);
+        }
+        // Free electrons without damping: λ_p = hc/ħω_p
+        let free = DrudeModel::new("free", 1.0, 9.0, 0.0);
+        assert_eq!(free.plasma_wavelength_nm(), Some(compound::plasma_wavelength_nm(9.0)));
+        // Au is screened by its d electrons: λ_p ≈ 430 nm rather than 137 nm
+        let gold = DrudeModel::gold().plasma_wavelength_nm().unwrap();
+        assert!((400.0..460.0).contains(&gold), "{}", gold);
+    }
+
+    #[test]
+    fn test_metallic_index_in_the_red() {
+        // Au at 700 nm: strongly negative Re ε, small n, large k, as in the measured data
+        let n = DrudeModel::gold().refractive_index_at(700.0);
+        assert!(n.real > 0.0 && n.real < 0.5);
+        assert!(n.imaginary > 3.0 && n.imaginary < 5.0);
+        let eps = n.to_permittivity();
+        assert!((eps - DrudeModel::gold().permittivity_at(700.0)).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,123 @@
+//! Effective-medium mixing rules
+//!
+//! Permittivity of a composite, e.g. nanoparticles dispersed in a polymer,
+//! from the permittivities of its constituents and their volume fractions.
+//! Maxwell-Garnett treats one phase as isolated inclusions in a host;
+//! Bruggeman treats both phases symmetrically. Convert the result with
+//! [`effective_index`].
+
+use crate::core::{RefractiveIndex, ValidationError, ValidationResult};
+use num_complex::Complex64;
+
+fn check_fraction(fraction: f64) -> ValidationResult<()> {
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(())
+    } else {
+        Err(ValidationError::OutOfRange {
+            value: fraction,
+            min: 0.0,
+            max: 1.0,
+        })
+    }
+}
+
+/// Maxwell-Garnett permittivity of spherical inclusions in a host
+///
+/// ε = ε_h (ε_i + 2ε_h + 2f(ε_i − ε_h)) / (ε_i + 2ε_h − f(ε_i − ε_h)),
+/// with `fill_fraction` f the inclusion volume fraction. Valid for dilute,
+/// well-separated inclusions.
+pub fn maxwell_garnett(eps_host: Complex64, eps_inclusion: Complex64, fill_fraction: f64) -> ValidationResult<Complex64> {
+    check_fraction(fill_fraction)?;
+    let contrast = fill_fraction * (eps_inclusion - eps_host);
+    let sum = eps_inclusion + 2.0 * eps_host;
+    Ok(eps_host * (sum + 2.0 * contrast) / (sum - contrast))
+}
+
+/// Bruggeman permittivity of a two-phase mixture, `fraction` of it phase b
+///
+/// Solves f_a(ε_a − ε)/(ε_a + 2ε) + f_b(ε_b − ε)/(ε_b + 2ε) = 0, i.e.
+/// 2ε² − βε − ε_aε_b = 0 with β = (3f_b − 1)ε_b + (3f_a − 1)ε_a. Of the
+/// two roots the physical one has the larger Im ε (the smaller one is
+/// non-positive); for lossless phases, the positive Re ε.
+pub fn bruggeman(eps_a: Complex64, eps_b: Complex64, fraction: f64) -> ValidationResult<Complex64> {
+    check_fraction(fraction)?;
+    let beta = (3.0 * fraction - 1.0) * eps_b + (2.0 - 3.0 * fraction) * eps_a;
+    let root = (beta * beta + 8.0 * eps_a * eps_b).sqrt();
+    let (plus, minus) = ((beta + root) / 4.0, (beta - root) / 4.0);
+    let tolerance = 1e-12 * (plus.norm() + minus.norm());
+    Ok(if (plus.im - minus.im).abs() > tolerance {
+        if plus.im > minus.im { plus } else { minus }
+    } else if plus.re >= minus.re {
+        plus
+    } else {
+        minus
+    })
+}
+
+/// Refractive index of an effective permittivity, with k ≥ 0
+pub fn effective_index(eps: Complex64) -> RefractiveIndex {
+    RefractiveIndex::from_permittivity(eps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::materials::johnson_christy;
+    use crate::physics::materials::{Dispersion, Interpolation};
+
+    const GLASS: Complex64 = Complex64::new(2.25, 0.0);
+
+    #[test]
+    fn test_zero_fill_fraction_is_the_host() {
+        let gold = Complex64::new(-4.7, 2.4);
+        assert!((maxwell_garnett(GLASS, gold, 0.0).unwrap() - GLASS).norm() < 1e-12);
+        assert!((bruggeman(GLASS, gold, 0.0).unwrap() - GLASS).norm() < 1e-12);
+        assert!((bruggeman(GLASS, gold, 1.0).unwrap() - gold).norm() < 1e-12);
+        assert!((maxwell_garnett(GLASS, gold, 1.0).unwrap() - gold).norm() < 1e-12);
+        let n = effective_index(maxwell_garnett(GLASS, gold, 0.0).unwrap());
+        assert!((n.real - 1.5).abs() < 1e-12 && n.imaginary == 0.0);
+
+        assert!(matches!(maxwell_garnett(GLASS, gold, 1.5), Err(ValidationError::OutOfRange { .. })));
+        assert!(bruggeman(GLASS, gold, -0.1).is_err());
+    }
+
+    #[test]
+    fn test_dilute_gold_in_glass() {
+        // Ruby glass: dilute Au colloid absorbs at the Fröhlich condition
+        // Re ε_Au = −2ε_glass, near 530 nm in the Johnson & Christy data
+        let gold = johnson_christy::gold(Interpolation::CubicSpline);
+        let fill = 1e-3;
+        let peak = (450..=650)
+            .map(f64::from)
+            .max_by(|&a, &b| {
+                let im = |wl: f64| maxwell_garnett(GLASS, gold.permittivity_at(wl), fill).unwrap().im;
+                im(a).total_cmp(&im(b))
+            })
+            .unwrap();
+        assert!((520.0..=545.0).contains(&peak), "peak at {} nm", peak);
+
+        // To first order in f both rules give ε_h + 3fε_h(ε_i − ε_h)/(ε_i + 2ε_h)
+        let eps = gold.permittivity_at(peak);
+        let dilute = GLASS + 3.0 * fill * GLASS * (eps - GLASS) / (eps + 2.0 * GLASS);
+        let mg = maxwell_garnett(GLASS, eps, fill).unwrap();
+        let br = bruggeman(GLASS, eps, fill).unwrap();
+        // On resonance the second-order terms differ, Bruggeman's being larger
+        assert!((mg - dilute).norm() < 1e-2 * (dilute - GLASS).norm());
+        assert!((br - dilute).norm() < 5e-2 * (dilute - GLASS).norm());
+        assert!(br.im > 0.0);
+    }
+
+    #[test]
+    fn test_bruggeman_is_symmetric_and_picks_the_physical_root() {
+        let (a, b) = (Complex64::new(1.0, 0.0), Complex64::new(4.0, 0.0));
+        let mixed = bruggeman(a, b, 0.3).unwrap();
+        assert!((mixed - bruggeman(b, a, 0.7).unwrap()).norm() < 1e-12);
+        assert!(mixed.re > 1.0 && mixed.re < 4.0 && mixed.im == 0.0);
+
+        // Metal-dielectric mixtures stay absorbing across the percolation range
+        let metal = Complex64::new(-10.0, 1.0);
+        for f in [0.1, 0.33, 0.5, 0.9] {
+            assert!(bruggeman(GLASS, metal, f).unwrap().im > 0.0, "f = {}", f);
+        }
+    }
+}
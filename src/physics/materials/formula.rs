@@ -0,0 +1,104 @@
+//! User-defined formula dispersion
+
+use super::dispersion::Dispersion;
+use crate::core::RefractiveIndex;
+use crate::utils::expr::{Expr, ParseError};
+
+/// Names accepted for the wavelength variable (in nm)
+pub const WAVELENGTH_VARIABLES: &[&str] = &["lambda", "λ"];
+
+/// Dispersion given by two typed formulas for n(λ) and k(λ)
+///
+/// λ is in nm, so a Cauchy law in µm reads `1.5 + 0.01/(lambda/1000)^2`.
+/// A leading `n =` or `k =` is accepted and ignored.
+#[derive(Debug, Clone)]
+pub struct FormulaDispersion {
+    pub name: String,
+    n: Expr,
+    k: Expr,
+    /// Valid range in nm
+    pub range_nm: Option<(f64, f64)>,
+}
+
+/// Strip an optional `label =` prefix from a formula
+fn strip_label<'a>(formula: &'a str, label: &str) -> &'a str {
+    let trimmed = formula.trim_start();
+    match trimmed.strip_prefix(label) {
+        Some(rest) if rest.trim_start().starts_with('=') => &rest.trim_start()[1..],
+        _ => formula,
+    }
+}
+
+impl FormulaDispersion {
+    /// Parse the n and k formulas, rejecting malformed input
+    pub fn new(name: impl Into<String>, n_formula: &str, k_formula: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            name: name.into(),
+            n: Expr::parse(strip_label(n_formula, "n"), WAVELENGTH_VARIABLES)?,
+            k: Expr::parse(strip_label(k_formula, "k"), WAVELENGTH_VARIABLES)?,
+            range_nm: None,
+        })
+    }
+
+    pub fn with_range(mut self, min_nm: f64, max_nm: f64) -> Self {
+        self.range_nm = Some((min_nm, max_nm));
+        self
+    }
+
+    /// Formula for the real part
+    pub fn n_formula(&self) -> &str {
+        self.n.source()
+    }
+
+    /// Formula for the imaginary part
+    pub fn k_formula(&self) -> &str {
+        self.k.source()
+    }
+}
+
+impl Dispersion for FormulaDispersion {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex {
+        RefractiveIndex::new(self.n.eval(wavelength_nm), self.k.eval(wavelength_nm))
+    }
+
+    fn wavelength_range(&self) -> Option<(f64, f64)> {
+        self.range_nm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cauchy_formula() {
+        let material = FormulaDispersion::new("Cauchy glass", "n = 1.5 + 0.01/(λ/1000)^2", "0").unwrap();
+        for wl in [400.0, 550.0, 800.0] {
+            let expected = 1.5 + 0.01 / (wl / 1000.0_f64).powi(2);
+            let n = material.refractive_index_at(wl);
+            assert!((n.real - expected).abs() < 1e-12);
+            assert_eq!(n.imaginary, 0.0);
+        }
+        assert_eq!(material.n_formula(), "1.5 + 0.01/(λ/1000)^2");
+    }
+
+    #[test]
+    fn test_wavelength_dependent_k() {
+        let material = FormulaDispersion::new("Absorber", "1.6", "k = 1e-3 * exp(-lambda/200)").unwrap();
+        let k = material.refractive_index_at(400.0).imaginary;
+        assert!((k - 1e-3 * (-2.0_f64).exp()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_malformed_formula_is_rejected() {
+        assert!(FormulaDispersion::new("bad", "1.5 + ", "0").is_err());
+        assert_eq!(
+            FormulaDispersion::new("bad", "1.5", "0.1 * wavelength").unwrap_err(),
+            ParseError::UnknownIdentifier("wavelength".to_string())
+        );
+    }
+}
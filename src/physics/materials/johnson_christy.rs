@@ -0,0 +1,159 @@
+//! Johnson & Christy optical constants of the noble metals
+//!
+//! P. B. Johnson and R. W. Christy, Phys. Rev. B 6, 4370 (1972): n, k of
+//! Au and Ag from thin-film reflection and transmission, 49 photon energies
+//! from 0.64 to 6.6 eV. The reference data for plasmonic spectra.
+
+use super::tabulated::{Interpolation, TabulatedMaterial};
+
+/// Gold rows of (wavelength nm, n, k)
+pub const GOLD: &[(f64, f64, f64)] = &[
+    (187.9, 1.28, 1.188),
+    (191.6, 1.32, 1.203),
+    (195.3, 1.34, 1.226),
+    (199.3, 1.33, 1.251),
+    (203.3, 1.33, 1.277),
+    (207.3, 1.30, 1.304),
+    (211.9, 1.30, 1.350),
+    (216.4, 1.30, 1.387),
+    (221.4, 1.30, 1.427),
+    (226.2, 1.31, 1.460),
+    (231.3, 1.30, 1.497),
+    (237.1, 1.32, 1.536),
+    (242.6, 1.32, 1.577),
+    (249.0, 1.33, 1.631),
+    (255.1, 1.33, 1.688),
+    (261.6, 1.35, 1.749),
+    (268.9, 1.38, 1.803),
+    (276.1, 1.43, 1.847),
+    (284.4, 1.47, 1.869),
+    (292.4, 1.49, 1.878),
+    (300.9, 1.53, 1.889),
+    (310.7, 1.53, 1.893),
+    (320.4, 1.54, 1.898),
+    (331.5, 1.48, 1.883),
+    (342.5, 1.48, 1.871),
+    (354.2, 1.50, 1.866),
+    (367.9, 1.48, 1.895),
+    (381.5, 1.46, 1.933),
+    (397.4, 1.47, 1.952),
+    (413.3, 1.46, 1.958),
+    (430.5, 1.45, 1.948),
+    (450.9, 1.38, 1.914),
+    (471.4, 1.31, 1.849),
+    (495.9, 1.04, 1.833),
+    (520.9, 0.62, 2.081),
+    (548.6, 0.43, 2.455),
+    (582.1, 0.29, 2.863),
+    (616.8, 0.21, 3.272),
+    (659.5, 0.14, 3.697),
+    (704.5, 0.13, 4.103),
+    (756.0, 0.14, 4.542),
+    (821.1, 0.16, 5.083),
+    (892.0, 0.17, 5.663),
+    (984.0, 0.22, 6.350),
+    (1088.0, 0.27, 7.150),
+    (1216.0, 0.35, 8.145),
+    (1393.0, 0.43, 9.519),
+    (1610.0, 0.56, 11.21),
+    (1937.0, 0.92, 13.78),
+];
+
+/// Silver rows of (wavelength nm, n, k)
+pub const SILVER: &[(f64, f64, f64)] = &[
+    (187.9, 1.07, 1.212),
+    (191.6, 1.10, 1.232),
+    (195.3, 1.12, 1.255),
+    (199.3, 1.14, 1.277),
+    (203.3, 1.15, 1.296),
+    (207.3, 1.18, 1.312),
+    (211.9, 1.20, 1.325),
+    (216.4, 1.22, 1.336),
+    (221.4, 1.25, 1.342),
+    (226.2, 1.26, 1.344),
+    (231.3, 1.28, 1.357),
+    (237.1, 1.28, 1.367),
+    (242.6, 1.30, 1.378),
+    (249.0, 1.31, 1.389),
+    (255.1, 1.33, 1.393),
+    (261.6, 1.35, 1.387),
+    (268.9, 1.38, 1.372),
+    (276.1, 1.41, 1.331),
+    (284.4, 1.41, 1.264),
+    (292.4, 1.39, 1.161),
+    (300.9, 1.34, 0.964),
+    (310.7, 1.13, 0.616),
+    (320.4, 0.81, 0.392),
+    (331.5, 0.17, 0.829),
+    (342.5, 0.14, 1.142),
+    (354.2, 0.10, 1.419),
+    (367.9, 0.07, 1.657),
+    (381.5, 0.05, 1.864),
+    (397.4, 0.05, 2.070),
+    (413.3, 0.05, 2.275),
+    (430.5, 0.04, 2.462),
+    (450.9, 0.04, 2.657),
+    (471.4, 0.05, 2.869),
+    (495.9, 0.05, 3.093),
+    (520.9, 0.05, 3.324),
+    (548.6, 0.06, 3.586),
+    (582.1, 0.05, 3.858),
+    (616.8, 0.06, 4.152),
+    (659.5, 0.05, 4.483),
+    (704.5, 0.04, 4.838),
+    (756.0, 0.03, 5.242),
+    (821.1, 0.04, 5.727),
+    (892.0, 0.04, 6.312),
+    (984.0, 0.04, 6.992),
+    (1088.0, 0.04, 7.795),
+    (1216.0, 0.09, 8.828),
+    (1393.0, 0.13, 10.10),
+    (1610.0, 0.15, 11.85),
+    (1937.0, 0.24, 14.08),
+];
+
+/// Gold, interpolated with `interpolation`
+pub fn gold(interpolation: Interpolation) -> TabulatedMaterial {
+    TabulatedMaterial::new("Au (Johnson & Christy)", GOLD)
+        .expect("built-in tables are sorted and finite")
+        .with_interpolation(interpolation)
+}
+
+/// Silver, interpolated with `interpolation`
+pub fn silver(interpolation: Interpolation) -> TabulatedMaterial {
+    TabulatedMaterial::new("Ag (Johnson & Christy)", SILVER)
+        .expect("built-in tables are sorted and finite")
+        .with_interpolation(interpolation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::materials::Dispersion;
+
+    #[test]
+    fn test_gold_midpoint_between_samples() {
+        // Halfway between the 520.9 nm (0.62 + 2.081i) and 548.6 nm
+        // (0.43 + 2.455i) samples
+        let n = gold(Interpolation::Linear).refractive_index_at(534.75);
+        assert!((n.real - 0.525).abs() < 1e-12);
+        assert!((n.imaginary - 2.268).abs() < 1e-12);
+
+        // The spline passes through the samples and stays close in between
+        let spline = gold(Interpolation::CubicSpline);
+        assert_eq!(spline.refractive_index_at(520.9).real, 0.62);
+        let n = spline.refractive_index_at(534.75);
+        assert!((0.43..0.62).contains(&n.real) && (2.081..2.455).contains(&n.imaginary));
+    }
+
+    #[test]
+    fn test_datasets_cover_the_same_energies() {
+        assert_eq!(GOLD.len(), 49);
+        assert_eq!(SILVER.len(), 49);
+        for (au, ag) in GOLD.iter().zip(SILVER) {
+            assert_eq!(au.0, ag.0);
+        }
+        let bounds = silver(Interpolation::Linear).bounds();
+        assert_eq!((bounds.min, bounds.max), (187.9, 1937.0));
+    }
+}
@@ -0,0 +1,209 @@
+//! Lorentz-Drude dispersion for metals
+//!
+//! A Drude term for the free electrons plus Lorentz oscillators for the
+//! interband transitions, with the parameters of Rakić et al., Appl. Opt.
+//! 37, 5271 (1998).
+
+use super::dispersion::Dispersion;
+use crate::core::constants::conversions;
+use crate::core::RefractiveIndex;
+use num_complex::Complex64;
+
+/// One interband transition of a [`LorentzDrudeModel`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LorentzOscillator {
+    /// Oscillator strength f_j
+    pub strength: f64,
+    /// Resonance energy ħω_j in eV
+    pub resonance_ev: f64,
+    /// Damping ħΓ_j in eV
+    pub damping_ev: f64,
+}
+
+impl LorentzOscillator {
+    pub const fn new(strength: f64, resonance_ev: f64, damping_ev: f64) -> Self {
+        Self {
+            strength,
+            resonance_ev,
+            damping_ev,
+        }
+    }
+}
+
+/// Lorentz-Drude permittivity
+///
+/// ε(ω) = 1 − f₀ω_p² / (ω(ω + iΓ₀)) + Σ_j f_j ω_p² / (ω_j² − ω² − iωΓ_j)
+///
+/// Frequencies are photon energies ħω in eV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LorentzDrudeModel {
+    pub name: String,
+    /// Plasma energy ħω_p in eV
+    pub plasma_ev: f64,
+    /// Drude strength f₀
+    pub drude_strength: f64,
+    /// Drude damping ħΓ₀ in eV
+    pub drude_damping_ev: f64,
+    pub oscillators: Vec<LorentzOscillator>,
+    /// Photon energies (min, max) in eV the parameters were fitted over
+    pub fit_range_ev: Option<(f64, f64)>,
+}
+
+impl LorentzDrudeModel {
+    pub fn new(
+        name: impl Into<String>,
+        plasma_ev: f64,
+        drude_strength: f64,
+        drude_damping_ev: f64,
+        oscillators: Vec<LorentzOscillator>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            plasma_ev,
+            drude_strength,
+            drude_damping_ev,
+            oscillators,
+            fit_range_ev: None,
+        }
+    }
+
+    fn rakic(name: &str, plasma_ev: f64, drude: (f64, f64), oscillators: &[(f64, f64, f64)], fit_range_ev: (f64, f64)) -> Self {
+        let oscillators = oscillators
+            .iter()
+            .map(|&(f, omega, gamma)| LorentzOscillator::new(f, omega, gamma))
+            .collect();
+        Self {
+            fit_range_ev: Some(fit_range_ev),
+            ..Self::new(name, plasma_ev, drude.0, drude.1, oscillators)
+        }
+    }
+
+    /// Gold (Rakić 1998, 0.2–5 eV)
+    pub fn gold() -> Self {
+        Self::rakic(
+            "Au (Lorentz-Drude)",
+            9.03,
+            (0.760, 0.053),
+            &[
+                (0.024, 0.415, 0.241),
+                (0.010, 0.830, 0.345),
+                (0.071, 2.969, 0.870),
+                (0.601, 4.304, 2.494),
+                (4.384, 13.32, 2.214),
+            ],
+            (0.2, 5.0),
+        )
+    }
+
+    /// Silver (Rakić 1998, 0.1–5 eV)
+    pub fn silver() -> Self {
+        Self::rakic(
+            "Ag (Lorentz-Drude)",
+            9.01,
+            (0.845, 0.048),
+            &[
+                (0.065, 0.816, 3.886),
+                (0.124, 4.481, 0.452),
+                (0.011, 8.185, 0.065),
+                (0.840, 9.083, 0.916),
+                (5.646, 20.29, 2.419),
+            ],
+            (0.1, 5.0),
+        )
+    }
+
+    /// Aluminium (Rakić 1998, 0.1–20 eV)
+    pub fn aluminium() -> Self {
+        Self::rakic(
+            "Al (Lorentz-Drude)",
+            14.98,
+            (0.523, 0.047),
+            &[
+                (0.227, 0.162, 0.333),
+                (0.050, 1.544, 0.312),
+                (0.166, 1.808, 1.351),
+                (0.030, 3.473, 3.382),
+            ],
+            (0.1, 20.0),
+        )
+    }
+
+    /// Copper (Rakić 1998, 0.1–6 eV)
+    pub fn copper() -> Self {
+        Self::rakic(
+            "Cu (Lorentz-Drude)",
+            10.83,
+            (0.575, 0.030),
+            &[
+                (0.061, 0.291, 0.378),
+                (0.104, 2.957, 1.056),
+                (0.723, 5.300, 3.213),
+                (0.638, 11.18, 4.305),
+            ],
+            (0.1, 6.0),
+        )
+    }
+}
+
+impl Dispersion for LorentzDrudeModel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex {
+        RefractiveIndex::from_permittivity(self.permittivity_at(wavelength_nm))
+    }
+
+    fn permittivity_at(&self, wavelength_nm: f64) -> Complex64 {
+        let omega = conversions::HC_EV_NM / wavelength_nm;
+        let wp2 = self.plasma_ev * self.plasma_ev;
+        let drude = self.drude_strength * wp2 / Complex64::new(omega * omega, self.drude_damping_ev * omega);
+        let interband: Complex64 = self
+            .oscillators
+            .iter()
+            .map(|osc| {
+                osc.strength * wp2
+                    / Complex64::new(osc.resonance_ev.powi(2) - omega * omega, -omega * osc.damping_ev)
+            })
+            .sum();
+        1.0 - drude + interband
+    }
+
+    /// The fitted photon-energy range converted to nm
+    fn wavelength_range(&self) -> Option<(f64, f64)> {
+        self.fit_range_ev
+            .map(|(min, max)| (conversions::HC_EV_NM / max, conversions::HC_EV_NM / min))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interband_absorption_of_gold() {
+        let gold = LorentzDrudeModel::gold();
+        for metal in [LorentzDrudeModel::gold(), LorentzDrudeModel::silver(), LorentzDrudeModel::aluminium(), LorentzDrudeModel::copper()] {
+            for wl in [300.0, 500.0, 800.0] {
+                let eps = metal.permittivity_at(wl);
+                assert!(eps.im > 0.0, "{} at {} nm", metal.name, wl);
+            }
+            assert!(metal.permittivity_at(800.0).re < 0.0, "{}", metal.name);
+        }
+        // Near 500 nm Au absorbs far more than its Drude part alone: Im ε ≈ 2–4
+        let eps = gold.permittivity_at(500.0);
+        assert!((1.5..5.0).contains(&eps.im), "{}", eps);
+        assert!((-4.0..0.0).contains(&eps.re), "{}", eps);
+        let drude_only = LorentzDrudeModel { oscillators: Vec::new(), ..gold.clone() };
+        assert!(drude_only.permittivity_at(500.0).im < eps.im / 2.0);
+    }
+
+    #[test]
+    fn test_wavelength_range_follows_fit() {
+        let gold = LorentzDrudeModel::gold();
+        let (min, max) = gold.wavelength_range().unwrap();
+        assert!((min - 247.97).abs() < 0.01 && (max - 6199.2).abs() < 0.1);
+        assert!(gold.contains(520.0) && !gold.contains(200.0));
+        assert_eq!(LorentzDrudeModel::new("bare", 9.0, 1.0, 0.1, Vec::new()).wavelength_range(), None);
+    }
+}
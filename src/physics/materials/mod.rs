@@ -1,2 +1,28 @@
 //! Material database
+//!
+//! [`Dispersion`] (also exported as [`DispersiveMaterial`]) is a material
+//! whose refractive index depends on wavelength, queried through
+//! `refractive_index_at` and `permittivity_at`. Implement it for any new
+//! source of optical constants — a model, a fit or a data table — so the
+//! optical models can follow the particle's index across a spectrum.
 
+pub mod database;
+pub mod dispersion;
+pub mod drude;
+pub mod effective_medium;
+pub mod formula;
+pub mod johnson_christy;
+pub mod lorentz_drude;
+pub mod sellmeier;
+pub mod table;
+pub mod tabulated;
+pub mod yaml;
+
+pub use dispersion::{Dispersion, Dispersion as DispersiveMaterial, MaterialClass, ABSORBING_K_THRESHOLD};
+pub use drude::DrudeModel;
+pub use effective_medium::{bruggeman, effective_index, maxwell_garnett};
+pub use formula::FormulaDispersion;
+pub use lorentz_drude::{LorentzDrudeModel, LorentzOscillator};
+pub use sellmeier::SellmeierMaterial;
+pub use table::{column_count, parse_dispersion_table, ColumnRole, ColumnSpec, XUnit};
+pub use tabulated::{DatasetBounds, Interpolation, OutOfRange, TabulatedMaterial};
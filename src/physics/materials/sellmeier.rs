@@ -0,0 +1,45 @@
+//! Sellmeier dispersion formulas
+
+use super::dispersion::Dispersion;
+use crate::core::RefractiveIndex;
+
+/// Sellmeier dispersion for transparent dielectrics
+///
+/// n² − 1 = C₀ + Σᵢ Bᵢ λ² / (λ² − Cᵢ), with λ in µm. When `squared_poles`
+/// is set the poles are given as Cᵢ² (refractiveindex.info "formula 1"),
+/// otherwise directly as Cᵢ ("formula 2").
+#[derive(Debug, Clone)]
+pub struct SellmeierMaterial {
+    pub name: String,
+    /// Constant term C₀
+    pub c0: f64,
+    /// (Bᵢ, Cᵢ) oscillator terms
+    pub terms: Vec<(f64, f64)>,
+    pub squared_poles: bool,
+    /// Valid range in nm
+    pub range_nm: Option<(f64, f64)>,
+}
+
+impl Dispersion for SellmeierMaterial {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex {
+        let l2 = (wavelength_nm * 1e-3).powi(2);
+        let n2_minus_1 = self.c0
+            + self
+                .terms
+                .iter()
+                .map(|&(b, c)| {
+                    let pole = if self.squared_poles { c * c } else { c };
+                    b * l2 / (l2 - pole)
+                })
+                .sum::<f64>();
+        RefractiveIndex::new((1.0 + n2_minus_1).max(0.0).sqrt(), 0.0)
+    }
+
+    fn wavelength_range(&self) -> Option<(f64, f64)> {
+        self.range_nm
+    }
+}
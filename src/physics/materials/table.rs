@@ -0,0 +1,304 @@
+//! Free-form optical constant tables
+//!
+//! Pasted or loaded data comes in many layouts: (λ, n, k), (λ, n) only, or
+//! photon energy against permittivity (E, ε', ε''). A [`ColumnSpec`] assigns
+//! a role to each column and gives the unit of the x column; the table is
+//! then converted to wavelength-ordered n + ik samples.
+
+use super::tabulated::TabulatedMaterial;
+use crate::core::conversions::HC_EV_NM;
+use crate::core::{CalcResult, CalculationError, RefractiveIndex};
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+/// Unit of the x column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum XUnit {
+    #[default]
+    Nanometer,
+    Micrometer,
+    /// Photon energy
+    ElectronVolt,
+}
+
+impl XUnit {
+    pub const ALL: [XUnit; 3] = [XUnit::Nanometer, XUnit::Micrometer, XUnit::ElectronVolt];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            XUnit::Nanometer => "nm",
+            XUnit::Micrometer => "µm",
+            XUnit::ElectronVolt => "eV",
+        }
+    }
+
+    /// Wavelength in nm of an x value in this unit
+    pub fn to_nm(self, x: f64) -> f64 {
+        match self {
+            XUnit::Nanometer => x,
+            XUnit::Micrometer => x * 1e3,
+            XUnit::ElectronVolt => HC_EV_NM / x,
+        }
+    }
+}
+
+/// What a column holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColumnRole {
+    #[default]
+    Ignore,
+    /// Wavelength or photon energy
+    X,
+    N,
+    K,
+    /// Real part of the permittivity ε'
+    EpsilonReal,
+    /// Imaginary part of the permittivity ε''
+    EpsilonImag,
+}
+
+impl ColumnRole {
+    pub const ALL: [ColumnRole; 6] = [
+        ColumnRole::Ignore,
+        ColumnRole::X,
+        ColumnRole::N,
+        ColumnRole::K,
+        ColumnRole::EpsilonReal,
+        ColumnRole::EpsilonImag,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ColumnRole::Ignore => "—",
+            ColumnRole::X => "x",
+            ColumnRole::N => "n",
+            ColumnRole::K => "k",
+            ColumnRole::EpsilonReal => "ε'",
+            ColumnRole::EpsilonImag => "ε''",
+        }
+    }
+}
+
+/// Role of each column, in order, and the unit of the x column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSpec {
+    pub roles: Vec<ColumnRole>,
+    pub x_unit: XUnit,
+}
+
+impl ColumnSpec {
+    /// The usual (λ nm, n, k) reading of a table with `columns` columns
+    pub fn guess(columns: usize) -> Self {
+        let mut roles = vec![ColumnRole::Ignore; columns];
+        for (role, guess) in roles.iter_mut().zip([ColumnRole::X, ColumnRole::N, ColumnRole::K]) {
+            *role = guess;
+        }
+        Self {
+            roles,
+            x_unit: XUnit::Nanometer,
+        }
+    }
+
+    fn column(&self, role: ColumnRole) -> CalcResult<Option<usize>> {
+        let mut found = self.roles.iter().enumerate().filter(|(_, &r)| r == role).map(|(i, _)| i);
+        let first = found.next();
+        if found.next().is_some() {
+            return Err(invalid(format!("Only one column can hold {}", role.symbol())));
+        }
+        Ok(first)
+    }
+
+    fn layout(&self) -> CalcResult<Layout> {
+        let x = self.column(ColumnRole::X)?.ok_or_else(|| invalid("No column is marked as x"))?;
+        let n = self.column(ColumnRole::N)?;
+        let k = self.column(ColumnRole::K)?;
+        let eps = (self.column(ColumnRole::EpsilonReal)?, self.column(ColumnRole::EpsilonImag)?);
+        match (n, k, eps) {
+            (Some(n), k, (None, None)) => Ok(Layout::Index { x, n, k }),
+            (None, None, (Some(re), Some(im))) => Ok(Layout::Permittivity { x, re, im }),
+            (None, None, (None, None)) | (None, Some(_), (None, None)) => {
+                Err(invalid("Mark an n column, or both ε' and ε'' columns"))
+            }
+            (None, None, _) => Err(invalid("Permittivity needs both ε' and ε'' columns")),
+            _ => Err(invalid("Columns mix n,k with ε'; use one or the other")),
+        }
+    }
+}
+
+/// Column indices of a validated spec
+enum Layout {
+    Index { x: usize, n: usize, k: Option<usize> },
+    Permittivity { x: usize, re: usize, im: usize },
+}
+
+impl Layout {
+    fn columns_needed(&self) -> usize {
+        match *self {
+            Layout::Index { x, n, k } => x.max(n).max(k.unwrap_or(0)) + 1,
+            Layout::Permittivity { x, re, im } => x.max(re).max(im) + 1,
+        }
+    }
+
+    /// (x, n, k) for one row of values
+    fn sample(&self, values: &[f64]) -> (f64, RefractiveIndex) {
+        match *self {
+            Layout::Index { x, n, k } => (values[x], RefractiveIndex::new(values[n], k.map_or(0.0, |k| values[k]))),
+            Layout::Permittivity { x, re, im } => (
+                values[x],
+                RefractiveIndex::from_permittivity(Complex64::new(values[re], values[im])),
+            ),
+        }
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> CalculationError {
+    CalculationError::InvalidInput(msg.into())
+}
+
+/// Numbers on a line separated by whitespace, commas or semicolons
+fn numbers(line: &str) -> Option<Vec<f64>> {
+    line.split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|tok| !tok.is_empty())
+        .map(|tok| tok.parse::<f64>().ok())
+        .collect()
+}
+
+fn is_comment(line: &str) -> bool {
+    line.is_empty() || line.starts_with('#') || line.starts_with('%')
+}
+
+/// Number of columns in the first data row, if the text has any
+pub fn column_count(text: &str) -> Option<usize> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !is_comment(line))
+        .find_map(numbers)
+        .map(|values| values.len())
+}
+
+/// Parse a pasted or loaded table into a wavelength-ordered dispersion
+///
+/// Blank lines and lines starting with `#` or `%` are skipped, as are
+/// header lines before the first row of numbers. The x column must be
+/// strictly monotonic in either direction.
+pub fn parse_dispersion_table(text: &str, spec: &ColumnSpec) -> CalcResult<TabulatedMaterial> {
+    let layout = spec.layout()?;
+    let needed = layout.columns_needed();
+
+    let mut samples = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if is_comment(line) {
+            continue;
+        }
+        let Some(values) = numbers(line) else {
+            if samples.is_empty() {
+                continue;
+            }
+            return Err(invalid(format!("Invalid number on line {}: '{}'", i + 1, line)));
+        };
+        if values.len() < needed {
+            return Err(invalid(format!(
+                "Expected at least {} columns on line {}, found {}",
+                needed,
+                i + 1,
+                values.len()
+            )));
+        }
+        samples.push(layout.sample(&values));
+    }
+    if samples.is_empty() {
+        return Err(invalid("Table has no data rows"));
+    }
+
+    let increasing = samples[0].0 < samples[samples.len() - 1].0;
+    if let Some(pair) = samples
+        .windows(2)
+        .find(|pair| (pair[1].0 > pair[0].0) != increasing || pair[1].0 == pair[0].0)
+    {
+        return Err(invalid(format!(
+            "x values must be strictly monotonic ({} after {})",
+            pair[1].0, pair[0].0
+        )));
+    }
+    if let Some(&(x, _)) = samples.iter().find(|s| s.0.is_nan() || s.0 <= 0.0) {
+        return Err(invalid(format!("x values must be positive, got {} {}", x, spec.x_unit.symbol())));
+    }
+
+    let mut rows: Vec<(f64, f64, f64)> = samples
+        .iter()
+        .map(|&(x, index)| (spec.x_unit.to_nm(x), index.real, index.imaginary))
+        .collect();
+    // Energies and decreasing wavelengths both arrive in the wrong order
+    if rows.len() > 1 && rows[0].0 > rows[1].0 {
+        rows.reverse();
+    }
+    TabulatedMaterial::new("Imported table", &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::materials::Dispersion;
+
+    #[test]
+    fn test_lambda_n_k_with_header_and_commas() {
+        let text = "wavelength (um), n, k\n0.4, 1.5, 0.1\n0.5, 1.6, 0.2\n0.6, 1.7, 0.3\n";
+        let spec = ColumnSpec {
+            x_unit: XUnit::Micrometer,
+            ..ColumnSpec::guess(3)
+        };
+        let material = parse_dispersion_table(text, &spec).unwrap();
+        assert_eq!(material.rows(), vec![(400.0, 1.5, 0.1), (500.0, 1.6, 0.2), (600.0, 1.7, 0.3)]);
+    }
+
+    #[test]
+    fn test_lambda_n_only_is_lossless() {
+        let text = "# silica\n800 1.453\n600\t1.458\n400 1.470\n";
+        assert_eq!(column_count(text), Some(2));
+        let material = parse_dispersion_table(text, &ColumnSpec::guess(2)).unwrap();
+        // Decreasing wavelengths are reordered
+        assert_eq!(material.rows(), vec![(400.0, 1.470, 0.0), (600.0, 1.458, 0.0), (800.0, 1.453, 0.0)]);
+    }
+
+    #[test]
+    fn test_energy_permittivity_converts_to_index() {
+        let gold = RefractiveIndex::new(0.25, 3.0).to_permittivity();
+        let glass = RefractiveIndex::new(1.5, 0.0).to_permittivity();
+        let text = format!("E eps1 eps2 extra\n1.5 {} {} 9\n3.0 {} {} 9\n", gold.re, gold.im, glass.re, glass.im);
+        let spec = ColumnSpec {
+            roles: vec![ColumnRole::X, ColumnRole::EpsilonReal, ColumnRole::EpsilonImag, ColumnRole::Ignore],
+            x_unit: XUnit::ElectronVolt,
+        };
+        let material = parse_dispersion_table(&text, &spec).unwrap();
+
+        let (short, long) = (HC_EV_NM / 3.0, HC_EV_NM / 1.5);
+        assert_eq!(material.wavelength_range(), Some((short, long)));
+        let n = material.refractive_index_at(long);
+        assert!((n.real - 0.25).abs() < 1e-12 && (n.imaginary - 3.0).abs() < 1e-12, "{}", n);
+        let n = material.refractive_index_at(short);
+        assert!((n.real - 1.5).abs() < 1e-12 && n.imaginary.abs() < 1e-12, "{}", n);
+    }
+
+    #[test]
+    fn test_rejects_bad_tables_and_specs() {
+        let spec = ColumnSpec::guess(3);
+        assert!(parse_dispersion_table("", &spec).is_err());
+        assert!(parse_dispersion_table("header only\n", &spec).is_err());
+        assert!(parse_dispersion_table("400 1 0\n500 1 0\n450 1 0\n", &spec).is_err());
+        assert!(parse_dispersion_table("400 1 0\n400 1 0\n", &spec).is_err());
+        assert!(parse_dispersion_table("400 1 0\n500 1\n", &spec).is_err());
+        assert!(parse_dispersion_table("400 1 0\noops\n", &spec).is_err());
+
+        let data = "400 1 0\n500 1 0\n";
+        for roles in [
+            vec![ColumnRole::N, ColumnRole::N, ColumnRole::K],
+            vec![ColumnRole::Ignore, ColumnRole::N, ColumnRole::K],
+            vec![ColumnRole::X, ColumnRole::EpsilonReal, ColumnRole::Ignore],
+            vec![ColumnRole::X, ColumnRole::N, ColumnRole::EpsilonImag],
+        ] {
+            let spec = ColumnSpec { roles, x_unit: XUnit::Nanometer };
+            assert!(parse_dispersion_table(data, &spec).is_err(), "{:?}", spec);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Tabulated optical constants with interpolation
+
+use super::dispersion::Dispersion;
+use super::table::{parse_dispersion_table, ColumnSpec};
+use crate::core::{CalcResult, CalculationError, RefractiveIndex, Warning, WarningCode, WarningSeverity};
+use crate::utils::interp::{self, natural_spline_curvature, Extrapolation};
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+
+/// Wavelength span covered by a table, in nm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatasetBounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl DatasetBounds {
+    pub fn contains(&self, wavelength_nm: f64) -> bool {
+        (self.min..=self.max).contains(&wavelength_nm)
+    }
+
+    /// `wavelength_nm` moved onto the nearest edge if outside the data
+    pub fn clamp(&self, wavelength_nm: f64) -> f64 {
+        wavelength_nm.clamp(self.min, self.max)
+    }
+
+    /// Warning listing the valid range if any of `wavelengths` is outside it
+    pub fn check(&self, wavelengths: &[f64]) -> Option<Warning> {
+        let outside = wavelengths.iter().filter(|&&wl| !self.contains(wl)).count();
+        (outside > 0).then(|| {
+            Warning::new(
+                WarningCode::WavelengthOutOfDatasetRange,
+                WarningSeverity::Caution,
+                format!(
+                    "data covers {}; {} of {} wavelengths clamped to the edge",
+                    self,
+                    outside,
+                    wavelengths.len()
+                ),
+            )
+        })
+    }
+}
+
+impl fmt::Display for DatasetBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}–{} nm", self.min, self.max)
+    }
+}
+
+/// How n and k are interpolated between tabulated samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Straight lines between neighbouring samples
+    #[default]
+    Linear,
+    /// Natural cubic spline through all samples
+    CubicSpline,
+}
+
+/// What [`TabulatedMaterial::index_at`] does outside the tabulated range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// Hold the edge values
+    #[default]
+    Clamp,
+    /// Fail with [`CalculationError::InvalidInput`]
+    Reject,
+}
+
+/// Optical constants sampled at discrete wavelengths
+///
+/// Between samples n and k are interpolated linearly or with a cubic
+/// spline. Outside the table the edge values are held, unless the table
+/// rejects such wavelengths in [`TabulatedMaterial::index_at`].
+#[derive(Debug, Clone)]
+pub struct TabulatedMaterial {
+    name: String,
+    /// Wavelengths in nm, strictly increasing
+    wavelengths: Vec<f64>,
+    n: Vec<f64>,
+    k: Vec<f64>,
+    interpolation: Interpolation,
+    out_of_range: OutOfRange,
+    /// Spline second derivatives of n and k at each sample; empty when linear
+    n_curvature: Vec<f64>,
+    k_curvature: Vec<f64>,
+}
+
+impl TabulatedMaterial {
+    /// Build a table from (wavelength nm, n, k) rows
+    pub fn new(name: impl Into<String>, rows: &[(f64, f64, f64)]) -> CalcResult<Self> {
+        if rows.is_empty() {
+            return Err(CalculationError::InvalidInput(
+                "Tabulated material needs at least one data point".to_string(),
+            ));
+        }
+        for pair in rows.windows(2) {
+            if pair[1].0 <= pair[0].0 {
+                return Err(CalculationError::InvalidInput(format!(
+                    "Wavelengths must be strictly increasing ({} nm after {} nm)",
+                    pair[1].0, pair[0].0
+                )));
+            }
+        }
+        if let Some(row) = rows
+            .iter()
+            .find(|r| !(r.0 > 0.0 && r.0.is_finite() && r.1.is_finite() && r.2.is_finite()))
+        {
+            return Err(CalculationError::InvalidInput(format!(
+                "Invalid data point (λ = {}, n = {}, k = {})",
+                row.0, row.1, row.2
+            )));
+        }
+
+        Ok(Self {
+            name: name.into(),
+            wavelengths: rows.iter().map(|r| r.0).collect(),
+            n: rows.iter().map(|r| r.1).collect(),
+            k: rows.iter().map(|r| r.2).collect(),
+            interpolation: Interpolation::Linear,
+            out_of_range: OutOfRange::Clamp,
+            n_curvature: Vec::new(),
+            k_curvature: Vec::new(),
+        })
+    }
+
+    /// Read a (wavelength nm, n, k) CSV file, named after the file stem
+    ///
+    /// See [`Self::from_reader`] for the accepted layout.
+    pub fn from_csv(path: impl AsRef<Path>) -> CalcResult<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|e| {
+            CalculationError::InvalidInput(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::from_reader(name, file)
+    }
+
+    /// Read (wavelength nm, n, k) rows from any reader
+    ///
+    /// Columns may be separated by commas, semicolons or whitespace. Blank
+    /// lines, `#` comments and header lines before the first row are
+    /// skipped; a malformed row is an error naming its line number.
+    /// Wavelengths may run in either direction but must not repeat.
+    pub fn from_reader(name: impl Into<String>, mut reader: impl Read) -> CalcResult<Self> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| CalculationError::InvalidInput(format!("Cannot read table: {}", e)))?;
+        let table = parse_dispersion_table(&text, &ColumnSpec::guess(3))?;
+        Self::new(name, &table.rows())
+    }
+
+    /// The table as `wavelength_nm,n,k` CSV, readable by [`Self::from_reader`]
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("# {}\nwavelength_nm,n,k\n", self.name);
+        for (wl, n, k) in self.rows() {
+            out.push_str(&format!("{},{},{}\n", wl, n, k));
+        }
+        out
+    }
+
+    /// The same table interpolated with `interpolation`
+    pub fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        let (n_curvature, k_curvature) = match interpolation {
+            Interpolation::Linear => (Vec::new(), Vec::new()),
+            Interpolation::CubicSpline => (
+                natural_spline_curvature(&self.wavelengths, &self.n),
+                natural_spline_curvature(&self.wavelengths, &self.k),
+            ),
+        };
+        Self {
+            interpolation,
+            n_curvature,
+            k_curvature,
+            ..self
+        }
+    }
+
+    /// The same table with `out_of_range` handling in [`Self::index_at`]
+    pub fn with_out_of_range(self, out_of_range: OutOfRange) -> Self {
+        Self { out_of_range, ..self }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn out_of_range(&self) -> OutOfRange {
+        self.out_of_range
+    }
+
+    /// Index at `wavelength_nm`, honouring the out-of-range setting
+    ///
+    /// [`Dispersion::refractive_index_at`] always clamps; this fails for
+    /// wavelengths outside the data when the table is set to
+    /// [`OutOfRange::Reject`].
+    pub fn index_at(&self, wavelength_nm: f64) -> CalcResult<RefractiveIndex> {
+        let bounds = self.bounds();
+        if self.out_of_range == OutOfRange::Reject && !bounds.contains(wavelength_nm) {
+            return Err(CalculationError::InvalidInput(format!(
+                "{} nm is outside the {} data ({})",
+                wavelength_nm, self.name, bounds
+            )));
+        }
+        Ok(self.refractive_index_at(wavelength_nm))
+    }
+
+    /// Data rows as (wavelength nm, n, k)
+    pub fn rows(&self) -> Vec<(f64, f64, f64)> {
+        self.wavelengths
+            .iter()
+            .zip(&self.n)
+            .zip(&self.k)
+            .map(|((&wl, &n), &k)| (wl, n, k))
+            .collect()
+    }
+
+    /// First and last tabulated wavelength
+    pub fn bounds(&self) -> DatasetBounds {
+        DatasetBounds {
+            min: self.wavelengths[0],
+            max: self.wavelengths[self.wavelengths.len() - 1],
+        }
+    }
+
+    /// Index of the last sample at or below `wavelength_nm`, clamped to the
+    /// first and last sample outside the table
+    pub fn edge_index(&self, wavelength_nm: f64) -> usize {
+        let last = self.wavelengths.len() - 1;
+        self.wavelengths
+            .partition_point(|&wl| wl <= wavelength_nm)
+            .saturating_sub(1)
+            .min(last)
+    }
+
+    /// Indices over `wavelengths`, clamped to the data, with a warning if
+    /// any wavelength lies outside it
+    pub fn spectrum(&self, wavelengths: &[f64]) -> (Vec<RefractiveIndex>, Option<Warning>) {
+        let indices = wavelengths.iter().map(|&wl| self.refractive_index_at(wl)).collect();
+        (indices, self.bounds().check(wavelengths))
+    }
+
+    fn interpolate(&self, values: &[f64], curvature: &[f64], wavelength_nm: f64) -> f64 {
+        // The table is non-empty with one curvature per sample, so clamping
+        // only fails for a NaN wavelength
+        interp::interpolate(&self.wavelengths, values, curvature, wavelength_nm, Extrapolation::Clamp).unwrap_or(f64::NAN)
+    }
+}
+
+impl Dispersion for TabulatedMaterial {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn refractive_index_at(&self, wavelength_nm: f64) -> RefractiveIndex {
+        RefractiveIndex::new(
+            self.interpolate(&self.n, &self.n_curvature, wavelength_nm),
+            self.interpolate(&self.k, &self.k_curvature, wavelength_nm),
+        )
+    }
+
+    fn wavelength_range(&self) -> Option<(f64, f64)> {
+        let bounds = self.bounds();
+        Some((bounds.min, bounds.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_interpolation_midpoint() {
+        let mat = TabulatedMaterial::new("test", &[(400.0, 1.0, 0.0), (600.0, 2.0, 1.0)]).unwrap();
+        let n = mat.refractive_index_at(500.0);
+        assert!((n.real - 1.5).abs() < 1e-12);
+        assert!((n.imaginary - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spectrum_past_dataset_warns_and_clamps_to_edge() {
+        let mat = TabulatedMaterial::new("test", &[(400.0, 1.0, 0.0), (500.0, 1.5, 0.2), (600.0, 2.0, 1.0)]).unwrap();
+        assert_eq!(mat.edge_index(300.0), 0);
+        assert_eq!(mat.edge_index(600.0), 2);
+        assert_eq!(mat.edge_index(800.0), 2);
+
+        let (indices, warning) = mat.spectrum(&[300.0, 400.0, 550.0, 600.0, 800.0]);
+        assert_eq!(indices[0], RefractiveIndex::new(1.0, 0.0));
+        assert_eq!(indices[3], RefractiveIndex::new(2.0, 1.0));
+        assert_eq!(indices[4], indices[3]);
+
+        let warning = warning.unwrap();
+        assert_eq!(warning.code, WarningCode::WavelengthOutOfDatasetRange);
+        assert!(warning.detail.contains("400–600 nm"), "{}", warning.detail);
+        assert!(warning.detail.contains("2 of 5"), "{}", warning.detail);
+
+        assert!(mat.spectrum(&[400.0, 600.0]).1.is_none());
+    }
+
+    #[test]
+    fn test_cubic_spline_passes_through_samples() {
+        // Samples of n = (λ/100)², k = λ/1000
+        let rows: Vec<_> = (0..7)
+            .map(|i| {
+                let wl = 400.0 + 50.0 * i as f64;
+                (wl, (wl / 100.0).powi(2), wl / 1000.0)
+            })
+            .collect();
+        let linear = TabulatedMaterial::new("test", &rows).unwrap();
+        let spline = linear.clone().with_interpolation(Interpolation::CubicSpline);
+        assert_eq!(spline.interpolation(), Interpolation::CubicSpline);
+
+        for &(wl, n, k) in &rows {
+            let at = spline.refractive_index_at(wl);
+            assert!((at.real - n).abs() < 1e-12 && (at.imaginary - k).abs() < 1e-12);
+        }
+        // Straight-line k stays exact; the curved n is far closer than linear
+        let at = spline.refractive_index_at(525.0);
+        assert!((at.imaginary - 0.525).abs() < 1e-12);
+        let exact = 5.25_f64.powi(2);
+        let spline_error = (at.real - exact).abs();
+        let linear_error = (linear.refractive_index_at(525.0).real - exact).abs();
+        assert!(spline_error < linear_error / 5.0, "{} vs {}", spline_error, linear_error);
+    }
+
+    #[test]
+    fn test_out_of_range_flag() {
+        let mat = TabulatedMaterial::new("test", &[(400.0, 1.0, 0.0), (600.0, 2.0, 1.0)]).unwrap();
+        assert_eq!(mat.index_at(300.0).unwrap(), RefractiveIndex::new(1.0, 0.0));
+
+        let strict = mat.with_out_of_range(OutOfRange::Reject);
+        assert!(matches!(strict.index_at(300.0), Err(CalculationError::InvalidInput(_))));
+        assert!(strict.index_at(700.0).is_err());
+        assert_eq!(strict.index_at(600.0).unwrap(), RefractiveIndex::new(2.0, 1.0));
+        // The trait method still clamps
+        assert_eq!(strict.refractive_index_at(700.0), RefractiveIndex::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mat = TabulatedMaterial::new("ellipsometry", &[(400.0, 1.47, 1.95), (550.5, 0.43, 2.46), (800.0, 0.15, 4.91)])
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("nanocalc_tabulated_{}.csv", std::process::id()));
+        std::fs::write(&path, mat.to_csv()).unwrap();
+        let read = TabulatedMaterial::from_csv(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.rows(), mat.rows());
+        assert_eq!(read.name(), format!("nanocalc_tabulated_{}", std::process::id()));
+    }
+
+    #[test]
+    fn test_csv_errors_name_the_line() {
+        let text = "# measured\nlambda,n,k\n400,1.5,0.1\n\n500,1.6\n";
+        match TabulatedMaterial::from_reader("short", text.as_bytes()) {
+            Err(CalculationError::InvalidInput(msg)) => assert!(msg.contains("line 5"), "{}", msg),
+            other => panic!("expected an error, got {:?}", other),
+        }
+        let text = "400,1.5,0.1\n500,x,0.2\n";
+        match TabulatedMaterial::from_reader("garbled", text.as_bytes()) {
+            Err(CalculationError::InvalidInput(msg)) => assert!(msg.contains("line 2"), "{}", msg),
+            other => panic!("expected an error, got {:?}", other),
+        }
+        assert!(TabulatedMaterial::from_reader("repeat", "400,1,0\n400,1,0\n".as_bytes()).is_err());
+        assert!(TabulatedMaterial::from_csv("/nonexistent/nk.csv").is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_monotonic_wavelengths() {
+        assert!(TabulatedMaterial::new("bad", &[(500.0, 1.0, 0.0), (400.0, 1.0, 0.0)]).is_err());
+    }
+}
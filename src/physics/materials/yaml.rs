@@ -0,0 +1,292 @@
+//! refractiveindex.info YAML import/export
+//!
+//! Supports the `tabulated nk`, `tabulated n`, `formula 1` and `formula 2`
+//! (Sellmeier) entry types. An n entry may be followed by a `tabulated k`
+//! entry, as in files that give n and k in separate blocks; the two are
+//! merged into one table. Wavelengths in these files are in µm.
+
+use super::dispersion::Dispersion;
+use super::sellmeier::SellmeierMaterial;
+use super::tabulated::TabulatedMaterial;
+use crate::core::{CalcResult, CalculationError};
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct RiiFile {
+    #[serde(rename = "DATA")]
+    data: Vec<RiiEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RiiEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    data: Option<String>,
+    coefficients: Option<serde_yaml::Value>,
+    wavelength_range: Option<serde_yaml::Value>,
+}
+
+fn invalid(msg: impl Into<String>) -> CalculationError {
+    CalculationError::InvalidInput(msg.into())
+}
+
+/// Parse a whitespace-separated list of numbers (YAML may give a scalar or a string)
+fn numbers(value: &serde_yaml::Value, field: &str) -> CalcResult<Vec<f64>> {
+    match value {
+        serde_yaml::Value::Number(n) => n
+            .as_f64()
+            .map(|v| vec![v])
+            .ok_or_else(|| invalid(format!("Invalid number in '{}'", field))),
+        serde_yaml::Value::String(s) => s
+            .split_whitespace()
+            .map(|tok| {
+                tok.parse::<f64>()
+                    .map_err(|_| invalid(format!("Invalid number '{}' in '{}'", tok, field)))
+            })
+            .collect(),
+        _ => Err(invalid(format!("Field '{}' must be a list of numbers", field))),
+    }
+}
+
+fn parse_table(data: &str, columns: usize) -> CalcResult<Vec<(f64, f64, f64)>> {
+    let mut rows = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let values: Vec<f64> = line
+            .split_whitespace()
+            .map(|tok| tok.parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid(format!("Invalid number on data line {}: '{}'", i + 1, line)))?;
+        if values.len() != columns {
+            return Err(invalid(format!(
+                "Expected {} columns on data line {}, found {}",
+                columns,
+                i + 1,
+                values.len()
+            )));
+        }
+        let k = if columns == 3 { values[2] } else { 0.0 };
+        rows.push((values[0] * 1e3, values[1], k));
+    }
+    Ok(rows)
+}
+
+/// Rows of a tabulated entry with `columns` numbers per line
+fn entry_rows(entry: &RiiEntry, columns: usize) -> CalcResult<Vec<(f64, f64, f64)>> {
+    let data = entry
+        .data
+        .as_deref()
+        .ok_or_else(|| invalid(format!("'{}' entry has no data block", entry.kind)))?;
+    parse_table(data, columns)
+}
+
+/// Parse refractiveindex.info YAML text into an evaluable dispersion
+pub fn parse(text: &str, name: &str) -> CalcResult<Box<dyn Dispersion>> {
+    let file: RiiFile =
+        serde_yaml::from_str(text).map_err(|e| invalid(format!("Invalid YAML: {}", e)))?;
+    match file.data.as_slice() {
+        [] => Err(invalid("YAML file has no DATA entries")),
+        [entry] => parse_entry(entry, name),
+        [n_entry, k_entry] if k_entry.kind == "tabulated k" && n_entry.kind != "tabulated nk" => {
+            Ok(Box::new(merge_k(n_entry, k_entry, name)?))
+        }
+        entries => Err(invalid(format!(
+            "Expected one DATA entry, or an n entry followed by 'tabulated k'; found {}",
+            entries.len()
+        ))),
+    }
+}
+
+/// n from `n_entry` and k from `k_entry`, tabulated on the wavelengths of
+/// both that lie in the range they share
+fn merge_k(n_entry: &RiiEntry, k_entry: &RiiEntry, name: &str) -> CalcResult<TabulatedMaterial> {
+    let n = parse_entry(n_entry, name)?;
+    let k_rows = entry_rows(k_entry, 2)?;
+    // parse_table puts the second column in the n slot
+    let k_table: Vec<_> = k_rows.iter().map(|&(wl, k, _)| (wl, 1.0, k)).collect();
+    let k = TabulatedMaterial::new(name, &k_table)?;
+
+    let (k_min, k_max) = (k_table[0].0, k_table[k_table.len() - 1].0);
+    let (n_min, n_max) = n.wavelength_range().unwrap_or((0.0, f64::INFINITY));
+    let (lo, hi) = (n_min.max(k_min), n_max.min(k_max));
+    if lo > hi {
+        return Err(invalid(format!(
+            "n ({}–{} nm) and k ({}–{} nm) blocks do not overlap",
+            n_min, n_max, k_min, k_max
+        )));
+    }
+
+    let n_samples = if n_entry.kind == "tabulated n" { entry_rows(n_entry, 2)? } else { Vec::new() };
+    let mut wavelengths: Vec<f64> = n_samples
+        .iter()
+        .chain(&k_table)
+        .map(|row| row.0)
+        .chain([lo, hi])
+        .filter(|wl| (lo..=hi).contains(wl))
+        .collect();
+    wavelengths.sort_by(f64::total_cmp);
+    wavelengths.dedup();
+    let rows: Vec<_> = wavelengths
+        .into_iter()
+        .map(|wl| (wl, n.refractive_index_at(wl).real, k.refractive_index_at(wl).imaginary))
+        .collect();
+    TabulatedMaterial::new(name, &rows)
+}
+
+/// One n (or n and k) entry as an evaluable dispersion
+fn parse_entry(entry: &RiiEntry, name: &str) -> CalcResult<Box<dyn Dispersion>> {
+    match entry.kind.as_str() {
+        kind @ ("tabulated nk" | "tabulated n") => {
+            let columns = if kind == "tabulated nk" { 3 } else { 2 };
+            let rows = entry_rows(entry, columns)?;
+            Ok(Box::new(TabulatedMaterial::new(name, &rows)?))
+        }
+        kind @ ("formula 1" | "formula 2") => {
+            let coeffs = numbers(
+                entry
+                    .coefficients
+                    .as_ref()
+                    .ok_or_else(|| invalid(format!("'{}' entry has no coefficients", kind)))?,
+                "coefficients",
+            )?;
+            if coeffs.is_empty() || coeffs.len() % 2 == 0 {
+                return Err(invalid(format!(
+                    "Sellmeier formula needs C₀ plus coefficient pairs, found {} values",
+                    coeffs.len()
+                )));
+            }
+            let range_nm = match &entry.wavelength_range {
+                Some(value) => {
+                    let range = numbers(value, "wavelength_range")?;
+                    if range.len() != 2 || !(range[0] > 0.0 && range[1] > range[0]) {
+                        return Err(invalid(format!(
+                            "Invalid wavelength_range {:?} (expected 'min max' in µm)",
+                            range
+                        )));
+                    }
+                    Some((range[0] * 1e3, range[1] * 1e3))
+                }
+                None => None,
+            };
+            Ok(Box::new(SellmeierMaterial {
+                name: name.to_string(),
+                c0: coeffs[0],
+                terms: coeffs[1..].chunks(2).map(|c| (c[0], c[1])).collect(),
+                squared_poles: kind == "formula 1",
+                range_nm,
+            }))
+        }
+        "tabulated k" => Err(invalid("'tabulated k' entry must follow an n entry")),
+        other => Err(invalid(format!("Unsupported refractiveindex.info type '{}'", other))),
+    }
+}
+
+/// Load a refractiveindex.info YAML file
+pub fn load(path: impl AsRef<Path>) -> CalcResult<Box<dyn Dispersion>> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| invalid(format!("Cannot read {}: {}", path.display(), e)))?;
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    parse(&text, &name)
+}
+
+/// Render tabulated data as a `tabulated nk` refractiveindex.info YAML document
+pub fn export_tabulated(material: &TabulatedMaterial) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("REFERENCES: \"Exported from NanoCalc ({})\"\n", material.name()));
+    out.push_str("DATA:\n  - type: tabulated nk\n    data: |\n");
+    for (wl, n, k) in material.rows() {
+        out.push_str(&format!("        {} {} {}\n", wl * 1e-3, n, k));
+    }
+    out
+}
+
+/// Save tabulated data to a refractiveindex.info YAML file
+pub fn save_tabulated(path: impl AsRef<Path>, material: &TabulatedMaterial) -> CalcResult<()> {
+    let path = path.as_ref();
+    std::fs::write(path, export_tabulated(material))
+        .map_err(|e| invalid(format!("Cannot write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABULATED_NK: &str = "\
+REFERENCES: \"test data\"
+DATA:
+  - type: tabulated nk
+    data: |
+        0.40 1.40 0.20
+        0.50 1.50 0.10
+        0.60 1.60 0.00
+";
+
+    // Fused silica (Malitson 1965)
+    const SELLMEIER: &str = "\
+DATA:
+  - type: formula 1
+    wavelength_range: 0.21 6.7
+    coefficients: 0 0.6961663 0.0684043 0.4079426 0.1162414 0.8974794 9.896161
+";
+
+    #[test]
+    fn test_load_tabulated_nk() {
+        let mat = parse(TABULATED_NK, "test").unwrap();
+        let n = mat.refractive_index_at(450.0);
+        assert!((n.real - 1.45).abs() < 1e-9);
+        assert!((n.imaginary - 0.15).abs() < 1e-9);
+        assert_eq!(mat.wavelength_range(), Some((400.0, 600.0)));
+    }
+
+    #[test]
+    fn test_load_sellmeier_fused_silica() {
+        let mat = parse(SELLMEIER, "SiO2").unwrap();
+        let n = mat.refractive_index_at(587.6);
+        assert!((n.real - 1.4585).abs() < 1e-3);
+        assert!(mat.contains(587.6));
+        assert!(!mat.contains(100.0));
+    }
+
+    #[test]
+    fn test_split_n_and_k_blocks_are_merged() {
+        let text = include_str!("../../../tests/fixtures/split_nk.yml");
+        let mat = parse(text, "split").unwrap();
+        // The k block covers 450–650 nm, inside the n block
+        assert_eq!(mat.wavelength_range(), Some((450.0, 650.0)));
+        let n = mat.refractive_index_at(500.0);
+        assert!((n.real - 1.50).abs() < 1e-9);
+        assert!((n.imaginary - 0.25).abs() < 1e-9);
+        let n = mat.refractive_index_at(650.0);
+        assert!((n.real - 1.65).abs() < 1e-9);
+        assert!((n.imaginary - 0.10).abs() < 1e-9);
+
+        // A third block has nowhere to go
+        let extra = format!("{}  - type: tabulated k\n    data: |\n        0.5 0.1\n", text);
+        assert!(parse(&extra, "x").is_err());
+        let k_only = "DATA:\n  - type: tabulated k\n    data: |\n        0.5 0.1\n";
+        assert!(parse(k_only, "x").is_err());
+    }
+
+    #[test]
+    fn test_unsupported_type_is_error() {
+        let text = "DATA:\n  - type: formula 9\n    coefficients: 1 2 3\n";
+        assert!(parse(text, "x").is_err());
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mat = TabulatedMaterial::new("rt", &[(400.0, 1.4, 0.2), (600.0, 1.6, 0.0)]).unwrap();
+        let reloaded = parse(&export_tabulated(&mat), "rt").unwrap();
+        let n = reloaded.refractive_index_at(500.0);
+        assert!((n.real - 1.5).abs() < 1e-9);
+        assert!((n.imaginary - 0.1).abs() < 1e-9);
+    }
+}
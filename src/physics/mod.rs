@@ -2,7 +2,11 @@
 //!
 //! Contains implementations of optical, thermal, and electronic models.
 
+pub mod classify;
 pub mod optical;
 pub mod thermal;
 pub mod electronic;
 pub mod materials;
+
+pub use classify::{classify_material, suggest_model};
+pub use materials::MaterialClass;
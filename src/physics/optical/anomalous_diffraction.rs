@@ -0,0 +1,281 @@
+//! van de Hulst's anomalous diffraction approximation (ADA)
+//!
+//! For large, optically soft spheres (x ≫ 1, |m−1| ≪ 1) rays cross the
+//! particle undeviated and only pick up a phase lag and attenuation, giving
+//! closed forms in terms of the phase shift ρ = 2x(n−1). Without absorption
+//!
+//! Q_ext = 2 − (4/ρ) sin ρ + (4/ρ²)(1 − cos ρ)
+//!
+//! which tends to the extinction-paradox limit Q_ext → 2 for large ρ.
+
+use crate::core::*;
+use crate::physics::materials::Dispersion;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Size parameter above which ADA is reliable
+pub const ADA_VALID_X: f64 = 10.0;
+/// Size parameter below which ADA breaks down
+pub const ADA_MARGINAL_X: f64 = 3.0;
+/// Contrast |m−1| below which ADA is reliable
+pub const ADA_VALID_CONTRAST: f64 = 0.2;
+/// Contrast |m−1| beyond which ADA breaks down
+pub const ADA_MARGINAL_CONTRAST: f64 = 0.5;
+
+/// Anomalous diffraction model for a sphere
+#[derive(Debug, Clone)]
+pub struct AnomalousDiffractionModel {
+    /// Particle radius in nm
+    pub radius: f64,
+    /// Wavelength in vacuum, nm
+    pub wavelength: f64,
+    /// Particle refractive index
+    pub n_particle: RefractiveIndex,
+    /// Medium refractive index
+    pub n_medium: f64,
+    /// Wavelength-dependent particle material; when set it replaces
+    /// `n_particle`
+    pub material: Option<Arc<dyn Dispersion>>,
+}
+
+/// K(w) = 1/2 + e^(−w)/w + (e^(−w) − 1)/w², with the series near w = 0
+fn k_function(w: Complex64) -> Complex64 {
+    if w.norm() < 1e-3 {
+        w / 3.0 - w * w / 8.0
+    } else {
+        let e = (-w).exp();
+        0.5 + e / w + (e - 1.0) / (w * w)
+    }
+}
+
+impl AnomalousDiffractionModel {
+    pub fn new(
+        radius: f64,
+        wavelength: f64,
+        n_particle: RefractiveIndex,
+        n_medium: f64,
+    ) -> Self {
+        Self {
+            radius,
+            wavelength,
+            n_particle,
+            n_medium,
+            material: None,
+        }
+    }
+
+    /// The same model with a dispersive particle material, see
+    /// [`MieModel::with_material`](super::mie::MieModel::with_material)
+    pub fn with_material(self, material: Arc<dyn Dispersion>) -> Self {
+        Self {
+            material: Some(material),
+            ..self
+        }
+    }
+
+    /// Particle index at `wavelength`
+    fn particle_index(&self, wavelength: f64) -> RefractiveIndex {
+        match &self.material {
+            Some(material) => material.refractive_index_at(wavelength),
+            None => self.n_particle,
+        }
+    }
+
+    /// Size parameter x = 2π·n_medium·r/λ
+    fn size_parameter(&self, wavelength: f64) -> f64 {
+        2.0 * PI * self.n_medium * self.radius / wavelength
+    }
+
+    /// Relative refractive index m = n_particle / n_medium at `wavelength`
+    fn relative_index(&self, wavelength: f64) -> Complex64 {
+        self.particle_index(wavelength).to_complex() / self.n_medium
+    }
+
+    /// Phase shift ρ = 2x(n−1) of a central ray
+    pub fn phase_shift(&self) -> f64 {
+        2.0 * self.size_parameter(self.wavelength) * (self.relative_index(self.wavelength).re - 1.0)
+    }
+
+    /// Regime flag combining the size and contrast criteria
+    fn validity_at(&self, wavelength: f64) -> ValidityFlag {
+        let x = self.size_parameter(wavelength);
+        // Size must be large: invert so that "small is good"
+        let size = ValidityFlag::from_parameter(1.0 / x, 1.0 / ADA_VALID_X, 1.0 / ADA_MARGINAL_X);
+        let contrast = ValidityFlag::from_parameter(
+            (self.relative_index(wavelength) - 1.0).norm(),
+            ADA_VALID_CONTRAST,
+            ADA_MARGINAL_CONTRAST,
+        );
+        size.max(contrast)
+    }
+}
+
+impl PhysicsModel for AnomalousDiffractionModel {
+    fn name(&self) -> &str {
+        "Anomalous Diffraction"
+    }
+
+    fn description(&self) -> &str {
+        "Closed-form efficiencies for large, low-contrast spheres (x ≫ 1, |m−1| ≪ 1)"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        if self.radius <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Radius must be positive".to_string(),
+            ));
+        }
+        if self.wavelength <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Wavelength must be positive".to_string(),
+            ));
+        }
+        if self.n_medium <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Medium refractive index must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        if self.validity_at(self.wavelength) == ValidityFlag::Invalid {
+            warnings.push(Warning::new(
+                WarningCode::AnomalousDiffractionRegime,
+                WarningSeverity::Caution,
+                format!(
+                    "x = {:.2}, |m−1| = {:.2}",
+                    self.size_parameter(self.wavelength),
+                    (self.relative_index(self.wavelength) - 1.0).norm()
+                ),
+            ));
+        }
+
+        warnings
+    }
+}
+
+impl OpticalModel for AnomalousDiffractionModel {
+    fn calculate(&self) -> CalcResult<OpticalResult> {
+        self.validate()?;
+
+        let x = self.size_parameter(self.wavelength);
+        let m = self.relative_index(self.wavelength);
+        // Q_ext = 4 Re K(w) with w = 2x(κ + i(n−1)); reduces to the ρ form for κ = 0
+        let w = 2.0 * x * Complex64::new(m.im, m.re - 1.0);
+        let q_ext = 4.0 * k_function(w).re;
+        // Q_abs = 2 K(4xκ)
+        let q_abs = 2.0 * k_function(Complex64::new(4.0 * x * m.im, 0.0)).re;
+        let q_sca = q_ext - q_abs;
+
+        let geometric_area = self.geometric_cross_section();
+        Ok(OpticalResult {
+            wavelength: self.wavelength,
+            q_sca,
+            q_abs,
+            q_ext,
+            c_sca: q_sca * geometric_area,
+            c_abs: q_abs * geometric_area,
+            c_ext: q_ext * geometric_area,
+            g: 0.0,
+            q_pr: 0.0,
+            metadata: OpticalMetadata {
+                num_terms: None,
+                converged: true,
+                size_parameter: x,
+                notes: vec![format!("Anomalous diffraction, ρ = {:.3}", self.phase_shift())],
+            },
+        })
+    }
+
+    fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        wavelengths
+            .iter()
+            .map(|&wl| {
+                let mut model = self.clone();
+                model.wavelength = wl;
+                model.calculate()
+            })
+            .collect()
+    }
+
+    fn shape(&self) -> ParticleShape {
+        ParticleShape::Sphere { radius: self.radius }
+    }
+
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+        wavelengths.iter().map(|&wl| self.validity_at(wl)).collect()
+    }
+}
+
+/// Every input that changes a result, as for [`MieModel`](super::mie::MieModel)
+impl Cacheable for AnomalousDiffractionModel {
+    fn cache_key(&self) -> String {
+        let material = match &self.material {
+            Some(material) => material.cache_key(),
+            None => "-".to_string(),
+        };
+        format!(
+            "ada|r={}|λ={}|n={}+{}i|n_med={}|mat={}",
+            self.radius, self.wavelength, self.n_particle.real, self.n_particle.imaginary, self.n_medium, material
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::optical::mie::MieCoefficients;
+
+    /// Model with size parameter `x` at λ = 500 nm in vacuum
+    fn model_at_x(x: f64, n: f64, k: f64) -> AnomalousDiffractionModel {
+        let radius = x * 500.0 / (2.0 * PI);
+        AnomalousDiffractionModel::new(radius, 500.0, RefractiveIndex::new(n, k), 1.0)
+    }
+
+    #[test]
+    fn test_closed_form_without_absorption() {
+        let model = model_at_x(20.0, 1.1, 0.0);
+        let rho = model.phase_shift();
+        let expected = 2.0 - 4.0 / rho * rho.sin() + 4.0 / (rho * rho) * (1.0 - rho.cos());
+        let result = model.calculate().unwrap();
+        assert!((result.q_ext - expected).abs() < 1e-12);
+        assert!(result.q_abs.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_extinction_paradox_limit() {
+        let result = model_at_x(2000.0, 1.2, 0.0).calculate().unwrap();
+        assert!((result.q_ext - 2.0).abs() < 0.01);
+        // Strong absorption: everything entering is absorbed, Q_abs → 1
+        let result = model_at_x(2000.0, 1.05, 0.05).calculate().unwrap();
+        assert!((result.q_ext - 2.0).abs() < 0.01);
+        assert!((result.q_abs - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tracks_mie_at_low_contrast() {
+        for x in [30.0, 60.0] {
+            let ada = model_at_x(x, 1.05, 0.0).calculate().unwrap();
+            let mie = MieCoefficients::compute(Complex64::new(1.05, 0.0), x).unwrap();
+            assert!((ada.q_ext - mie.q_ext()).abs() / mie.q_ext() < 0.1, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn test_warns_for_small_or_high_contrast_particles() {
+        assert!(model_at_x(50.0, 1.05, 0.0).warnings().is_empty());
+        assert_eq!(
+            model_at_x(1.0, 1.05, 0.0).warnings()[0].code,
+            WarningCode::AnomalousDiffractionRegime
+        );
+        assert!(!model_at_x(50.0, 2.0, 0.0).warnings().is_empty());
+        assert_eq!(
+            model_at_x(5.0, 1.05, 0.0).validity_region(&[500.0]),
+            vec![ValidityFlag::Marginal]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Structure factor of periodic particle arrays
+//!
+//! In an array each particle is driven by the incident wave plus the waves
+//! scattered by its neighbours. This module keeps only the first-order
+//! (single-scattering, kinematic) correction: every neighbour re-radiates a
+//! spherical wave of unit relative amplitude, e^(ikr)/(kr), phased by the
+//! in-plane component of the incident wave. The multiplier
+//!
+//! S(λ) = |1 + Σⱼ e^(ik rⱼ)/(k rⱼ) · e^(ik sinθ xⱼ)|²
+//!
+//! tends to 1 for widely spaced particles and peaks near the Rayleigh
+//! anomalies λ = d(1 ± sinθ)/m, where the scattered waves arrive in phase
+//! and collective (surface lattice) resonances appear. The sum is truncated
+//! at [`LATTICE_SHELLS`] periods and damped exponentially so it converges;
+//! the peak heights are therefore qualitative, but their positions are not.
+
+use crate::core::OpticalResult;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// Periods included on each side of the reference particle
+pub const LATTICE_SHELLS: usize = 50;
+
+/// Geometry of the periodic arrangement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lattice {
+    /// 1D chain along the plane of incidence
+    Chain,
+    /// 2D square lattice
+    #[default]
+    Square,
+}
+
+/// Structure factor of a square lattice of period `spacing_nm`
+///
+/// `wavelength` is in the surrounding medium (vacuum wavelength / n_medium)
+/// and `incidence_angle` in radians from the array normal.
+pub fn structure_factor(spacing_nm: f64, wavelength: f64, incidence_angle: f64) -> f64 {
+    lattice_structure_factor(Lattice::Square, spacing_nm, wavelength, incidence_angle)
+}
+
+/// Structure factor of `lattice` with period `spacing_nm`
+pub fn lattice_structure_factor(lattice: Lattice, spacing_nm: f64, wavelength: f64, incidence_angle: f64) -> f64 {
+    let k = 2.0 * PI / wavelength;
+    let k_parallel = k * incidence_angle.sin();
+    let shells = LATTICE_SHELLS as i64;
+    let cutoff = LATTICE_SHELLS as f64 * spacing_nm;
+    let damping = cutoff / 3.0;
+    let rows = match lattice {
+        Lattice::Chain => 0..=0,
+        Lattice::Square => -shells..=shells,
+    };
+
+    let mut sum = Complex64::new(0.0, 0.0);
+    for b in rows {
+        for a in -shells..=shells {
+            let (x, y) = (a as f64 * spacing_nm, b as f64 * spacing_nm);
+            let r = x.hypot(y);
+            if r == 0.0 || r > cutoff {
+                continue;
+            }
+            let phase = k * r + k_parallel * x;
+            sum += Complex64::from_polar((-r / damping).exp() / (k * r), phase);
+        }
+    }
+    (1.0 + sum).norm_sqr()
+}
+
+/// Wavelengths (in the medium) of the Rayleigh anomalies of diffraction
+/// order `order`, as (d(1 − sinθ)/m, d(1 + sinθ)/m)
+pub fn rayleigh_anomalies(spacing_nm: f64, incidence_angle: f64, order: u32) -> (f64, f64) {
+    let sin = incidence_angle.sin();
+    let m = order as f64;
+    (spacing_nm * (1.0 - sin) / m, spacing_nm * (1.0 + sin) / m)
+}
+
+/// Single-particle results multiplied by the array structure factor
+///
+/// Vacuum wavelengths in `results` are converted to the medium with `n_medium`.
+pub fn apply_structure_factor(
+    results: &[OpticalResult],
+    lattice: Lattice,
+    spacing_nm: f64,
+    incidence_angle: f64,
+    n_medium: f64,
+) -> Vec<OpticalResult> {
+    results
+        .iter()
+        .map(|r| {
+            let s = lattice_structure_factor(lattice, spacing_nm, r.wavelength / n_medium, incidence_angle);
+            OpticalResult {
+                q_sca: r.q_sca * s,
+                q_abs: r.q_abs * s,
+                q_ext: r.q_ext * s,
+                c_sca: r.c_sca * s,
+                c_abs: r.c_abs * s,
+                c_ext: r.c_ext * s,
+                q_pr: r.q_pr * s,
+                ..r.clone()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolated_particle_limit() {
+        for lattice in [Lattice::Chain, Lattice::Square] {
+            let s = lattice_structure_factor(lattice, 1e7, 500.0, 0.0);
+            assert!((s - 1.0).abs() < 1e-3, "{:?}: {}", lattice, s);
+        }
+    }
+
+    #[test]
+    fn test_lattice_resonance_at_rayleigh_anomaly() {
+        for lattice in [Lattice::Chain, Lattice::Square] {
+            let curve: Vec<(f64, f64)> = (400..=600)
+                .step_by(2)
+                .map(|wl| (wl as f64, lattice_structure_factor(lattice, 500.0, wl as f64, 0.0)))
+                .collect();
+            let (peak_wl, peak) = curve.iter().cloned().fold((0.0, 0.0), |m, p| if p.1 > m.1 { p } else { m });
+            assert!((peak_wl - rayleigh_anomalies(500.0, 0.0, 1).1).abs() <= 2.0, "{:?} peak at {}", lattice, peak_wl);
+            assert!(peak > 2.0, "{:?} peak {}", lattice, peak);
+        }
+
+        // Oblique incidence splits the anomaly: the red branch moves to d(1 + sinθ)
+        let theta = 0.2;
+        let (_, red) = rayleigh_anomalies(500.0, theta, 1);
+        let near = lattice_structure_factor(Lattice::Chain, 500.0, red + 0.5, theta);
+        let away = lattice_structure_factor(Lattice::Chain, 500.0, 500.0, theta);
+        assert!(near > away, "{} vs {}", near, away);
+    }
+}
@@ -0,0 +1,322 @@
+//! Coated (core-shell) sphere
+//!
+//! Aden-Kerker solution for a sphere of one material inside a concentric
+//! shell of another, e.g. Au@SiO₂. Coefficients follow Bohren & Huffman's
+//! BHCOAT: the shell's aₙ, bₙ use modified logarithmic derivatives D̄ₙ, Ḡₙ
+//! that carry the core/shell interface, and the interface terms are dropped
+//! once they no longer contribute.
+
+use super::mie::{wiscombe_terms, MieCoefficients, LOSSLESS_NOTE};
+use crate::core::*;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// Interface terms below this fraction of Dₙ(m₂y) are treated as zero
+const INTERFACE_TOLERANCE: f64 = 1e-8;
+
+/// Concentric core-shell sphere
+#[derive(Debug, Clone)]
+pub struct CoreShellModel {
+    /// Core radius in nm
+    pub core_radius: f64,
+    /// Outer radius of the shell in nm
+    pub shell_radius: f64,
+    /// Wavelength in vacuum, nm
+    pub wavelength: f64,
+    /// Core refractive index
+    pub n_core: RefractiveIndex,
+    /// Shell refractive index
+    pub n_shell: RefractiveIndex,
+    /// Medium refractive index
+    pub n_medium: f64,
+}
+
+/// cot z for complex z
+fn cot(z: Complex64) -> Complex64 {
+    z.cos() / z.sin()
+}
+
+impl CoreShellModel {
+    pub fn new(
+        core_radius: f64,
+        shell_radius: f64,
+        wavelength: f64,
+        n_core: RefractiveIndex,
+        n_shell: RefractiveIndex,
+        n_medium: f64,
+    ) -> Self {
+        Self {
+            core_radius,
+            shell_radius,
+            wavelength,
+            n_core,
+            n_shell,
+            n_medium,
+        }
+    }
+
+    /// Size parameters (x, y) = k·(core radius, shell radius), k = 2π·n_medium/λ
+    pub fn size_parameters(&self) -> (f64, f64) {
+        let k = 2.0 * PI * self.n_medium / self.wavelength;
+        (k * self.core_radius, k * self.shell_radius)
+    }
+
+    /// Mie coefficients (aₙ, bₙ) of the coated sphere
+    pub fn coefficients(&self) -> CalcResult<(Vec<Complex64>, Vec<Complex64>)> {
+        self.validate()?;
+        let (x, y) = self.size_parameters();
+        let m1 = self.n_core.to_complex() / self.n_medium;
+        let m2 = self.n_shell.to_complex() / self.n_medium;
+
+        let x1 = m1 * x;
+        let x2 = m2 * x;
+        let y2 = m2 * y;
+        let refrel = m2 / m1;
+        let nstop = wiscombe_terms(y);
+
+        let mut d0x1 = cot(x1);
+        let mut d0x2 = cot(x2);
+        let mut d0y2 = cot(y2);
+        let mut psi0y = y.cos();
+        let mut psi1y = y.sin();
+        let mut chi0y = -y.sin();
+        let mut chi1y = y.cos();
+        let mut xi1y = Complex64::new(psi1y, -chi1y);
+        let mut chi0y2 = -y2.sin();
+        let mut chi1y2 = y2.cos();
+        let mut chi0x2 = -x2.sin();
+        let mut chi1x2 = x2.cos();
+        let mut interface = true;
+
+        let mut a = Vec::with_capacity(nstop);
+        let mut b = Vec::with_capacity(nstop);
+
+        for n in 1..=nstop {
+            let nf = n as f64;
+            let psiy = (2.0 * nf - 1.0) * psi1y / y - psi0y;
+            let chiy = (2.0 * nf - 1.0) * chi1y / y - chi0y;
+            let xiy = Complex64::new(psiy, -chiy);
+            let d1y2 = 1.0 / (nf / y2 - d0y2) - nf / y2;
+
+            let d1x1 = 1.0 / (nf / x1 - d0x1) - nf / x1;
+            let d1x2 = 1.0 / (nf / x2 - d0x2) - nf / x2;
+            let chix2 = (2.0 * nf - 1.0) * chi1x2 / x2 - chi0x2;
+            let chiy2 = (2.0 * nf - 1.0) * chi1y2 / y2 - chi0y2;
+            let chipx2 = chi1x2 - nf * chix2 / x2;
+            let chipy2 = chi1y2 - nf * chiy2 / y2;
+
+            // Core/shell interface terms
+            let (mut brack, mut crack) = (Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0));
+            if interface {
+                let ancap = (refrel * d1x1 - d1x2)
+                    / (refrel * d1x1 * chix2 - chipx2)
+                    / (chix2 * d1x2 - chipx2);
+                let bncap = (refrel * d1x2 - d1x1)
+                    / (refrel * chipx2 - d1x1 * chix2)
+                    / (chix2 * d1x2 - chipx2);
+                brack = ancap * (chiy2 * d1y2 - chipy2);
+                crack = bncap * (chiy2 * d1y2 - chipy2);
+
+                let limit = INTERFACE_TOLERANCE * d1y2.norm();
+                let negligible = [brack * chipy2, brack * chiy2, crack * chipy2, crack * chiy2]
+                    .iter()
+                    .all(|term| term.norm() <= limit);
+                if negligible {
+                    brack = Complex64::new(0.0, 0.0);
+                    crack = Complex64::new(0.0, 0.0);
+                    interface = false;
+                }
+            }
+
+            let dnbar = (d1y2 - brack * chipy2) / (1.0 - brack * chiy2);
+            let gnbar = (d1y2 - crack * chipy2) / (1.0 - crack * chiy2);
+            let da = dnbar / m2 + nf / y;
+            let db = m2 * gnbar + nf / y;
+            let an = (da * psiy - psi1y) / (da * xiy - xi1y);
+            let bn = (db * psiy - psi1y) / (db * xiy - xi1y);
+
+            if !an.is_finite() || !bn.is_finite() {
+                return Err(CalculationError::NumericalInstability(format!(
+                    "Non-finite coated-sphere coefficient at order n = {}",
+                    n
+                )));
+            }
+            a.push(an);
+            b.push(bn);
+
+            psi0y = psi1y;
+            psi1y = psiy;
+            chi0y = chi1y;
+            chi1y = chiy;
+            xi1y = Complex64::new(psi1y, -chi1y);
+            chi0x2 = chi1x2;
+            chi1x2 = chix2;
+            chi0y2 = chi1y2;
+            chi1y2 = chiy2;
+            d0x1 = d1x1;
+            d0x2 = d1x2;
+            d0y2 = d1y2;
+        }
+
+        Ok((a, b))
+    }
+}
+
+impl PhysicsModel for CoreShellModel {
+    fn name(&self) -> &str {
+        "Core-Shell Mie (Aden-Kerker)"
+    }
+
+    fn description(&self) -> &str {
+        "Scattering and absorption by a sphere with a concentric shell"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        if self.core_radius <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Core radius must be positive".to_string(),
+            ));
+        }
+        if self.shell_radius.is_nan() || self.shell_radius <= self.core_radius {
+            return Err(ValidationError::InvalidParameter(format!(
+                "Shell radius ({} nm) must exceed the core radius ({} nm)",
+                self.shell_radius, self.core_radius
+            )));
+        }
+        if self.wavelength <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Wavelength must be positive".to_string(),
+            ));
+        }
+        if self.n_medium <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Medium refractive index must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl OpticalModel for CoreShellModel {
+    fn calculate(&self) -> CalcResult<OpticalResult> {
+        let (a, b) = self.coefficients()?;
+        let (x, y) = self.size_parameters();
+        let lossless = self.n_core.imaginary == 0.0 && self.n_shell.imaginary == 0.0;
+        // Sums over aₙ, bₙ are the same as for a homogeneous sphere of size y
+        let series = MieCoefficients { x: y, a, b, lossless };
+        let (q_sca, q_abs, q_ext, g) = (series.q_sca(), series.q_abs(), series.q_ext(), series.g());
+
+        let mut notes = vec![format!("core-shell: x_core = {:.4}, x_shell = {:.4}", x, y)];
+        if lossless {
+            notes.push(LOSSLESS_NOTE.to_string());
+        }
+
+        let geometric_area = self.geometric_cross_section();
+        Ok(OpticalResult {
+            wavelength: self.wavelength,
+            q_sca,
+            q_abs,
+            q_ext,
+            c_sca: q_sca * geometric_area,
+            c_abs: q_abs * geometric_area,
+            c_ext: q_ext * geometric_area,
+            g,
+            q_pr: q_ext - g * q_sca,
+            metadata: OpticalMetadata {
+                num_terms: Some(series.num_terms()),
+                converged: true,
+                size_parameter: y,
+                notes,
+            },
+        })
+    }
+
+    fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        wavelengths
+            .iter()
+            .map(|&wl| {
+                let mut model = self.clone();
+                model.wavelength = wl;
+                model.calculate()
+            })
+            .collect()
+    }
+
+    fn shape(&self) -> ParticleShape {
+        ParticleShape::Sphere { radius: self.shell_radius }
+    }
+
+    /// The coated-sphere solution is exact at every size
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+        vec![ValidityFlag::Valid; wavelengths.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::optical::mie::MieModel;
+
+    fn relative_error(a: f64, b: f64) -> f64 {
+        (a - b).abs() / b.abs()
+    }
+
+    #[test]
+    fn test_vanishing_shell_matches_mie() {
+        let gold = RefractiveIndex::new(0.47, 2.4);
+        let silica = RefractiveIndex::new(1.46, 0.0);
+        for n_medium in [1.0, 1.33] {
+            for (core, wavelength) in [(40.0, 520.0), (40.0, 600.0), (80.0, 600.0), (150.0, 450.0)] {
+                let coated = CoreShellModel::new(core, core * (1.0 + 1e-9), wavelength, gold, silica, n_medium)
+                    .calculate()
+                    .unwrap();
+                let bare = MieModel::new(core, wavelength, gold, n_medium).calculate().unwrap();
+                let case = format!("r = {}, λ = {}, n_medium = {}", core, wavelength, n_medium);
+                assert!(relative_error(coated.c_ext, bare.c_ext) < 1e-6, "{}", case);
+                assert!(relative_error(coated.c_sca, bare.c_sca) < 1e-6, "{}", case);
+                assert!(relative_error(coated.c_abs, bare.c_abs) < 1e-6, "{}", case);
+                assert!(relative_error(coated.metadata.size_parameter, bare.metadata.size_parameter) < 1e-8, "{}", case);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shell_of_core_material_is_a_bigger_sphere() {
+        let n = RefractiveIndex::new(1.59, 0.01);
+        for n_medium in [1.0, 1.33] {
+            let coated = CoreShellModel::new(30.0, 90.0, 550.0, n, n, n_medium).calculate().unwrap();
+            let sphere = MieModel::new(90.0, 550.0, n, n_medium).calculate().unwrap();
+            assert!(relative_error(coated.q_ext, sphere.q_ext) < 1e-9, "n_medium = {}", n_medium);
+            assert!(relative_error(coated.q_sca, sphere.q_sca) < 1e-9, "n_medium = {}", n_medium);
+            assert!(coated.check_conservation() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_shell_must_enclose_core() {
+        let n = RefractiveIndex::new(1.5, 0.0);
+        for shell in [20.0, 10.0, f64::NAN] {
+            let model = CoreShellModel::new(20.0, shell, 500.0, n, n, 1.33);
+            assert!(matches!(model.validate(), Err(ValidationError::InvalidParameter(_))));
+            assert!(model.calculate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_notes_report_both_size_parameters() {
+        let model = CoreShellModel::new(
+            25.0,
+            35.0,
+            500.0,
+            RefractiveIndex::new(0.97, 1.87),
+            RefractiveIndex::new(1.46, 0.0),
+            1.33,
+        );
+        let result = model.calculate().unwrap();
+        let (x, y) = model.size_parameters();
+        assert_eq!(result.metadata.notes[0], format!("core-shell: x_core = {:.4}, x_shell = {:.4}", x, y));
+        assert_eq!(result.metadata.size_parameter, y);
+        assert!(result.q_abs > 0.0);
+        assert!(result.check_conservation() < 1e-6);
+    }
+}
@@ -0,0 +1,199 @@
+//! Host media with independent permittivity and permeability
+//!
+//! A conventional medium is described by one real index. Magnetic hosts
+//! (µ ≠ 1) need ε and µ separately: the index n = √ε·√µ, the impedance Z = √µ/√ε and
+//! the wavenumber k = 2πn/λ all follow from the pair.
+//!
+//! # Branch conventions
+//!
+//! Each square root is the principal root of its own factor, √ε and √µ
+//! separately, never √(εµ). For a passive medium (Im ε ≥ 0, Im µ ≥ 0) both
+//! roots lie in the first quadrant, so the product has Im n ≥ 0 (waves decay
+//! along their direction of travel) and Re n < 0 exactly when ε and µ are
+//! both negative. √(εµ) would put every double-negative medium on the
+//! positive branch. A zero imaginary part is read as +0 so that ε = −1 and
+//! ε = −1 − 0i land on the same branch. Active media (Im ε < 0 or Im µ < 0)
+//! have no consistent branch and are rejected.
+
+use crate::core::*;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Host medium given by its relative permittivity ε and permeability µ
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MediumSpec {
+    pub eps_real: f64,
+    pub eps_imag: f64,
+    pub mu_real: f64,
+    pub mu_imag: f64,
+}
+
+impl Default for MediumSpec {
+    /// Vacuum, ε = µ = 1
+    fn default() -> Self {
+        Self::from_index(1.0)
+    }
+}
+
+/// Principal square root with a zero imaginary part taken as +0
+fn principal_sqrt(z: Complex64) -> Complex64 {
+    let im = if z.im == 0.0 { 0.0 } else { z.im };
+    Complex64::new(z.re, im).sqrt()
+}
+
+impl MediumSpec {
+    pub fn new(permittivity: Complex64, permeability: Complex64) -> Self {
+        Self {
+            eps_real: permittivity.re,
+            eps_imag: permittivity.im,
+            mu_real: permeability.re,
+            mu_imag: permeability.im,
+        }
+    }
+
+    /// Conventional non-magnetic medium of real index `n`: ε = n², µ = 1
+    pub fn from_index(n: f64) -> Self {
+        Self::new(Complex64::new(n * n, 0.0), Complex64::new(1.0, 0.0))
+    }
+
+    pub fn permittivity(&self) -> Complex64 {
+        Complex64::new(self.eps_real, self.eps_imag)
+    }
+
+    pub fn permeability(&self) -> Complex64 {
+        Complex64::new(self.mu_real, self.mu_imag)
+    }
+
+    /// Check that ε and µ are finite, non-zero and passive
+    pub fn validate(&self) -> ValidationResult<()> {
+        for (name, value) in [("Permittivity", self.permittivity()), ("Permeability", self.permeability())] {
+            if !value.is_finite() {
+                return Err(ValidationError::InvalidParameter(format!(
+                    "{} must be finite, got {}",
+                    name, value
+                )));
+            }
+            if value.norm() == 0.0 {
+                return Err(ValidationError::InvalidParameter(format!("{} must be non-zero", name)));
+            }
+            if value.im < 0.0 {
+                return Err(ValidationError::PhysicsViolation(format!(
+                    "{} has Im < 0 (gain medium): {}",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Refractive index n = √ε·√µ, with Re n < 0 for double-negative media
+    pub fn refractive_index(&self) -> Complex64 {
+        principal_sqrt(self.permittivity()) * principal_sqrt(self.permeability())
+    }
+
+    /// Relative wave impedance Z = √µ/√ε, in units of the vacuum impedance
+    ///
+    /// Re Z ≥ 0 on both branches: a negative index reverses the phase
+    /// velocity but not the direction of energy flow.
+    pub fn impedance(&self) -> Complex64 {
+        principal_sqrt(self.permeability()) / principal_sqrt(self.permittivity())
+    }
+
+    /// Wavenumber k = 2πn/λ in nm⁻¹ for a vacuum wavelength in nm
+    pub fn wavenumber(&self, wavelength: f64) -> Complex64 {
+        2.0 * PI * self.refractive_index() / wavelength
+    }
+
+    /// Size parameter x = k·r of a sphere of `radius` nm
+    pub fn size_parameter(&self, radius: f64, wavelength: f64) -> Complex64 {
+        self.wavenumber(wavelength) * radius
+    }
+
+    /// Relative index m = n_particle / n_medium
+    pub fn relative_index(&self, n_particle: RefractiveIndex) -> Complex64 {
+        n_particle.to_complex() / self.refractive_index()
+    }
+
+    /// Re ε < 0 and Re µ < 0
+    pub fn is_double_negative(&self) -> bool {
+        self.eps_real < 0.0 && self.mu_real < 0.0
+    }
+
+    /// Real index and permeability of a host the scattering models accept
+    ///
+    /// Their far-field efficiencies assume propagating, unattenuated waves
+    /// in the host, so absorbing, single-negative and negative-index media
+    /// are reported as not applicable; their n, Z and k remain available.
+    pub fn host(&self) -> CalcResult<(f64, f64)> {
+        self.validate()?;
+        let n = self.refractive_index();
+        if self.is_double_negative() {
+            return Err(CalculationError::ModelNotApplicable(format!(
+                "Negative-index medium (n = {:.4}): far-field efficiencies assume a positive-index host",
+                n
+            )));
+        }
+        if self.eps_real <= 0.0 || self.mu_real <= 0.0 {
+            return Err(CalculationError::ModelNotApplicable(format!(
+                "Single-negative medium (n = {:.4}): waves in the host are evanescent",
+                n
+            )));
+        }
+        if self.eps_imag > 0.0 || self.mu_imag > 0.0 {
+            return Err(CalculationError::ModelNotApplicable(format!(
+                "Absorbing medium (n = {:.4}): the models take a lossless host",
+                n
+            )));
+        }
+        Ok((n.re, self.mu_real))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventional_medium_is_exact() {
+        for n in [1.0, 1.33, 1.46, 1.5, 2.4] {
+            let medium = MediumSpec::from_index(n);
+            assert_eq!(medium.refractive_index(), Complex64::new(n, 0.0));
+            assert_eq!(medium.host().unwrap(), (n, 1.0));
+            assert!((medium.impedance() - 1.0 / n).norm() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_double_negative_takes_negative_branch() {
+        let lossless = MediumSpec::new(Complex64::new(-4.0, 0.0), Complex64::new(-1.0, 0.0));
+        assert_eq!(lossless.refractive_index(), Complex64::new(-2.0, 0.0));
+        assert_eq!(lossless.impedance(), Complex64::new(0.5, 0.0));
+        assert!(lossless.wavenumber(500.0).re < 0.0);
+
+        // A negative zero must not flip the branch
+        let signed_zero = MediumSpec::new(Complex64::new(-4.0, -0.0), Complex64::new(-1.0, 0.0));
+        assert_eq!(signed_zero.refractive_index(), lossless.refractive_index());
+
+        let lossy = MediumSpec::new(Complex64::new(-4.0, 0.2), Complex64::new(-1.0, 0.05));
+        let n = lossy.refractive_index();
+        assert!(n.re < 0.0 && n.im > 0.0, "{}", n);
+        assert!(lossy.impedance().re > 0.0);
+        assert!(matches!(lossy.host(), Err(CalculationError::ModelNotApplicable(_))));
+    }
+
+    #[test]
+    fn test_single_negative_and_gain_media() {
+        // Re ε < 0, µ > 0: a plasma-like host with a purely imaginary index
+        let plasma = MediumSpec::new(Complex64::new(-2.0, 0.0), Complex64::new(1.0, 0.0));
+        let n = plasma.refractive_index();
+        assert_eq!(n.re, 0.0);
+        assert!(n.im > 0.0);
+        assert!(matches!(plasma.host(), Err(CalculationError::ModelNotApplicable(_))));
+
+        let gain = MediumSpec::new(Complex64::new(2.0, -0.1), Complex64::new(1.0, 0.0));
+        assert!(matches!(gain.validate(), Err(ValidationError::PhysicsViolation(_))));
+        let zero = MediumSpec::new(Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0));
+        assert!(zero.validate().is_err());
+    }
+}
@@ -1,13 +1,16 @@
-//! Mie scattering theory implementation (simplified for MVP)
+//! Mie scattering theory for homogeneous spheres
 //!
-//! This is a placeholder implementation. Full Mie theory requires Bessel functions
-//! and series convergence. For MVP, we implement a Rayleigh approximation.
+//! [`MieModel::calculate`] uses the Rayleigh approximation for small size
+//! parameters and sums the full Mie series ([`MieCoefficients`]) otherwise.
 
+use super::medium::MediumSpec;
 use crate::core::*;
+use crate::physics::materials::Dispersion;
 use num_complex::Complex64;
 use std::f64::consts::PI;
+use std::sync::Arc;
 
-/// Mie scattering model (Rayleigh approximation for MVP)
+/// Mie scattering model for a homogeneous sphere
 pub struct MieModel {
     /// Particle radius in nm
     pub radius: f64,
@@ -17,6 +20,70 @@ pub struct MieModel {
     pub n_particle: RefractiveIndex,
     /// Medium refractive index (real only for MVP)
     pub n_medium: f64,
+    /// Medium relative permeability µ; the particle is non-magnetic
+    pub mu_medium: f64,
+    /// Stopping rule for the full series
+    pub config: MieConfig,
+    /// Dispersive particle material; when set, its index at `wavelength`
+    /// replaces `n_particle`
+    pub material: Option<Arc<dyn Dispersion>>,
+}
+
+/// Which branch [`MieModel::calculate`] takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MieDispatch {
+    /// Rayleigh dipoles at any size parameter
+    Rayleigh,
+    /// Rayleigh below [`MIE_SERIES_X`], the full series above
+    #[default]
+    Auto,
+    /// The full series at any size parameter
+    FullSeries,
+}
+
+/// Branch choice and stopping rule for the Mie series
+///
+/// The series runs to at least the Wiscombe order, then on until the last
+/// order's relative contribution (2n+1)(|aₙ| + |bₙ|) / Σ falls to
+/// `tolerance`. Reaching `max_terms` first is a convergence failure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MieConfig {
+    pub tolerance: f64,
+    pub max_terms: usize,
+    pub dispatch: MieDispatch,
+}
+
+/// Default series tolerance; the Wiscombe order always meets it in practice
+pub const DEFAULT_MIE_TOLERANCE: f64 = 1e-6;
+/// Default cap on series orders
+pub const DEFAULT_MAX_TERMS: usize = 10_000;
+
+impl Default for MieConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: DEFAULT_MIE_TOLERANCE,
+            max_terms: DEFAULT_MAX_TERMS,
+            dispatch: MieDispatch::Auto,
+        }
+    }
+}
+
+impl MieConfig {
+    pub fn validate(&self) -> ValidationResult<()> {
+        if self.tolerance.is_nan() || self.tolerance <= 0.0 || self.tolerance >= 1.0 {
+            return Err(ValidationError::OutOfRange {
+                value: self.tolerance,
+                min: 0.0,
+                max: 1.0,
+            });
+        }
+        if self.max_terms == 0 {
+            return Err(ValidationError::InvalidParameter(
+                "Mie series needs at least one term".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl MieModel {
@@ -31,37 +98,206 @@ impl MieModel {
             wavelength,
             n_particle,
             n_medium,
+            mu_medium: 1.0,
+            config: MieConfig::default(),
+            material: None,
+        }
+    }
+
+    /// Model with an explicit series stopping rule
+    pub fn with_config(
+        radius: f64,
+        wavelength: f64,
+        n_particle: RefractiveIndex,
+        n_medium: f64,
+        config: MieConfig,
+    ) -> Self {
+        Self {
+            config,
+            ..Self::new(radius, wavelength, n_particle, n_medium)
+        }
+    }
+
+    /// Model for a sphere in a host given by its ε and µ
+    ///
+    /// The series uses the host's wavenumber k = 2π√ε√µ/λ through
+    /// [`Self::medium`], so x matches `medium.size_parameter(radius, wavelength)`.
+    /// Fails with [`CalculationError::ModelNotApplicable`] for hosts the
+    /// model cannot handle; see [`MediumSpec::host`].
+    pub fn in_medium(
+        radius: f64,
+        wavelength: f64,
+        n_particle: RefractiveIndex,
+        medium: &MediumSpec,
+    ) -> CalcResult<Self> {
+        let (n_medium, mu_medium) = medium.host()?;
+        Ok(Self {
+            mu_medium,
+            ..Self::new(radius, wavelength, n_particle, n_medium)
+        })
+    }
+
+    /// The same model with a dispersive particle material
+    ///
+    /// Each wavelength of [`OpticalModel::calculate_spectrum`] then uses the
+    /// material's index at that wavelength.
+    pub fn with_material(self, material: Arc<dyn Dispersion>) -> Self {
+        Self {
+            material: Some(material),
+            ..self
         }
     }
 
-    /// Calculate size parameter x = 2πr/λ
+    /// Particle index at the current wavelength
+    pub fn particle_index(&self) -> RefractiveIndex {
+        match &self.material {
+            Some(material) => material.refractive_index_at(self.wavelength),
+            None => self.n_particle,
+        }
+    }
+
+    /// The host as ε = n_medium²/µ and µ, so that √ε·√µ = n_medium
+    pub fn medium(&self) -> MediumSpec {
+        MediumSpec::new(
+            Complex64::new(self.n_medium * self.n_medium / self.mu_medium, 0.0),
+            Complex64::new(self.mu_medium, 0.0),
+        )
+    }
+
+    /// Size parameter x = k·r in the host, 2π·n_medium·r/λ for a vacuum
+    /// wavelength λ; see [`MediumSpec::size_parameter`]
     fn size_parameter(&self) -> f64 {
-        2.0 * PI * self.radius / self.wavelength
+        self.medium().size_parameter(self.radius, self.wavelength).re
+    }
+
+    /// Mie expansion coefficients for the current parameters
+    pub fn coefficients(&self) -> CalcResult<MieCoefficients> {
+        self.validate()?;
+        let m = self.particle_index().to_complex() / self.n_medium;
+        MieCoefficients::compute_converged(m, self.size_parameter(), 1.0 / self.mu_medium, &self.config)
+    }
+
+    /// Scattering amplitudes (S₁, S₂) at each scattering angle in radians
+    ///
+    /// Uses the full series at any size parameter, including the Rayleigh
+    /// range where [`OpticalModel::calculate`] does not.
+    pub fn scattering_amplitudes(&self, theta: &[f64]) -> CalcResult<Vec<(Complex64, Complex64)>> {
+        let coeffs = self.coefficients()?;
+        Ok(theta.iter().map(|&t| coeffs.amplitudes(t)).collect())
+    }
+
+    /// Phase function p(θ) for unpolarized light at each angle, ∫ p dΩ = 1
+    pub fn phase_function(&self, theta: &[f64]) -> CalcResult<Vec<f64>> {
+        let coeffs = self.coefficients()?;
+        Ok(theta.iter().map(|&t| coeffs.phase_function(t)).collect())
+    }
+
+    /// Electric dipole polarizability α = 4πr³(ε₁/ε − 1)/(ε₁/ε + 2) in nm³
+    pub fn dipole_polarizability(&self) -> Complex64 {
+        let m = self.particle_index().to_complex() / self.n_medium;
+        let eps_ratio = m * m * self.mu_medium;
+        4.0 * PI * self.radius.powi(3) * (eps_ratio - 1.0) / (eps_ratio + 2.0)
+    }
+
+    /// Whether [`OpticalModel::calculate`] takes the Rayleigh branch
+    fn uses_rayleigh(&self) -> bool {
+        match self.config.dispatch {
+            MieDispatch::Rayleigh => true,
+            MieDispatch::Auto => self.size_parameter() < MIE_SERIES_X,
+            MieDispatch::FullSeries => false,
+        }
+    }
+
+    /// Full Mie series result
+    fn mie_series(&self) -> CalcResult<OpticalResult> {
+        let coeffs = self.coefficients()?;
+        let geometric_area = self.geometric_cross_section();
+        let (q_sca, q_abs, q_ext) = (coeffs.q_sca(), coeffs.q_abs(), coeffs.q_ext());
+        let g = coeffs.g();
+
+        let mut notes = vec![MIE_SERIES_NOTE.to_string()];
+        if coeffs.lossless {
+            notes.push(LOSSLESS_NOTE.to_string());
+        }
+        if self.mu_medium != 1.0 {
+            notes.push(format!("magnetic host: µ = {}", self.mu_medium));
+        }
+
+        Ok(OpticalResult {
+            wavelength: self.wavelength,
+            q_sca,
+            q_abs,
+            q_ext,
+            c_sca: q_sca * geometric_area,
+            c_abs: q_abs * geometric_area,
+            c_ext: q_ext * geometric_area,
+            g,
+            q_pr: q_ext - g * q_sca,
+            metadata: OpticalMetadata {
+                num_terms: Some(coeffs.num_terms()),
+                converged: true,
+                size_parameter: coeffs.x,
+                notes,
+            },
+        })
     }
 
     /// Rayleigh approximation (x << 1)
     fn rayleigh_approximation(&self) -> OpticalResult {
         let x = self.size_parameter();
-        let m = self.n_particle.to_complex() / self.n_medium;
+        let m = self.particle_index().to_complex() / self.n_medium;
         
-        // Scattering efficiency (Rayleigh)
-        let m2_minus_1 = m * m - Complex64::new(1.0, 0.0);
-        let m2_plus_2 = m * m + Complex64::new(2.0, 0.0);
+        // Electric dipole from the permittivity ratio ε₁/ε = m²µ, magnetic
+        // dipole from the permeability ratio 1/µ (zero in a non-magnetic host)
+        let eps_ratio = m * m * self.mu_medium;
+        let m2_minus_1 = eps_ratio - Complex64::new(1.0, 0.0);
+        let m2_plus_2 = eps_ratio + Complex64::new(2.0, 0.0);
         let factor = m2_minus_1 / m2_plus_2;
+        let mu_ratio = 1.0 / self.mu_medium;
+        let magnetic = (mu_ratio - 1.0) / (mu_ratio + 2.0);
         
-        let q_sca = (8.0 / 3.0) * x.powi(4) * factor.norm_sqr();
-        
-        // Absorption efficiency
-        let q_abs = 4.0 * x * (m2_minus_1 / m2_plus_2).im;
-        
+        let lossless = m.im == 0.0;
+        let geometric_area = self.geometric_cross_section();
+        let point_dipole = x < POINT_DIPOLE_X;
+
+        let (q_sca, q_abs, c_sca, c_abs) = if point_dipole {
+            // Cross sections straight from the polarizabilities, so they stay
+            // accurate however small x⁴ gets: C_sca = k⁴|α|²/6π, C_abs = k·Im α
+            let k = x / self.radius;
+            let alpha = self.dipole_polarizability();
+            let alpha_m = 4.0 * PI * self.radius.powi(3) * magnetic;
+            let c_sca = k.powi(4) / (6.0 * PI) * (alpha.norm_sqr() + alpha_m * alpha_m);
+            let c_abs = if lossless { 0.0 } else { k * alpha.im };
+            (c_sca / geometric_area, c_abs / geometric_area, c_sca, c_abs)
+        } else {
+            // Scattering efficiency (Rayleigh)
+            let q_sca = (8.0 / 3.0) * x.powi(4) * (factor.norm_sqr() + magnetic * magnetic);
+            // Absorption efficiency, forced to zero for a lossless particle
+            let q_abs = if lossless { 0.0 } else { 4.0 * x * factor.im };
+            (q_sca, q_abs, q_sca * geometric_area, q_abs * geometric_area)
+        };
+
         // Extinction
         let q_ext = q_sca + q_abs;
-        
-        // Cross sections
-        let geometric_area = PI * self.radius.powi(2);
-        let c_sca = q_sca * geometric_area;
-        let c_abs = q_abs * geometric_area;
-        let c_ext = q_ext * geometric_area;
+        let c_ext = c_sca + c_abs;
+
+        // Only interference between the electric and magnetic dipoles makes
+        // scattering asymmetric: g = Re(α_e)·α_m / (|α_e|² + α_m²)
+        let dipoles = factor.norm_sqr() + magnetic * magnetic;
+        let g = if dipoles > 0.0 { factor.re * magnetic / dipoles } else { 0.0 };
+
+        let mut notes = vec![RAYLEIGH_NOTE.to_string()];
+        if point_dipole {
+            let alpha = self.dipole_polarizability();
+            notes.push(POINT_DIPOLE_NOTE.to_string());
+            notes.push(format!("α = {:.4e} {:+.4e}i nm³", alpha.re, alpha.im));
+        }
+        if lossless {
+            notes.push(LOSSLESS_NOTE.to_string());
+        }
+        if self.mu_medium != 1.0 {
+            notes.push(format!("magnetic host: µ = {}", self.mu_medium));
+        }
         
         OpticalResult {
             wavelength: self.wavelength,
@@ -71,11 +307,13 @@ impl MieModel {
             c_sca,
             c_abs,
             c_ext,
+            g,
+            q_pr: q_ext - g * q_sca,
             metadata: OpticalMetadata {
                 num_terms: Some(1),
                 converged: true,
                 size_parameter: x,
-                notes: vec!["Rayleigh approximation".to_string()],
+                notes,
             },
         }
     }
@@ -83,11 +321,11 @@ impl MieModel {
 
 impl PhysicsModel for MieModel {
     fn name(&self) -> &str {
-        "Mie Scattering (Rayleigh Approximation)"
+        "Mie Scattering"
     }
 
     fn description(&self) -> &str {
-        "Calculate scattering and absorption for spherical nanoparticles (x < 1)"
+        "Calculate scattering and absorption for spherical nanoparticles of any size"
     }
 
     fn validate(&self) -> ValidationResult<()> {
@@ -106,29 +344,49 @@ impl PhysicsModel for MieModel {
                 "Medium refractive index must be positive".to_string(),
             ));
         }
-        Ok(())
+        if !self.mu_medium.is_finite() || self.mu_medium <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Medium permeability must be positive".to_string(),
+            ));
+        }
+        self.config.validate()
     }
 
-    fn warnings(&self) -> Vec<String> {
-        let mut warnings = Vec::new();
+    /// Rayleigh forced past its range, or a series that hits the term cap
+    fn warnings(&self) -> Vec<Warning> {
         let x = self.size_parameter();
-        
-        if x > 1.0 {
-            warnings.push(format!(
-                "Size parameter x={:.2} > 1. Rayleigh approximation may be inaccurate. \
-                 Full Mie theory recommended.",
-                x
-            ));
+        if self.uses_rayleigh() {
+            return match ValidityFlag::from_parameter(x, RAYLEIGH_VALID_X, RAYLEIGH_MARGINAL_X) {
+                ValidityFlag::Valid => Vec::new(),
+                flag => {
+                    let severity = if flag == ValidityFlag::Invalid {
+                        WarningSeverity::Severe
+                    } else {
+                        WarningSeverity::Caution
+                    };
+                    vec![Warning::new(WarningCode::SizeParameterTooLarge, severity, format!("x = {:.3}", x))]
+                }
+            };
+        }
+        match self.coefficients() {
+            Err(CalculationError::ConvergenceFailed { iterations }) => vec![Warning::new(
+                WarningCode::SeriesNotConverged,
+                WarningSeverity::Severe,
+                format!("x = {:.3}, n_max = {}", x, iterations),
+            )],
+            _ => Vec::new(),
         }
-        
-        warnings
     }
 }
 
 impl OpticalModel for MieModel {
     fn calculate(&self) -> CalcResult<OpticalResult> {
         self.validate()?;
-        Ok(self.rayleigh_approximation())
+        if self.uses_rayleigh() {
+            Ok(self.rayleigh_approximation())
+        } else {
+            self.mie_series()
+        }
     }
 
     fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
@@ -141,8 +399,52 @@ impl OpticalModel for MieModel {
             })
             .collect()
     }
+
+    fn shape(&self) -> ParticleShape {
+        ParticleShape::Sphere { radius: self.radius }
+    }
+
+    /// Series points are valid unless the series fails to converge within
+    /// the term cap; Rayleigh points are graded by x, which only matters
+    /// when Rayleigh is forced past [`MIE_SERIES_X`]
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+        wavelengths
+            .iter()
+            .map(|&wl| {
+                let mut model = self.clone();
+                model.wavelength = wl;
+                if model.uses_rayleigh() {
+                    ValidityFlag::from_parameter(model.size_parameter(), RAYLEIGH_VALID_X, RAYLEIGH_MARGINAL_X)
+                } else if model.coefficients().is_ok() {
+                    ValidityFlag::Valid
+                } else {
+                    ValidityFlag::Invalid
+                }
+            })
+            .collect()
+    }
 }
 
+/// Size parameter from which [`MieModel::calculate`] sums the full series
+pub const MIE_SERIES_X: f64 = 0.1;
+/// Note attached to results of the Rayleigh branch
+pub const RAYLEIGH_NOTE: &str = "Rayleigh approximation";
+/// Note attached to results of the full series branch
+pub const MIE_SERIES_NOTE: &str = "full Mie series";
+
+/// Note attached to results of a particle with k = 0
+pub const LOSSLESS_NOTE: &str = "lossless: absorption forced to zero";
+
+/// Size parameter below which a particle is treated as a point dipole
+pub const POINT_DIPOLE_X: f64 = 1e-2;
+/// Note attached to results in the point-dipole limit
+pub const POINT_DIPOLE_NOTE: &str = "point-dipole limit";
+
+/// Size parameter below which the Rayleigh approximation is reliable
+pub const RAYLEIGH_VALID_X: f64 = 0.3;
+/// Size parameter beyond which the Rayleigh approximation breaks down
+pub const RAYLEIGH_MARGINAL_X: f64 = 1.0;
+
 impl Clone for MieModel {
     fn clone(&self) -> Self {
         Self {
@@ -150,10 +452,291 @@ impl Clone for MieModel {
             wavelength: self.wavelength,
             n_particle: self.n_particle,
             n_medium: self.n_medium,
+            mu_medium: self.mu_medium,
+            config: self.config,
+            material: self.material.clone(),
         }
     }
 }
 
+/// Every input that changes a result, floats in their exact shortest form;
+/// a dispersive material is identified by its contents
+impl Cacheable for MieModel {
+    fn cache_key(&self) -> String {
+        let material = match &self.material {
+            Some(material) => material.cache_key(),
+            None => "-".to_string(),
+        };
+        format!(
+            "mie|r={}|λ={}|n={}+{}i|n_med={}|µ={}|tol={}|max={}|{:?}|mat={}",
+            self.radius,
+            self.wavelength,
+            self.n_particle.real,
+            self.n_particle.imaginary,
+            self.n_medium,
+            self.mu_medium,
+            self.config.tolerance,
+            self.config.max_terms,
+            self.config.dispatch,
+            material
+        )
+    }
+}
+
+/// Number of series terms from the Wiscombe criterion: x + 4x^(1/3) + 2
+pub fn wiscombe_terms(x: f64) -> usize {
+    (x + 4.0 * x.cbrt() + 2.0).ceil() as usize
+}
+
+/// Mie expansion coefficients aₙ, bₙ of a homogeneous sphere
+///
+/// Computed with the Bohren & Huffman (BHMIE) recurrences: downward
+/// recurrence for the logarithmic derivative Dₙ(mx), upward recurrence
+/// for the Riccati-Bessel functions ψₙ(x) and χₙ(x).
+#[derive(Debug, Clone)]
+pub struct MieCoefficients {
+    /// Size parameter x
+    pub x: f64,
+    /// Electric multipole coefficients a₁..a_N
+    pub a: Vec<Complex64>,
+    /// Magnetic multipole coefficients b₁..b_N
+    pub b: Vec<Complex64>,
+    /// Im(m) = 0: absorption is forced to zero instead of summed
+    pub lossless: bool,
+}
+
+impl MieCoefficients {
+    /// Compute coefficients for relative index `m` and size parameter `x`
+    pub fn compute(m: Complex64, x: f64) -> CalcResult<Self> {
+        Self::compute_magnetic(m, x, 1.0)
+    }
+
+    /// Coefficients with a permeability contrast `mu_ratio` = µ_particle/µ_medium
+    ///
+    /// Bohren & Huffman eq. 4.53 in logarithmic-derivative form: Dₙ enters
+    /// aₙ as Dₙ·µ₁/(µm) and bₙ as Dₙ·mµ/µ₁. A ratio of 1 gives [`Self::compute`].
+    pub fn compute_magnetic(m: Complex64, x: f64, mu_ratio: f64) -> CalcResult<Self> {
+        if !x.is_finite() || x <= 0.0 {
+            return Err(CalculationError::InvalidInput(format!(
+                "Size parameter must be positive and finite (x = {})",
+                x
+            )));
+        }
+        if !m.re.is_finite() || !m.im.is_finite() || m.norm() == 0.0 {
+            return Err(CalculationError::InvalidInput(format!(
+                "Relative refractive index must be finite and non-zero (m = {})",
+                m
+            )));
+        }
+        if !mu_ratio.is_finite() || mu_ratio <= 0.0 {
+            return Err(CalculationError::InvalidInput(format!(
+                "Permeability ratio must be positive and finite (µ₁/µ = {})",
+                mu_ratio
+            )));
+        }
+        Self::series(m, x, mu_ratio, wiscombe_terms(x))
+    }
+
+    /// Coefficients summed to the stopping rule in `config`
+    ///
+    /// Fails with [`CalculationError::ConvergenceFailed`] if `config.max_terms`
+    /// orders are not enough.
+    pub fn compute_converged(m: Complex64, x: f64, mu_ratio: f64, config: &MieConfig) -> CalcResult<Self> {
+        let minimum = wiscombe_terms(x).min(config.max_terms);
+        let mut coeffs = Self::compute_magnetic(m, x, mu_ratio)?;
+        coeffs.a.truncate(minimum);
+        coeffs.b.truncate(minimum);
+        loop {
+            let nstop = coeffs.num_terms();
+            if let Some(order) = coeffs.converged_order(minimum, config.tolerance) {
+                coeffs.a.truncate(order);
+                coeffs.b.truncate(order);
+                return Ok(coeffs);
+            }
+            if nstop >= config.max_terms {
+                return Err(CalculationError::ConvergenceFailed {
+                    iterations: config.max_terms,
+                });
+            }
+            coeffs = Self::series(m, x, mu_ratio, (2 * nstop).min(config.max_terms))?;
+        }
+    }
+
+    /// First order n ≥ `minimum` whose relative contribution is within `tolerance`
+    fn converged_order(&self, minimum: usize, tolerance: f64) -> Option<usize> {
+        let mut sum = 0.0;
+        for (i, (an, bn)) in self.a.iter().zip(&self.b).enumerate() {
+            let term = (2 * i + 3) as f64 * (an.norm() + bn.norm());
+            sum += term;
+            if i + 1 >= minimum && term <= tolerance * sum {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+
+    /// aₙ, bₙ for n = 1..=`nstop`
+    fn series(m: Complex64, x: f64, mu_ratio: f64, nstop: usize) -> CalcResult<Self> {
+        let mx = m * x;
+        let nmx = (nstop as f64).max(mx.norm()).ceil() as usize + 15;
+
+        // Logarithmic derivative Dₙ(mx) by downward recurrence
+        let mut d = vec![Complex64::new(0.0, 0.0); nmx + 1];
+        for n in (1..=nmx).rev() {
+            let nf = n as f64;
+            d[n - 1] = nf / mx - 1.0 / (d[n] + nf / mx);
+        }
+
+        let mut psi0 = x.cos();
+        let mut psi1 = x.sin();
+        let mut chi0 = -x.sin();
+        let mut chi1 = x.cos();
+        let mut xi1 = Complex64::new(psi1, -chi1);
+
+        let mut a = Vec::with_capacity(nstop);
+        let mut b = Vec::with_capacity(nstop);
+
+        for (n, &dn) in d.iter().enumerate().take(nstop + 1).skip(1) {
+            let nf = n as f64;
+            let psi = (2.0 * nf - 1.0) * psi1 / x - psi0;
+            let chi = (2.0 * nf - 1.0) * chi1 / x - chi0;
+            let xi = Complex64::new(psi, -chi);
+
+            let da = dn / m * mu_ratio + nf / x;
+            let db = dn * m / mu_ratio + nf / x;
+            let an = (da * psi - psi1) / (da * xi - xi1);
+            let bn = (db * psi - psi1) / (db * xi - xi1);
+
+            if !an.is_finite() || !bn.is_finite() {
+                return Err(CalculationError::NumericalInstability(format!(
+                    "Non-finite Mie coefficient at order n = {}",
+                    n
+                )));
+            }
+            a.push(an);
+            b.push(bn);
+
+            psi0 = psi1;
+            psi1 = psi;
+            chi0 = chi1;
+            chi1 = chi;
+            xi1 = Complex64::new(psi1, -chi1);
+        }
+
+        Ok(Self {
+            x,
+            a,
+            b,
+            lossless: m.im == 0.0,
+        })
+    }
+
+    /// Number of multipole orders retained
+    pub fn num_terms(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Extinction efficiency Q_ext = (2/x²) Σ (2n+1) Re(aₙ + bₙ)
+    ///
+    /// For a lossless sphere this is exactly Q_sca.
+    pub fn q_ext(&self) -> f64 {
+        if self.lossless {
+            return self.q_sca();
+        }
+        let sum: f64 = self
+            .a
+            .iter()
+            .zip(&self.b)
+            .enumerate()
+            .map(|(i, (an, bn))| (2 * i + 3) as f64 * (an + bn).re)
+            .sum();
+        2.0 * sum / (self.x * self.x)
+    }
+
+    /// Scattering efficiency Q_sca = (2/x²) Σ (2n+1) (|aₙ|² + |bₙ|²)
+    pub fn q_sca(&self) -> f64 {
+        let sum: f64 = self
+            .a
+            .iter()
+            .zip(&self.b)
+            .enumerate()
+            .map(|(i, (an, bn))| (2 * i + 3) as f64 * (an.norm_sqr() + bn.norm_sqr()))
+            .sum();
+        2.0 * sum / (self.x * self.x)
+    }
+
+    /// Absorption efficiency Q_abs = Q_ext - Q_sca
+    ///
+    /// Exactly zero for a lossless sphere, where the difference of the two
+    /// series would only leave rounding noise.
+    pub fn q_abs(&self) -> f64 {
+        if self.lossless {
+            return 0.0;
+        }
+        self.q_ext() - self.q_sca()
+    }
+
+    /// Asymmetry parameter g = ⟨cos θ⟩
+    ///
+    /// Bohren & Huffman eq. 4.80: Q_sca·g = (4/x²)[Σ n(n+2)/(n+1) Re(aₙa*ₙ₊₁ + bₙb*ₙ₊₁)
+    /// + Σ (2n+1)/(n(n+1)) Re(aₙb*ₙ)].
+    pub fn g(&self) -> f64 {
+        let q_sca = self.q_sca();
+        if q_sca <= 0.0 {
+            return 0.0;
+        }
+        let mut sum = 0.0;
+        for (i, (an, bn)) in self.a.iter().zip(&self.b).enumerate() {
+            let n = (i + 1) as f64;
+            sum += (2.0 * n + 1.0) / (n * (n + 1.0)) * (an * bn.conj()).re;
+            if let (Some(an1), Some(bn1)) = (self.a.get(i + 1), self.b.get(i + 1)) {
+                sum += n * (n + 2.0) / (n + 1.0) * (an * an1.conj() + bn * bn1.conj()).re;
+            }
+        }
+        4.0 * sum / (self.x * self.x * q_sca)
+    }
+
+    /// Radiation-pressure efficiency Q_pr = Q_ext − g·Q_sca
+    pub fn q_pr(&self) -> f64 {
+        self.q_ext() - self.g() * self.q_sca()
+    }
+
+    /// Scattering amplitudes (S₁, S₂) at scattering angle `theta` in radians
+    ///
+    /// Uses the angular functions πₙ, τₙ from the Legendre recurrences.
+    pub fn amplitudes(&self, theta: f64) -> (Complex64, Complex64) {
+        let mu = theta.cos();
+        let mut pi_prev = 0.0;
+        let mut pi_n = 1.0;
+        let mut s1 = Complex64::new(0.0, 0.0);
+        let mut s2 = Complex64::new(0.0, 0.0);
+
+        for (i, (an, bn)) in self.a.iter().zip(&self.b).enumerate() {
+            let n = (i + 1) as f64;
+            let tau_n = n * mu * pi_n - (n + 1.0) * pi_prev;
+            let factor = (2.0 * n + 1.0) / (n * (n + 1.0));
+            s1 += factor * (an * pi_n + bn * tau_n);
+            s2 += factor * (an * tau_n + bn * pi_n);
+
+            let pi_next = ((2.0 * n + 1.0) * mu * pi_n - (n + 1.0) * pi_prev) / n;
+            pi_prev = pi_n;
+            pi_n = pi_next;
+        }
+
+        (s1, s2)
+    }
+
+    /// Phase function p(θ) for unpolarized light, normalized so ∫ p dΩ = 1
+    pub fn phase_function(&self, theta: f64) -> f64 {
+        let (s1, s2) = self.amplitudes(theta);
+        let q_sca = self.q_sca();
+        if q_sca <= 0.0 {
+            return 0.0;
+        }
+        (s1.norm_sqr() + s2.norm_sqr()) / (2.0 * PI * self.x * self.x * q_sca)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,5 +773,463 @@ mod tests {
         let x = model.size_parameter();
         let expected = 2.0 * PI * 50.0 / 500.0;
         assert!((x - expected).abs() < 1e-10);
+
+        // The wavelength shrinks by n_medium inside the host
+        let in_water = MieModel::new(50.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        assert!((in_water.size_parameter() - 1.33 * expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_bohren_huffman_reference_in_water() {
+        // The Appendix A sphere (m = 1.55, x = 2π·0.525/0.6328) rebuilt in
+        // water: the particle index and vacuum wavelength both scale by 1.33
+        let n_medium = 1.33;
+        let model = MieModel::new(525.0, 632.8 * n_medium, RefractiveIndex::new(1.55 * n_medium, 0.0), n_medium);
+        assert!((model.size_parameter() - 5.213).abs() < 1e-3);
+
+        let result = model.calculate().unwrap();
+        assert!((result.q_ext - 3.10543).abs() < 1e-3, "{}", result.q_ext);
+        assert!((result.q_sca - 3.10543).abs() < 1e-3, "{}", result.q_sca);
+    }
+
+    #[test]
+    fn test_large_particles_need_no_size_warning() {
+        let small = MieModel::new(10.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.0);
+        assert!(small.warnings().is_empty());
+
+        let large = MieModel::new(200.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.0);
+        assert!(large.warnings().is_empty());
+        let result = large.calculate().unwrap();
+        assert!(result.metadata.notes.iter().any(|n| n == MIE_SERIES_NOTE));
+    }
+
+    #[test]
+    fn test_dispatch_records_branch_and_terms() {
+        let n = RefractiveIndex::new(0.47, 2.4);
+        let wl_for = |x: f64| 2.0 * PI * 1.33 * 50.0 / x;
+
+        let small = MieModel::new(50.0, wl_for(0.05), n, 1.33).calculate().unwrap();
+        assert_eq!(small.metadata.notes[0], RAYLEIGH_NOTE);
+        assert_eq!(small.metadata.num_terms, Some(1));
+
+        for x in [MIE_SERIES_X, 2.5, 20.0] {
+            let model = MieModel::new(50.0, wl_for(x), n, 1.33);
+            let result = model.calculate().unwrap();
+            assert_eq!(result.metadata.notes[0], MIE_SERIES_NOTE);
+            assert_eq!(result.metadata.num_terms, Some(wiscombe_terms(x)));
+            assert_eq!(result.q_ext, model.coefficients().unwrap().q_ext());
+            assert!((result.c_ext - result.q_ext * PI * 50.0 * 50.0).abs() < 1e-9 * result.c_ext);
+        }
+
+        // Both branches agree where they meet
+        let below = MieModel::new(50.0, wl_for(MIE_SERIES_X * (1.0 - 1e-9)), n, 1.33).calculate().unwrap();
+        let above = MieModel::new(50.0, wl_for(MIE_SERIES_X), n, 1.33).calculate().unwrap();
+        assert!((below.q_ext - above.q_ext).abs() / above.q_ext < 0.05);
+    }
+
+    #[test]
+    fn test_forced_dispatch_overrides_size_parameter() {
+        let n = RefractiveIndex::new(0.47, 2.4);
+        let wl_for = |x: f64| 2.0 * PI * 1.33 * 50.0 / x;
+        let with = |x: f64, dispatch| {
+            let config = MieConfig { dispatch, ..MieConfig::default() };
+            MieModel::with_config(50.0, wl_for(x), n, 1.33, config).calculate().unwrap()
+        };
+
+        let rayleigh = with(2.5, MieDispatch::Rayleigh);
+        assert_eq!(rayleigh.metadata.notes[0], RAYLEIGH_NOTE);
+        let series = with(0.05, MieDispatch::FullSeries);
+        assert_eq!(series.metadata.notes[0], MIE_SERIES_NOTE);
+        assert_eq!(series.metadata.num_terms, Some(wiscombe_terms(0.05)));
+
+        // Auto matches whichever branch applies
+        assert_eq!(with(2.5, MieDispatch::Auto).q_ext, with(2.5, MieDispatch::FullSeries).q_ext);
+        assert_eq!(with(0.05, MieDispatch::Auto).q_ext, with(0.05, MieDispatch::Rayleigh).q_ext);
+    }
+
+    #[test]
+    fn test_conservation_across_size_parameters() {
+        let radius = 50.0;
+        for n in [
+            RefractiveIndex::new(1.5, 0.0),
+            RefractiveIndex::new(0.47, 2.4),
+            RefractiveIndex::new(3.5, 0.01),
+        ] {
+            for x in [0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0] {
+                let model = MieModel::new(radius, 2.0 * PI * 1.33 * radius / x, n, 1.33);
+                let result = model.calculate().unwrap();
+                assert!(result.check_conservation() < 1e-6, "x = {}, n = {:?}", x, n);
+                assert!(result.q_sca > 0.0 && result.q_abs >= 0.0 && result.q_ext.is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mie_coefficients_rayleigh_limit() {
+        let m = Complex64::new(1.5, 0.0);
+        let x = 0.01;
+        let coeffs = MieCoefficients::compute(m, x).unwrap();
+
+        let factor = (m * m - 1.0) / (m * m + 2.0);
+        let q_sca_rayleigh = (8.0 / 3.0) * x.powi(4) * factor.norm_sqr();
+        assert!((coeffs.q_sca() - q_sca_rayleigh).abs() / q_sca_rayleigh < 1e-3);
+        assert!(coeffs.q_abs().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_phase_function_rayleigh_shape() {
+        // Rayleigh limit: p(θ) ∝ 1 + cos²θ, so p(0)/p(π/2) = 2
+        let coeffs = MieCoefficients::compute(Complex64::new(1.5, 0.0), 0.01).unwrap();
+        let ratio = coeffs.phase_function(0.0) / coeffs.phase_function(PI / 2.0);
+        assert!((ratio - 2.0).abs() < 1e-3);
+        assert!((coeffs.phase_function(0.0) - 3.0 / (8.0 * PI)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_mie_coefficients_bohren_huffman_reference() {
+        // Bohren & Huffman, Appendix A: m = 1.55, r = 0.525 µm, λ = 0.6328 µm
+        let x = 2.0 * PI * 0.525 / 0.6328;
+        let coeffs = MieCoefficients::compute(Complex64::new(1.55, 0.0), x).unwrap();
+
+        assert!((coeffs.q_ext() - 3.10543).abs() < 1e-3);
+        assert!((coeffs.q_sca() - 3.10543).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_lossless_sphere_has_exactly_zero_absorption() {
+        let model = MieModel::new(30.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33);
+        let result = model.calculate().unwrap();
+        assert_eq!(result.q_abs, 0.0);
+        assert_eq!(result.c_abs, 0.0);
+        assert_eq!(result.check_conservation(), 0.0);
+        assert!(result.metadata.notes.iter().any(|n| n == LOSSLESS_NOTE));
+
+        // Well inside the full series, where Q_ext − Q_sca leaves rounding noise
+        for x in [5.0, 25.0, 80.0] {
+            let coeffs = MieCoefficients::compute(Complex64::new(1.5, 0.0), x).unwrap();
+            assert_eq!(coeffs.q_abs(), 0.0);
+            assert_eq!(coeffs.q_ext(), coeffs.q_sca());
+        }
+
+        let absorbing = MieModel::new(30.0, 500.0, RefractiveIndex::new(1.5, 0.1), 1.33);
+        let result = absorbing.calculate().unwrap();
+        assert!(result.q_abs > 0.0);
+        assert!(!result.metadata.notes.iter().any(|n| n == LOSSLESS_NOTE));
+    }
+
+    #[test]
+    fn test_rayleigh_validity_boundaries() {
+        // Wavelengths chosen to straddle x = 0.3 and x = 1 for r = 10 nm
+        let n = RefractiveIndex::new(1.5, 0.0);
+        let wl_for = |x: f64| 2.0 * PI * 10.0 / x;
+        let wavelengths = [wl_for(0.05), wl_for(0.29), wl_for(0.31), wl_for(0.99), wl_for(1.01), wl_for(20.0)];
+
+        // The automatic branch is exact everywhere
+        let auto = MieModel::new(10.0, 500.0, n, 1.0);
+        assert!(auto.validity_region(&wavelengths).iter().all(|&f| f == ValidityFlag::Valid));
+        assert!(MieModel { wavelength: wl_for(20.0), ..auto.clone() }.warnings().is_empty());
+
+        // Forced Rayleigh is graded by x
+        let config = MieConfig { dispatch: MieDispatch::Rayleigh, ..MieConfig::default() };
+        let rayleigh = MieModel::with_config(10.0, 500.0, n, 1.0, config);
+        use ValidityFlag::*;
+        assert_eq!(rayleigh.validity_region(&wavelengths), vec![Valid, Valid, Marginal, Marginal, Invalid, Invalid]);
+        let large = MieModel { wavelength: wl_for(1.01), ..rayleigh.clone() }.warnings();
+        assert_eq!(large[0].code, WarningCode::SizeParameterTooLarge);
+        assert_eq!(large[0].severity, WarningSeverity::Severe);
+
+        // A series capped below what it needs does not converge
+        let config = MieConfig { max_terms: 5, ..MieConfig::default() };
+        let capped = MieModel::with_config(10.0, 500.0, n, 1.0, config);
+        assert_eq!(capped.validity_region(&[wl_for(0.5), wl_for(20.0)]), vec![Valid, Invalid]);
+        let warnings = MieModel { wavelength: wl_for(20.0), ..capped.clone() }.warnings();
+        assert_eq!(warnings[0].code, WarningCode::SeriesNotConverged);
+    }
+
+    #[test]
+    fn test_conventional_medium_spec_reproduces_standard_results() {
+        for (n_particle, n_medium) in [
+            (RefractiveIndex::new(0.47, 2.40), 1.33),
+            (RefractiveIndex::new(1.5, 0.0), 1.0),
+            (RefractiveIndex::new(3.5, 0.01), 1.46),
+        ] {
+            let standard = MieModel::new(40.0, 520.0, n_particle, n_medium);
+            let medium = MieModel::in_medium(40.0, 520.0, n_particle, &MediumSpec::from_index(n_medium)).unwrap();
+
+            let (a, b) = (standard.calculate().unwrap(), medium.calculate().unwrap());
+            assert_eq!((a.q_sca, a.q_abs, a.q_ext), (b.q_sca, b.q_abs, b.q_ext));
+            assert_eq!((a.c_sca, a.c_abs, a.c_ext), (b.c_sca, b.c_abs, b.c_ext));
+            assert_eq!(a.metadata.notes, b.metadata.notes);
+
+            let (a, b) = (standard.coefficients().unwrap(), medium.coefficients().unwrap());
+            assert_eq!((a.a, a.b), (b.a, b.b));
+        }
+    }
+
+    #[test]
+    fn test_magnetic_host_sets_the_size_parameter() {
+        // ε = 0.442225, µ = 4: n = √ε·√µ = 0.665 · 2 = 1.33
+        let medium = MediumSpec::new(Complex64::new(0.442225, 0.0), Complex64::new(4.0, 0.0));
+        let model = MieModel::in_medium(40.0, 600.0, RefractiveIndex::new(0.47, 2.4), &medium).unwrap();
+        let expected = 2.0 * PI * 1.33 * 40.0 / 600.0;
+        assert!((model.size_parameter() - expected).abs() < 1e-12, "{}", model.size_parameter());
+        assert!((model.size_parameter() - medium.size_parameter(40.0, 600.0).re).abs() < 1e-12);
+
+        let result = model.calculate().unwrap();
+        assert_eq!(result.metadata.size_parameter, model.size_parameter());
+        assert!((model.medium().refractive_index() - medium.refractive_index()).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_magnetic_contrast_matches_rayleigh_dipoles() {
+        // µ₁/µ = 0.5 gives ε₁/ε = m²/0.5 and a magnetic dipole (µ₁/µ − 1)/(µ₁/µ + 2)
+        let (m, x, mu_ratio) = (Complex64::new(1.5, 0.0), 0.01, 0.5);
+        let coeffs = MieCoefficients::compute_magnetic(m, x, mu_ratio).unwrap();
+        let eps_ratio = m * m / mu_ratio;
+        let electric = (eps_ratio - 1.0) / (eps_ratio + 2.0);
+        let magnetic = (mu_ratio - 1.0) / (mu_ratio + 2.0);
+        let expected = (8.0 / 3.0) * x.powi(4) * (electric.norm_sqr() + magnetic * magnetic);
+        assert!((coeffs.q_sca() - expected).abs() / expected < 1e-3);
+
+        // The model's Rayleigh path agrees with the series in a µ = 2 host
+        let model = MieModel {
+            mu_medium: 2.0,
+            ..MieModel::new(1.0, 628.0, RefractiveIndex::new(1.5, 0.0), 1.0)
+        };
+        let rayleigh = model.calculate().unwrap();
+        let series = model.coefficients().unwrap();
+        assert!((rayleigh.q_sca - series.q_sca()).abs() / series.q_sca() < 1e-3);
+        assert!(MieCoefficients::compute_magnetic(m, x, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_hosts_are_not_applicable() {
+        let n = RefractiveIndex::new(1.5, 0.0);
+        for medium in [
+            MediumSpec::new(Complex64::new(-4.0, 0.0), Complex64::new(-1.0, 0.0)),
+            MediumSpec::new(Complex64::new(-2.0, 0.0), Complex64::new(1.0, 0.0)),
+            MediumSpec::new(Complex64::new(1.77, 0.01), Complex64::new(1.0, 0.0)),
+        ] {
+            assert!(
+                matches!(MieModel::in_medium(20.0, 500.0, n, &medium), Err(CalculationError::ModelNotApplicable(_))),
+                "{:?}",
+                medium
+            );
+        }
+    }
+
+    #[test]
+    fn test_tiny_particle_point_dipole_limit() {
+        // 0.1 nm gold-like particle at 1000 nm: x ≈ 6e-4
+        let n = RefractiveIndex::new(0.27, 7.0);
+        let tiny = MieModel::new(0.1, 1000.0, n, 1.33).calculate().unwrap();
+        assert!(tiny.metadata.notes.iter().any(|note| note == POINT_DIPOLE_NOTE));
+        assert!(tiny.metadata.notes.iter().any(|note| note.starts_with("α = ")));
+
+        for value in [tiny.q_sca, tiny.q_abs, tiny.q_ext, tiny.c_sca, tiny.c_abs, tiny.c_ext] {
+            assert!(value.is_finite() && value > 0.0, "{}", value);
+        }
+        assert!(tiny.c_sca < 1e-12);
+
+        // C_sca = (8π/3) k⁴ r⁶ |(m² − 1)/(m² + 2)|²
+        let m = n.to_complex() / 1.33;
+        let factor = ((m * m - 1.0) / (m * m + 2.0)).norm_sqr();
+        let k = 2.0 * PI * 1.33 / 1000.0;
+        let expected = 8.0 * PI / 3.0 * k.powi(4) * 0.1f64.powi(6) * factor;
+        assert!((tiny.c_sca - expected).abs() / expected < 1e-12);
+
+        // Scattering scales as r⁶, absorption as r³
+        let double = MieModel::new(0.2, 1000.0, n, 1.33).calculate().unwrap();
+        assert!((double.c_sca / tiny.c_sca - 64.0).abs() < 1e-9);
+        assert!((double.c_abs / tiny.c_abs - 8.0).abs() < 1e-9);
+        assert!(tiny.check_conservation() < 1e-12);
+
+        // Above the threshold the regular Rayleigh path runs
+        let small = MieModel::new(10.0, 500.0, n, 1.33).calculate().unwrap();
+        assert!(!small.metadata.notes.iter().any(|note| note == POINT_DIPOLE_NOTE));
+    }
+
+    #[test]
+    fn test_default_config_keeps_wiscombe_series() {
+        assert_eq!(MieModel::new(50.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.0).config, MieConfig::default());
+        for m in [Complex64::new(1.5, 0.0), Complex64::new(0.35, 1.8), Complex64::new(3.5, 0.0)] {
+            for x in [0.1, 1.0, 9.52, 30.0, 120.0] {
+                let plain = MieCoefficients::compute(m, x).unwrap();
+                let converged = MieCoefficients::compute_converged(m, x, 1.0, &MieConfig::default()).unwrap();
+                assert_eq!((&plain.a, &plain.b), (&converged.a, &converged.b), "m = {}, x = {}", m, x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_controls_series_length() {
+        let n = RefractiveIndex::new(1.5, 0.01);
+        let radius = 20.0 * 500.0 / (2.0 * PI);
+        let default = MieModel::new(radius, 500.0, n, 1.0).calculate().unwrap();
+
+        let tight = MieConfig {
+            tolerance: 1e-15,
+            ..MieConfig::default()
+        };
+        let result = MieModel::with_config(radius, 500.0, n, 1.0, tight).calculate().unwrap();
+        assert!(result.metadata.converged);
+        assert!(result.metadata.num_terms.unwrap() > default.metadata.num_terms.unwrap());
+        assert!((result.q_ext - default.q_ext).abs() / default.q_ext < 1e-9);
+
+        let capped = MieConfig {
+            max_terms: 5,
+            ..MieConfig::default()
+        };
+        let model = MieModel::with_config(radius, 500.0, n, 1.0, capped);
+        assert!(matches!(model.calculate(), Err(CalculationError::ConvergenceFailed { iterations: 5 })));
+
+        for config in [
+            MieConfig { tolerance: 0.0, ..MieConfig::default() },
+            MieConfig { tolerance: f64::NAN, ..MieConfig::default() },
+            MieConfig { max_terms: 0, ..MieConfig::default() },
+        ] {
+            assert!(MieModel::with_config(radius, 500.0, n, 1.0, config).validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_angular_scattering_of_large_particle() {
+        // x ≈ 10 water-like droplet: strongly forward-peaked
+        let model = MieModel::new(800.0, 500.0, RefractiveIndex::new(1.33, 0.0), 1.0);
+        let n = 2000;
+        let theta: Vec<f64> = (0..=n).map(|i| PI * i as f64 / n as f64).collect();
+
+        let amplitudes = model.scattering_amplitudes(&theta).unwrap();
+        assert_eq!(amplitudes.len(), theta.len());
+        let intensity: Vec<f64> = amplitudes.iter().map(|(s1, s2)| s1.norm_sqr() + s2.norm_sqr()).collect();
+        assert!(intensity[1..].iter().all(|&i| i < intensity[0]));
+
+        // 2π ∫ p(θ) sinθ dθ = 1, by Simpson's rule
+        let p = model.phase_function(&theta).unwrap();
+        let h = PI / n as f64;
+        let integral: f64 = (0..=n)
+            .map(|i| {
+                let weight = if i == 0 || i == n { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+                weight * p[i] * theta[i].sin()
+            })
+            .sum::<f64>()
+            * h
+            / 3.0
+            * 2.0
+            * PI;
+        assert!((integral - 1.0).abs() < 0.01, "∫ p dΩ = {}", integral);
+
+        let invalid = MieModel::new(-1.0, 500.0, RefractiveIndex::new(1.33, 0.0), 1.0);
+        assert!(invalid.phase_function(&theta).is_err());
+    }
+
+    #[test]
+    fn test_asymmetry_and_radiation_pressure() {
+        let n = RefractiveIndex::new(1.5, 0.01);
+        let radius = 50.0;
+        let at_x = |x: f64| MieModel::new(radius, 2.0 * PI * radius / x, n, 1.0).calculate().unwrap();
+
+        // Dipole scattering is symmetric front to back
+        let small = at_x(0.05);
+        assert_eq!(small.g, 0.0);
+        assert_eq!(small.q_pr, small.q_ext);
+        let series = at_x(MIE_SERIES_X);
+        assert!(series.g.abs() < 1e-2, "g = {}", series.g);
+
+        // Large spheres scatter forward
+        let mut previous = series.g;
+        for x in [1.0, 3.0, 20.0] {
+            let result = at_x(x);
+            assert!(result.g > previous && result.g < 1.0, "x = {}, g = {}", x, result.g);
+            assert!((result.q_pr - (result.q_ext - result.g * result.q_sca)).abs() < 1e-12);
+            previous = result.g;
+        }
+
+        // g = 2π ∫ p(θ) cosθ sinθ dθ
+        let model = MieModel::new(radius, 2.0 * PI * radius / 3.0, n, 1.0);
+        let steps = 4000;
+        let h = PI / steps as f64;
+        let p = model.phase_function(&(0..=steps).map(|i| i as f64 * h).collect::<Vec<_>>()).unwrap();
+        let integral: f64 = (0..=steps)
+            .map(|i| {
+                let weight = if i == 0 || i == steps { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+                let theta = i as f64 * h;
+                weight * p[i] * theta.cos() * theta.sin()
+            })
+            .sum::<f64>()
+            * 2.0
+            * PI
+            * h
+            / 3.0;
+        assert!((integral - model.calculate().unwrap().g).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_magnetic_dipoles_give_rayleigh_asymmetry() {
+        let model = MieModel {
+            mu_medium: 2.0,
+            ..MieModel::new(1.0, 628.0, RefractiveIndex::new(1.5, 0.0), 1.0)
+        };
+        let rayleigh = model.calculate().unwrap();
+        let series = model.coefficients().unwrap();
+        assert!(rayleigh.g != 0.0);
+        assert!((rayleigh.g - series.g()).abs() < 1e-3 * rayleigh.g.abs());
+    }
+
+    #[test]
+    fn test_gold_plasmon_peak_in_water() {
+        use crate::physics::materials::LorentzDrudeModel;
+
+        // 20 nm Au sphere in water: the dipole plasmon sits near 520 nm
+        let gold = Arc::new(LorentzDrudeModel::gold());
+        let model = MieModel::new(10.0, 500.0, RefractiveIndex::new(1.5, 0.0), 1.33).with_material(gold.clone());
+        let wavelengths: Vec<f64> = (450..=650).map(f64::from).collect();
+        let spectrum = model.calculate_spectrum(&wavelengths).unwrap();
+        let peak = spectrum
+            .iter()
+            .max_by(|a, b| a.q_ext.total_cmp(&b.q_ext))
+            .unwrap()
+            .wavelength;
+        assert!((510.0..=530.0).contains(&peak), "peak at {} nm", peak);
+
+        // Each point uses the material's index there, not n_particle
+        let fixed = MieModel::new(10.0, 600.0, gold.refractive_index_at(600.0), 1.33);
+        assert_eq!(spectrum[150].q_ext, fixed.calculate().unwrap().q_ext);
+    }
+
+    #[test]
+    fn test_progress_and_cancellation() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let model = MieModel::new(40.0, 500.0, RefractiveIndex::new(0.47, 2.40), 1.33);
+        let wavelengths: Vec<f64> = (0..200).map(|i| 300.0 + 2.5 * i as f64).collect();
+
+        let never = AtomicBool::new(false);
+        let mut calls = Vec::new();
+        let full = model
+            .calculate_spectrum_with_progress(&wavelengths, &mut |done, total| calls.push((done, total)), &never)
+            .unwrap();
+        assert_eq!(full.len(), 200);
+        assert_eq!(calls.len(), 200_usize.div_ceil(PROGRESS_INTERVAL));
+        assert_eq!(calls[0], (PROGRESS_INTERVAL, 200));
+        assert_eq!(calls.last(), Some(&(200, 200)));
+        assert_eq!(full[137].q_ext.to_bits(), model.calculate_spectrum(&wavelengths).unwrap()[137].q_ext.to_bits());
+
+        // Stop once K points have been reported
+        let k = 2 * PROGRESS_INTERVAL;
+        let cancel = AtomicBool::new(false);
+        let partial = model
+            .calculate_spectrum_with_progress(
+                &wavelengths,
+                &mut |done, _| {
+                    if done >= k {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                },
+                &cancel,
+            )
+            .unwrap();
+        assert_eq!(partial.len(), k);
+        assert_eq!(partial[k - 1].wavelength, wavelengths[k - 1]);
     }
 }
@@ -1,6 +1,13 @@
 //! Optical physics models
 
+pub mod anomalous_diffraction;
+pub mod array;
+pub mod core_shell;
+pub mod medium;
 pub mod mie;
+pub mod rgd;
 pub mod traits;
 
+pub use core_shell::CoreShellModel;
+pub use medium::MediumSpec;
 pub use traits::*;
@@ -0,0 +1,261 @@
+//! Rayleigh-Gans-Debye (RGD) scattering by a homogeneous sphere
+//!
+//! Valid for optically soft particles, |m−1| ≪ 1 and |m−1|·x ≪ 1: every
+//! volume element scatters as an independent Rayleigh dipole driven by the
+//! incident field, and the sphere's form factor
+//! G(u) = 3(sin u − u cos u)/u³, u = 2x·sin(θ/2), accounts for interference.
+
+use crate::core::*;
+use crate::physics::materials::Dispersion;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Phase-shift parameter |m−1|·x below which RGD is reliable
+pub const RGD_VALID_PHASE: f64 = 0.3;
+/// Phase-shift parameter |m−1|·x beyond which RGD breaks down
+pub const RGD_MARGINAL_PHASE: f64 = 1.0;
+
+/// Rayleigh-Gans-Debye model for a sphere
+#[derive(Debug, Clone)]
+pub struct RgdModel {
+    /// Particle radius in nm
+    pub radius: f64,
+    /// Wavelength in vacuum, nm
+    pub wavelength: f64,
+    /// Particle refractive index
+    pub n_particle: RefractiveIndex,
+    /// Medium refractive index
+    pub n_medium: f64,
+    /// Wavelength-dependent particle material; when set it replaces
+    /// `n_particle`
+    pub material: Option<Arc<dyn Dispersion>>,
+}
+
+/// Sphere form factor G(u), with the u → 0 limit G = 1
+fn form_factor(u: f64) -> f64 {
+    if u < 1e-3 {
+        1.0 - u * u / 10.0
+    } else {
+        3.0 * (u.sin() - u * u.cos()) / u.powi(3)
+    }
+}
+
+impl RgdModel {
+    pub fn new(
+        radius: f64,
+        wavelength: f64,
+        n_particle: RefractiveIndex,
+        n_medium: f64,
+    ) -> Self {
+        Self {
+            radius,
+            wavelength,
+            n_particle,
+            n_medium,
+            material: None,
+        }
+    }
+
+    /// The same model with a dispersive particle material, see
+    /// [`MieModel::with_material`](super::mie::MieModel::with_material)
+    pub fn with_material(self, material: Arc<dyn Dispersion>) -> Self {
+        Self {
+            material: Some(material),
+            ..self
+        }
+    }
+
+    /// Particle index at `wavelength`
+    fn particle_index(&self, wavelength: f64) -> RefractiveIndex {
+        match &self.material {
+            Some(material) => material.refractive_index_at(wavelength),
+            None => self.n_particle,
+        }
+    }
+
+    /// Size parameter x = 2π·n_medium·r/λ
+    fn size_parameter(&self, wavelength: f64) -> f64 {
+        2.0 * PI * self.n_medium * self.radius / wavelength
+    }
+
+    /// Phase-shift parameter |m−1|·x that sets RGD validity
+    pub fn phase_shift(&self, wavelength: f64) -> f64 {
+        let m = self.particle_index(wavelength).to_complex() / self.n_medium;
+        (m - 1.0).norm() * self.size_parameter(wavelength)
+    }
+
+    /// ∫₀^π G(u)²(1 + cos²θ) sinθ dθ by composite Simpson's rule
+    fn angular_integral(x: f64) -> f64 {
+        let n = ((40.0 * x).ceil() as usize).max(400) & !1;
+        let h = PI / n as f64;
+        let f = |theta: f64| {
+            let g = form_factor(2.0 * x * (theta / 2.0).sin());
+            g * g * (1.0 + theta.cos().powi(2)) * theta.sin()
+        };
+        let interior: f64 = (1..n)
+            .map(|i| if i % 2 == 1 { 4.0 } else { 2.0 } * f(i as f64 * h))
+            .sum();
+        h / 3.0 * (f(0.0) + interior + f(PI))
+    }
+}
+
+impl PhysicsModel for RgdModel {
+    fn name(&self) -> &str {
+        "Rayleigh-Gans-Debye"
+    }
+
+    fn description(&self) -> &str {
+        "Scattering by optically soft spheres (|m−1| ≪ 1, |m−1|·x ≪ 1)"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        if self.radius <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Radius must be positive".to_string(),
+            ));
+        }
+        if self.wavelength <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Wavelength must be positive".to_string(),
+            ));
+        }
+        if self.n_medium <= 0.0 {
+            return Err(ValidationError::InvalidParameter(
+                "Medium refractive index must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn warnings(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        let phase = self.phase_shift(self.wavelength);
+
+        if phase > RGD_MARGINAL_PHASE {
+            warnings.push(Warning::new(
+                WarningCode::PhaseShiftTooLarge,
+                WarningSeverity::Caution,
+                format!("|m−1|·x = {:.2} > {}", phase, RGD_MARGINAL_PHASE),
+            ));
+        }
+
+        warnings
+    }
+}
+
+impl OpticalModel for RgdModel {
+    fn calculate(&self) -> CalcResult<OpticalResult> {
+        self.validate()?;
+
+        let x = self.size_parameter(self.wavelength);
+        let k = 2.0 * PI * self.n_medium / self.wavelength;
+        let m = self.particle_index(self.wavelength).to_complex() / self.n_medium;
+        let volume = 4.0 / 3.0 * PI * self.radius.powi(3);
+
+        // C_sca = k⁴|m−1|²V²/(8π²) · 2π ∫ G²(1 + cos²θ) sinθ dθ
+        let c_sca = k.powi(4) * (m - 1.0).norm_sqr() * volume * volume / (4.0 * PI)
+            * Self::angular_integral(x);
+        // Each volume element absorbs independently
+        let c_abs = 2.0 * k * volume * m.im;
+        let c_ext = c_sca + c_abs;
+
+        let geometric_area = self.geometric_cross_section();
+        Ok(OpticalResult {
+            wavelength: self.wavelength,
+            q_sca: c_sca / geometric_area,
+            q_abs: c_abs / geometric_area,
+            q_ext: c_ext / geometric_area,
+            g: 0.0,
+            q_pr: 0.0,
+            c_sca,
+            c_abs,
+            c_ext,
+            metadata: OpticalMetadata {
+                num_terms: None,
+                converged: true,
+                size_parameter: x,
+                notes: vec![format!(
+                    "Rayleigh-Gans-Debye, |m−1|·x = {:.3}",
+                    self.phase_shift(self.wavelength)
+                )],
+            },
+        })
+    }
+
+    fn calculate_spectrum(&self, wavelengths: &[f64]) -> CalcResult<Vec<OpticalResult>> {
+        wavelengths
+            .iter()
+            .map(|&wl| {
+                let mut model = self.clone();
+                model.wavelength = wl;
+                model.calculate()
+            })
+            .collect()
+    }
+
+    fn shape(&self) -> ParticleShape {
+        ParticleShape::Sphere { radius: self.radius }
+    }
+
+    fn validity_region(&self, wavelengths: &[f64]) -> Vec<ValidityFlag> {
+        wavelengths
+            .iter()
+            .map(|&wl| ValidityFlag::from_parameter(self.phase_shift(wl), RGD_VALID_PHASE, RGD_MARGINAL_PHASE))
+            .collect()
+    }
+}
+
+/// Every input that changes a result, as for [`MieModel`](super::mie::MieModel)
+impl Cacheable for RgdModel {
+    fn cache_key(&self) -> String {
+        let material = match &self.material {
+            Some(material) => material.cache_key(),
+            None => "-".to_string(),
+        };
+        format!(
+            "rgd|r={}|λ={}|n={}+{}i|n_med={}|mat={}",
+            self.radius, self.wavelength, self.n_particle.real, self.n_particle.imaginary, self.n_medium, material
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::optical::mie::MieCoefficients;
+
+    #[test]
+    fn test_small_soft_sphere_matches_rayleigh() {
+        // x → 0: C_sca → (32π/27)k⁴r⁶|m−1|²
+        let model = RgdModel::new(2.0, 600.0, RefractiveIndex::new(1.05, 0.0), 1.0);
+        let result = model.calculate().unwrap();
+        let k = 2.0 * PI / 600.0;
+        let expected = 32.0 * PI / 27.0 * k.powi(4) * 2.0f64.powi(6) * 0.05f64.powi(2);
+        assert!((result.c_sca - expected).abs() / expected < 1e-3);
+    }
+
+    #[test]
+    fn test_soft_sphere_close_to_mie() {
+        // m = 1.02, x = 5: |m−1|·x = 0.1, well inside RGD
+        let radius = 5.0 * 500.0 / (2.0 * PI);
+        let model = RgdModel::new(radius, 500.0, RefractiveIndex::new(1.02, 0.0), 1.0);
+        let rgd = model.calculate().unwrap();
+        let mie = MieCoefficients::compute(num_complex::Complex64::new(1.02, 0.0), 5.0).unwrap();
+        assert!((rgd.q_sca - mie.q_sca()).abs() / mie.q_sca() < 0.05);
+    }
+
+    #[test]
+    fn test_rgd_validity_boundaries() {
+        // |m−1| = 0.1, so |m−1|·x crosses 0.3 at x = 3 and 1 at x = 10
+        let model = RgdModel::new(100.0, 500.0, RefractiveIndex::new(1.1, 0.0), 1.0);
+        let wl_for = |phase: f64| 2.0 * PI * 100.0 * 0.1 / phase;
+        let flags = model.validity_region(&[wl_for(0.29), wl_for(0.31), wl_for(0.99), wl_for(1.01)]);
+        assert_eq!(
+            flags,
+            vec![ValidityFlag::Valid, ValidityFlag::Marginal, ValidityFlag::Marginal, ValidityFlag::Invalid]
+        );
+        assert!(model.warnings().is_empty());
+        let short = RgdModel { wavelength: wl_for(1.5), ..model };
+        assert_eq!(short.warnings()[0].code, WarningCode::PhaseShiftTooLarge);
+    }
+}
@@ -0,0 +1,274 @@
+//! Callaway model of lattice thermal conductivity
+//!
+//! Debye phonons with a frequency-dependent relaxation time:
+//!
+//! κ = k_B/(2π² v) · (k_B T/ħ)³ ∫₀^{θ_D/T} τ_c(x) x⁴eˣ/(eˣ − 1)² dx,  x = ħω/k_BT
+//!
+//! where 1/τ_c = Aω⁴ + Bω²T·e^(−θ_D/bT) + v/L sums impurity, Umklapp and
+//! boundary scattering (Matthiessen's rule). Normal processes and the
+//! Callaway correction term are left out.
+
+use crate::core::*;
+
+/// Composite Simpson intervals over x = ħω/k_BT; must be even
+const QUADRATURE_INTERVALS: usize = 400;
+
+/// Phonon scattering process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScatteringMechanism {
+    Boundary,
+    Impurity,
+    Umklapp,
+}
+
+impl ScatteringMechanism {
+    pub fn label(self) -> &'static str {
+        match self {
+            ScatteringMechanism::Boundary => "boundary scattering",
+            ScatteringMechanism::Impurity => "impurity scattering",
+            ScatteringMechanism::Umklapp => "phonon-phonon (Umklapp)",
+        }
+    }
+}
+
+/// Callaway thermal conductivity of a crystal of characteristic size `size`
+#[derive(Debug, Clone)]
+pub struct CallawayModel {
+    /// Temperature in K
+    pub temperature: f64,
+    /// Characteristic size (wire diameter, film thickness) in nm
+    pub size: f64,
+    /// Debye temperature θ_D in K
+    pub debye_temperature: f64,
+    /// Average sound velocity in m/s
+    pub sound_velocity: f64,
+    /// Impurity (mass-difference) coefficient A in s³
+    pub impurity: f64,
+    /// Umklapp coefficient B in s/K
+    pub umklapp: f64,
+    /// Umklapp exponent divisor b in e^(−θ_D/bT)
+    pub umklapp_exponent: f64,
+    /// Constants k_B and ħ are read from
+    pub context: PhysicsContext,
+}
+
+impl CallawayModel {
+    /// Silicon, with B fitted to a bulk κ of ~150 W/(m·K) at 300 K and
+    /// the natural-isotope A of Tamura
+    pub fn silicon(size: f64, temperature: f64) -> Self {
+        Self {
+            temperature,
+            size,
+            debye_temperature: 645.0,
+            sound_velocity: 6400.0,
+            impurity: 1.32e-45,
+            umklapp: 3.5e-19,
+            umklapp_exponent: 3.0,
+            context: PhysicsContext::CODATA,
+        }
+    }
+
+    /// Evaluate with the constants of `context`
+    pub fn with_context(mut self, context: PhysicsContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Angular frequency k_B·T/ħ in rad/s
+    fn thermal_omega(&self) -> f64 {
+        self.context.value(ScalableConstant::Boltzmann) * self.temperature / self.context.hbar()
+    }
+
+    /// Scattering rates (boundary, impurity, Umklapp) in 1/s at angular frequency ω
+    fn rates(&self, omega: f64, boundary: bool) -> [f64; 3] {
+        let t = self.temperature;
+        let boundary_rate = if boundary {
+            self.sound_velocity / (self.size * conversions::NM_TO_M)
+        } else {
+            0.0
+        };
+        [
+            boundary_rate,
+            self.impurity * omega.powi(4),
+            self.umklapp * omega * omega * t * (-self.debye_temperature / (self.umklapp_exponent * t)).exp(),
+        ]
+    }
+
+    /// ∫ f(x, ω) w(x) dx over the Debye spectrum, w = x⁴eˣ/(eˣ − 1)²
+    fn integrate(&self, f: impl Fn(f64) -> f64) -> f64 {
+        let thermal_omega = self.thermal_omega();
+        let x_max = self.debye_temperature / self.temperature;
+        let h = x_max / QUADRATURE_INTERVALS as f64;
+        let sum: f64 = (1..=QUADRATURE_INTERVALS)
+            .map(|i| {
+                let x = i as f64 * h;
+                let weight = x.powi(4) * (-x).exp() / (-(-x).exp_m1()).powi(2);
+                let simpson = if i == QUADRATURE_INTERVALS { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+                // The integrand vanishes as x² at x = 0
+                simpson * weight * f(x * thermal_omega)
+            })
+            .sum();
+        sum * h / 3.0
+    }
+
+    /// Conductivity in W/(m·K), with or without boundary scattering
+    fn kappa(&self, boundary: bool) -> f64 {
+        let prefactor = self.context.value(ScalableConstant::Boltzmann)
+            / (2.0 * std::f64::consts::PI.powi(2) * self.sound_velocity)
+            * self.thermal_omega().powi(3);
+        prefactor * self.integrate(|omega| 1.0 / self.rates(omega, boundary).iter().sum::<f64>())
+    }
+
+    /// Process with the largest heat-capacity-weighted mean rate
+    pub fn dominant_mechanism(&self) -> ScatteringMechanism {
+        let mechanisms = [
+            ScatteringMechanism::Boundary,
+            ScatteringMechanism::Impurity,
+            ScatteringMechanism::Umklapp,
+        ];
+        mechanisms
+            .into_iter()
+            .enumerate()
+            .map(|(i, mechanism)| (self.integrate(|omega| self.rates(omega, true)[i]), mechanism))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, mechanism)| mechanism)
+            .unwrap_or(ScatteringMechanism::Umklapp)
+    }
+
+    /// Mean free path v⟨τ⟩ in nm, τ averaged with the spectral heat capacity
+    fn mean_free_path(&self) -> f64 {
+        let tau = self.integrate(|omega| 1.0 / self.rates(omega, true).iter().sum::<f64>()) / self.integrate(|_| 1.0);
+        self.sound_velocity * tau / conversions::NM_TO_M
+    }
+}
+
+impl PhysicsModel for CallawayModel {
+    fn name(&self) -> &str {
+        "Callaway Phonon Conductivity"
+    }
+
+    fn description(&self) -> &str {
+        "Lattice thermal conductivity from frequency-dependent phonon scattering"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        let positive = [
+            ("Temperature", self.temperature),
+            ("Characteristic size", self.size),
+            ("Debye temperature", self.debye_temperature),
+            ("Sound velocity", self.sound_velocity),
+            ("Umklapp exponent", self.umklapp_exponent),
+        ];
+        for (name, value) in positive {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(ValidationError::InvalidParameter(format!("{} must be positive", name)));
+            }
+        }
+        if !(self.impurity >= 0.0 && self.umklapp >= 0.0) {
+            return Err(ValidationError::InvalidParameter(
+                "Scattering coefficients must be non-negative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ThermalModel for CallawayModel {
+    fn calculate(&self) -> CalcResult<ThermalResult> {
+        self.validate()?;
+        let kappa_eff = self.kappa(true);
+        let kappa_bulk = self.kappa(false);
+        if !(kappa_eff.is_finite() && kappa_bulk.is_finite()) {
+            return Err(CalculationError::NumericalInstability(format!(
+                "Callaway integral diverged at {} K",
+                self.temperature
+            )));
+        }
+        let mfp = self.mean_free_path();
+
+        Ok(ThermalResult {
+            temperature: self.temperature,
+            kappa_eff,
+            kappa_bulk,
+            reduction_factor: kappa_eff / kappa_bulk,
+            mfp: Some(mfp),
+            metadata: ThermalMetadata {
+                size_to_mfp_ratio: Some(self.size / mfp),
+                dominant_mechanism: Some(self.dominant_mechanism().label().to_string()),
+                notes: vec![format!("Callaway: θ_D = {} K, L = {} nm", self.debye_temperature, self.size)],
+            },
+        })
+    }
+
+    fn calculate_temperature_sweep(&self, temperatures: &[f64]) -> CalcResult<Vec<ThermalResult>> {
+        temperatures
+            .iter()
+            .map(|&t| {
+                let mut model = self.clone();
+                model.temperature = t;
+                model.calculate()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_silicon_at_room_temperature() {
+        let bulk = CallawayModel::silicon(1e6, 300.0).calculate().unwrap();
+        assert!((135.0..165.0).contains(&bulk.kappa_eff), "{}", bulk.kappa_eff);
+        assert!(bulk.reduction_factor > 0.95);
+        assert_eq!(bulk.metadata.dominant_mechanism.as_deref(), Some("phonon-phonon (Umklapp)"));
+    }
+
+    #[test]
+    fn test_sweep_peaks_then_declines() {
+        let temperatures: Vec<f64> = (2..=20).map(|i| 25.0 * i as f64).collect();
+        let sweep = CallawayModel::silicon(1000.0, 300.0)
+            .calculate_temperature_sweep(&temperatures)
+            .unwrap();
+        let kappa: Vec<f64> = sweep.iter().map(|r| r.kappa_eff).collect();
+        let peak = kappa
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap();
+
+        // Boundary-limited rise, then Umklapp-limited decline
+        assert!(peak > 0 && peak < kappa.len() - 1, "peak at {} K", temperatures[peak]);
+        assert!(kappa[..=peak].windows(2).all(|w| w[1] > w[0]));
+        assert!(kappa[peak..].windows(2).all(|w| w[1] < w[0]));
+        assert!(kappa[peak] > 2.0 * kappa[0] && kappa[peak] > 1.5 * kappa[kappa.len() - 1]);
+        assert_eq!(sweep[0].metadata.dominant_mechanism.as_deref(), Some("boundary scattering"));
+        assert_eq!(sweep[sweep.len() - 1].metadata.dominant_mechanism.as_deref(), Some("phonon-phonon (Umklapp)"));
+    }
+
+    #[test]
+    fn test_smaller_structures_conduct_less() {
+        let wide = CallawayModel::silicon(1000.0, 300.0).calculate().unwrap();
+        let narrow = CallawayModel::silicon(50.0, 300.0).calculate().unwrap();
+        assert!(narrow.kappa_eff < wide.kappa_eff);
+        assert_eq!(narrow.kappa_bulk, wide.kappa_bulk);
+        assert!(narrow.mfp.unwrap() < wide.mfp.unwrap());
+        assert!(CallawayModel::silicon(-5.0, 300.0).calculate().is_err());
+    }
+
+    #[test]
+    fn test_context_reaches_the_phonon_integral() {
+        let codata = CallawayModel::silicon(1000.0, 300.0).calculate().unwrap();
+        let same = CallawayModel::silicon(1000.0, 300.0)
+            .with_context(PhysicsContext::CODATA)
+            .calculate()
+            .unwrap();
+        assert_eq!(same.kappa_eff.to_bits(), codata.kappa_eff.to_bits());
+
+        let mut context = PhysicsContext::CODATA;
+        *context.scale_mut(ScalableConstant::Boltzmann) = 1.5;
+        let what_if = CallawayModel::silicon(1000.0, 300.0).with_context(context).calculate().unwrap();
+        assert!((what_if.kappa_eff / codata.kappa_eff - 1.0).abs() > 0.01);
+    }
+}
@@ -1,5 +1,10 @@
 //! Thermal physics models
 
+pub mod callaway;
+pub mod nanowire;
+pub mod photothermal;
 pub mod traits;
 
+pub use callaway::{CallawayModel, ScatteringMechanism};
+pub use nanowire::{NanowireThermalModel, PhononMaterial};
 pub use traits::*;
@@ -0,0 +1,186 @@
+//! Phonon boundary scattering in nanowires
+//!
+//! Gray (single mean free path) model: phonons scattering off the wire
+//! surface add a boundary rate to the bulk one, so with Matthiessen's rule
+//! κ_eff = κ_bulk / (1 + λ/d). The mean free path follows from kinetic
+//! theory, κ = C v λ / 3.
+
+use crate::core::*;
+
+/// Above this λ/d, boundary scattering dominates
+const BOUNDARY_DOMINATED: f64 = 1.0;
+
+/// Bulk phonon properties of a crystal
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhononMaterial {
+    pub name: String,
+    /// Bulk thermal conductivity at 300 K in W/(m·K)
+    pub kappa_300k: f64,
+    /// Exponent of κ_bulk(T) = κ_300K · (300 K / T)^exponent
+    pub exponent: f64,
+    /// Volumetric heat capacity in J/(m³·K)
+    pub heat_capacity: f64,
+    /// Average phonon group velocity in m/s
+    pub sound_velocity: f64,
+}
+
+impl PhononMaterial {
+    /// Crystalline silicon: Umklapp-limited κ ∝ T^−1.3 above ~150 K
+    pub fn silicon() -> Self {
+        Self {
+            name: "Si".to_string(),
+            kappa_300k: 148.0,
+            exponent: 1.3,
+            heat_capacity: 1.66e6,
+            sound_velocity: 6400.0,
+        }
+    }
+
+    /// Bulk thermal conductivity in W/(m·K) at `temperature` K
+    pub fn kappa_bulk(&self, temperature: f64) -> f64 {
+        self.kappa_300k * (300.0 / temperature).powf(self.exponent)
+    }
+
+    /// Gray phonon mean free path in nm at `temperature` K, λ = 3κ/(C v)
+    pub fn mean_free_path(&self, temperature: f64) -> f64 {
+        3.0 * self.kappa_bulk(temperature) / (self.heat_capacity * self.sound_velocity) / conversions::NM_TO_M
+    }
+}
+
+/// Thermal conductivity of a wire of diameter `diameter` along its axis
+#[derive(Debug, Clone)]
+pub struct NanowireThermalModel {
+    /// Wire diameter in nm
+    pub diameter: f64,
+    /// Temperature in K
+    pub temperature: f64,
+    pub material: PhononMaterial,
+}
+
+impl NanowireThermalModel {
+    pub fn new(diameter: f64, temperature: f64, material: PhononMaterial) -> Self {
+        Self {
+            diameter,
+            temperature,
+            material,
+        }
+    }
+
+    /// Silicon wire
+    pub fn silicon(diameter: f64, temperature: f64) -> Self {
+        Self::new(diameter, temperature, PhononMaterial::silicon())
+    }
+}
+
+impl PhysicsModel for NanowireThermalModel {
+    fn name(&self) -> &str {
+        "Nanowire Boundary Scattering (gray model)"
+    }
+
+    fn description(&self) -> &str {
+        "Size-suppressed thermal conductivity of a nanowire"
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        if !(self.diameter.is_finite() && self.diameter > 0.0) {
+            return Err(ValidationError::InvalidParameter(
+                "Wire diameter must be positive".to_string(),
+            ));
+        }
+        if !(self.temperature.is_finite() && self.temperature > 0.0) {
+            return Err(ValidationError::InvalidParameter(
+                "Temperature must be positive".to_string(),
+            ));
+        }
+        let material = &self.material;
+        if [material.kappa_300k, material.heat_capacity, material.sound_velocity]
+            .iter()
+            .any(|v| !(v.is_finite() && *v > 0.0))
+        {
+            return Err(ValidationError::InvalidParameter(format!(
+                "{}: bulk κ, heat capacity and sound velocity must be positive",
+                material.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ThermalModel for NanowireThermalModel {
+    fn calculate(&self) -> CalcResult<ThermalResult> {
+        self.validate()?;
+        let kappa_bulk = self.material.kappa_bulk(self.temperature);
+        let mfp = self.material.mean_free_path(self.temperature);
+        let knudsen = mfp / self.diameter;
+        let reduction_factor = 1.0 / (1.0 + knudsen);
+
+        let mechanism = if knudsen > BOUNDARY_DOMINATED {
+            "boundary scattering"
+        } else {
+            "phonon-phonon (Umklapp)"
+        };
+        Ok(ThermalResult {
+            temperature: self.temperature,
+            kappa_eff: kappa_bulk * reduction_factor,
+            kappa_bulk,
+            reduction_factor,
+            mfp: Some(mfp),
+            metadata: ThermalMetadata {
+                size_to_mfp_ratio: Some(self.diameter / mfp),
+                dominant_mechanism: Some(mechanism.to_string()),
+                notes: vec![format!("gray model: κ_eff = κ_bulk / (1 + λ/d), {}", self.material.name)],
+            },
+        })
+    }
+
+    fn calculate_temperature_sweep(&self, temperatures: &[f64]) -> CalcResult<Vec<ThermalResult>> {
+        temperatures
+            .iter()
+            .map(|&t| {
+                let mut model = self.clone();
+                model.temperature = t;
+                model.calculate()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduction_factor_limits() {
+        let mfp = PhononMaterial::silicon().mean_free_path(300.0);
+        // Kinetic-theory gray MFP of Si at room temperature is ~40 nm
+        assert!((35.0..50.0).contains(&mfp), "{}", mfp);
+
+        let bulk = NanowireThermalModel::silicon(1e7, 300.0).calculate().unwrap();
+        assert!(bulk.reduction_factor > 0.99999);
+        assert!((bulk.kappa_eff - 148.0).abs() < 1e-2);
+
+        // d = λ halves κ; a decade thinner leaves under a tenth
+        let at_mfp = NanowireThermalModel::silicon(mfp, 300.0).calculate().unwrap();
+        assert!((at_mfp.reduction_factor - 0.5).abs() < 1e-12);
+        assert_eq!(at_mfp.metadata.size_to_mfp_ratio, Some(1.0));
+        let thin = NanowireThermalModel::silicon(mfp / 10.0, 300.0).calculate().unwrap();
+        assert!(thin.reduction_factor < 0.1);
+        assert_eq!(thin.metadata.dominant_mechanism.as_deref(), Some("boundary scattering"));
+        assert!((thin.kappa_eff / thin.kappa_bulk - thin.reduction_factor).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_temperature_sweep_uses_bulk_dependence() {
+        let model = NanowireThermalModel::silicon(50.0, 300.0);
+        let sweep = model.calculate_temperature_sweep(&[200.0, 300.0, 600.0]).unwrap();
+        assert_eq!(sweep.len(), 3);
+        assert_eq!(sweep[1].kappa_bulk, 148.0);
+        // Hotter: lower bulk κ and shorter MFP, so less boundary suppression
+        assert!(sweep[0].kappa_bulk > sweep[1].kappa_bulk && sweep[1].kappa_bulk > sweep[2].kappa_bulk);
+        assert!(sweep[2].reduction_factor > sweep[0].reduction_factor);
+        assert!(sweep.iter().all(|r| r.kappa_eff < r.kappa_bulk));
+
+        assert!(model.calculate_temperature_sweep(&[300.0, -1.0]).is_err());
+        assert!(NanowireThermalModel::silicon(0.0, 300.0).calculate().is_err());
+    }
+}
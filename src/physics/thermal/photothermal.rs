@@ -0,0 +1,70 @@
+//! Photothermal heating: optical absorption coupled to heat dissipation
+//!
+//! A particle absorbing P_abs = C_abs·I in steady state, with heat carried
+//! away by conduction through a uniform medium, sits at a temperature rise
+//! ΔT = P_abs / (4π κ_medium r) above the far-field temperature.
+
+use crate::core::conversions::NM_TO_M;
+use crate::core::{CalcResult, CalculationError};
+use std::f64::consts::PI;
+
+/// Power absorbed in W for a cross section `c_abs_nm2` (nm²) under `irradiance` (W/m²)
+pub fn absorbed_power(c_abs_nm2: f64, irradiance: f64) -> f64 {
+    c_abs_nm2 * NM_TO_M * NM_TO_M * irradiance
+}
+
+/// Steady-state temperature rise in K of a heated sphere
+///
+/// * `c_abs_nm2` - absorption cross section in nm²
+/// * `irradiance` - incident irradiance in W/m²
+/// * `kappa_medium` - thermal conductivity of the medium in W/(m·K)
+/// * `radius` - particle radius in nm
+pub fn steady_state_temperature(
+    c_abs_nm2: f64,
+    irradiance: f64,
+    kappa_medium: f64,
+    radius: f64,
+) -> CalcResult<f64> {
+    if !(c_abs_nm2 >= 0.0 && irradiance >= 0.0) {
+        return Err(CalculationError::InvalidInput(
+            "Absorption cross section and irradiance must be non-negative".to_string(),
+        ));
+    }
+    if kappa_medium <= 0.0 || radius <= 0.0 {
+        return Err(CalculationError::InvalidInput(
+            "Thermal conductivity and radius must be positive".to_string(),
+        ));
+    }
+
+    let p_abs = absorbed_power(c_abs_nm2, irradiance);
+    Ok(p_abs / (4.0 * PI * kappa_medium * radius * NM_TO_M))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_computed_value() {
+        // C_abs = 1000 nm² = 1e-15 m², I = 1e9 W/m² → P = 1e-6 W
+        // ΔT = 1e-6 / (4π · 0.6 · 20e-9) = 6.631 K
+        let dt = steady_state_temperature(1000.0, 1e9, 0.6, 20.0).unwrap();
+        assert!((dt - 6.6315).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_linear_in_irradiance_and_absorption() {
+        let base = steady_state_temperature(500.0, 1e8, 0.6, 25.0).unwrap();
+        let double_i = steady_state_temperature(500.0, 2e8, 0.6, 25.0).unwrap();
+        let triple_c = steady_state_temperature(1500.0, 1e8, 0.6, 25.0).unwrap();
+        assert!((double_i / base - 2.0).abs() < 1e-12);
+        assert!((triple_c / base - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejects_non_physical_inputs() {
+        assert!(steady_state_temperature(100.0, 1e9, 0.0, 20.0).is_err());
+        assert!(steady_state_temperature(100.0, 1e9, 0.6, -1.0).is_err());
+        assert!(steady_state_temperature(-1.0, 1e9, 0.6, 20.0).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Parameter caption for exported figures
+//!
+//! A caption block under the chart records the particle, index and medium
+//! plus the resonance summary, so an exported figure documents itself.
+
+use crate::app::AppState;
+use crate::compute::peaks::dominant_peak;
+use crate::core::OpticalResult;
+
+/// Height of one caption line in pixels
+pub const CAPTION_LINE_HEIGHT: u32 = 28;
+/// Space above and below the caption block in pixels
+pub const CAPTION_PADDING: u32 = 16;
+
+/// Caption lines describing `state` and the dominant peak of `results`
+pub fn caption_lines(state: &AppState, results: &[OpticalResult]) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "Radius: {} nm   Particle index: n = {:.3} + {:.3}i   Medium: n = {:.3}",
+            state.particle_radius, state.n_particle_real, state.n_particle_imag, state.n_medium
+        ),
+    ];
+    match dominant_peak(results) {
+        Some(peak) => {
+            let mut line = format!("Peak Q_ext = {:.3} at {:.1} nm", peak.value, peak.wavelength);
+            if let Some(fwhm) = peak.fwhm {
+                line.push_str(&format!(", FWHM {:.1} nm", fwhm));
+            }
+            lines.push(line);
+        }
+        None => lines.push("No resolved Q_ext peak in range".to_string()),
+    }
+    lines
+}
+
+/// Pixel height of a caption block with `lines` lines
+pub fn caption_height(lines: usize) -> u32 {
+    2 * CAPTION_PADDING + lines as u32 * CAPTION_LINE_HEIGHT
+}
+
+/// Image size for a chart of `chart_size`, grown to fit `caption` beneath it
+pub fn image_size(chart_size: (u32, u32), caption: Option<&[String]>) -> (u32, u32) {
+    let (width, height) = chart_size;
+    match caption {
+        Some(lines) if !lines.is_empty() => (width, height + caption_height(lines.len())),
+        _ => chart_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::peaks::tests::lorentzian;
+
+    #[test]
+    fn test_caption_lists_parameters_and_peak() {
+        let state = AppState {
+            particle_radius: 40.0,
+            n_particle_real: 0.47,
+            n_particle_imag: 2.4,
+            n_medium: 1.33,
+            ..AppState::default()
+        };
+        let lines = caption_lines(&state, &lorentzian(520.0, 60.0, 300.0, 800.0));
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("Radius: 40 nm"), "{}", lines[0]);
+        assert!(lines[0].contains("n = 0.470 + 2.400i"), "{}", lines[0]);
+        assert!(lines[0].contains("Medium: n = 1.330"), "{}", lines[0]);
+        assert!(lines[1].contains("at 520.0 nm"), "{}", lines[1]);
+        assert!(lines[1].contains("FWHM 60."), "{}", lines[1]);
+    }
+
+    #[test]
+    fn test_caption_grows_image_height() {
+        let lines = caption_lines(&AppState::default(), &[]);
+        assert_eq!(image_size((1200, 800), None), (1200, 800));
+        let (width, height) = image_size((1200, 800), Some(&lines));
+        assert_eq!(width, 1200);
+        assert_eq!(height, 800 + caption_height(lines.len()));
+        assert!(height - 800 >= lines.len() as u32 * CAPTION_LINE_HEIGHT);
+    }
+}
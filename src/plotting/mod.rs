@@ -1,3 +1,15 @@
 //! Plotting functionality
 
-// Placeholder for MVP
+pub mod caption;
+pub mod overlays;
+pub mod picking;
+pub mod scaling;
+pub mod validity;
+pub mod zoom;
+
+pub use caption::caption_lines;
+pub use overlays::{legend_keys, Overlay, VISIBLE_BAND};
+pub use picking::wavelength_from_plot_x;
+pub use scaling::{auto_log_y_bounds, auto_y_bounds, log_points};
+pub use validity::{validity_spans, ValiditySpan};
+pub use zoom::{peak_zoom_window, DEFAULT_ZOOM_FWHM};
@@ -0,0 +1,86 @@
+//! Background shadings of the spectrum plot and their legend
+//!
+//! The key under the plot is generated from the set of enabled overlays, so
+//! it always lists exactly the shadings being drawn.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Visible-light band, nm
+pub const VISIBLE_BAND: (f64, f64) = (380.0, 750.0);
+
+/// A shading that can be drawn behind the spectrum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Overlay {
+    /// Visible wavelength range
+    VisibleBand,
+    /// Wavelengths where the model's approximation is marginal
+    MarginalRegion,
+    /// Wavelengths where the model's approximation is invalid
+    InvalidRegion,
+}
+
+impl Overlay {
+    pub const ALL: [Overlay; 3] = [Overlay::VisibleBand, Overlay::MarginalRegion, Overlay::InvalidRegion];
+
+    /// Fill color as unmultiplied RGBA
+    pub fn rgba(self) -> [u8; 4] {
+        match self {
+            Overlay::VisibleBand => [150, 150, 255, 18],
+            Overlay::MarginalRegion => [255, 200, 80, 25],
+            Overlay::InvalidRegion => [255, 90, 90, 30],
+        }
+    }
+
+    /// Short name as an (English, Spanish) pair
+    pub fn label(self) -> (&'static str, &'static str) {
+        match self {
+            Overlay::VisibleBand => ("Visible band", "Banda visible"),
+            Overlay::MarginalRegion => ("Marginal validity", "Validez marginal"),
+            Overlay::InvalidRegion => ("Invalid approximation", "Aproximación no válida"),
+        }
+    }
+
+    /// What the shading means, as an (English, Spanish) pair
+    pub fn meaning(self) -> (&'static str, &'static str) {
+        match self {
+            Overlay::VisibleBand => ("380–750 nm, visible to the eye", "380–750 nm, visible al ojo"),
+            Overlay::MarginalRegion => (
+                "Model approximation near its limit; check with full Mie",
+                "Aproximación del modelo cerca de su límite; verificar con Mie completo",
+            ),
+            Overlay::InvalidRegion => (
+                "Model approximation not valid; results unreliable",
+                "Aproximación del modelo no válida; resultados poco fiables",
+            ),
+        }
+    }
+}
+
+/// Legend entries for the enabled overlays, in drawing order
+pub fn legend_keys(active: &BTreeSet<Overlay>) -> Vec<Overlay> {
+    active.iter().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggling_an_overlay_changes_exactly_one_key() {
+        let mut active: BTreeSet<Overlay> = Overlay::ALL.into_iter().collect();
+        assert_eq!(legend_keys(&active), Overlay::ALL);
+
+        for overlay in Overlay::ALL {
+            let before = legend_keys(&active);
+            active.remove(&overlay);
+            let after = legend_keys(&active);
+            assert_eq!(after.len() + 1, before.len());
+            assert!(!after.contains(&overlay));
+
+            active.insert(overlay);
+            assert_eq!(legend_keys(&active), before);
+        }
+        assert!(legend_keys(&BTreeSet::new()).is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+//! Mapping plot interactions back to physical inputs
+
+/// Resolution of a picked wavelength in nm
+pub const PICK_RESOLUTION_NM: f64 = 0.1;
+
+/// Convert a clicked plot x-coordinate to a wavelength in nm
+///
+/// Returns `None` when the click is not finite or falls outside the computed
+/// range `[min, max]`. The result is snapped to [`PICK_RESOLUTION_NM`].
+pub fn wavelength_from_plot_x(plot_x: f64, min: f64, max: f64) -> Option<f64> {
+    if !plot_x.is_finite() || plot_x < min || plot_x > max {
+        return None;
+    }
+    let snapped = (plot_x / PICK_RESOLUTION_NM).round() * PICK_RESOLUTION_NM;
+    Some(snapped.clamp(min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_inside_range_maps_to_wavelength() {
+        let wl = wavelength_from_plot_x(532.04, 300.0, 800.0).unwrap();
+        assert!((wl - 532.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_click_outside_range_is_ignored() {
+        assert!(wavelength_from_plot_x(250.0, 300.0, 800.0).is_none());
+        assert!(wavelength_from_plot_x(801.0, 300.0, 800.0).is_none());
+        assert!(wavelength_from_plot_x(f64::NAN, 300.0, 800.0).is_none());
+    }
+
+    #[test]
+    fn test_snapping_respects_range_edges() {
+        let wl = wavelength_from_plot_x(799.99, 300.0, 799.99).unwrap();
+        assert!(wl <= 799.99);
+    }
+}
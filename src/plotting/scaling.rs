@@ -0,0 +1,115 @@
+//! Axis auto-scaling
+
+/// Fractional margin added above and below the data range
+const MARGIN_FRACTION: f64 = 0.1;
+
+/// Y-axis bounds for a set of values
+///
+/// Non-finite values (NaN, ±∞) are ignored. A 10% margin is added, without
+/// dipping below zero for non-negative data. All-equal data expand to a
+/// symmetric range around the value, and no finite data falls back to [0, 1].
+pub fn auto_y_bounds<I>(values: I) -> (f64, f64)
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (min, max) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+
+    if !min.is_finite() || !max.is_finite() {
+        return (0.0, 1.0);
+    }
+
+    if max == min {
+        let half = if min == 0.0 { 0.5 } else { min.abs() * MARGIN_FRACTION };
+        return (min - half, max + half);
+    }
+
+    let margin = (max - min) * MARGIN_FRACTION;
+    let lower = if min >= 0.0 { (min - margin).max(0.0) } else { min - margin };
+    (lower, max + margin)
+}
+
+/// Points for a log₁₀ Y axis: y replaced by log₁₀ y
+///
+/// Points with non-positive or non-finite y have no logarithm and are
+/// skipped, so a line joins the neighbouring positive points across them.
+pub fn log_points<I>(points: I) -> Vec<[f64; 2]>
+where
+    I: IntoIterator<Item = [f64; 2]>,
+{
+    points
+        .into_iter()
+        .filter(|[_, y]| y.is_finite() && *y > 0.0)
+        .map(|[x, y]| [x, y.log10()])
+        .collect()
+}
+
+/// [`auto_y_bounds`] in log₁₀ space
+///
+/// Bounds are in decades. Non-positive values are ignored; a single
+/// distinct value is bracketed by half a decade either side.
+pub fn auto_log_y_bounds<I>(values: I) -> (f64, f64)
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (min, max) = values
+        .into_iter()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .map(f64::log10)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+
+    if !min.is_finite() || !max.is_finite() {
+        return (-1.0, 1.0);
+    }
+    if max == min {
+        return (min - 0.5, max + 0.5);
+    }
+    let margin = (max - min) * MARGIN_FRACTION;
+    (min - margin, max + margin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_zero_expands_symmetrically() {
+        let (lo, hi) = auto_y_bounds(vec![0.0; 10]);
+        assert!(lo < 0.0 && hi > 0.0);
+        assert_eq!(lo, -hi);
+    }
+
+    #[test]
+    fn test_all_equal_nonzero_brackets_value() {
+        let (lo, hi) = auto_y_bounds(vec![2.0; 5]);
+        assert!(lo < 2.0 && hi > 2.0);
+        assert!(((2.0 - lo) - (hi - 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_infinities_ignored() {
+        let (lo, hi) = auto_y_bounds(vec![1.0, f64::INFINITY, 3.0, f64::NEG_INFINITY, f64::NAN]);
+        assert_eq!(lo, 0.8);
+        assert!((hi - 3.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_no_finite_data_defaults() {
+        assert_eq!(auto_y_bounds(vec![f64::NAN]), (0.0, 1.0));
+        assert_eq!(auto_y_bounds(Vec::new()), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_log_space_skips_non_positive_values() {
+        let points = log_points([[400.0, 1e-3], [450.0, 0.0], [500.0, -2.0], [550.0, 10.0], [600.0, f64::NAN]]);
+        assert_eq!(points, vec![[400.0, -3.0], [550.0, 1.0]]);
+
+        // Four decades plus a 10% margin each side; zero does not drag the bound to −∞
+        let (lo, hi) = auto_log_y_bounds(vec![1e-3, 0.0, 10.0]);
+        assert!((lo + 3.4).abs() < 1e-12 && (hi - 1.4).abs() < 1e-12, "{} {}", lo, hi);
+        assert_eq!(auto_log_y_bounds(vec![1.0, 1.0]), (-0.5, 0.5));
+        assert_eq!(auto_log_y_bounds(vec![0.0, -1.0]), (-1.0, 1.0));
+    }
+}
@@ -0,0 +1,73 @@
+//! Plot regions where a model's approximations are marginal or invalid
+
+use crate::core::ValidityFlag;
+
+/// A contiguous wavelength range sharing one non-valid flag
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValiditySpan {
+    pub start: f64,
+    pub end: f64,
+    pub flag: ValidityFlag,
+}
+
+/// Group per-sample flags into spans to shade
+///
+/// Valid samples produce no span. Span edges sit halfway between samples
+/// so that adjacent regions tile the axis without gaps.
+pub fn validity_spans(wavelengths: &[f64], flags: &[ValidityFlag]) -> Vec<ValiditySpan> {
+    let n = wavelengths.len().min(flags.len());
+    let edge = |i: usize| -> f64 {
+        if i == 0 {
+            wavelengths[0]
+        } else if i == n {
+            wavelengths[n - 1]
+        } else {
+            0.5 * (wavelengths[i - 1] + wavelengths[i])
+        }
+    };
+
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let flag = flags[i];
+        let mut j = i + 1;
+        while j < n && flags[j] == flag {
+            j += 1;
+        }
+        if flag != ValidityFlag::Valid {
+            spans.push(ValiditySpan {
+                start: edge(i),
+                end: edge(j),
+                flag,
+            });
+        }
+        i = j;
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ValidityFlag::*;
+
+    #[test]
+    fn test_spans_group_runs_and_skip_valid() {
+        let wavelengths = [300.0, 310.0, 320.0, 330.0, 340.0];
+        let flags = [Invalid, Invalid, Marginal, Valid, Valid];
+        let spans = validity_spans(&wavelengths, &flags);
+        assert_eq!(
+            spans,
+            vec![
+                ValiditySpan { start: 300.0, end: 315.0, flag: Invalid },
+                ValiditySpan { start: 315.0, end: 325.0, flag: Marginal },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_valid_has_no_spans() {
+        assert!(validity_spans(&[1.0, 2.0], &[Valid, Valid]).is_empty());
+        assert!(validity_spans(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+//! Resonance auto-zoom
+
+use crate::compute::peaks::dominant_peak;
+use crate::core::OpticalResult;
+
+/// Default half-width of the zoom window, in FWHM
+pub const DEFAULT_ZOOM_FWHM: f64 = 3.0;
+
+/// X range centered on the dominant Q_ext peak, ± `fwhm_multiple` FWHM
+///
+/// Clamped to the data range. `None` when there is no resolved peak, in
+/// which case the caller should show the full range.
+pub fn peak_zoom_window(results: &[OpticalResult], fwhm_multiple: f64) -> Option<(f64, f64)> {
+    let peak = dominant_peak(results)?;
+    let half_width = fwhm_multiple * peak.fwhm?;
+    if half_width.is_nan() || half_width <= 0.0 {
+        return None;
+    }
+    let (first, last) = (results.first()?.wavelength, results.last()?.wavelength);
+    Some((
+        (peak.wavelength - half_width).max(first),
+        (peak.wavelength + half_width).min(last),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::peaks::tests::lorentzian;
+
+    #[test]
+    fn test_window_around_synthetic_peak() {
+        let results = lorentzian(550.0, 20.0, 300.0, 800.0);
+        let (lo, hi) = peak_zoom_window(&results, 3.0).unwrap();
+        assert!((lo - 490.0).abs() < 1.0, "lo = {}", lo);
+        assert!((hi - 610.0).abs() < 1.0, "hi = {}", hi);
+    }
+
+    #[test]
+    fn test_window_clamped_to_data() {
+        let results = lorentzian(330.0, 40.0, 300.0, 800.0);
+        let (lo, hi) = peak_zoom_window(&results, 3.0).unwrap();
+        assert_eq!(lo, 300.0);
+        assert!((hi - 450.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_no_peak_gives_no_window() {
+        assert!(peak_zoom_window(&lorentzian(900.0, 40.0, 300.0, 800.0), 3.0).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Side-by-side comparison of several saved projects
+
+use super::file::{Project, ProjectResult};
+use std::path::Path;
+
+/// A loaded project with its display label
+#[derive(Debug, Clone)]
+pub struct ComparedProject {
+    /// Label derived from the file name
+    pub label: String,
+    pub project: Project,
+}
+
+impl ComparedProject {
+    /// Wavelength grid as (first, last, points), if the project has a spectrum
+    pub fn grid(&self) -> Option<(f64, f64, usize)> {
+        let first = self.project.spectrum.first()?.wavelength;
+        let last = self.project.spectrum.last()?.wavelength;
+        Some((first, last, self.project.spectrum.len()))
+    }
+
+    /// Q_ext spectrum as plot points
+    pub fn q_ext_points(&self) -> Vec<[f64; 2]> {
+        self.project
+            .spectrum
+            .iter()
+            .map(|r| [r.wavelength, r.q_ext])
+            .collect()
+    }
+}
+
+/// Collection of projects being compared
+#[derive(Debug, Clone, Default)]
+pub struct ProjectComparison {
+    pub projects: Vec<ComparedProject>,
+}
+
+impl ProjectComparison {
+    /// Load a project file and add it under a label taken from its file stem
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> ProjectResult<()> {
+        let path = path.as_ref();
+        self.push(path, Project::load(path)?);
+        Ok(())
+    }
+
+    /// Like [`add_file`](Self::add_file), but accept a newer project format
+    pub fn add_file_best_effort(&mut self, path: impl AsRef<Path>) -> ProjectResult<()> {
+        let path = path.as_ref();
+        self.push(path, Project::load_best_effort(path)?);
+        Ok(())
+    }
+
+    fn push(&mut self, path: &Path, project: Project) {
+        let label = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("Project {}", self.projects.len() + 1));
+        self.projects.push(ComparedProject { label, project });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.projects.len() {
+            self.projects.remove(index);
+        }
+    }
+
+    /// True when all projects with spectra share the same wavelength grid
+    pub fn grids_match(&self) -> bool {
+        let mut grids = self.projects.iter().filter_map(|p| {
+            let wl: Vec<f64> = p.project.spectrum.iter().map(|r| r.wavelength).collect();
+            (!wl.is_empty()).then_some(wl)
+        });
+        match grids.next() {
+            Some(first) => grids.all(|g| g == first),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppState;
+    use crate::core::OpticalResult;
+
+    fn spectrum(step: usize, q: f64) -> Vec<OpticalResult> {
+        (400..=600)
+            .step_by(step)
+            .map(|wl| OpticalResult::for_test(wl as f64, q, 0.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_load_two_projects_with_labels() {
+        let dir = std::env::temp_dir().join(format!("nanocalc_compare_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let gold = dir.join("gold_50nm.ncp");
+        let silver = dir.join("silver_40nm.ncp");
+        Project::new(AppState::default(), spectrum(5, 1.0)).save(&gold).unwrap();
+        Project::new(AppState::default(), spectrum(10, 2.0)).save(&silver).unwrap();
+
+        let mut comparison = ProjectComparison::default();
+        comparison.add_file(&gold).unwrap();
+        comparison.add_file(&silver).unwrap();
+
+        let labels: Vec<&str> = comparison.projects.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, ["gold_50nm", "silver_40nm"]);
+        assert_eq!(comparison.projects[0].q_ext_points().len(), 41);
+        assert_eq!(comparison.projects[1].q_ext_points()[0], [400.0, 2.0]);
+        assert!(!comparison.grids_match());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_missing_file_is_error() {
+        let mut comparison = ProjectComparison::default();
+        assert!(comparison.add_file("/nonexistent/project.ncp").is_err());
+        assert!(comparison.projects.is_empty());
+    }
+}
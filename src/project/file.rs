@@ -0,0 +1,338 @@
+//! NanoCalc project files (`.ncp`)
+//!
+//! A project is the input state plus the computed spectrum, stored as
+//! pretty-printed JSON. The format version is read before the rest of the
+//! file: older files are migrated, newer ones are refused unless the caller
+//! asks for a best-effort load.
+
+use crate::app::AppState;
+use crate::core::{OpticalModelKind, OpticalResult, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+
+/// File extension for project files
+pub const PROJECT_EXTENSION: &str = "ncp";
+
+/// Current project format version
+///
+/// - v1: first versioned format
+/// - v2: the state carries the spectrum range (`wl_min`, `wl_max`)
+/// - v3: the state names the optical model (`model_kind`)
+pub const PROJECT_FORMAT_VERSION: u32 = 3;
+
+/// Format version stored in a project file
+///
+/// Version 0 stands for files written before the version field existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SchemaVersion(pub u32);
+
+impl SchemaVersion {
+    /// The version this build writes
+    pub const CURRENT: SchemaVersion = SchemaVersion(PROJECT_FORMAT_VERSION);
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// Project file errors
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid project file: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Project format {found} is newer than this version of NanoCalc supports ({supported})")]
+    NewerVersion {
+        found: SchemaVersion,
+        supported: SchemaVersion,
+    },
+
+    #[error("Invalid project file: {0}")]
+    Validation(#[from] ValidationError),
+}
+
+pub type ProjectResult<T> = Result<T, ProjectError>;
+
+/// The only field read by [`inspect_version`]
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    format_version: u32,
+}
+
+/// Format version of project JSON, without parsing the rest
+pub fn inspect_version_str(text: &str) -> ProjectResult<SchemaVersion> {
+    let header: Header = serde_json::from_str(text)?;
+    Ok(SchemaVersion(header.format_version))
+}
+
+/// Format version of the project file at `path`, without parsing the rest
+pub fn inspect_version(path: impl AsRef<Path>) -> ProjectResult<SchemaVersion> {
+    inspect_version_str(&std::fs::read_to_string(path)?)
+}
+
+/// Upgrade project JSON to the current format, one version at a time
+///
+/// The starting version is read from `format_version` (missing means v0).
+/// Each step fills in what its version added, so the result deserializes
+/// into the live [`AppState`]; fields added without a version bump take
+/// their serde defaults. Newer documents are refused, and a version with
+/// no registered step is a [`ValidationError`].
+pub fn migrate(mut value: Value) -> ProjectResult<Value> {
+    let from = SchemaVersion(Header::deserialize(&value)?.format_version);
+    if from > SchemaVersion::CURRENT {
+        return Err(ProjectError::NewerVersion {
+            found: from,
+            supported: SchemaVersion::CURRENT,
+        });
+    }
+    for version in from.0..PROJECT_FORMAT_VERSION {
+        match version {
+            // Unversioned files only lack the version field
+            0 => {}
+            1 => {
+                let defaults = AppState::default();
+                if let Some(state) = value.get_mut("state").and_then(Value::as_object_mut) {
+                    state.entry("wl_min").or_insert(Value::from(defaults.wl_min));
+                    state.entry("wl_max").or_insert(Value::from(defaults.wl_max));
+                }
+            }
+            // Projects saved before the model selector used Mie
+            2 => {
+                if let Some(state) = value.get_mut("state").and_then(Value::as_object_mut) {
+                    state.entry("model_kind").or_insert(serde_json::to_value(OpticalModelKind::Mie)?);
+                }
+            }
+            _ => {
+                return Err(ValidationError::InvalidParameter(format!(
+                    "no migration registered from project format {}",
+                    SchemaVersion(version)
+                ))
+                .into())
+            }
+        }
+        value["format_version"] = Value::from(version + 1);
+    }
+    Ok(value)
+}
+
+/// Saved inputs and results of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub format_version: u32,
+    pub state: AppState,
+    pub spectrum: Vec<OpticalResult>,
+}
+
+impl Project {
+    pub fn new(state: AppState, spectrum: Vec<OpticalResult>) -> Self {
+        Self {
+            format_version: PROJECT_FORMAT_VERSION,
+            state,
+            spectrum,
+        }
+    }
+
+    pub fn to_json(&self) -> ProjectResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse project JSON, migrating older formats and refusing newer ones
+    pub fn from_json(text: &str) -> ProjectResult<Self> {
+        Ok(serde_json::from_value(migrate(serde_json::from_str(text)?)?)?)
+    }
+
+    /// Parse project JSON of any version, keeping the fields this build knows
+    ///
+    /// Fields added by a newer format are dropped; the load fails only if a
+    /// field this build requires is missing or has changed type.
+    pub fn from_json_best_effort(text: &str) -> ProjectResult<Self> {
+        let mut value: Value = serde_json::from_str(text)?;
+        value["format_version"] = Value::from(PROJECT_FORMAT_VERSION);
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ProjectResult<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> ProjectResult<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn load_best_effort(path: impl AsRef<Path>) -> ProjectResult<Self> {
+        Self::from_json_best_effort(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Save `state` and `spectrum` as a project at `path`
+pub fn save(path: impl AsRef<Path>, state: &AppState, spectrum: &[OpticalResult]) -> ProjectResult<()> {
+    Project::new(state.clone(), spectrum.to_vec()).save(path)
+}
+
+/// Inputs and spectrum of the project at `path`, see [`Project::load`]
+pub fn load(path: impl AsRef<Path>) -> ProjectResult<(AppState, Vec<OpticalResult>)> {
+    let project = Project::load(path)?;
+    Ok((project.state, project.spectrum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let state = AppState {
+            particle_radius: 42.0,
+            ..Default::default()
+        };
+        let project = Project::new(state, Vec::new());
+
+        let loaded = Project::from_json(&project.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.format_version, PROJECT_FORMAT_VERSION);
+        assert_eq!(loaded.state.particle_radius, 42.0);
+    }
+
+    fn with_version(version: Option<u32>) -> String {
+        let mut value = serde_json::to_value(Project::new(AppState::default(), Vec::new())).unwrap();
+        match version {
+            Some(v) => value["format_version"] = Value::from(v),
+            None => {
+                value.as_object_mut().unwrap().remove("format_version");
+            }
+        }
+        value.to_string()
+    }
+
+    #[test]
+    fn test_older_version_is_migrated() {
+        let text = with_version(None);
+        assert_eq!(inspect_version_str(&text).unwrap(), SchemaVersion(0));
+        let project = Project::from_json(&text).unwrap();
+        assert_eq!(project.format_version, PROJECT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_current_version_loads() {
+        let text = with_version(Some(PROJECT_FORMAT_VERSION));
+        assert_eq!(inspect_version_str(&text).unwrap(), SchemaVersion::CURRENT);
+        assert!(Project::from_json(&text).is_ok());
+    }
+
+    #[test]
+    fn test_newer_version_needs_best_effort_load() {
+        let newer = SchemaVersion(PROJECT_FORMAT_VERSION + 1);
+        let mut value: Value = serde_json::from_str(&with_version(Some(newer.0))).unwrap();
+        value["added_in_future"] = Value::from("ignored");
+        let text = value.to_string();
+
+        assert_eq!(inspect_version_str(&text).unwrap(), newer);
+        match Project::from_json(&text) {
+            Err(ProjectError::NewerVersion { found, supported }) => {
+                assert_eq!(found, newer);
+                assert_eq!(supported, SchemaVersion::CURRENT);
+            }
+            other => panic!("expected a version error, got {:?}", other.map(|p| p.format_version)),
+        }
+        let project = Project::from_json_best_effort(&text).unwrap();
+        assert_eq!(project.format_version, PROJECT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_invalid_json_is_parse_error() {
+        assert!(matches!(Project::from_json("not json"), Err(ProjectError::Parse(_))));
+    }
+
+    #[test]
+    fn test_session_survives_save_and_load() {
+        use crate::core::{OpticalModel, RefractiveIndex};
+        use crate::physics::optical::mie::MieModel;
+
+        let state = AppState {
+            particle_radius: 35.0,
+            n_medium: 1.5,
+            wl_min: 400.0,
+            wl_max: 1100.0,
+            dispersive_spectrum: true,
+            ..Default::default()
+        };
+        let spectrum = MieModel::new(35.0, 500.0, RefractiveIndex::new(0.2, 3.1), 1.5)
+            .calculate_spectrum(&[400.0, 750.0, 1100.0])
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("nanocalc_session_{}.{}", std::process::id(), PROJECT_EXTENSION));
+
+        save(&path, &state, &spectrum).unwrap();
+        let (loaded_state, loaded_spectrum) = load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            serde_json::to_value(&loaded_state).unwrap(),
+            serde_json::to_value(&state).unwrap()
+        );
+        assert_eq!(loaded_spectrum.len(), spectrum.len());
+        // serde_json may round the last bit of a float
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-15 * b.abs();
+        for (a, b) in loaded_spectrum.iter().zip(&spectrum) {
+            assert_eq!(a.wavelength, b.wavelength);
+            assert!(close(a.q_ext, b.q_ext) && close(a.c_abs, b.c_abs) && close(a.g, b.g));
+        }
+    }
+
+    #[test]
+    fn test_unknown_version_error_names_both_versions() {
+        let path = std::env::temp_dir().join(format!("nanocalc_future_{}.{}", std::process::id(), PROJECT_EXTENSION));
+        std::fs::write(&path, with_version(Some(99))).unwrap();
+        let error = load(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+
+        let message = error.to_string();
+        assert!(message.contains("v99") && message.contains(&SchemaVersion::CURRENT.to_string()), "{}", message);
+    }
+
+    const V1_FIXTURE: &str = include_str!("../../tests/fixtures/project_v1.ncp");
+    const V2_FIXTURE: &str = include_str!("../../tests/fixtures/project_v2.ncp");
+
+    #[test]
+    fn test_v1_file_migrates_to_a_complete_current_state() {
+        let v1: Value = serde_json::from_str(V1_FIXTURE).unwrap();
+        assert_eq!(inspect_version_str(V1_FIXTURE).unwrap(), SchemaVersion(1));
+        // A v1 file holds the particle, λ, medium and thread count only
+        assert_eq!(v1["state"].as_object().unwrap().len(), 6);
+
+        let migrated = migrate(v1).unwrap();
+        assert_eq!(migrated["format_version"], Value::from(PROJECT_FORMAT_VERSION));
+        assert!(migrated["state"].get("wl_min").is_some() && migrated["state"].get("model_kind").is_some());
+
+        // Fields added since take their defaults
+        let project = Project::from_json(V1_FIXTURE).unwrap();
+        let defaults = AppState::default();
+        assert_eq!(project.format_version, PROJECT_FORMAT_VERSION);
+        assert_eq!(project.state.spectrum_range(), defaults.spectrum_range());
+        assert_eq!(project.state.temperature, defaults.temperature);
+        assert_eq!(project.state.history_limit, defaults.history_limit);
+        assert_eq!(project.state.particle_radius, 25.0);
+        assert_eq!(project.spectrum.len(), 3);
+        assert_eq!(project.spectrum[0].g, 0.0);
+    }
+
+    #[test]
+    fn test_v2_file_only_gains_the_model_kind() {
+        let v2: Value = serde_json::from_str(V2_FIXTURE).unwrap();
+        let mut expected = v2.clone();
+        expected["format_version"] = Value::from(3);
+        expected["state"]["model_kind"] = Value::from("Mie");
+        assert_eq!(migrate(v2).unwrap(), expected);
+        let project = Project::from_json(V2_FIXTURE).unwrap();
+        assert_eq!(project.state.spectrum_range(), (400.0, 1000.0));
+        assert_eq!(project.spectrum[1].wavelength, 520.0);
+    }
+}
@@ -1,3 +1,7 @@
 //! Project management
 
-// Placeholder for MVP
+pub mod compare;
+pub mod file;
+
+pub use compare::{ComparedProject, ProjectComparison};
+pub use file::{inspect_version, load, migrate, save, Project, ProjectError, SchemaVersion, PROJECT_EXTENSION};
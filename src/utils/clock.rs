@@ -0,0 +1,67 @@
+//! Injectable wall clock
+//!
+//! Timestamped features (log entries, run history, export provenance) read
+//! the time through a [`Clock`] so tests can pin it with [`FixedClock`].
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of wall-clock time
+pub trait Clock: Send + Sync {
+    /// Seconds since the Unix epoch
+    fn now_unix_secs(&self) -> u64;
+}
+
+/// The operating-system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    /// A clock set before 1970 reads as the epoch instead of panicking
+    fn now_unix_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+/// A clock stopped at a given Unix time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now_unix_secs(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Time of day of a Unix timestamp as HH:MM:SS (UTC)
+pub fn time_of_day(unix_secs: u64) -> String {
+    let secs = unix_secs % 86400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+}
+
+/// `message` prefixed with the current time as `[HH:MM:SS]`
+pub fn timestamped(clock: &dyn Clock, message: &str) -> String {
+    format!("[{}] {}", time_of_day(clock.now_unix_secs()), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_gives_deterministic_log_entries() {
+        // 2024-01-01 13:05:09 UTC
+        let clock = FixedClock(1_704_114_309);
+        assert_eq!(timestamped(&clock, "✅ Done"), "[13:05:09] ✅ Done");
+        assert_eq!(timestamped(&clock, "✅ Done"), timestamped(&clock, "✅ Done"));
+        assert_eq!(time_of_day(0), "00:00:00");
+        assert_eq!(time_of_day(86_399), "23:59:59");
+    }
+
+    #[test]
+    fn test_system_clock_is_after_epoch() {
+        assert!(SystemClock.now_unix_secs() > 1_600_000_000);
+    }
+}
@@ -0,0 +1,68 @@
+//! Unit conversions between the typed quantities in [`crate::core::units`]
+//!
+//! These use the fixed values in [`crate::core::constants`]. Code that must
+//! honour scaled constants goes through a
+//! [`PhysicsContext`](crate::core::PhysicsContext) instead.
+
+use crate::core::conversions::{HC_EV_NM, NM_TO_CM, ZERO_CELSIUS_K};
+use crate::core::units::{ElectronVolt, Kelvin, Micrometer, Nanometer, Wavelength};
+use crate::core::{ValidationError, ValidationResult};
+
+/// Photon energy E = hc/λ
+pub fn wavelength_to_energy_ev(wavelength: Wavelength) -> ValidationResult<ElectronVolt> {
+    Ok(ElectronVolt(HC_EV_NM / wavelength.checked()?.0))
+}
+
+/// Vacuum wavelength λ = hc/E of a photon of energy `energy`
+pub fn energy_ev_to_wavelength(energy: ElectronVolt) -> ValidationResult<Wavelength> {
+    if !(energy.0.is_finite() && energy.0 > 0.0) {
+        return Err(ValidationError::OutOfRange {
+            value: energy.0,
+            min: 0.0,
+            max: f64::INFINITY,
+        });
+    }
+    Ok(Wavelength(HC_EV_NM / energy.0))
+}
+
+/// Spectroscopic wavenumber 1/λ in cm⁻¹
+pub fn wavelength_to_wavenumber_cm(wavelength: Wavelength) -> ValidationResult<f64> {
+    Ok(1.0 / (wavelength.checked()?.0 * NM_TO_CM))
+}
+
+pub fn nm_to_um(length: Nanometer) -> Micrometer {
+    Micrometer(length.0 * 1e-3)
+}
+
+pub fn celsius_to_kelvin(celsius: f64) -> Kelvin {
+    Kelvin(celsius + ZERO_CELSIUS_K)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_energy_matches_wavelength_conversion() {
+        for nm in [200.0, 532.0, 633.0, 1064.0, 10_600.0] {
+            let direct = wavelength_to_energy_ev(Wavelength(nm)).unwrap();
+            let typed = Wavelength(nm).to_energy_ev().unwrap();
+            assert!((direct.0 - typed.0).abs() < 1e-12 * typed.0, "{} nm", nm);
+
+            let back = energy_ev_to_wavelength(direct).unwrap();
+            assert!((back.0 - nm).abs() < 1e-9 * nm);
+        }
+        assert!(wavelength_to_energy_ev(Wavelength(0.0)).is_err());
+        assert!(energy_ev_to_wavelength(ElectronVolt(0.0)).is_err());
+        assert!(energy_ev_to_wavelength(ElectronVolt(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_lengths_and_temperatures() {
+        assert!((wavelength_to_wavenumber_cm(Wavelength(500.0)).unwrap() - 20_000.0).abs() < 1e-9);
+        assert!(wavelength_to_wavenumber_cm(Wavelength(-1.0)).is_err());
+        assert_eq!(nm_to_um(Nanometer(1550.0)), Micrometer(1.55));
+        assert_eq!(celsius_to_kelvin(25.0), Kelvin(298.15));
+        assert!((celsius_to_kelvin(-40.0).to_celsius() + 40.0).abs() < 1e-12);
+    }
+}
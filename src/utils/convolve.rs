@@ -0,0 +1,118 @@
+//! Instrument-response broadening
+//!
+//! A spectrometer of finite resolution records the true spectrum convolved
+//! with its line shape. Broadening a computed curve with the same response
+//! makes it directly comparable to measured data.
+
+use super::interp::{self, Extrapolation};
+
+/// FWHM of a Gaussian in units of its standard deviation, 2√(2 ln 2)
+const FWHM_PER_SIGMA: f64 = 2.354_820_045_030_949;
+/// Kernel half-width in standard deviations
+const KERNEL_HALF_WIDTH_SIGMAS: f64 = 4.0;
+/// Resampling steps per FWHM
+const STEPS_PER_FWHM: f64 = 8.0;
+/// Upper bound on the resampled grid size
+const MAX_RESAMPLED_POINTS: usize = 20_000;
+
+/// Convolve the curve (`xs`, `ys`) with a unit-area Gaussian of FWHM `fwhm`
+///
+/// `xs` must be increasing but need not be evenly spaced: the curve is
+/// linearly resampled onto a uniform grid, convolved there and interpolated
+/// back to `xs`. Near the ends the kernel is renormalized over the part that
+/// overlaps the data, so edges do not droop. A non-positive FWHM returns `ys`
+/// unchanged.
+pub fn gaussian_broaden(xs: &[f64], ys: &[f64], fwhm: f64) -> Vec<f64> {
+    let n = xs.len();
+    if fwhm.is_nan() || fwhm <= 0.0 || n < 2 || ys.len() != n {
+        return ys.to_vec();
+    }
+    let (first, last) = (xs[0], xs[n - 1]);
+    let finest = xs.windows(2).map(|w| w[1] - w[0]).fold(f64::INFINITY, f64::min);
+    let step = finest
+        .min(fwhm / STEPS_PER_FWHM)
+        .max((last - first) / (MAX_RESAMPLED_POINTS - 1) as f64);
+    let count = ((last - first) / step).ceil() as usize + 1;
+    let step = (last - first) / (count - 1) as f64;
+
+    let grid: Vec<f64> = (0..count).map(|i| first + i as f64 * step).collect();
+    let resampled: Vec<f64> = grid.iter().map(|&x| linear(xs, ys, x)).collect();
+
+    let sigma = fwhm / FWHM_PER_SIGMA;
+    let half = (KERNEL_HALF_WIDTH_SIGMAS * sigma / step).ceil() as usize;
+    let kernel: Vec<f64> = (0..=half)
+        .map(|k| (-0.5 * (k as f64 * step / sigma).powi(2)).exp())
+        .collect();
+
+    let broadened: Vec<f64> = (0..count)
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = (i + half).min(count - 1);
+            let (sum, weight) = (lo..=hi).fold((0.0, 0.0), |(sum, weight), j| {
+                let w = kernel[i.abs_diff(j)];
+                (sum + w * resampled[j], weight + w)
+            });
+            sum / weight
+        })
+        .collect();
+
+    xs.iter().map(|&x| linear(&grid, &broadened, x)).collect()
+}
+
+/// Linear interpolation of (`xs`, `ys`) at `x`, clamped to the end values
+fn linear(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    interp::linear(xs, ys, x, Extrapolation::Clamp).unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trapezoid(xs: &[f64], ys: &[f64]) -> f64 {
+        xs.windows(2).zip(ys.windows(2)).map(|(x, y)| 0.5 * (x[1] - x[0]) * (y[0] + y[1])).sum()
+    }
+
+    fn fwhm_of(xs: &[f64], ys: &[f64]) -> f64 {
+        let peak = ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let above: Vec<f64> = xs.iter().zip(ys).filter(|(_, &y)| y >= 0.5 * peak).map(|(&x, _)| x).collect();
+        above[above.len() - 1] - above[0]
+    }
+
+    /// Non-uniform grid: 1 nm steps near 500 nm, 5 nm steps elsewhere
+    fn grid() -> Vec<f64> {
+        let mut xs: Vec<f64> = (60..=90).map(|i| i as f64 * 5.0).collect();
+        xs.extend((451..550).map(|i| i as f64));
+        xs.extend((110..=160).map(|i| i as f64 * 5.0));
+        xs.sort_by(f64::total_cmp);
+        xs
+    }
+
+    #[test]
+    fn test_zero_fwhm_is_identity() {
+        let xs = grid();
+        let ys: Vec<f64> = xs.iter().map(|x| (x / 37.0).sin()).collect();
+        assert_eq!(gaussian_broaden(&xs, &ys, 0.0), ys);
+    }
+
+    #[test]
+    fn test_broadening_conserves_integral_and_widens() {
+        let xs = grid();
+        let ys: Vec<f64> = xs.iter().map(|x| (-0.5 * ((x - 500.0) / 8.0).powi(2)).exp()).collect();
+        let broadened = gaussian_broaden(&xs, &ys, 30.0);
+
+        let (before, after) = (trapezoid(&xs, &ys), trapezoid(&xs, &broadened));
+        assert!((after - before).abs() < 0.01 * before, "{} vs {}", before, after);
+
+        // Gaussian widths add in quadrature: √(18.8² + 30²) ≈ 35.4 nm
+        let expected = (8.0f64 * FWHM_PER_SIGMA).hypot(30.0);
+        let measured = fwhm_of(&xs, &broadened);
+        assert!((measured - expected).abs() < 2.0, "FWHM {} vs {}", measured, expected);
+    }
+
+    #[test]
+    fn test_constant_curve_is_unchanged() {
+        let xs = grid();
+        let broadened = gaussian_broaden(&xs, &vec![2.0; xs.len()], 50.0);
+        assert!(broadened.iter().all(|y| (y - 2.0).abs() < 1e-12));
+    }
+}
@@ -0,0 +1,342 @@
+//! Small arithmetic expression evaluator
+//!
+//! Parses formulas in one variable, e.g. `1.5 + 0.01/(lambda/1000)^2`, into a
+//! tree that can be evaluated repeatedly. Supports `+ - * / ^`, parentheses,
+//! unary minus, scientific-notation numbers, the constant `pi` and the
+//! functions `sqrt`, `exp`, `ln`, `log10`, `abs`, `sin` and `cos`.
+
+use std::fmt;
+
+/// Expression parse errors, with byte offsets into the source
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ParseError {
+    #[error("Empty expression")]
+    Empty,
+
+    #[error("Unexpected character '{0}' at position {1}")]
+    UnexpectedChar(char, usize),
+
+    #[error("Unexpected end of expression")]
+    UnexpectedEnd,
+
+    #[error("Invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("Unknown identifier '{0}'")]
+    UnknownIdentifier(String),
+
+    #[error("Unknown function '{0}'")]
+    UnknownFunction(String),
+}
+
+/// Built-in single-argument functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Sqrt,
+    Exp,
+    Ln,
+    Log10,
+    Abs,
+    Sin,
+    Cos,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sqrt" => Function::Sqrt,
+            "exp" => Function::Exp,
+            "ln" => Function::Ln,
+            "log10" => Function::Log10,
+            "abs" => Function::Abs,
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, v: f64) -> f64 {
+        match self {
+            Function::Sqrt => v.sqrt(),
+            Function::Exp => v.exp(),
+            Function::Ln => v.ln(),
+            Function::Log10 => v.log10(),
+            Function::Abs => v.abs(),
+            Function::Sin => v.sin(),
+            Function::Cos => v.cos(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Variable,
+    Neg(Box<Node>),
+    Add(Box<Node>, Box<Node>),
+    Sub(Box<Node>, Box<Node>),
+    Mul(Box<Node>, Box<Node>),
+    Div(Box<Node>, Box<Node>),
+    Pow(Box<Node>, Box<Node>),
+    Call(Function, Box<Node>),
+}
+
+impl Node {
+    fn eval(&self, x: f64) -> f64 {
+        match self {
+            Node::Number(v) => *v,
+            Node::Variable => x,
+            Node::Neg(a) => -a.eval(x),
+            Node::Add(a, b) => a.eval(x) + b.eval(x),
+            Node::Sub(a, b) => a.eval(x) - b.eval(x),
+            Node::Mul(a, b) => a.eval(x) * b.eval(x),
+            Node::Div(a, b) => a.eval(x) / b.eval(x),
+            Node::Pow(a, b) => a.eval(x).powf(b.eval(x)),
+            Node::Call(f, a) => f.apply(a.eval(x)),
+        }
+    }
+}
+
+/// A parsed expression in a single variable
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    source: String,
+    root: Node,
+}
+
+impl Expr {
+    /// Parse `source`, accepting any of `variables` as the free variable
+    pub fn parse(source: &str, variables: &[&str]) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            src: source,
+            pos: 0,
+            variables,
+        };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Err(ParseError::Empty);
+        }
+        let root = parser.expression()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(ParseError::UnexpectedChar(c, parser.pos));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            root,
+        })
+    }
+
+    /// Evaluate with the variable set to `x`
+    pub fn eval(&self, x: f64) -> f64 {
+        self.root.eval(x)
+    }
+
+    /// The trimmed source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Recursive-descent parser
+///
+/// expr  := term (('+' | '-') term)*
+/// term  := unary (('*' | '/') unary)*
+/// unary := ('-' | '+') unary | power
+/// power := atom ('^' unary)?
+/// atom  := number | ident | ident '(' expr ')' | '(' expr ')'
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    variables: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Consume `c` after optional whitespace, if it is next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, ParseError> {
+        let mut lhs = self.term()?;
+        loop {
+            if self.eat('+') {
+                lhs = Node::Add(Box::new(lhs), Box::new(self.term()?));
+            } else if self.eat('-') {
+                lhs = Node::Sub(Box::new(lhs), Box::new(self.term()?));
+            } else {
+                return Ok(lhs);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, ParseError> {
+        let mut lhs = self.unary()?;
+        loop {
+            if self.eat('*') {
+                lhs = Node::Mul(Box::new(lhs), Box::new(self.unary()?));
+            } else if self.eat('/') {
+                lhs = Node::Div(Box::new(lhs), Box::new(self.unary()?));
+            } else {
+                return Ok(lhs);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ParseError> {
+        if self.eat('-') {
+            Ok(Node::Neg(Box::new(self.unary()?)))
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Node, ParseError> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            // Right-associative, and binds tighter than a leading minus
+            Ok(Node::Pow(Box::new(base), Box::new(self.unary()?)))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn atom(&mut self) -> Result<Node, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(ParseError::UnexpectedEnd),
+            Some('(') => {
+                self.bump();
+                let inner = self.expression()?;
+                self.expect_close()?;
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.identifier(),
+            Some(c) => Err(ParseError::UnexpectedChar(c, self.pos)),
+        }
+    }
+
+    fn expect_close(&mut self) -> Result<(), ParseError> {
+        if self.eat(')') {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(c) => Err(ParseError::UnexpectedChar(c, self.pos)),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.bump();
+        }
+        // Exponent, e.g. 1.2e-3
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let rest = &self.src[self.pos + 1..];
+            let signed = rest.starts_with(['+', '-']);
+            let digits = if signed { &rest[1..] } else { rest };
+            if digits.starts_with(|c: char| c.is_ascii_digit()) {
+                self.bump();
+                if signed {
+                    self.bump();
+                }
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.bump();
+                }
+            }
+        }
+        let text = &self.src[start..self.pos];
+        text.parse()
+            .map(Node::Number)
+            .map_err(|_| ParseError::InvalidNumber(text.to_string()))
+    }
+
+    fn identifier(&mut self) -> Result<Node, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.bump();
+        }
+        let name = &self.src[start..self.pos];
+
+        if self.eat('(') {
+            let function = Function::from_name(name)
+                .ok_or_else(|| ParseError::UnknownFunction(name.to_string()))?;
+            let arg = self.expression()?;
+            self.expect_close()?;
+            return Ok(Node::Call(function, Box::new(arg)));
+        }
+        if self.variables.contains(&name) {
+            return Ok(Node::Variable);
+        }
+        if name == "pi" {
+            return Ok(Node::Number(std::f64::consts::PI));
+        }
+        Err(ParseError::UnknownIdentifier(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(src: &str, x: f64) -> f64 {
+        Expr::parse(src, &["x"]).unwrap().eval(x)
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(eval("1 + 2 * 3", 0.0), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", 0.0), 9.0);
+        assert_eq!(eval("2 ^ 3 ^ 2", 0.0), 512.0);
+        assert_eq!(eval("-x^2", 3.0), -9.0);
+        assert_eq!(eval("2^-1", 0.0), 0.5);
+        assert_eq!(eval("10 - 4 - 3", 0.0), 3.0);
+        assert_eq!(eval("1.5e3 / x", 3.0), 500.0);
+        assert!((eval("sqrt(x) + cos(pi)", 4.0) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_malformed_expressions_are_rejected() {
+        let parse = |s| Expr::parse(s, &["x"]);
+        assert_eq!(parse("   "), Err(ParseError::Empty));
+        assert_eq!(parse("1 +"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("(1 + x"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("1 + y"), Err(ParseError::UnknownIdentifier("y".to_string())));
+        assert_eq!(parse("foo(x)"), Err(ParseError::UnknownFunction("foo".to_string())));
+        assert_eq!(parse("1..2"), Err(ParseError::InvalidNumber("1..2".to_string())));
+        assert_eq!(parse("2 x"), Err(ParseError::UnexpectedChar('x', 2)));
+        assert_eq!(parse("1 # 2"), Err(ParseError::UnexpectedChar('#', 2)));
+    }
+}
@@ -0,0 +1,227 @@
+//! Spectral feature detection
+//!
+//! Finds peaks (local maxima), dips (local minima) and shoulders in a
+//! sampled curve. A shoulder is a point on a flank where the slope nearly
+//! flattens without changing sign, bracketed by the steeper slope on either
+//! side — the signature of a feature hidden under a stronger neighbour.
+
+use serde::{Deserialize, Serialize};
+
+/// Classification of a [`SpectralFeature`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FeatureKind {
+    Peak,
+    Dip,
+    Shoulder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpectralFeature {
+    pub kind: FeatureKind,
+    /// Index of the sample at the feature
+    pub index: usize,
+    pub x: f64,
+    pub y: f64,
+    /// Height above (or depth below) the surrounding curve, in y units
+    ///
+    /// Topographic prominence for peaks and dips; for shoulders, the smaller
+    /// rise or fall to the steepest points on either side.
+    pub prominence: f64,
+    /// Width in x units: at half prominence for peaks and dips, between the
+    /// bracketing steepest points for shoulders
+    pub width: f64,
+}
+
+/// Detection thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeatureConfig {
+    /// Minimum prominence as a fraction of the curve's y range
+    pub min_prominence: f64,
+    /// A shoulder's slope must fall below this fraction of the flank slope
+    pub shoulder_tolerance: f64,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            min_prominence: 0.05,
+            shoulder_tolerance: 0.2,
+        }
+    }
+}
+
+/// Features of the curve (`xs`, `ys`) with the default thresholds
+pub fn detect(xs: &[f64], ys: &[f64]) -> Vec<SpectralFeature> {
+    detect_with(xs, ys, &FeatureConfig::default())
+}
+
+/// Features of the curve (`xs`, `ys`), ordered by x
+///
+/// `xs` must be increasing. Curves shorter than three points, or with
+/// mismatched lengths, have no features.
+pub fn detect_with(xs: &[f64], ys: &[f64], config: &FeatureConfig) -> Vec<SpectralFeature> {
+    let n = xs.len();
+    if n < 3 || ys.len() != n {
+        return Vec::new();
+    }
+    let (lo, hi) = ys.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &y| (lo.min(y), hi.max(y)));
+    let threshold = config.min_prominence * (hi - lo);
+
+    let mut features = Vec::new();
+    let negated: Vec<f64> = ys.iter().map(|y| -y).collect();
+    for (kind, curve) in [(FeatureKind::Peak, ys), (FeatureKind::Dip, &negated[..])] {
+        for i in 1..n - 1 {
+            if curve[i] > curve[i - 1] && curve[i] >= curve[i + 1] {
+                let (prominence, width) = prominence_and_width(xs, curve, i);
+                if prominence >= threshold {
+                    features.push(SpectralFeature { kind, index: i, x: xs[i], y: ys[i], prominence, width });
+                }
+            }
+        }
+    }
+
+    let slope = slopes(xs, ys);
+    for i in 1..n - 1 {
+        let flattening = slope[i - 1] * slope[i] > 0.0
+            && slope[i] * slope[i + 1] > 0.0
+            && slope[i].abs() < slope[i - 1].abs()
+            && slope[i].abs() <= slope[i + 1].abs();
+        if !flattening {
+            continue;
+        }
+        let steeper = |j: usize, next: usize| slope[next].abs() > slope[j].abs() && slope[next] * slope[i] > 0.0;
+        let mut l = i;
+        while l > 0 && steeper(l, l - 1) {
+            l -= 1;
+        }
+        let mut r = i;
+        while r + 1 < n && steeper(r, r + 1) {
+            r += 1;
+        }
+        // A flank running into the end of the data is not bracketed
+        if l == 0 || r == n - 1 {
+            continue;
+        }
+        let flank = slope[l].abs().min(slope[r].abs());
+        let prominence = (ys[i] - ys[l]).abs().min((ys[i] - ys[r]).abs());
+        if slope[i].abs() <= config.shoulder_tolerance * flank && prominence >= threshold {
+            features.push(SpectralFeature {
+                kind: FeatureKind::Shoulder,
+                index: i,
+                x: xs[i],
+                y: ys[i],
+                prominence,
+                width: xs[r] - xs[l],
+            });
+        }
+    }
+
+    features.sort_by_key(|f| f.index);
+    features
+}
+
+/// Central-difference slope, one-sided at the ends
+fn slopes(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (i.saturating_sub(1), (i + 1).min(n - 1));
+            (ys[b] - ys[a]) / (xs[b] - xs[a])
+        })
+        .collect()
+}
+
+/// Topographic prominence of the maximum at `i` and its width at half prominence
+fn prominence_and_width(xs: &[f64], ys: &[f64], i: usize) -> (f64, f64) {
+    let peak = ys[i];
+    // Lowest point before the curve rises above the peak, on each side
+    let left = ys[..i].iter().rev().take_while(|&&y| y <= peak).fold(peak, |m, &y| m.min(y));
+    let right = ys[i + 1..].iter().take_while(|&&y| y <= peak).fold(peak, |m, &y| m.min(y));
+    let prominence = peak - left.max(right);
+
+    // Both sides dip below `level` before rising above the peak, so the
+    // crossings always exist
+    let level = peak - 0.5 * prominence;
+    let crossing = |forward| level_crossing(xs, ys, i, level, forward).unwrap_or(xs[i]);
+    (prominence, crossing(true) - crossing(false))
+}
+
+/// x where the curve falls to `level` walking away from the maximum at `start`
+///
+/// Interpolates linearly between the bracketing samples. `None` if the walk
+/// reaches the end of the data, or the curve rises above the maximum, first.
+pub(crate) fn level_crossing(xs: &[f64], ys: &[f64], start: usize, level: f64, forward: bool) -> Option<f64> {
+    let mut j = start;
+    loop {
+        let next = if forward {
+            (j + 1 < ys.len()).then_some(j + 1)?
+        } else {
+            j.checked_sub(1)?
+        };
+        if ys[next] > ys[start] {
+            return None;
+        }
+        if ys[next] <= level {
+            let t = (ys[j] - level) / (ys[j] - ys[next]);
+            return Some(xs[j] + t * (xs[next] - xs[j]));
+        }
+        j = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lorentzian(x: f64, center: f64, fwhm: f64) -> f64 {
+        let half = 0.5 * fwhm;
+        half * half / ((x - center).powi(2) + half * half)
+    }
+
+    fn gaussian(x: f64, center: f64, sigma: f64) -> f64 {
+        (-0.5 * ((x - center) / sigma).powi(2)).exp()
+    }
+
+    #[test]
+    fn test_peak_dip_and_shoulder_are_classified() {
+        // Peak at 520 nm, dip at 440 nm, weak band at 595 nm on the peak's red flank
+        let xs: Vec<f64> = (300..=800).map(|x| x as f64).collect();
+        let ys: Vec<f64> = xs
+            .iter()
+            .map(|&x| 0.5 + lorentzian(x, 520.0, 60.0) + 0.1 * gaussian(x, 595.0, 15.0) - 0.3 * gaussian(x, 440.0, 25.0))
+            .collect();
+
+        let features = detect(&xs, &ys);
+        let kinds: Vec<FeatureKind> = features.iter().map(|f| f.kind).collect();
+        assert_eq!(kinds, [FeatureKind::Dip, FeatureKind::Peak, FeatureKind::Shoulder], "{:?}", features);
+
+        let (dip, peak, shoulder) = (features[0], features[1], features[2]);
+        assert!((dip.x - 440.0).abs() < 10.0);
+        assert!((peak.x - 520.0).abs() < 1.0);
+        assert!((peak.prominence - 1.0).abs() < 0.1);
+        assert!(peak.width > 40.0 && peak.width < 80.0, "width {}", peak.width);
+        assert!((shoulder.x - 595.0).abs() < 20.0);
+        assert!(shoulder.prominence > 0.0 && shoulder.width > 0.0);
+    }
+
+    #[test]
+    fn test_prominence_threshold_filters_ripples() {
+        let xs: Vec<f64> = (0..200).map(|x| x as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|&x| lorentzian(x, 100.0, 20.0) + 0.001 * (x * 0.9).sin()).collect();
+
+        let features = detect(&xs, &ys);
+        assert_eq!(features.len(), 1, "{:?}", features);
+        assert_eq!(features[0].kind, FeatureKind::Peak);
+
+        let permissive = FeatureConfig { min_prominence: 0.0, ..Default::default() };
+        assert!(detect_with(&xs, &ys, &permissive).len() > 1);
+    }
+
+    #[test]
+    fn test_degenerate_curves_have_no_features() {
+        assert!(detect(&[], &[]).is_empty());
+        assert!(detect(&[1.0, 2.0], &[0.0, 1.0]).is_empty());
+        assert!(detect(&[1.0, 2.0, 3.0], &[0.0, 1.0]).is_empty());
+        assert!(detect(&[1.0, 2.0, 3.0], &[1.0, 1.0, 1.0]).is_empty());
+    }
+}
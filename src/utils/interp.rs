@@ -0,0 +1,187 @@
+//! Interpolation of sampled data
+//!
+//! Every function takes the samples as `xs` (sorted, strictly increasing;
+//! not checked) and `ys` of the same length. Spline callers that evaluate
+//! many points should compute [`natural_spline_curvature`] once and pass it
+//! to [`interpolate`].
+
+use crate::core::{CalcResult, CalculationError};
+
+/// What to return for a query outside the sampled range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Extrapolation {
+    /// Hold the edge value
+    #[default]
+    Clamp,
+    /// Fail with [`CalculationError::InvalidInput`]
+    Error,
+    /// Continue along the interpolant's slope at the edge
+    Linear,
+}
+
+/// Straight lines between neighbouring samples
+pub fn linear(xs: &[f64], ys: &[f64], x: f64, extrapolation: Extrapolation) -> CalcResult<f64> {
+    interpolate(xs, ys, &[], x, extrapolation)
+}
+
+/// Natural cubic spline through all samples
+pub fn cubic_spline(xs: &[f64], ys: &[f64], x: f64, extrapolation: Extrapolation) -> CalcResult<f64> {
+    interpolate(xs, ys, &natural_spline_curvature(xs, ys), x, extrapolation)
+}
+
+/// Second derivatives of the natural cubic spline through (xs, ys)
+///
+/// Solves the tridiagonal system with zero curvature at both ends.
+pub fn natural_spline_curvature(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let len = xs.len().min(ys.len());
+    let mut curvature = vec![0.0; len];
+    if len < 3 {
+        return curvature;
+    }
+    // Forward elimination: `upper` holds the eliminated super-diagonal,
+    // `curvature` the right-hand side until back substitution
+    let mut upper = vec![0.0; len];
+    for i in 1..len - 1 {
+        let sigma = (xs[i] - xs[i - 1]) / (xs[i + 1] - xs[i - 1]);
+        let p = sigma * upper[i - 1] + 2.0;
+        upper[i] = (sigma - 1.0) / p;
+        let slope_change = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]) - (ys[i] - ys[i - 1]) / (xs[i] - xs[i - 1]);
+        curvature[i] = (6.0 * slope_change / (xs[i + 1] - xs[i - 1]) - sigma * curvature[i - 1]) / p;
+    }
+    for i in (1..len - 1).rev() {
+        curvature[i] += upper[i] * curvature[i + 1];
+    }
+    curvature
+}
+
+/// Value at `x` of the piecewise cubic with second derivatives `curvature`
+/// at the samples, or of the linear interpolant if `curvature` is empty
+pub fn interpolate(xs: &[f64], ys: &[f64], curvature: &[f64], x: f64, extrapolation: Extrapolation) -> CalcResult<f64> {
+    if xs.is_empty() || xs.len() != ys.len() {
+        return Err(CalculationError::InvalidInput(format!(
+            "Interpolation needs matching non-empty samples ({} x, {} y)",
+            xs.len(),
+            ys.len()
+        )));
+    }
+    if !curvature.is_empty() && curvature.len() != xs.len() {
+        return Err(CalculationError::InvalidInput(format!(
+            "Spline has {} curvatures for {} samples",
+            curvature.len(),
+            xs.len()
+        )));
+    }
+    if x.is_nan() {
+        return Err(CalculationError::InvalidInput("Cannot interpolate at NaN".to_string()));
+    }
+
+    let last = xs.len() - 1;
+    if (xs[0]..=xs[last]).contains(&x) {
+        if last == 0 || x == xs[last] {
+            return Ok(ys[last]);
+        }
+        let i = xs.partition_point(|&xi| xi <= x) - 1;
+        return Ok(segment(xs, ys, curvature, i, x));
+    }
+
+    let (edge, x_edge) = if x < xs[0] { (0, xs[0]) } else { (last, xs[last]) };
+    match extrapolation {
+        Extrapolation::Clamp => Ok(ys[edge]),
+        Extrapolation::Error => Err(CalculationError::InvalidInput(format!(
+            "{} is outside the sampled range {}–{}",
+            x, xs[0], xs[last]
+        ))),
+        Extrapolation::Linear => Ok(ys[edge] + edge_slope(xs, ys, curvature, edge) * (x - x_edge)),
+    }
+}
+
+/// Interpolant on [xs[i], xs[i + 1]]
+fn segment(xs: &[f64], ys: &[f64], curvature: &[f64], i: usize, x: f64) -> f64 {
+    let h = xs[i + 1] - xs[i];
+    let t = (x - xs[i]) / h;
+    let linear = ys[i] + t * (ys[i + 1] - ys[i]);
+    if curvature.is_empty() {
+        return linear;
+    }
+    let s = 1.0 - t;
+    linear + ((s * s * s - s) * curvature[i] + (t * t * t - t) * curvature[i + 1]) * h * h / 6.0
+}
+
+/// Derivative of the interpolant at the first (`edge` = 0) or last sample
+fn edge_slope(xs: &[f64], ys: &[f64], curvature: &[f64], edge: usize) -> f64 {
+    if xs.len() < 2 {
+        return 0.0;
+    }
+    let i = edge.min(xs.len() - 2);
+    let h = xs[i + 1] - xs[i];
+    let secant = (ys[i + 1] - ys[i]) / h;
+    if curvature.is_empty() {
+        return secant;
+    }
+    if edge == 0 {
+        secant - h * (2.0 * curvature[i] + curvature[i + 1]) / 6.0
+    } else {
+        secant + h * (curvature[i] + 2.0 * curvature[i + 1]) / 6.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn sine_samples() -> (Vec<f64>, Vec<f64>) {
+        let xs: Vec<f64> = (0..=12).map(|i| i as f64 * PI / 6.0).collect();
+        let ys = xs.iter().map(|x| x.sin()).collect();
+        (xs, ys)
+    }
+
+    #[test]
+    fn test_spline_beats_linear_at_midpoints() {
+        let (xs, ys) = sine_samples();
+        let mut linear_error: f64 = 0.0;
+        let mut spline_error: f64 = 0.0;
+        for pair in xs.windows(2) {
+            let mid = 0.5 * (pair[0] + pair[1]);
+            linear_error = linear_error.max((linear(&xs, &ys, mid, Extrapolation::Error).unwrap() - mid.sin()).abs());
+            spline_error = spline_error.max((cubic_spline(&xs, &ys, mid, Extrapolation::Error).unwrap() - mid.sin()).abs());
+        }
+        // Linear sags by h²/8 ≈ 0.034 at the crests
+        assert!((0.02..0.04).contains(&linear_error), "{}", linear_error);
+        assert!(spline_error < linear_error / 10.0, "{} vs {}", spline_error, linear_error);
+
+        // Both pass through the samples
+        for (&x, &y) in xs.iter().zip(&ys) {
+            assert!((cubic_spline(&xs, &ys, x, Extrapolation::Error).unwrap() - y).abs() < 1e-12);
+            assert_eq!(linear(&xs, &ys, x, Extrapolation::Error).unwrap(), y);
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_queries() {
+        let xs = [1.0, 2.0, 4.0];
+        let ys = [10.0, 20.0, 30.0];
+        assert_eq!(linear(&xs, &ys, 0.0, Extrapolation::Clamp).unwrap(), 10.0);
+        assert_eq!(linear(&xs, &ys, 5.0, Extrapolation::Clamp).unwrap(), 30.0);
+        assert!(linear(&xs, &ys, 5.0, Extrapolation::Error).is_err());
+        assert_eq!(linear(&xs, &ys, 0.0, Extrapolation::Linear).unwrap(), 0.0);
+        assert_eq!(linear(&xs, &ys, 6.0, Extrapolation::Linear).unwrap(), 40.0);
+
+        // A spline extends along its end tangent, so it stays continuous in slope
+        let (sx, sy) = sine_samples();
+        let end = *sx.last().unwrap();
+        let beyond = cubic_spline(&sx, &sy, end + 1e-6, Extrapolation::Linear).unwrap();
+        let inside = cubic_spline(&sx, &sy, end - 1e-6, Extrapolation::Linear).unwrap();
+        assert!((beyond - inside - 2e-6 * end.cos()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_degenerate_input() {
+        assert!(linear(&[], &[], 1.0, Extrapolation::Clamp).is_err());
+        assert!(linear(&[1.0, 2.0], &[1.0], 1.5, Extrapolation::Clamp).is_err());
+        assert!(linear(&[1.0, 2.0], &[1.0, 2.0], f64::NAN, Extrapolation::Clamp).is_err());
+        assert!(interpolate(&[1.0, 2.0], &[1.0, 2.0], &[0.0], 1.5, Extrapolation::Clamp).is_err());
+        assert_eq!(cubic_spline(&[3.0], &[7.0], 3.0, Extrapolation::Error).unwrap(), 7.0);
+        assert_eq!(cubic_spline(&[3.0], &[7.0], 9.0, Extrapolation::Linear).unwrap(), 7.0);
+    }
+}
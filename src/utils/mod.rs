@@ -1,3 +1,17 @@
 //! Utilities
 
-// Placeholder for MVP
+pub mod clock;
+pub mod convert;
+pub mod convolve;
+pub mod expr;
+pub mod features;
+pub mod interp;
+pub mod smoothing;
+
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use convert::{celsius_to_kelvin, energy_ev_to_wavelength, nm_to_um, wavelength_to_energy_ev, wavelength_to_wavenumber_cm};
+pub use convolve::gaussian_broaden;
+pub use expr::{Expr, ParseError};
+pub use features::{detect, FeatureConfig, FeatureKind, SpectralFeature};
+pub use interp::{cubic_spline, linear, Extrapolation};
+pub use smoothing::moving_average;
@@ -0,0 +1,35 @@
+//! Smoothing of sampled data
+
+/// Centered moving average with window `window` (samples)
+///
+/// The window shrinks near the ends so the output has the same length as
+/// the input. Windows of 0 or 1 return the data unchanged.
+pub fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    if window <= 1 || values.is_empty() {
+        return values.to_vec();
+    }
+    let half = window / 2;
+    (0..values.len())
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = (i + half + 1).min(values.len());
+            values[lo..hi].iter().sum::<f64>() / (hi - lo) as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_is_unchanged() {
+        assert_eq!(moving_average(&[2.0; 6], 3), vec![2.0; 6]);
+    }
+
+    #[test]
+    fn test_window_three() {
+        let out = moving_average(&[0.0, 3.0, 0.0, 3.0], 3);
+        assert_eq!(out, vec![1.5, 1.0, 2.0, 1.5]);
+    }
+}
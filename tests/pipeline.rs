@@ -0,0 +1,181 @@
+//! Headless calculate → export → re-import round trip
+
+use nanocalc::app::controller::{self, PipelineError};
+use nanocalc::app::{AppState, RunHistory};
+use nanocalc::compute::ComputeEngine;
+use nanocalc::core::{OpticalModel, OpticalModelKind, PROGRESS_INTERVAL};
+use nanocalc::export::{ExportError, OpticalColumn};
+use nanocalc::physics::materials::{Dispersion, LorentzDrudeModel};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("nanocalc_pipeline_{}_{}", std::process::id(), name))
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs().max(b.abs()).max(1.0)
+}
+
+#[test]
+fn spectrum_round_trips_through_csv_and_json() {
+    let state = AppState::default();
+    let engine = ComputeEngine::new(Some(2)).unwrap();
+    let mut history = RunHistory::default();
+    let results = controller::calculate_spectrum(&state, None, &engine, &mut history).unwrap();
+    assert!(!results.is_empty());
+    assert_eq!(history.len(), 1);
+
+    let csv_path = temp_path("spectrum.csv");
+    controller::write_csv(&csv_path, &results, &OpticalColumn::ALL).unwrap();
+    let (columns, rows) = controller::read_csv(&csv_path).unwrap();
+    assert_eq!(columns, OpticalColumn::ALL);
+    assert_eq!(rows.len(), results.len());
+    for (row, result) in rows.iter().zip(&results) {
+        assert!(close(row[0], result.wavelength));
+        for (value, column) in row[1..].iter().zip(&columns) {
+            assert!(close(*value, column.value(result)), "{:?} at {} nm", column, result.wavelength);
+        }
+    }
+
+    let json_path = temp_path("spectrum.json");
+    controller::write_json(&json_path, &state, &results).unwrap();
+    let file = controller::read_json(&json_path).unwrap();
+    assert_eq!(file.spectrum_data.len(), results.len());
+    for (point, result) in file.spectrum_data.iter().zip(&results) {
+        assert!(close(point.wavelength_nm, result.wavelength));
+        assert!(close(point.q_sca, result.q_sca));
+        assert!(close(point.q_abs, result.q_abs));
+        assert!(close(point.q_ext, result.q_ext));
+    }
+    let mut restored = AppState {
+        particle_radius: 1.0,
+        ..AppState::default()
+    };
+    assert!(file.metadata.apply_to(&mut restored).is_empty());
+    assert_eq!(restored.particle_radius, state.particle_radius);
+
+    std::fs::remove_file(csv_path).unwrap();
+    std::fs::remove_file(json_path).unwrap();
+}
+
+#[test]
+fn empty_spectrum_exports_as_json_but_not_csv() {
+    let state = AppState::default();
+
+    let csv_path = temp_path("empty.csv");
+    assert!(matches!(
+        controller::write_csv(&csv_path, &[], &OpticalColumn::DEFAULT),
+        Err(PipelineError::Export(ExportError::Empty(_)))
+    ));
+    assert!(!csv_path.exists());
+
+    let json_path = temp_path("empty.json");
+    controller::write_json(&json_path, &state, &[]).unwrap();
+    let file = controller::read_json(&json_path).unwrap();
+    assert!(file.spectrum_data.is_empty());
+    assert_eq!(file.metadata.particle_radius, Some(state.particle_radius));
+    std::fs::remove_file(json_path).unwrap();
+}
+
+#[test]
+fn invalid_inputs_are_not_calculated_or_recorded() {
+    let state = AppState {
+        particle_radius: f64::NAN,
+        ..AppState::default()
+    };
+    let mut history = RunHistory::default();
+    assert!(controller::calculate_spectrum(&state, None, &ComputeEngine::new(Some(1)).unwrap(), &mut history).is_err());
+    assert!(history.is_empty());
+}
+
+#[test]
+fn dispersive_spectrum_follows_the_material() {
+    let gold: Arc<dyn Dispersion> = Arc::new(LorentzDrudeModel::gold());
+    let engine = ComputeEngine::new(Some(1)).unwrap();
+    let mut history = RunHistory::default();
+    let snapshot = AppState {
+        particle_radius: 10.0,
+        ..AppState::default()
+    };
+    let dispersive = AppState {
+        dispersive_spectrum: true,
+        ..snapshot.clone()
+    };
+
+    // The material is ignored unless the state asks for it
+    let fixed = controller::calculate_spectrum(&snapshot, Some(&gold), &engine, &mut history).unwrap();
+    let plain = controller::calculate_spectrum(&snapshot, None, &engine, &mut history).unwrap();
+    assert!(fixed.iter().zip(&plain).all(|(a, b)| a.q_ext == b.q_ext));
+
+    let followed = controller::calculate_spectrum(&dispersive, Some(&gold), &engine, &mut history).unwrap();
+    for point in &followed {
+        let mut state = snapshot.clone();
+        let n = gold.refractive_index_at(point.wavelength);
+        state.wavelength = point.wavelength;
+        state.n_particle_real = n.real;
+        state.n_particle_imag = n.imaginary;
+        let expected = controller::spectrum_model(&state).unwrap();
+        assert!(close(point.q_ext, expected.calculate().unwrap().q_ext));
+    }
+}
+
+#[test]
+fn spectrum_uses_the_selected_model() {
+    let engine = ComputeEngine::new(Some(2)).unwrap();
+    let mut history = RunHistory::default();
+    let state = AppState {
+        model_kind: OpticalModelKind::RayleighGansDebye,
+        n_particle_real: 1.4,
+        n_particle_imag: 0.0,
+        ..AppState::default()
+    };
+    let rgd = controller::calculate_spectrum(&state, None, &engine, &mut history).unwrap();
+    assert_eq!(history.records().next_back().unwrap().model, "Rayleigh-Gans-Debye");
+    // Approximations share the engine's cache with Mie
+    controller::calculate_spectrum(&state, None, &engine, &mut history).unwrap();
+    assert_eq!(engine.cache().stats(), (1, 1));
+
+    let mie_state = AppState {
+        model_kind: OpticalModelKind::Mie,
+        ..state
+    };
+    let mie = controller::calculate_spectrum(&mie_state, None, &engine, &mut history).unwrap();
+    assert_eq!(rgd.len(), mie.len());
+    assert!(rgd.iter().zip(&mie).any(|(a, b)| a.q_sca != b.q_sca));
+}
+
+#[test]
+fn stopped_spectrum_keeps_its_points_but_is_not_cached() {
+    let engine = ComputeEngine::new(Some(2)).unwrap();
+    let state = AppState::default();
+    for kind in OpticalModelKind::ALL {
+        let state = AppState { model_kind: kind, ..state.clone() };
+        let cancel = AtomicBool::new(false);
+        let mut reports = Vec::new();
+        let partial = controller::spectrum_with_progress(
+            &state,
+            None,
+            &engine,
+            &mut |done, total| {
+                reports.push((done, total));
+                cancel.store(true, Ordering::Relaxed);
+            },
+            &|_| {},
+            &cancel,
+        )
+        .unwrap();
+        assert_eq!(partial.len(), PROGRESS_INTERVAL, "{:?}", kind);
+        assert_eq!(reports.len(), 1);
+        assert!(engine.cache().is_empty());
+
+        let mut history = RunHistory::default();
+        let full = controller::calculate_spectrum(&state, None, &engine, &mut history).unwrap();
+        assert_eq!(reports[0], (PROGRESS_INTERVAL, full.len()));
+        for (a, b) in partial.iter().zip(&full) {
+            assert_eq!((a.wavelength, a.q_ext.to_bits()), (b.wavelength, b.q_ext.to_bits()), "{:?}", kind);
+        }
+        engine.cache().clear();
+    }
+}